@@ -5,23 +5,34 @@
 //! tests later down the line.
 
 use self::{lints::LintCrate, toolchain::Toolchain};
-use crate::config::LintDependencyEntry;
+use crate::config::{LintDependencyEntry, LintLevel};
 use crate::error::prelude::*;
 use crate::observability::display::{self, print_stage};
 use crate::observability::prelude::*;
-use camino::Utf8PathBuf;
+use crate::utils::utf8::IntoUtf8;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::Message;
 use itertools::Itertools;
 use std::collections::BTreeMap;
+use std::io::BufReader;
+use std::process::{Command, ExitStatus, Stdio};
+use yansi::Paint;
 
 pub mod cargo;
+pub mod diff;
 pub mod driver;
+pub mod fix;
 pub mod lints;
+pub mod manifest;
+pub mod message_format;
+pub mod report;
 pub mod toolchain;
 
 /// Markers configuration for any action that requires lint crates to be available.
 ///
 /// It's assumed that all paths in this struct are absolute paths.
 #[derive(Debug)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     /// The base directory used by Marker to fetch and compile lints.
     /// This will default to something like `./target/marker`.
@@ -32,23 +43,91 @@ pub struct Config {
     pub marker_dir: Utf8PathBuf,
     /// The list of lints.
     pub lints: BTreeMap<String, LintDependencyEntry>,
+    /// The levels of lints, which should override the default levels of the
+    /// lint crates. These are passed to the driver as rustc flags.
+    pub lint_levels: BTreeMap<String, LintLevel>,
+    /// Glob patterns of files, in which diagnostics of lints are suppressed.
+    /// These are passed to the driver, which matches them against the paths
+    /// of the diagnostic spans.
+    pub ignore_paths: Vec<String>,
+    /// Glob patterns of files, to which the diagnostics of lints are limited.
+    /// All files are included, if this is empty.
+    pub include_paths: Vec<String>,
     /// Additional flags, which should be passed to rustc during the compilation
     /// of crates.
     pub build_rustc_flags: String,
     /// Indicates if this is a release or debug build.
     pub debug_build: bool,
+    /// The git revision used to filter diagnostics. If set, only diagnostics
+    /// on lines, which have changed since this revision, will be displayed.
+    pub diff_base: Option<String>,
+    /// Requires the `Cargo.lock` file of the lint crates to be up to date, like
+    /// Cargo's `--locked` flag.
+    pub locked: bool,
+    /// Requires the lint crates to be fetched and compiled already. This
+    /// implies [`locked`](Self::locked) and prevents network access.
+    pub frozen: bool,
+    /// Adds a note to every diagnostic, naming the lint crate, which emitted it.
+    pub show_lint_source: bool,
+    /// The maximum number of diagnostics, which are emitted per lint and crate.
+    /// Further diagnostics of the lint are suppressed by the driver.
+    pub max_per_lint: Option<usize>,
+    /// If set, the diagnostics of all crates are collected and emitted as a
+    /// single report in this format, once all crates have been checked.
+    pub report: Option<report::ReportFormat>,
+    /// The file, which JSON and SARIF reports should be written to. The report
+    /// is printed to stdout, if this is `None`.
+    pub report_output: Option<Utf8PathBuf>,
+    /// The order of the diagnostics. Grouping by lint requires the diagnostics
+    /// of all crates to be collected, like for a [`report`](Self::report).
+    pub group_by: report::GroupBy,
+    /// The format, in which the diagnostics are printed, if they're not
+    /// collected in a report.
+    pub message_format: message_format::MessageFormat,
+    /// Applies the machine-applicable suggestions of the emitted diagnostics,
+    /// once all crates have been checked.
+    pub fix: bool,
     pub toolchain: Toolchain,
 }
 
 impl Config {
     pub fn try_base_from(toolchain: Toolchain) -> Result<Self> {
-        Ok(Self {
-            marker_dir: toolchain.find_target_dir()?.join("marker"),
+        let marker_dir = toolchain.find_target_dir()?.join("marker");
+        Ok(Self::base_with_marker_dir(toolchain, marker_dir))
+    }
+
+    /// Creates a base config for linting a single file. Files can be linted outside
+    /// of Cargo projects. In that case, the marker directory will be placed in the
+    /// temporary directory of the system.
+    pub fn base_for_file(toolchain: Toolchain) -> Result<Self> {
+        let marker_dir = match toolchain.find_target_dir() {
+            Ok(target_dir) => target_dir.join("marker"),
+            Err(_) => std::env::temp_dir().into_utf8()?.join("marker"),
+        };
+        Ok(Self::base_with_marker_dir(toolchain, marker_dir))
+    }
+
+    fn base_with_marker_dir(toolchain: Toolchain, marker_dir: Utf8PathBuf) -> Self {
+        Self {
+            marker_dir,
             lints: BTreeMap::default(),
+            lint_levels: BTreeMap::default(),
+            ignore_paths: vec![],
+            include_paths: vec![],
             build_rustc_flags: String::new(),
             debug_build: false,
+            diff_base: None,
+            locked: false,
+            frozen: false,
+            show_lint_source: false,
+            max_per_lint: None,
+            report: None,
+            report_output: None,
+            group_by: report::GroupBy::Location,
+            message_format: message_format::MessageFormat::Human,
+            fix: false,
             toolchain,
-        })
+        }
     }
 
     fn markers_target_dir(&self) -> Utf8PathBuf {
@@ -58,6 +137,19 @@ impl Config {
     fn lint_crate_dir(&self) -> Utf8PathBuf {
         self.marker_dir.join("lints")
     }
+
+    /// Returns the Cargo flag, which should be used for commands operating on
+    /// the lint crates, if [`frozen`](Self::frozen) or [`locked`](Self::locked)
+    /// is set.
+    fn lock_flag(&self) -> Option<&'static str> {
+        if self.frozen {
+            Some("--frozen")
+        } else if self.locked {
+            Some("--locked")
+        } else {
+            None
+        }
+    }
 }
 
 /// This struct contains all information to use rustc as a driver.
@@ -81,6 +173,52 @@ pub fn prepare_check(config: &Config) -> Result<CheckInfo> {
     if let Some(toolchain) = &config.toolchain.cargo.toolchain {
         env.push(("RUSTUP_TOOLCHAIN", toolchain.into()));
     }
+    let lint_filters: BTreeMap<_, _> = config
+        .lints
+        .iter()
+        .filter(|(_, entry)| entry.enabled_lints.is_some() || !entry.disabled_lints.is_empty())
+        .map(|(name, entry)| {
+            let filter = serde_json::json!({
+                "enabled_lints": entry.enabled_lints,
+                "disabled_lints": entry.disabled_lints,
+            });
+            (name, filter)
+        })
+        .collect();
+    if !lint_filters.is_empty() {
+        let filters = serde_json::to_string(&lint_filters).expect("lint filters can be serialized");
+        env.push(("MARKER_LINT_FILTERS", filters));
+    }
+    let lint_configs: BTreeMap<_, _> = config
+        .lints
+        .iter()
+        .filter_map(|(name, entry)| Some((name, entry.config.as_ref()?)))
+        .collect();
+    if !lint_configs.is_empty() {
+        let configs = serde_json::to_string(&lint_configs).expect("lint configs can be serialized");
+        env.push(("MARKER_LINT_CONFIGS", configs));
+    }
+    if !config.lint_levels.is_empty() {
+        let flags = config
+            .lint_levels
+            .iter()
+            .map(|(lint, level)| level.to_rustc_flag(lint))
+            .join(" ");
+        env.push(("MARKER_LINT_LEVELS", flags));
+    }
+    if !config.ignore_paths.is_empty() {
+        env.push(("MARKER_IGNORE_PATHS", config.ignore_paths.join(";")));
+    }
+    if !config.include_paths.is_empty() {
+        env.push(("MARKER_INCLUDE_PATHS", config.include_paths.join(";")));
+    }
+    if config.show_lint_source {
+        env.push(("MARKER_SHOW_LINT_SOURCE", "1".to_string()));
+    }
+    if let Some(max) = config.max_per_lint {
+        env.push(("MARKER_MAX_PER_LINT", max.to_string()));
+    }
+    env.push(("MARKER_MANIFESTS", manifest::manifests_env(config)?));
 
     Ok(CheckInfo { env })
 }
@@ -95,12 +233,69 @@ pub fn run_check(config: &Config, info: CheckInfo, additional_cargo_args: &[Stri
 
     cmd.envs(info.env);
 
+    let exit_status = if config.diff_base.is_some()
+        || config.report.is_some()
+        || config.group_by == report::GroupBy::Lint
+        || config.message_format == message_format::MessageFormat::Github
+        || config.fix
+    {
+        // The captured diagnostics are parsed from Cargo's JSON output
+        if additional_cargo_args
+            .iter()
+            .any(|arg| arg.starts_with("--message-format"))
+        {
+            return Err(Error::root(
+                "Cargo's `--message-format` can't be set together with `--diff`, `--report`, \
+                `--group-by=lint`, `--message-format=github` or `--fix`, as these options \
+                require Marker to read Cargo's JSON output",
+            ));
+        }
+        run_check_captured(config, cmd)?
+    } else {
+        cmd.log()
+            .spawn()
+            .expect("could not run cargo")
+            .wait()
+            .expect("failed to wait for cargo?")
+    };
+
+    if exit_status.success() {
+        return Ok(());
+    }
+
+    Err(Error::root(format!("{} finished with an error", display::stage(stage))))
+}
+
+/// Lints a single file, by invoking the driver directly, without Cargo. The file
+/// is used as the crate root. Dependencies, other than the standard library, are
+/// therefore not available.
+pub fn run_check_file(
+    config: &Config,
+    info: CheckInfo,
+    file: &Utf8Path,
+    edition: &str,
+    additional_rustc_args: &[String],
+) -> Result {
+    let stage = "linting";
+    print_stage(stage);
+
+    let mut cmd = config.toolchain.driver_command();
+    cmd.arg(file.as_os_str());
+    cmd.arg(format!("--edition={edition}"));
+    // Only the analysis is needed, the metadata is the cheapest output to emit
+    cmd.arg("--emit=metadata");
+    cmd.arg("--out-dir");
+    cmd.arg(config.marker_dir.join("file").as_os_str());
+    cmd.args(additional_rustc_args);
+
+    cmd.envs(info.env);
+
     let exit_status = cmd
         .log()
         .spawn()
-        .expect("could not run cargo")
+        .expect("could not run the driver")
         .wait()
-        .expect("failed to wait for cargo?");
+        .expect("failed to wait for the driver?");
 
     if exit_status.success() {
         return Ok(());
@@ -108,3 +303,99 @@ pub fn run_check(config: &Config, info: CheckInfo, additional_cargo_args: &[Stri
 
     Err(Error::root(format!("{} finished with an error", display::stage(stage))))
 }
+
+/// Runs the given `cargo check` command, with JSON diagnostics. If a diff base is
+/// set, only the diagnostics of lines, which have changed since the given revision,
+/// are kept. If a report format is set or the diagnostics are grouped by lint, the
+/// diagnostics of all crates are collected and emitted as a single report.
+/// Otherwise, they are printed directly, in the configured message format. With `--fix`, the
+/// suggestions of the emitted diagnostics are applied, once Cargo finished successfully.
+fn run_check_captured(config: &Config, mut cmd: Command) -> Result<ExitStatus> {
+    let changes = config
+        .diff_base
+        .as_deref()
+        .map(diff::ChangedLines::try_from_git)
+        .transpose()?;
+    let workspace_root = config
+        .toolchain
+        .cargo
+        .metadata()
+        .no_deps()
+        .exec()
+        .context(|| "Couldn't find the workspace root")?
+        .workspace_root;
+
+    let mut report = (config.report.is_some() || config.group_by == report::GroupBy::Lint)
+        .then(|| report::Report::new(workspace_root.clone(), config.group_by));
+    let mut fixes = config.fix.then(|| fix::Fixes::new(workspace_root.clone()));
+
+    cmd.arg("--message-format=json-diagnostic-rendered-ansi");
+    cmd.stdout(Stdio::piped());
+
+    let mut child = cmd.log().spawn().expect("could not run cargo");
+    let stdout = child.stdout.take().expect("stdout was piped");
+
+    for message in Message::parse_stream(BufReader::new(stdout)) {
+        let message = message.context(|| "Failed to read the output of cargo")?;
+        let Message::CompilerMessage(msg) = message else {
+            continue;
+        };
+        if let Some(changes) = &changes {
+            if !changes.should_emit(&msg.message, &workspace_root) {
+                continue;
+            }
+        }
+        if let Some(fixes) = &mut fixes {
+            fixes.add(&msg.message);
+        }
+        match &mut report {
+            Some(report) => report.add(msg),
+            None => {
+                if let Some(rendered) = &msg.message.rendered {
+                    eprint!("{rendered}");
+                }
+                if config.message_format == message_format::MessageFormat::Github {
+                    if let Some(command) = message_format::github_command(&msg.message, &workspace_root) {
+                        println!("{command}");
+                    }
+                }
+            },
+        }
+    }
+
+    let exit_status = child.wait().expect("failed to wait for cargo?");
+    if let Some(report) = report {
+        let format = config.report.unwrap_or(report::ReportFormat::Human);
+        report.emit(format, config.report_output.as_deref())?;
+    }
+    if let Some(fixes) = fixes {
+        apply_fixes(&fixes, exit_status)?;
+    }
+
+    Ok(exit_status)
+}
+
+fn apply_fixes(fixes: &fix::Fixes, exit_status: ExitStatus) -> Result {
+    if fixes.is_empty() {
+        return Ok(());
+    }
+    // The spans of the suggestions might be outdated, if the compilation failed
+    if !exit_status.success() {
+        warn!("The suggestions have not been applied, since the check finished with an error");
+        return Ok(());
+    }
+
+    let summary = fixes.apply()?;
+    for message in &summary.skipped {
+        warn!("Skipped the suggestion of `{message}`, as it overlaps with another suggestion or leaves the workspace");
+    }
+    eprintln!(
+        "{}: applied {} suggestion{} in {} file{}",
+        "fixed".bold(),
+        summary.applied,
+        if summary.applied == 1 { "" } else { "s" },
+        summary.files.len(),
+        if summary.files.len() == 1 { "" } else { "s" },
+    );
+    Ok(())
+}
@@ -0,0 +1,264 @@
+//! This module implements the filtering used by `cargo marker --diff <rev>`.
+//!
+//! The changed lines are determined by parsing the output of `git diff` against
+//! the given revision. Diagnostics emitted by the driver are then only forwarded
+//! to the user, if their primary span intersects one of the changed line ranges.
+
+use crate::error::prelude::*;
+use crate::observability::prelude::*;
+use crate::utils::utf8::IntoUtf8;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+use std::collections::BTreeMap;
+use std::ops::RangeInclusive;
+use std::process::Command;
+
+/// The lines which have been added or modified, according to `git diff`.
+///
+/// All paths in this struct are absolute paths.
+#[derive(Debug, Default)]
+pub struct ChangedLines {
+    files: BTreeMap<Utf8PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedLines {
+    /// Collects the changed lines of the working tree compared to the given
+    /// git revision. Untracked files are not part of the result.
+    pub fn try_from_git(rev: &str) -> Result<Self> {
+        let root = git_output(Command::new("git").args(["rev-parse", "--show-toplevel"]))?;
+        let root = Utf8PathBuf::from(root.trim());
+
+        // `--no-prefix` and `core.quotePath=false` keep the paths in the file
+        // headers as written, apart from the quoting of special characters
+        let diff = git_output(Command::new("git").args([
+            "-c",
+            "core.quotePath=false",
+            "diff",
+            "--unified=0",
+            "--no-color",
+            "--no-ext-diff",
+            "--no-prefix",
+            rev,
+            "--",
+        ]))?;
+
+        Ok(Self::parse(&diff, &root))
+    }
+
+    /// Parses the output of `git diff --unified=0 --no-prefix`. The file paths
+    /// in the diff are relative to the given repository root.
+    ///
+    /// The lines of a hunk are skipped, based on the line counts of the hunk
+    /// header. This ensures that changed lines, which look like file or hunk
+    /// headers, like an added line starting with `++ `, are not parsed as such.
+    fn parse(diff: &str, root: &Utf8Path) -> Self {
+        let mut files: BTreeMap<Utf8PathBuf, Vec<RangeInclusive<usize>>> = BTreeMap::new();
+        let mut current_file = None;
+        let mut hunk_lines = 0;
+
+        for line in diff.lines() {
+            if hunk_lines > 0 {
+                // Markers like `\ No newline at end of file` are not counted
+                if !line.starts_with('\\') {
+                    hunk_lines -= 1;
+                }
+                continue;
+            }
+
+            if let Some(path) = line.strip_prefix("+++ ") {
+                // Deleted files are marked with `/dev/null` and have no new lines
+                current_file = parse_header_path(path).map(|path| root.join(path));
+                continue;
+            }
+
+            let Some(hunk) = line.strip_prefix("@@ ") else {
+                continue;
+            };
+
+            // A hunk header looks like: `@@ -<start>[,<len>] +<start>[,<len>] @@`
+            let mut ranges = hunk.split(' ');
+            let old_range = ranges.next().and_then(|range| range.strip_prefix('-'));
+            let new_range = ranges.next().and_then(|range| range.strip_prefix('+'));
+            let (Some((_, old_len)), Some((start, new_len))) =
+                (old_range.and_then(parse_range), new_range.and_then(parse_range))
+            else {
+                continue;
+            };
+            hunk_lines = old_len + new_len;
+
+            // A length of zero means, that lines have only been removed
+            if let (Some(file), 1..) = (&current_file, new_len) {
+                let end = start + new_len - 1;
+                files.entry(file.clone()).or_default().push(start..=end);
+            }
+        }
+
+        Self { files }
+    }
+
+    /// Returns `true`, if the given line range of the file intersects with
+    /// a changed line range.
+    pub fn intersects(&self, file: &Utf8Path, lines: &RangeInclusive<usize>) -> bool {
+        self.files.get(file).map_or(false, |ranges| {
+            ranges
+                .iter()
+                .any(|range| range.start() <= lines.end() && lines.start() <= range.end())
+        })
+    }
+
+    /// Returns `true`, if the given diagnostic should be displayed. Paths in the
+    /// diagnostic are resolved relative to the given workspace root.
+    ///
+    /// Diagnostics without a primary span are only kept, if they are errors,
+    /// to ensure that compilation errors are still visible to the user.
+    pub fn should_emit(&self, diag: &Diagnostic, workspace_root: &Utf8Path) -> bool {
+        let Some(span) = diag.spans.iter().find(|span| span.is_primary) else {
+            return matches!(diag.level, DiagnosticLevel::Error | DiagnosticLevel::Ice);
+        };
+
+        let file = workspace_root.join(&span.file_name);
+        self.intersects(&file, &(span.line_start..=span.line_end))
+    }
+}
+
+/// Parses a `<start>[,<len>]` range of a hunk header.
+fn parse_range(range: &str) -> Option<(usize, usize)> {
+    match range.split_once(',') {
+        Some((start, len)) => Some((start.parse().ok()?, len.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// Parses the path of a `+++ <path>` file header. Git appends a tab to paths
+/// containing spaces and quotes paths with special characters, like `"a\"b.rs"`.
+/// `None` is returned for `/dev/null`.
+fn parse_header_path(path: &str) -> Option<String> {
+    let path = path.strip_suffix('\t').unwrap_or(path);
+    if path == "/dev/null" {
+        return None;
+    }
+    let Some(quoted) = path.strip_prefix('"').and_then(|path| path.strip_suffix('"')) else {
+        return Some(path.to_string());
+    };
+
+    // Unescape the C-style escapes, which Git uses in quoted paths. Octal
+    // escapes encode the individual bytes of UTF-8 characters.
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+            continue;
+        }
+        let byte = match chars.next()? {
+            'a' => 0x07,
+            'b' => 0x08,
+            'f' => 0x0c,
+            'n' => b'\n',
+            'r' => b'\r',
+            't' => b'\t',
+            'v' => 0x0b,
+            digit @ '0'..='3' => {
+                let digits = [digit, chars.next()?, chars.next()?];
+                let digits: String = digits.iter().collect();
+                u8::from_str_radix(&digits, 8).ok()?
+            },
+            other => u8::try_from(other).ok()?,
+        };
+        bytes.push(byte);
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn git_output(cmd: &mut Command) -> Result<String> {
+    let output = cmd.log().output().context(|| "Failed to execute git")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(Error::wrap(stderr.trim(), format!("Command failed: {}", cmd.display())));
+    }
+
+    output.stdout.into_utf8()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_diff() {
+        let diff = "\
+diff --git src/main.rs src/main.rs
+index 1111111..2222222 100644
+--- src/main.rs
++++ src/main.rs
+@@ -3 +3 @@ fn main() {
+-    let a = 1;
++    let a = 2;
+@@ -10,0 +11,3 @@ fn main() {
++    foo();
++    bar();
++    baz();
+@@ -20,2 +23,0 @@ fn main() {
+-    removed();
+-    removed();
+diff --git src/old.rs src/old.rs
+deleted file mode 100644
+--- src/old.rs
++++ /dev/null
+@@ -1 +0,0 @@
+-fn old() {}
+";
+        let root = Utf8Path::new("/repo");
+        let changes = ChangedLines::parse(diff, root);
+        let main = root.join("src/main.rs");
+
+        assert_eq!(changes.files.len(), 1);
+        assert_eq!(changes.files[&main], vec![3..=3, 11..=13]);
+
+        assert!(changes.intersects(&main, &(3..=3)));
+        assert!(changes.intersects(&main, &(1..=11)));
+        assert!(changes.intersects(&main, &(13..=20)));
+        assert!(!changes.intersects(&main, &(4..=10)));
+        assert!(!changes.intersects(&main, &(23..=23)));
+        assert!(!changes.intersects(&root.join("src/old.rs"), &(1..=1)));
+    }
+
+    #[test]
+    fn test_parse_diff_header_like_lines() {
+        // The added and removed lines look like file and hunk headers
+        let diff = "\
+diff --git src/a.rs src/a.rs
+--- src/a.rs
++++ src/a.rs
+@@ -1,2 +1,3 @@
+--- b.rs
+-@@ -1 +1 @@
++++ b.rs
++@@ -7 +7 @@
++\\ not a marker
+\\ No newline at end of file
+@@ -5,0 +7 @@
++let a = 1;
+diff --git src/with space.rs src/with space.rs
+--- src/with space.rs\t
++++ src/with space.rs\t
+@@ -1 +2 @@
+-a
++b
+diff --git \"src/quo\\\"te\\303\\274.rs\" \"src/quo\\\"te\\303\\274.rs\"
+--- \"src/quo\\\"te\\303\\274.rs\"
++++ \"src/quo\\\"te\\303\\274.rs\"
+@@ -1 +4 @@
+-a
++b
+";
+        let root = Utf8Path::new("/repo");
+        let changes = ChangedLines::parse(diff, root);
+
+        assert_eq!(changes.files.len(), 3);
+        assert_eq!(changes.files[&root.join("src/a.rs")], vec![1..=3, 7..=7]);
+        assert_eq!(changes.files[&root.join("src/with space.rs")], vec![2..=2]);
+        assert_eq!(changes.files[&root.join("src/quo\"teü.rs")], vec![4..=4]);
+    }
+}
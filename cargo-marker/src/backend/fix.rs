@@ -0,0 +1,301 @@
+//! This module implements `cargo marker --fix`, which applies the suggestions of
+//! the emitted diagnostics to the source files.
+//!
+//! Only suggestions with the `MachineApplicable` applicability are applied. A
+//! suggestion can consist of several parts, which can be located in different
+//! files. The parts of a suggestion are either applied together or not at all.
+//! Suggestions, which overlap with an already accepted suggestion, are skipped
+//! and reported. All files are written at the end. If writing one of them fails,
+//! the already written files are restored.
+
+use crate::error::prelude::*;
+use crate::observability::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::diagnostic::{Applicability, Diagnostic};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The machine-applicable suggestions of all checked crates.
+#[derive(Debug)]
+pub struct Fixes {
+    workspace_root: Utf8PathBuf,
+    /// The suggestions, deduplicated, since code can be checked multiple times,
+    /// for example, as part of a library and its tests.
+    suggestions: BTreeSet<Suggestion>,
+}
+
+/// A single suggestion, with all of its parts.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Suggestion {
+    edits: Vec<Edit>,
+    /// The message of the diagnostic, used to report skipped suggestions
+    message: String,
+}
+
+/// A replacement of a byte range in a file.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct Edit {
+    file: Utf8PathBuf,
+    start: usize,
+    end: usize,
+    replacement: String,
+}
+
+impl Edit {
+    /// Returns `true`, if the edits can't be applied together. Identical edits
+    /// don't conflict, they're applied once. Insertions at the same position
+    /// conflict, since their order would be ambiguous.
+    fn conflicts_with(&self, other: &Edit) -> bool {
+        self.file == other.file
+            && self != other
+            && ((self.start < other.end && other.start < self.end) || self.start == other.start)
+    }
+}
+
+/// The outcome of [`Fixes::apply`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct FixSummary {
+    /// The number of applied suggestions
+    pub applied: usize,
+    /// The files, which have been modified
+    pub files: BTreeSet<Utf8PathBuf>,
+    /// The messages of the diagnostics, whose suggestions have been skipped
+    pub skipped: Vec<String>,
+}
+
+impl Fixes {
+    pub fn new(workspace_root: Utf8PathBuf) -> Self {
+        Self {
+            workspace_root,
+            suggestions: BTreeSet::new(),
+        }
+    }
+
+    /// Collects the machine-applicable suggestions of the given diagnostic and
+    /// its children.
+    pub fn add(&mut self, diag: &Diagnostic) {
+        self.add_suggestion(diag, &diag.message);
+        for child in &diag.children {
+            self.add_suggestion(child, &diag.message);
+        }
+    }
+
+    fn add_suggestion(&mut self, diag: &Diagnostic, message: &str) {
+        let spans: Vec<_> = diag
+            .spans
+            .iter()
+            .filter(|span| span.suggested_replacement.is_some())
+            .collect();
+        if spans.is_empty()
+            || !spans
+                .iter()
+                .all(|span| span.suggestion_applicability == Some(Applicability::MachineApplicable))
+        {
+            return;
+        }
+
+        let mut edits: Vec<_> = spans
+            .into_iter()
+            .map(|span| Edit {
+                file: self.workspace_root.join(&span.file_name),
+                start: span.byte_start as usize,
+                end: span.byte_end as usize,
+                replacement: span.suggested_replacement.clone().unwrap_or_default(),
+            })
+            .collect();
+        edits.sort();
+        edits.dedup();
+        self.suggestions.insert(Suggestion {
+            edits,
+            message: message.to_string(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.suggestions.is_empty()
+    }
+
+    /// Applies the collected suggestions to the files in the workspace.
+    ///
+    /// Suggestions are skipped, if they touch files outside the workspace, if
+    /// their parts overlap each other or if they overlap with a previously
+    /// accepted suggestion. The files are only modified, if all of them could be
+    /// read and all edits are valid.
+    pub fn apply(&self) -> Result<FixSummary> {
+        let mut summary = FixSummary::default();
+        let mut accepted: BTreeMap<&Utf8Path, Vec<&Edit>> = BTreeMap::new();
+        for sugg in &self.suggestions {
+            let outside_workspace = sugg
+                .edits
+                .iter()
+                .any(|edit| !edit.file.starts_with(&self.workspace_root));
+            let self_overlap = sugg
+                .edits
+                .iter()
+                .enumerate()
+                .any(|(index, edit)| sugg.edits[index + 1..].iter().any(|other| edit.conflicts_with(other)));
+            let overlap = sugg.edits.iter().any(|edit| {
+                accepted
+                    .get(edit.file.as_path())
+                    .is_some_and(|edits| edits.iter().any(|other| edit.conflicts_with(other)))
+            });
+            if outside_workspace || self_overlap || overlap {
+                summary.skipped.push(sugg.message.clone());
+                continue;
+            }
+
+            for edit in &sugg.edits {
+                let file_edits = accepted.entry(edit.file.as_path()).or_default();
+                if !file_edits.contains(&edit) {
+                    file_edits.push(edit);
+                }
+            }
+            summary.applied += 1;
+        }
+
+        // All files are prepared first, to avoid partial modifications
+        let mut outputs = Vec::with_capacity(accepted.len());
+        for (file, mut edits) in accepted {
+            let original =
+                std::fs::read_to_string(file).context(|| format!("Failed to read `{file}` to apply fixes"))?;
+            edits.sort_by_key(|edit| std::cmp::Reverse((edit.start, edit.end)));
+            let mut fixed = original.clone();
+            for edit in edits {
+                if edit.end > fixed.len() || !fixed.is_char_boundary(edit.start) || !fixed.is_char_boundary(edit.end) {
+                    return Err(Error::root(format!(
+                        "The suggestion for `{file}` at the bytes {}..{} is invalid, no fixes have been applied",
+                        edit.start, edit.end
+                    )));
+                }
+                fixed.replace_range(edit.start..edit.end, &edit.replacement);
+            }
+            outputs.push((file, original, fixed));
+        }
+
+        for (index, (file, _, fixed)) in outputs.iter().enumerate() {
+            if let Err(err) = std::fs::write(file, fixed) {
+                for (written, original, _) in &outputs[..index] {
+                    if let Err(err) = std::fs::write(written, original) {
+                        error!("Failed to restore `{written}` after a failed fix: {err}");
+                    }
+                }
+                return Err(Error::wrap(
+                    err,
+                    format!("Failed to write `{file}`, no fixes have been applied"),
+                ));
+            }
+            summary.files.insert(file.to_path_buf());
+        }
+
+        Ok(summary)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn span(file: &str, start: usize, end: usize, replacement: &str) -> serde_json::Value {
+        json!({
+            "file_name": file,
+            "byte_start": start,
+            "byte_end": end,
+            "line_start": 1,
+            "line_end": 1,
+            "column_start": 1,
+            "column_end": 1,
+            "is_primary": true,
+            "text": [],
+            "label": null,
+            "suggested_replacement": replacement,
+            "suggestion_applicability": "MachineApplicable",
+            "expansion": null,
+        })
+    }
+
+    fn diagnostic(message: &str, spans: &[serde_json::Value]) -> Diagnostic {
+        let diag = json!({
+            "message": message,
+            "code": { "code": "marker::lints::lint", "explanation": null },
+            "level": "warning",
+            "spans": [],
+            "children": [{
+                "message": "try",
+                "code": null,
+                "level": "help",
+                "spans": spans,
+                "children": [],
+                "rendered": null,
+            }],
+            "rendered": null,
+        });
+        serde_json::from_value(diag).unwrap()
+    }
+
+    fn workspace(name: &str, files: &[(&str, &str)]) -> Utf8PathBuf {
+        let root = Utf8PathBuf::try_from(std::env::temp_dir())
+            .unwrap()
+            .join(format!("marker-fix-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        for (file, content) in files {
+            std::fs::write(root.join(file), content).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_fix_multiple_files() {
+        let root = workspace("multi", &[("a.rs", "fn old() {}\n"), ("b.rs", "old();\n")]);
+        let mut fixes = Fixes::new(root.clone());
+        let rename = diagnostic("rename", &[span("a.rs", 3, 6, "new"), span("b.rs", 0, 3, "new")]);
+        fixes.add(&rename);
+        // Diagnostics of code, which is checked multiple times, are deduplicated
+        fixes.add(&rename);
+
+        let summary = fixes.apply().unwrap();
+        assert_eq!(summary.applied, 1);
+        assert!(summary.skipped.is_empty());
+        assert_eq!(std::fs::read_to_string(root.join("a.rs")).unwrap(), "fn new() {}\n");
+        assert_eq!(std::fs::read_to_string(root.join("b.rs")).unwrap(), "new();\n");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fix_skips_overlapping_suggestions() {
+        let root = workspace("overlap", &[("a.rs", "let value = 1;\n"), ("b.rs", "value\n")]);
+        let mut fixes = Fixes::new(root.clone());
+        fixes.add(&diagnostic(
+            "first",
+            &[span("a.rs", 4, 9, "x"), span("b.rs", 0, 5, "x")],
+        ));
+        fixes.add(&diagnostic(
+            "second",
+            &[span("a.rs", 6, 9, "y"), span("b.rs", 5, 5, ";")],
+        ));
+        fixes.add(&diagnostic(
+            "self overlap",
+            &[span("a.rs", 12, 13, "2"), span("a.rs", 12, 14, "3")],
+        ));
+
+        let summary = fixes.apply().unwrap();
+        assert_eq!(summary.applied, 1);
+        assert_eq!(summary.skipped, ["second", "self overlap"]);
+        // The second suggestion is skipped entirely, including the part in `b.rs`
+        assert_eq!(std::fs::read_to_string(root.join("a.rs")).unwrap(), "let x = 1;\n");
+        assert_eq!(std::fs::read_to_string(root.join("b.rs")).unwrap(), "x\n");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_fix_invalid_edit_changes_nothing() {
+        let root = workspace("invalid", &[("a.rs", "fn a() {}\n"), ("b.rs", "b\n")]);
+        let mut fixes = Fixes::new(root.clone());
+        fixes.add(&diagnostic("valid", &[span("a.rs", 3, 4, "c")]));
+        fixes.add(&diagnostic("out of bounds", &[span("b.rs", 10, 20, "x")]));
+
+        assert!(fixes.apply().is_err());
+        assert_eq!(std::fs::read_to_string(root.join("a.rs")).unwrap(), "fn a() {}\n");
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
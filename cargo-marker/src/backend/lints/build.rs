@@ -3,9 +3,10 @@ use crate::backend::Config;
 use crate::error::prelude::*;
 use crate::observability::prelude::*;
 use crate::utils::utf8::IntoUtf8;
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
+use cargo_metadata::Message;
 use itertools::Itertools;
-use std::{collections::HashSet, ffi::OsStr};
+use std::{collections::HashSet, ffi::OsStr, io::BufReader, process::Stdio, time::SystemTime};
 use yansi::Paint;
 
 #[cfg(target_os = "linux")]
@@ -15,6 +16,11 @@ const DYNAMIC_LIB_FILE_ENDING: &str = "dylib";
 #[cfg(target_os = "windows")]
 const DYNAMIC_LIB_FILE_ENDING: &str = "dll";
 
+#[cfg(not(target_os = "windows"))]
+const DYNAMIC_LIB_FILE_PREFIX: &str = "lib";
+#[cfg(target_os = "windows")]
+const DYNAMIC_LIB_FILE_PREFIX: &str = "";
+
 /// A list of file endings which are expected to be inside the lint crate dir.
 /// It's assumed that these can be safely removed.
 const ARTIFACT_ENDINGS: &[&str] = &[
@@ -41,6 +47,12 @@ pub fn build_lints(sources: &[LintCrateSource], config: &Config) -> Result<Vec<L
     //
     // This would be so much simpler if we could get an output name from Cargo
 
+    // With `--frozen` the lint crates have to be compiled already. This is checked
+    // before anything is built or removed.
+    if config.frozen {
+        check_frozen_artifacts(sources, config)?;
+    }
+
     // Clear previously build lints
     let lints_dir = config.lint_crate_dir();
     clear_lints_dir(&lints_dir)?;
@@ -117,8 +129,73 @@ fn clear_lints_dir(lints_dir: &Utf8Path) -> Result {
     std::fs::remove_dir(lints_dir).context(|| format!("Failed to remove lints directory {lints_dir}"))
 }
 
+/// Checks that the compiled artifacts of all lint crates are up to date, without
+/// invoking Cargo. The dep-info files, written by Cargo next to the artifacts,
+/// list the source files of each artifact. An artifact is outdated, if it's
+/// missing or older than one of its source files or its manifest.
+fn check_frozen_artifacts(sources: &[LintCrateSource], config: &Config) -> Result {
+    let profile = if config.debug_build { "debug" } else { "release" };
+    let profile_dir = config.markers_target_dir().join(profile);
+
+    if sources.iter().all(|lint_src| is_artifact_fresh(&profile_dir, lint_src)) {
+        return Ok(());
+    }
+
+    Err(Error::from_kind(ErrorKind::LintCratesOutdated {
+        action: "rebuilt",
+        flag: "--frozen",
+    }))
+}
+
+fn is_artifact_fresh(profile_dir: &Utf8Path, lint_src: &LintCrateSource) -> bool {
+    let crate_name = lint_src.name.replace('-', "_");
+    let dep_info = profile_dir.join(format!("{DYNAMIC_LIB_FILE_PREFIX}{crate_name}.d"));
+    let Some((artifact, deps)) = std::fs::read_to_string(dep_info)
+        .ok()
+        .and_then(|content| parse_dep_info(&content))
+    else {
+        return false;
+    };
+
+    let modified = |path: &Utf8Path| -> Option<SystemTime> { std::fs::metadata(path).ok()?.modified().ok() };
+    let Some(artifact_time) = modified(&artifact) else {
+        return false;
+    };
+    deps.iter()
+        .map(Utf8PathBuf::as_path)
+        .chain(std::iter::once(lint_src.manifest.as_path()))
+        .all(|dep| modified(dep).map_or(false, |dep_time| dep_time <= artifact_time))
+}
+
+/// Parses the first line of a dep-info file, which has the format:
+/// `<artifact>: <dep> <dep>...`. Spaces in paths are escaped with a backslash.
+fn parse_dep_info(content: &str) -> Option<(Utf8PathBuf, Vec<Utf8PathBuf>)> {
+    let (artifact, deps) = content.lines().next()?.split_once(": ")?;
+
+    let mut paths = vec![];
+    let mut current = String::new();
+    let mut chars = deps.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&' ') => current.push(chars.next()?),
+            ' ' => {
+                if !current.is_empty() {
+                    paths.push(Utf8PathBuf::from(std::mem::take(&mut current)));
+                }
+            },
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        paths.push(Utf8PathBuf::from(current));
+    }
+
+    Some((Utf8PathBuf::from(artifact.replace("\\ ", " ")), paths))
+}
+
 fn build_lint(lint_src: &LintCrateSource, config: &Config) -> Result {
     let mut cmd = config.toolchain.cargo_build_command(config, &lint_src.manifest);
+    cmd.args(config.lock_flag());
 
     // Set output dir. This currently requires unstable options
     cmd.arg("-Z");
@@ -126,19 +203,61 @@ fn build_lint(lint_src: &LintCrateSource, config: &Config) -> Result {
     cmd.arg("--out-dir");
     cmd.arg(config.lint_crate_dir().as_os_str());
 
-    let exit_status = cmd
-        .log()
-        .spawn()
-        .expect("could not run cargo")
-        .wait()
-        .expect("failed to wait for cargo?");
+    // With `--frozen` the artifacts are checked again, to detect if Cargo had
+    // to rebuild something, which isn't covered by the dep-info files, like a
+    // dependency. The diagnostics are still rendered by Cargo.
+    if config.frozen {
+        cmd.arg("--message-format=json-render-diagnostics");
+        cmd.stdout(Stdio::piped());
+    }
+
+    let mut child = cmd.log().spawn().expect("could not run cargo");
 
-    if exit_status.success() {
-        return Ok(());
+    let mut rebuilt = false;
+    if let Some(stdout) = child.stdout.take() {
+        for message in Message::parse_stream(BufReader::new(stdout)) {
+            let message = message.context(|| "Failed to read the output of cargo")?;
+            if let Message::CompilerArtifact(artifact) = message {
+                rebuilt |= !artifact.fresh;
+            }
+        }
     }
 
-    Err(Error::root(format!(
-        "Failed to compile the lint crate {}",
-        lint_src.name.red().bold()
-    )))
+    let exit_status = child.wait().expect("failed to wait for cargo?");
+
+    if !exit_status.success() {
+        return Err(Error::root(format!(
+            "Failed to compile the lint crate {}",
+            lint_src.name.red().bold()
+        )));
+    }
+
+    if rebuilt {
+        return Err(Error::from_kind(ErrorKind::LintCratesOutdated {
+            action: "rebuilt",
+            flag: "--frozen",
+        }));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_dep_info() {
+        let content = "/ws/target/debug/liblints.so: /ws/lints/src/lib.rs /ws/lints/src/with\\ space.rs\n";
+        let (artifact, deps) = parse_dep_info(content).unwrap();
+        assert_eq!(artifact, "/ws/target/debug/liblints.so");
+        assert_eq!(deps, ["/ws/lints/src/lib.rs", "/ws/lints/src/with space.rs"]);
+
+        let content = r"C:\ws\target\debug\lints.dll: C:\ws\lints\src\lib.rs";
+        let (artifact, deps) = parse_dep_info(content).unwrap();
+        assert_eq!(artifact, r"C:\ws\target\debug\lints.dll");
+        assert_eq!(deps, [r"C:\ws\lints\src\lib.rs"]);
+
+        assert!(parse_dep_info("").is_none());
+    }
 }
@@ -98,6 +98,7 @@ fn call_cargo_fetch(manifest: &Utf8Path, config: &Config) -> Result {
     cmd.arg("fetch");
     cmd.arg("--manifest-path");
     cmd.arg(manifest.as_os_str());
+    cmd.args(config.lock_flag());
 
     // Only fetch for the specified target. Cargo will just fetch everything,
     // if the `--target` flag is not specified.
@@ -117,6 +118,15 @@ fn call_cargo_fetch(manifest: &Utf8Path, config: &Config) -> Result {
         return Ok(());
     }
 
+    // Cargo already printed the reason. With one of these flags, it's most
+    // likely, that the lock file or the fetched crates are outdated.
+    if let Some(flag) = config.lock_flag() {
+        return Err(Error::from_kind(ErrorKind::LintCratesOutdated {
+            action: "fetched",
+            flag,
+        }));
+    }
+
     Err(Error::root("cargo fetch failed for lint crates"))
 }
 
@@ -126,6 +136,7 @@ fn call_cargo_metadata(manifest: &Utf8Path, config: &Config) -> Result<Metadata>
         .cargo
         .metadata()
         .manifest_path(manifest)
+        .other_options(config.lock_flag().map(String::from).into_iter().collect::<Vec<_>>())
         .exec()
         .context(|| format!("Failed to get cargo metadata for the lint crates at {manifest}"))
 }
@@ -0,0 +1,73 @@
+//! The driver has no access to the `Cargo.toml` file of the checked package.
+//! This module therefore collects the relevant information of all workspace
+//! packages and serializes it into the `MARKER_MANIFESTS` environment value.
+//! The format is defined in the `README.md` of `marker_adapter`.
+
+use super::Config;
+use crate::error::prelude::*;
+use camino::Utf8Path;
+use cargo_metadata::{DependencyKind, Edition};
+use serde::Serialize;
+
+#[derive(Serialize, Debug)]
+struct ManifestInfo<'a> {
+    manifest_dir: &'a Utf8Path,
+    edition: Edition,
+    features: Vec<FeatureInfo<'a>>,
+    dependencies: Vec<DependencyInfo<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+struct FeatureInfo<'a> {
+    name: &'a str,
+    enables: &'a [String],
+}
+
+#[derive(Serialize, Debug)]
+struct DependencyInfo<'a> {
+    name: &'a str,
+    rename: Option<&'a str>,
+    kind: DependencyKind,
+    optional: bool,
+}
+
+/// Returns the JSON value of the `MARKER_MANIFESTS` environment value for the
+/// packages of the current workspace.
+pub fn manifests_env(config: &Config) -> Result<String> {
+    let metadata = config
+        .toolchain
+        .cargo
+        .metadata()
+        .no_deps()
+        .exec()
+        .context(|| "Couldn't read the manifests of the workspace")?;
+
+    let manifests: Vec<_> = metadata
+        .workspace_packages()
+        .into_iter()
+        .map(|package| ManifestInfo {
+            manifest_dir: package
+                .manifest_path
+                .parent()
+                .expect("the manifest path always points to a file"),
+            edition: package.edition,
+            features: package
+                .features
+                .iter()
+                .map(|(name, enables)| FeatureInfo { name, enables })
+                .collect(),
+            dependencies: package
+                .dependencies
+                .iter()
+                .map(|dep| DependencyInfo {
+                    name: &dep.name,
+                    rename: dep.rename.as_deref(),
+                    kind: dep.kind,
+                    optional: dep.optional,
+                })
+                .collect(),
+        })
+        .collect();
+
+    Ok(serde_json::to_string(&manifests).expect("serializing the manifests can't fail"))
+}
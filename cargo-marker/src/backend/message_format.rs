@@ -0,0 +1,147 @@
+//! This module implements `cargo marker --message-format github`, which prints
+//! every diagnostic as a [GitHub Actions workflow command]. GitHub displays these
+//! commands as annotations, inline on the changed files of a pull request.
+//!
+//! The level of the annotation is taken from the diagnostic emitted by rustc.
+//! Lints promoted by `--deny`, for example via `RUSTFLAGS=--deny warnings`, are
+//! therefore reported as errors.
+//!
+//! [GitHub Actions workflow command]: https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions
+
+use super::report::normalize_path;
+use camino::Utf8Path;
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+use std::fmt::Write;
+
+/// The format, in which diagnostics are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum MessageFormat {
+    /// The rendered diagnostics, like rustc prints them
+    #[default]
+    Human,
+    /// The rendered diagnostics on stderr and GitHub Actions workflow commands
+    /// on stdout
+    Github,
+}
+
+/// Returns the workflow command for the given diagnostic, or `None`, if the
+/// diagnostic is one of rustc's per crate summaries, like "2 warnings emitted"
+/// or "aborting due to 2 previous errors". Summaries have no span and no code.
+pub fn github_command(diag: &Diagnostic, workspace_root: &Utf8Path) -> Option<String> {
+    if diag.spans.is_empty() && diag.code.is_none() {
+        return None;
+    }
+
+    let command = match diag.level {
+        DiagnosticLevel::Error | DiagnosticLevel::Ice => "error",
+        DiagnosticLevel::Warning => "warning",
+        _ => "notice",
+    };
+    let mut properties = vec![];
+    if let Some(span) = diag.spans.iter().find(|span| span.is_primary) {
+        // GitHub expects paths relative to the repository root. Files outside
+        // the workspace keep their absolute path.
+        let file = normalize_path(&workspace_root.join(&span.file_name));
+        let file = file.strip_prefix(workspace_root).unwrap_or(&file);
+        properties.push(("file", file.to_string()));
+        properties.push(("line", span.line_start.to_string()));
+        properties.push(("endLine", span.line_end.to_string()));
+        if span.line_start == span.line_end {
+            // GitHub only supports columns for annotations on a single line
+            properties.push(("col", span.column_start.to_string()));
+            properties.push(("endColumn", span.column_end.to_string()));
+        }
+    }
+    if let Some(code) = &diag.code {
+        properties.push(("title", code.code.clone()));
+    }
+
+    let mut result = format!("::{command}");
+    for (index, (key, value)) in properties.iter().enumerate() {
+        let separator = if index == 0 { ' ' } else { ',' };
+        write!(result, "{separator}{key}={}", escape_property(value)).unwrap();
+    }
+    write!(result, "::{}", escape_data(&diag.message)).unwrap();
+    Some(result)
+}
+
+/// Escapes the message of a workflow command.
+fn escape_data(value: &str) -> String {
+    value.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// Escapes the value of a workflow command property. In addition to the
+/// message escapes, this escapes the separators of properties.
+fn escape_property(value: &str) -> String {
+    escape_data(value).replace(':', "%3A").replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn diagnostic(level: &str, message: &str, line_end: usize) -> Diagnostic {
+        let diag = json!({
+            "message": message,
+            "code": { "code": "marker::lints::a", "explanation": null },
+            "level": level,
+            "spans": [{
+                "file_name": "a/src/../src/lib.rs",
+                "byte_start": 0,
+                "byte_end": 1,
+                "line_start": 3,
+                "line_end": line_end,
+                "column_start": 5,
+                "column_end": 9,
+                "is_primary": true,
+                "text": [],
+                "label": null,
+                "suggested_replacement": null,
+                "suggestion_applicability": null,
+                "expansion": null,
+            }],
+            "children": [],
+            "rendered": null,
+        });
+        serde_json::from_value(diag).unwrap()
+    }
+
+    #[test]
+    fn test_github_command() {
+        let root = Utf8Path::new("/ws");
+        assert_eq!(
+            github_command(&diagnostic("warning", "a finding", 3), root).unwrap(),
+            "::warning file=a/src/lib.rs,line=3,endLine=3,col=5,endColumn=9,title=marker%3A%3Alints%3A%3Aa::a finding"
+        );
+        assert_eq!(
+            github_command(&diagnostic("error", "100% wrong,\nreally", 4), root).unwrap(),
+            "::error file=a/src/lib.rs,line=3,endLine=4,title=marker%3A%3Alints%3A%3Aa::100%25 wrong,%0Areally"
+        );
+    }
+
+    #[test]
+    fn test_github_command_skips_summaries() {
+        let summary = json!({
+            "message": "2 warnings emitted",
+            "code": null,
+            "level": "warning",
+            "spans": [],
+            "children": [],
+            "rendered": null,
+        });
+        let summary: Diagnostic = serde_json::from_value(summary).unwrap();
+        assert_eq!(github_command(&summary, Utf8Path::new("/ws")), None);
+
+        let aborting = json!({
+            "message": "aborting due to 2 previous errors",
+            "code": null,
+            "level": "error",
+            "spans": [],
+            "children": [],
+            "rendered": null,
+        });
+        let aborting: Diagnostic = serde_json::from_value(aborting).unwrap();
+        assert_eq!(github_command(&aborting, Utf8Path::new("/ws")), None);
+    }
+}
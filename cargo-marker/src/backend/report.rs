@@ -0,0 +1,384 @@
+//! This module implements the aggregated report used by `cargo marker --report <format>`.
+//!
+//! Cargo invokes the driver once per crate, which would usually print the
+//! diagnostics of every crate as soon as it has been checked. For the report,
+//! the diagnostics of all crates are collected first. Diagnostics in code, which
+//! is compiled multiple times, like shared path dependencies or a library that
+//! is also compiled as a test target, are deduplicated. The report is then
+//! emitted at once, sorted by location and with a summary per lint.
+//!
+//! `cargo marker --group-by lint` uses the same report to group the diagnostics
+//! of all crates by lint, instead of emitting them in source order.
+
+use crate::error::prelude::*;
+use camino::{Utf8Component, Utf8Path, Utf8PathBuf};
+use cargo_metadata::diagnostic::{Diagnostic, DiagnosticLevel};
+use cargo_metadata::CompilerMessage;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::io::Write;
+use yansi::Paint;
+
+/// The format of the aggregated report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ReportFormat {
+    /// The rendered diagnostics, followed by a summary
+    Human,
+    /// A single JSON document, containing all diagnostics and the summary
+    Json,
+    /// A single SARIF 2.1.0 document
+    Sarif,
+}
+
+/// The order in which the diagnostics are emitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum GroupBy {
+    /// Sorted by location, in source order
+    #[default]
+    Location,
+    /// Grouped by lint, with a header per lint. The diagnostics of each lint
+    /// are sorted by location
+    Lint,
+}
+
+/// The diagnostics of all checked crates.
+#[derive(Debug)]
+pub struct Report {
+    workspace_root: Utf8PathBuf,
+    group_by: GroupBy,
+    entries: BTreeMap<DiagKey, ReportEntry>,
+}
+
+/// The key used to sort and deduplicate diagnostics. The field order defines
+/// the order of the report.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct DiagKey {
+    /// Diagnostics without a primary span are placed at the end
+    no_span: bool,
+    file: Utf8PathBuf,
+    line_start: usize,
+    column_start: usize,
+    line_end: usize,
+    column_end: usize,
+    lint: Option<String>,
+    message: String,
+}
+
+#[derive(Debug)]
+struct ReportEntry {
+    diag: Diagnostic,
+    /// The packages, which emitted this diagnostic
+    packages: Vec<String>,
+}
+
+impl Report {
+    pub fn new(workspace_root: Utf8PathBuf, group_by: GroupBy) -> Self {
+        Self {
+            workspace_root,
+            group_by,
+            entries: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the diagnostic of the given message to the report, unless an equal
+    /// diagnostic has already been emitted by another crate.
+    pub fn add(&mut self, msg: CompilerMessage) {
+        let diag = msg.message;
+        // Rustc's summaries, like "2 warnings emitted", are specific to a single
+        // crate. The report provides its own summary instead.
+        if diag.spans.is_empty() && diag.code.is_none() && diag.level == DiagnosticLevel::Warning {
+            return;
+        }
+
+        let primary = diag.spans.iter().find(|span| span.is_primary);
+        let key = DiagKey {
+            no_span: primary.is_none(),
+            file: primary.map_or_else(Utf8PathBuf::new, |span| {
+                normalize_path(&self.workspace_root.join(&span.file_name))
+            }),
+            line_start: primary.map_or(0, |span| span.line_start),
+            column_start: primary.map_or(0, |span| span.column_start),
+            line_end: primary.map_or(0, |span| span.line_end),
+            column_end: primary.map_or(0, |span| span.column_end),
+            lint: diag.code.as_ref().map(|code| code.code.clone()),
+            message: diag.message.clone(),
+        };
+
+        let package = msg.package_id.repr;
+        let entry = self
+            .entries
+            .entry(key)
+            .or_insert_with(|| ReportEntry { diag, packages: vec![] });
+        if !entry.packages.contains(&package) {
+            entry.packages.push(package);
+        }
+    }
+
+    /// The number of diagnostics per lint. Diagnostics without a lint, like
+    /// compiler errors, are not included.
+    fn summary(&self) -> BTreeMap<&str, usize> {
+        let mut summary = BTreeMap::new();
+        for key in self.entries.keys() {
+            if let Some(lint) = &key.lint {
+                *summary.entry(lint.as_str()).or_default() += 1;
+            }
+        }
+        summary
+    }
+
+    /// Returns the entries in the order of [`GroupBy`]. Diagnostics without a
+    /// lint, like compiler errors, are placed after all lint groups.
+    fn ordered_entries(&self) -> Vec<(&DiagKey, &ReportEntry)> {
+        let mut entries: Vec<_> = self.entries.iter().collect();
+        if self.group_by == GroupBy::Lint {
+            // The sort is stable, each group therefore remains sorted by location
+            entries.sort_by_key(|&(key, _)| (key.lint.is_none(), key.lint.as_deref()));
+        }
+        entries
+    }
+
+    /// Emits the report in the given format. The human format is printed to
+    /// stderr, like normal diagnostics. JSON and SARIF documents are written to
+    /// the given file or stdout.
+    pub fn emit(&self, format: ReportFormat, output: Option<&Utf8Path>) -> Result {
+        let document = match format {
+            ReportFormat::Human => {
+                self.print_human();
+                return Ok(());
+            },
+            ReportFormat::Json => self.to_json(),
+            ReportFormat::Sarif => self.to_sarif(),
+        };
+        let document = serde_json::to_string_pretty(&document).expect("the report can be serialized");
+
+        if let Some(path) = output {
+            return std::fs::write(path, document).context(|| format!("Failed to write the report to `{path}`"));
+        }
+
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{document}").context(|| "Failed to write the report to stdout")
+    }
+
+    fn print_human(&self) {
+        let summary = self.summary();
+        let mut current_group = None;
+        for (key, entry) in self.ordered_entries() {
+            if self.group_by == GroupBy::Lint && current_group != Some(key.lint.as_deref()) {
+                current_group = Some(key.lint.as_deref());
+                match key.lint.as_deref() {
+                    Some(lint) => {
+                        let count = summary[lint];
+                        eprintln!(
+                            "{}: `{lint}` ({count} diagnostic{})\n",
+                            "lint".bold(),
+                            if count == 1 { "" } else { "s" }
+                        );
+                    },
+                    None => eprintln!("{}\n", "other diagnostics".bold()),
+                }
+            }
+            if let Some(rendered) = &entry.diag.rendered {
+                eprint!("{rendered}");
+            }
+        }
+
+        let total: usize = summary.values().sum();
+        eprintln!(
+            "{}: {total} diagnostic{} emitted by lints",
+            "summary".bold(),
+            if total == 1 { "" } else { "s" }
+        );
+        for (lint, count) in summary {
+            eprintln!("{count:>6} {lint}");
+        }
+    }
+
+    fn to_json(&self) -> serde_json::Value {
+        let diagnostics: Vec<_> = self
+            .ordered_entries()
+            .into_iter()
+            .map(|(_, entry)| {
+                json!({
+                    "packages": entry.packages,
+                    "diagnostic": entry.diag,
+                })
+            })
+            .collect();
+        let summary = self.summary();
+
+        json!({
+            "diagnostics": diagnostics,
+            "summary": {
+                "total": summary.values().sum::<usize>(),
+                "lints": summary,
+            },
+        })
+    }
+
+    fn to_sarif(&self) -> serde_json::Value {
+        let rules: Vec<_> = self.summary().into_keys().map(|lint| json!({ "id": lint })).collect();
+        let results: Vec<_> = self
+            .ordered_entries()
+            .into_iter()
+            .map(|(key, entry)| {
+                let level = match entry.diag.level {
+                    DiagnosticLevel::Error | DiagnosticLevel::Ice => "error",
+                    DiagnosticLevel::Warning => "warning",
+                    _ => "note",
+                };
+                let mut result = json!({
+                    "level": level,
+                    "message": { "text": entry.diag.message },
+                });
+                if let Some(lint) = &key.lint {
+                    result["ruleId"] = json!(lint);
+                }
+                if !key.no_span {
+                    result["locations"] = json!([{
+                        "physicalLocation": {
+                            "artifactLocation": self.sarif_artifact_location(&key.file),
+                            "region": {
+                                "startLine": key.line_start,
+                                "startColumn": key.column_start,
+                                "endLine": key.line_end,
+                                "endColumn": key.column_end,
+                            },
+                        },
+                    }]);
+                }
+                result
+            })
+            .collect();
+
+        json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": "marker",
+                        "informationUri": "https://github.com/rust-marker/marker",
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "rules": rules,
+                    },
+                },
+                "originalUriBaseIds": {
+                    "%SRCROOT%": { "uri": format!("file://{}/", self.workspace_root) },
+                },
+                "results": results,
+            }],
+        })
+    }
+
+    /// Files in the workspace are referenced relative to the workspace root,
+    /// other files by their absolute path.
+    fn sarif_artifact_location(&self, file: &Utf8Path) -> serde_json::Value {
+        match file.strip_prefix(&self.workspace_root) {
+            Ok(relative) => json!({ "uri": relative, "uriBaseId": "%SRCROOT%" }),
+            Err(_) => json!({ "uri": format!("file://{file}") }),
+        }
+    }
+}
+
+/// Lexically removes `.` and `..` components from the path. Files included via
+/// `#[path]` attributes, are otherwise reported relative to the including crate.
+pub(super) fn normalize_path(path: &Utf8Path) -> Utf8PathBuf {
+    let mut normalized = Utf8PathBuf::new();
+    for component in path.components() {
+        match component {
+            Utf8Component::CurDir => {},
+            Utf8Component::ParentDir => {
+                normalized.pop();
+            },
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(package: &str, file: &str, line_nr: usize, lint: &str) -> CompilerMessage {
+        let msg = json!({
+            "reason": "compiler-message",
+            "package_id": package,
+            "manifest_path": "/ws/Cargo.toml",
+            "target": {
+                "name": package,
+                "kind": ["lib"],
+                "crate_types": ["lib"],
+                "src_path": "/ws/src/lib.rs",
+                "edition": "2021",
+                "doctest": true,
+                "test": true,
+            },
+            "message": {
+                "message": "a finding",
+                "code": { "code": lint, "explanation": null },
+                "level": "warning",
+                "spans": [{
+                    "file_name": file,
+                    "byte_start": 0,
+                    "byte_end": 1,
+                    "line_start": line_nr,
+                    "line_end": line_nr,
+                    "column_start": 1,
+                    "column_end": 2,
+                    "is_primary": true,
+                    "text": [],
+                    "label": null,
+                    "suggested_replacement": null,
+                    "suggestion_applicability": null,
+                    "expansion": null,
+                }],
+                "children": [],
+                "rendered": null,
+            },
+        });
+        serde_json::from_value(msg).unwrap()
+    }
+
+    #[test]
+    fn test_report_dedup_and_sort() {
+        let mut report = Report::new(Utf8PathBuf::from("/ws"), GroupBy::Location);
+        report.add(message("b", "b/src/lib.rs", 3, "marker::lints::a"));
+        report.add(message("a", "shared/mod.rs", 1, "marker::lints::b"));
+        report.add(message("b", "b/src/../../shared/mod.rs", 1, "marker::lints::b"));
+        report.add(message("a", "a/src/lib.rs", 7, "marker::lints::a"));
+
+        let order: Vec<_> = report.entries.keys().map(|key| key.file.as_str()).collect();
+        assert_eq!(order, ["/ws/a/src/lib.rs", "/ws/b/src/lib.rs", "/ws/shared/mod.rs"]);
+
+        let shared = report.entries.values().last().unwrap();
+        assert_eq!(shared.packages, ["a", "b"]);
+
+        let summary: Vec<_> = report.summary().into_iter().collect();
+        assert_eq!(summary, [("marker::lints::a", 2), ("marker::lints::b", 1)]);
+    }
+
+    #[test]
+    fn test_report_group_by_lint() {
+        let mut report = Report::new(Utf8PathBuf::from("/ws"), GroupBy::Lint);
+        report.add(message("a", "a/src/lib.rs", 9, "marker::lints::b"));
+        report.add(message("a", "a/src/lib.rs", 5, "marker::lints::a"));
+        report.add(message("a", "a/src/lib.rs", 1, "marker::lints::b"));
+        report.add(message("a", "a/src/lib.rs", 3, "marker::lints::a"));
+
+        let order: Vec<_> = report
+            .ordered_entries()
+            .into_iter()
+            .map(|(key, _)| (key.lint.as_deref().unwrap(), key.line_start))
+            .collect();
+        assert_eq!(
+            order,
+            [
+                ("marker::lints::a", 3),
+                ("marker::lints::a", 5),
+                ("marker::lints::b", 1),
+                ("marker::lints::b", 9),
+            ]
+        );
+    }
+}
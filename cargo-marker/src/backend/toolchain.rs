@@ -30,6 +30,20 @@ impl Toolchain {
         cmd
     }
 
+    /// This returns a command, which invokes the driver directly. The driver is
+    /// called via rustup, to ensure that rustc's shared libraries are available.
+    pub fn driver_command(&self) -> Command {
+        let toolchain = self
+            .cargo
+            .toolchain
+            .clone()
+            .unwrap_or_else(|| default_driver_info().toolchain);
+        let mut cmd = Command::new("rustup");
+        cmd.args(["run", &toolchain]);
+        cmd.arg(self.driver_path.as_os_str());
+        cmd
+    }
+
     pub fn cargo_build_command(&self, config: &Config, manifest: &Utf8Path) -> Command {
         let mut cmd = self.cargo.command();
         cmd.arg("build");
@@ -56,8 +56,17 @@ impl MarkerCli {
     }
 
     pub(crate) fn run(self) -> Result {
-        let manifest_path = crate::backend::cargo::Cargo::default().cargo_locate_project()?;
-        let config = Config::try_from_manifest(&manifest_path)?;
+        let file_mode = match &self.command {
+            Some(CliCommand::Check(cmd)) => cmd.file.is_some(),
+            Some(_) => false,
+            None => self.check.file.is_some(),
+        };
+        let config = match crate::backend::cargo::Cargo::default().cargo_locate_project() {
+            Ok(manifest_path) => Config::try_from_manifest(&manifest_path)?,
+            // Single files can be linted outside of Cargo projects
+            Err(_) if file_mode => None,
+            Err(err) => return Err(err),
+        };
 
         let Some(command) = self.command else {
             return self.check.run(config);
@@ -94,6 +103,10 @@ mod tests {
         assert!(cli.check.cargo_args[0] == "ducks");
         assert!(cli.check.cargo_args[1] == "penguins");
 
+        let cli = MarkerCli::parse_from(["cargo-marker", "--frozen"]);
+        assert!(cli.check.frozen);
+        assert!(!cli.check.locked);
+
         let cli = MarkerCli::parse_from(["cargo-marker", "check", "--", "ducks", "penguins"]);
         assert!(cli.check.cargo_args.is_empty());
         if let Some(CliCommand::Check(check_args)) = cli.command {
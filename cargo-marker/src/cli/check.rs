@@ -1,11 +1,13 @@
-use crate::config::{Config, LintDependency};
+use crate::config::{Config, LintDependency, LintDependencyEntry, LintLevel};
 use crate::error::prelude::*;
+use crate::observability::prelude::*;
 use crate::{backend, utils};
-use camino::Utf8Path;
+use camino::{Utf8Path, Utf8PathBuf};
 use clap::Args;
 use std::collections::BTreeMap;
 
 #[derive(Args, Debug)]
+#[allow(clippy::struct_excessive_bools)]
 #[command(override_usage = "cargo marker check [OPTIONS] -- <CARGO ARGS>")]
 pub(crate) struct CheckCommand {
     /// Specifies lint crates which should be used. (Lints in `Cargo.toml` will be ignored)
@@ -16,7 +18,80 @@ pub(crate) struct CheckCommand {
     #[arg(long)]
     pub(crate) forward_rust_flags: bool,
 
-    /// Arguments which will be forwarded to Cargo. See `cargo check --help`
+    /// Only report diagnostics on lines, which have changed since the given git revision
+    #[arg(long, value_name = "REV", conflicts_with = "file")]
+    pub(crate) diff: Option<String>,
+
+    /// Require the `Cargo.lock` file of the lint crates to be up to date
+    #[arg(long)]
+    pub(crate) locked: bool,
+
+    /// Require the lint crates to be fetched and compiled already. This implies
+    /// `--locked` and prevents network access
+    #[arg(long)]
+    pub(crate) frozen: bool,
+
+    /// Add a note to every diagnostic, naming the lint crate that emitted it
+    #[arg(long)]
+    pub(crate) show_lint_source: bool,
+
+    /// Collect the diagnostics of all crates and emit them as a single report,
+    /// sorted by location, deduplicated and followed by a summary per lint
+    #[arg(long, value_enum, value_name = "FORMAT", conflicts_with = "file")]
+    pub(crate) report: Option<backend::report::ReportFormat>,
+
+    /// Write JSON and SARIF reports to this file instead of stdout
+    #[arg(long, value_name = "PATH", requires = "report")]
+    pub(crate) report_output: Option<Utf8PathBuf>,
+
+    /// The order of the diagnostics. `lint` collects the diagnostics of all crates
+    /// and groups them by lint, with a header and count per lint
+    #[arg(
+        long,
+        value_enum,
+        value_name = "ORDER",
+        default_value = "location",
+        conflicts_with = "file"
+    )]
+    pub(crate) group_by: backend::report::GroupBy,
+
+    /// The format of the printed diagnostics. `github` additionally prints every
+    /// diagnostic as a GitHub Actions workflow command, which is shown as an
+    /// annotation on pull requests
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FMT",
+        default_value = "human",
+        conflicts_with_all = ["report", "group_by", "file"]
+    )]
+    pub(crate) message_format: backend::message_format::MessageFormat,
+
+    /// Only run the lint with the given name, like `marker::<lint_crate>::<lint_name>`.
+    /// Other lint crates are not loaded and all other lints are disabled
+    #[arg(long, value_name = "LINT")]
+    pub(crate) only: Option<String>,
+
+    /// Stop emitting a lint after the given number of diagnostics per crate.
+    /// The number of suppressed diagnostics is reported in a note
+    #[arg(long, value_name = "N")]
+    pub(crate) max_per_lint: Option<usize>,
+
+    /// Apply the machine-applicable suggestions of the emitted diagnostics.
+    /// Suggestions are skipped, if they overlap with another suggestion
+    #[arg(long, conflicts_with = "file")]
+    pub(crate) fix: bool,
+
+    /// Lint a single file without Cargo. The file is used as the crate root
+    #[arg(long, value_name = "PATH")]
+    pub(crate) file: Option<Utf8PathBuf>,
+
+    /// The Rust edition used to lint the file given by `--file`
+    #[arg(long, default_value = "2021", requires = "file")]
+    pub(crate) edition: String,
+
+    /// Arguments which will be forwarded to Cargo. See `cargo check --help`.
+    /// With `--file` they are forwarded to rustc instead.
     #[clap(last = true)]
     pub(crate) cargo_args: Vec<String>,
 }
@@ -27,8 +102,19 @@ impl CheckCommand {
     }
 
     pub(crate) fn compile_lints(self, config: Option<Config>) -> Result<CompiledLints> {
+        // The policy is applied, even if the lints are specified via the CLI
+        let policy = config.as_ref().map(|config| config.policy.clone()).unwrap_or_default();
+        let ignore_paths = config
+            .as_ref()
+            .map(|config| config.ignore_paths.clone())
+            .unwrap_or_default();
+        let include_paths = config
+            .as_ref()
+            .map(|config| config.include_paths.clone())
+            .unwrap_or_default();
+
         // determine lints
-        let lints: BTreeMap<_, _> = self
+        let mut lints: BTreeMap<_, _> = self
             .lints_from_cli()?
             .or_else(|| config.map(|config| config.lints))
             .into_iter()
@@ -40,6 +126,10 @@ impl CheckCommand {
         if lints.is_empty() {
             return Err(Error::from_kind(ErrorKind::LintsNotFound));
         }
+        let mut lint_levels = filter_policy(policy, &lints);
+        if let Some(only) = &self.only {
+            select_only_lint(only, &mut lints, &mut lint_levels)?;
+        }
 
         // If this is a dev build, we want to rebuild the driver before checking
         if utils::is_local_driver() {
@@ -48,9 +138,27 @@ impl CheckCommand {
 
         // Configure backend
         let toolchain = backend::toolchain::Toolchain::try_find_toolchain()?;
+        let base_conf = if self.file.is_some() {
+            backend::Config::base_for_file(toolchain)?
+        } else {
+            backend::Config::try_base_from(toolchain)?
+        };
         let backend_conf = backend::Config {
             lints,
-            ..backend::Config::try_base_from(toolchain)?
+            lint_levels,
+            ignore_paths,
+            include_paths,
+            diff_base: self.diff,
+            locked: self.locked,
+            frozen: self.frozen,
+            show_lint_source: self.show_lint_source,
+            max_per_lint: self.max_per_lint,
+            report: self.report,
+            report_output: self.report_output,
+            group_by: self.group_by,
+            message_format: self.message_format,
+            fix: self.fix,
+            ..base_conf
         };
 
         // Prepare backend
@@ -59,6 +167,7 @@ impl CheckCommand {
         Ok(CompiledLints {
             backend_conf,
             info,
+            file: self.file.map(|file| (file, self.edition)),
             cargo_args: self.cargo_args,
         })
     }
@@ -76,7 +185,7 @@ impl CheckCommand {
 
         let path = Utf8Path::new(".");
 
-        let Config { lints } = Config::try_from_str(&virtual_manifest, path)?.unwrap_or_else(|| {
+        let Config { lints, .. } = Config::try_from_str(&virtual_manifest, path)?.unwrap_or_else(|| {
             panic!(
                 "BUG: the config must definitely contain the marker metadata:\
                 \n---\n{virtual_manifest}\n---"
@@ -87,16 +196,215 @@ impl CheckCommand {
     }
 }
 
+/// Removes all entries from the policy, which don't belong to one of the given
+/// lint crates. A warning is emitted for every removed entry, as they would
+/// otherwise be silently ignored. Entries of lints, which are disabled by the
+/// lint crate entry, are skipped by the driver.
+fn filter_policy(
+    policy: BTreeMap<String, LintLevel>,
+    lints: &BTreeMap<String, LintDependencyEntry>,
+) -> BTreeMap<String, LintLevel> {
+    policy
+        .into_iter()
+        .filter(|(name, _)| {
+            // Lint names have the form `marker::<lint_crate>::<lint_name>`
+            let Some((lint_crate, _)) = name.strip_prefix("marker::").and_then(|rest| rest.split_once("::")) else {
+                warn_unknown_policy_lint(name);
+                return false;
+            };
+            if !lints.keys().any(|name| name.replace('-', "_") == lint_crate) {
+                warn_unknown_policy_lint(name);
+                return false;
+            }
+            true
+        })
+        .collect()
+}
+
+/// Restricts the lint crates and the policy to the single lint selected by
+/// `--only`. The other lint crates are removed and all other lints of the
+/// selected crate are disabled. The selection overrides the `disabled-lints`
+/// of the lint crate entry. The lint is emitted at least as a warning, even if
+/// its default level or the policy would allow it.
+fn select_only_lint(
+    only: &str,
+    lints: &mut BTreeMap<String, LintDependencyEntry>,
+    lint_levels: &mut BTreeMap<String, LintLevel>,
+) -> Result {
+    let Some((lint_crate, lint_name)) = only.strip_prefix("marker::").and_then(|rest| rest.split_once("::")) else {
+        return Err(Error::root(format!(
+            "The lint name `{only}` given to `--only` is invalid. \
+            Lint names have the form `marker::<lint_crate>::<lint_name>`"
+        )));
+    };
+    let lint_crate = lint_crate.replace('-', "_");
+
+    lints.retain(|name, _| name.replace('-', "_") == lint_crate);
+    let Some(entry) = lints.values_mut().next() else {
+        return Err(Error::root(format!(
+            "The lint `{only}` given to `--only` doesn't belong to any used lint crate"
+        )));
+    };
+    entry.enabled_lints = Some(vec![lint_name.to_string()]);
+    entry.disabled_lints.clear();
+
+    let full_name = format!("marker::{lint_crate}::{lint_name}").to_ascii_lowercase();
+    let level = lint_levels
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(&full_name))
+        .map(|(_, level)| *level)
+        .filter(|level| *level != LintLevel::Allow)
+        .unwrap_or(LintLevel::Warn);
+    *lint_levels = BTreeMap::from([(full_name, level)]);
+
+    Ok(())
+}
+
+fn warn_unknown_policy_lint(name: &str) {
+    warn!(
+        "The lint `{name}` in `workspace.metadata.marker.policy` doesn't belong to any \
+        used lint crate. Lint names have the form `marker::<lint_crate>::<lint_name>`. \
+        This entry will be ignored"
+    );
+}
+
 /// The result of discovering and compiling the lint libraries
 #[derive(Debug)]
 pub(crate) struct CompiledLints {
     pub(crate) backend_conf: backend::Config,
     pub(crate) info: backend::CheckInfo,
+    /// The file and edition, if a single file should be linted without Cargo
+    pub(crate) file: Option<(Utf8PathBuf, String)>,
     pub(crate) cargo_args: Vec<String>,
 }
 
 impl CompiledLints {
     fn lint(self) -> Result {
+        if let Some((file, edition)) = &self.file {
+            return backend::run_check_file(&self.backend_conf, self.info, file, edition, &self.cargo_args);
+        }
+
         backend::run_check(&self.backend_conf, self.info, &self.cargo_args)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filter_policy() {
+        let manifest = r#"
+[workspace.metadata.marker.lints]
+lint-crate = "0.1.0"
+
+[workspace.metadata.marker.policy]
+"marker::lint_crate::lint_a" = "deny"
+"marker::lint_crate::lint_b" = "allow"
+"marker::other_crate::lint_c" = "warn"
+"marker::lint_crate" = "warn"
+"unused_variables" = "forbid"
+"#;
+        let config = Config::try_from_str(manifest, Utf8Path::new(".")).unwrap().unwrap();
+        let lints = config
+            .lints
+            .into_iter()
+            .map(|(name, dep)| (name, dep.into_dep_entry()))
+            .collect();
+
+        let levels = filter_policy(config.policy, &lints);
+        let flags: Vec<_> = levels.iter().map(|(lint, level)| level.to_rustc_flag(lint)).collect();
+        assert_eq!(flags, ["-Dmarker::lint_crate::lint_a", "-Amarker::lint_crate::lint_b"]);
+    }
+
+    #[test]
+    fn test_filter_policy_with_lint_filter() {
+        let manifest = r#"
+[workspace.metadata.marker.lints]
+lint-crate = { path = ".", disabled-lints = ["lint_b"] }
+other-crate = { path = ".", enabled-lints = ["LINT_C"] }
+
+[workspace.metadata.marker.policy]
+"marker::lint_crate::lint_a" = "deny"
+"marker::lint_crate::lint_b" = "allow"
+"marker::other_crate::lint_c" = "warn"
+"marker::other_crate::lint_d" = "warn"
+"#;
+        let config = Config::try_from_str(manifest, Utf8Path::new(".")).unwrap().unwrap();
+        let lints = config
+            .lints
+            .into_iter()
+            .map(|(name, dep)| (name, dep.into_dep_entry()))
+            .collect();
+
+        let levels = filter_policy(config.policy, &lints);
+        let flags: Vec<_> = levels.iter().map(|(lint, level)| level.to_rustc_flag(lint)).collect();
+        // The levels of disabled lints are skipped by the driver
+        assert_eq!(
+            flags,
+            [
+                "-Dmarker::lint_crate::lint_a",
+                "-Amarker::lint_crate::lint_b",
+                "-Wmarker::other_crate::lint_c",
+                "-Wmarker::other_crate::lint_d"
+            ]
+        );
+        assert_eq!(lints["lint-crate"].disabled_lints, ["lint_b"]);
+        assert_eq!(lints["other-crate"].enabled_lints, Some(vec!["LINT_C".to_string()]));
+    }
+
+    #[test]
+    fn test_select_only_lint() {
+        let manifest = r#"
+[workspace.metadata.marker.lints]
+lint-crate = { path = ".", disabled-lints = ["lint_b"] }
+other-crate = "0.1.0"
+
+[workspace.metadata.marker.policy]
+"marker::lint_crate::lint_a" = "allow"
+"marker::lint_crate::lint_c" = "deny"
+"#;
+        let config = Config::try_from_str(manifest, Utf8Path::new(".")).unwrap().unwrap();
+        let lints: BTreeMap<_, _> = config
+            .lints
+            .into_iter()
+            .map(|(name, dep)| (name, dep.into_dep_entry()))
+            .collect();
+        let levels = filter_policy(config.policy, &lints);
+
+        let (mut only_lints, mut only_levels) = (lints.clone(), levels.clone());
+        select_only_lint("marker::lint_crate::LINT_A", &mut only_lints, &mut only_levels).unwrap();
+        assert_eq!(only_lints.keys().collect::<Vec<_>>(), ["lint-crate"]);
+        assert_eq!(only_lints["lint-crate"].enabled_lints, Some(vec!["LINT_A".to_string()]));
+        assert!(only_lints["lint-crate"].disabled_lints.is_empty());
+        let flags: Vec<_> = only_levels
+            .iter()
+            .map(|(lint, level)| level.to_rustc_flag(lint))
+            .collect();
+        assert_eq!(flags, ["-Wmarker::lint_crate::lint_a"]);
+
+        let (mut only_lints, mut only_levels) = (lints.clone(), levels.clone());
+        select_only_lint("marker::lint_crate::lint_c", &mut only_lints, &mut only_levels).unwrap();
+        let flags: Vec<_> = only_levels
+            .iter()
+            .map(|(lint, level)| level.to_rustc_flag(lint))
+            .collect();
+        assert_eq!(flags, ["-Dmarker::lint_crate::lint_c"]);
+
+        // `--only` overrides the `disabled-lints` of the lint crate entry
+        let (mut only_lints, mut only_levels) = (lints.clone(), levels.clone());
+        select_only_lint("marker::lint_crate::lint_b", &mut only_lints, &mut only_levels).unwrap();
+        assert_eq!(only_lints["lint-crate"].enabled_lints, Some(vec!["lint_b".to_string()]));
+        assert!(only_lints["lint-crate"].disabled_lints.is_empty());
+        let flags: Vec<_> = only_levels
+            .iter()
+            .map(|(lint, level)| level.to_rustc_flag(lint))
+            .collect();
+        assert_eq!(flags, ["-Wmarker::lint_crate::lint_b"]);
+
+        for invalid in ["lint_a", "marker::unknown_crate::lint_a"] {
+            let (mut only_lints, mut only_levels) = (lints.clone(), levels.clone());
+            assert!(select_only_lint(invalid, &mut only_lints, &mut only_levels).is_err());
+        }
+    }
+}
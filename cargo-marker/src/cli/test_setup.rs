@@ -14,7 +14,14 @@ impl TestSetupCommand {
     pub(crate) fn run(self, config: Option<Config>) -> Result {
         let lints = self.check.compile_lints(config)?;
 
-        for (name, value) in lints.info.env {
+        // UI tests aren't packages of the workspace, the manifests are
+        // therefore set by the tests themselves.
+        for (name, value) in lints
+            .info
+            .env
+            .into_iter()
+            .filter(|(name, _)| *name != "MARKER_MANIFESTS")
+        {
             println!("env:{name}={value}");
         }
 
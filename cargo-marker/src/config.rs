@@ -36,6 +36,44 @@ struct WorkspaceMetadata {
 pub struct Config {
     /// A list of lints.
     pub lints: BTreeMap<String, LintDependency>,
+    /// The levels of individual lints, like:
+    /// `"marker::lint_crate::lint_name" = "deny"`
+    ///
+    /// These levels override the default levels defined by the lint crates.
+    #[serde(default)]
+    pub policy: BTreeMap<String, LintLevel>,
+    /// Glob patterns of files, relative to the workspace root, in which
+    /// diagnostics of lints should be suppressed, like `**/generated/**`.
+    #[serde(default)]
+    pub ignore_paths: Vec<String>,
+    /// Glob patterns of files, relative to the workspace root, to which the
+    /// diagnostics of lints should be limited, like `crates/**`. All files are
+    /// included, if this is empty.
+    #[serde(default)]
+    pub include_paths: Vec<String>,
+}
+
+/// A lint level, used by the `workspace.metadata.marker.policy` section.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    Allow,
+    Warn,
+    Deny,
+    Forbid,
+}
+
+impl LintLevel {
+    /// Returns the rustc flag, which sets the given lint to this level.
+    pub fn to_rustc_flag(self, lint: &str) -> String {
+        let flag = match self {
+            LintLevel::Allow => 'A',
+            LintLevel::Warn => 'W',
+            LintLevel::Deny => 'D',
+            LintLevel::Forbid => 'F',
+        };
+        format!("-{flag}{lint}")
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -67,6 +105,9 @@ impl LintDependency {
                 package: None,
                 default_features: None,
                 features: None,
+                enabled_lints: None,
+                disabled_lints: vec![],
+                config: None,
             },
             LintDependency::Full(entry) => entry,
         }
@@ -82,6 +123,17 @@ pub struct LintDependencyEntry {
     #[serde(rename = "default-features")]
     pub(crate) default_features: Option<bool>,
     pub(crate) features: Option<Vec<String>>,
+    /// The lints of this lint crate, which should be enabled. All lints are
+    /// enabled by default. The filter is applied by the driver.
+    #[serde(rename = "enabled-lints", skip_serializing)]
+    pub(crate) enabled_lints: Option<Vec<String>>,
+    /// The lints of this lint crate, which should be disabled.
+    #[serde(rename = "disabled-lints", default, skip_serializing)]
+    pub(crate) disabled_lints: Vec<String>,
+    /// The configuration of the lint crate, which is deserialized by the lint
+    /// crate into its config type.
+    #[serde(skip_serializing)]
+    pub(crate) config: Option<toml::Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -157,6 +209,57 @@ impl Config {
         for (package, lint) in &mut self.lints {
             lint.normalize(package, workspace_path)?;
         }
+        let patterns = [
+            ("ignore_paths", &self.ignore_paths),
+            ("include_paths", &self.include_paths),
+        ];
+        for (key, pattern) in patterns
+            .iter()
+            .flat_map(|(key, patterns)| patterns.iter().map(move |pattern| (key, pattern)))
+        {
+            glob::Pattern::new(pattern).context(|| {
+                format!(
+                    "Invalid glob pattern in {}",
+                    display::toml(&format!("{key} = [\"{pattern}\"]"))
+                )
+            })?;
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_path_patterns() {
+        let path = Utf8Path::new("/ws/Cargo.toml");
+        let config = Config::try_from_str(
+            r#"
+            [workspace.metadata.marker]
+            lints = {}
+            ignore_paths = ["**/generated/**"]
+            include_paths = ["crates/*/src/**"]
+            "#,
+            path,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(config.ignore_paths, ["**/generated/**"]);
+        assert_eq!(config.include_paths, ["crates/*/src/**"]);
+
+        let invalid = r#"
+            [workspace.metadata.marker]
+            lints = {}
+            ignore_paths = ["src/[invalid"]
+        "#;
+        assert!(Config::try_from_str(invalid, path).is_err());
+        let invalid = r#"
+            [workspace.metadata.marker]
+            lints = {}
+            include_paths = ["***"]
+        "#;
+        assert!(Config::try_from_str(invalid, path).is_err());
+    }
+}
@@ -48,6 +48,13 @@ or:
         display::cli("rustup toolchain install {toolchain} --component rustc-dev llvm-tools")
     ))]
     BuildDriver,
+
+    #[error("The lint crates need to be {action}, but {} was specified", flag.blue())]
+    #[diagnostic(help(
+        "Run Marker once without {} to update the lint crates",
+        flag.blue()
+    ))]
+    LintCratesOutdated { action: &'static str, flag: &'static str },
 }
 
 // region replace marker version stable
@@ -16,6 +16,10 @@ use cli::MarkerCli;
 use error::prelude::*;
 use std::process::ExitCode;
 
+/// The exit code used, if `--locked` or `--frozen` was specified, but the lint
+/// crates would need to be fetched or rebuilt.
+const OUTDATED_LINT_CRATES_EXIT_CODE: u8 = 2;
+
 fn main() -> ExitCode {
     observability::init();
 
@@ -25,5 +29,9 @@ fn main() -> ExitCode {
 
     err.print();
 
+    if let Some(ErrorKind::LintCratesOutdated { .. }) = err.kind() {
+        return ExitCode::from(OUTDATED_LINT_CRATES_EXIT_CODE);
+    }
+
     ExitCode::FAILURE
 }
@@ -92,11 +92,14 @@ mod map;
 pub use map::*;
 
 use marker_api::{
-    common::{ExpnId, ExprId, SpanId, SymbolId},
-    context::{MarkerContextCallbacks, MarkerContextData},
+    ast::{Attribute, Derive, FormatArgs, ItemField, PanicMacro},
+    common::{DriverTyId, ExpnId, ExprId, GenericId, NodeId, SpanId, StmtId, SymbolId, TargetInfo},
+    context::{MarkerContextCallbacks, MarkerContextData, TryConversion},
     diagnostic::Diagnostic,
     ffi::{self, FfiOption},
+    manifest::Manifest,
     prelude::*,
+    sem::{ConstFnViolation, ConstInt, ConstValue, ObjectSafetyViolation, TyLayout},
     span::{ExpnInfo, FileInfo, FilePos, SpanPos, SpanSource},
 };
 
@@ -127,14 +130,63 @@ impl<'ast> MarkerContextWrapper<'ast> {
             data: unsafe { &*(self as *const MarkerContextWrapper).cast::<MarkerContextData>() },
             emit_diag,
             resolve_ty_ids,
+            crate_attrs,
+            manifest,
+            target,
+            object_safety_violations,
+            enclosing_loop,
+            normalize_alias,
+            is_param_used,
+            is_used_in_crate,
+            is_doc_hidden,
+            is_ty_doc_hidden,
+            node_at,
+            panic_macro,
+            is_last_use,
+            needless_collect,
+            format_args,
+            try_eval_const,
+            iterator_item_ty,
+            ty_layout,
+            needs_drop,
+            has_drop_impl,
+            parses_as_expr,
+            const_fn_violation,
+            var_name,
+            var_ty,
+            var_decl_span,
+            call_target_path,
+            is_in_async_context,
+            is_in_test_code,
+            is_guarded_unwrap,
+            is_identity_conversion,
+            is_local_def,
+            try_residual_conversion,
+            enum_discriminants,
+            transparent_inner_field,
+            derivable_trait,
+            auto_trait_impl,
             expr_ty,
+            var_init,
             span,
             span_snippet,
             span_source,
             span_pos_to_file_loc,
+            file_span,
             span_expn_info,
             symbol_str,
             resolve_method_target,
+            attrs,
+            derives,
+            aliased_sem_ty,
+            overloaded_op_fn,
+            method_trait,
+            is_dynamic_dispatch,
+            async_output_ty,
+            is_iterator_adapter,
+            all_supertraits,
+            annotation_matches_init,
+            let_expr_is_irrefutable,
         }
     }
 }
@@ -143,15 +195,64 @@ pub trait MarkerContextDriver<'ast> {
     fn emit_diag(&'ast self, diag: &Diagnostic<'_, 'ast>);
 
     fn resolve_ty_ids(&'ast self, path: &str) -> &'ast [TyDefId];
+    fn crate_attrs(&'ast self) -> &'ast [Attribute<'ast>];
+    fn manifest(&'ast self) -> Option<&'ast Manifest<'ast>>;
+    fn target(&'ast self) -> &'ast TargetInfo<'ast>;
+    fn object_safety_violations(&'ast self, trait_id: TyDefId) -> Option<&'ast [ObjectSafetyViolation<'ast>]>;
+    fn enclosing_loop(&'ast self, expr: ExprId) -> Option<ExprId>;
+    fn normalize_alias(&'ast self, ty: DriverTyId) -> Option<marker_api::sem::TyKind<'ast>>;
+    fn is_param_used(&'ast self, param: GenericId, item: ItemId) -> bool;
+    fn is_used_in_crate(&'ast self, item: ItemId) -> bool;
+    fn is_doc_hidden(&'ast self, item: ItemId) -> bool;
+    fn is_ty_doc_hidden(&'ast self, ty: TyDefId) -> bool;
+    fn node_at(&'ast self, span: &Span<'ast>) -> Option<NodeId>;
+    fn panic_macro(&'ast self, expr: ExprId) -> Option<PanicMacro<'ast>>;
+    fn is_last_use(&'ast self, expr: ExprId) -> bool;
+    fn needless_collect(&'ast self, expr: ExprId) -> Option<SpanId>;
+    fn format_args(&'ast self, expr: ExprId) -> Option<&'ast FormatArgs<'ast>>;
+    fn try_eval_const(&'ast self, expr: ExprId) -> Option<&'ast ConstValue<'ast>>;
+    fn iterator_item_ty(&'ast self, ty: DriverTyId, owner: ItemId) -> Option<marker_api::sem::TyKind<'ast>>;
+    fn ty_layout(&'ast self, ty: DriverTyId, owner: ItemId) -> Option<TyLayout>;
+    fn needs_drop(&'ast self, ty: DriverTyId, owner: ItemId) -> bool;
+    fn has_drop_impl(&'ast self, ty: DriverTyId) -> bool;
+    fn parses_as_expr(&'ast self, text: &str) -> bool;
+    fn const_fn_violation(&'ast self, id: ItemId) -> Option<&'ast ConstFnViolation<'ast>>;
+    fn var_name(&'ast self, var: VarId) -> SymbolId;
+    fn var_ty(&'ast self, var: VarId) -> marker_api::sem::TyKind<'ast>;
+    fn var_decl_span(&'ast self, var: VarId) -> SpanId;
+    fn call_target_path(&'ast self, expr: ExprId) -> Option<&'ast str>;
+    fn is_in_async_context(&'ast self, expr: ExprId) -> bool;
+    fn is_in_test_code(&'ast self, node: NodeId) -> bool;
+    fn is_guarded_unwrap(&'ast self, expr: ExprId) -> bool;
+    fn is_identity_conversion(&'ast self, expr: ExprId) -> bool;
+    fn is_local_def(&'ast self, id: TyDefId) -> bool;
+    fn try_residual_conversion(&'ast self, expr: ExprId) -> Option<TryConversion<'ast>>;
+    fn enum_discriminants(&'ast self, enum_id: TyDefId) -> Option<&'ast [ConstInt<'ast>]>;
+    fn transparent_inner_field(&'ast self, struct_id: ItemId) -> Option<&'ast ItemField<'ast>>;
+    fn derivable_trait(&'ast self, impl_id: ItemId) -> Option<&'ast str>;
+    fn auto_trait_impl(&'ast self, impl_id: ItemId) -> Option<&'ast str>;
 
     fn expr_ty(&'ast self, expr: ExprId) -> marker_api::sem::TyKind<'ast>;
+    fn var_init(&'ast self, var: VarId) -> Option<ExprId>;
     fn span(&'ast self, owner: SpanId) -> &'ast Span<'ast>;
     fn span_snippet(&'ast self, span: &Span<'_>) -> Option<&'ast str>;
     fn span_source(&'ast self, span: &Span<'_>) -> SpanSource<'ast>;
     fn span_expn_info(&'ast self, expn_id: ExpnId) -> Option<&'ast ExpnInfo<'ast>>;
     fn span_pos_to_file_loc(&'ast self, file: &FileInfo<'ast>, pos: SpanPos) -> Option<FilePos<'ast>>;
+    fn file_span(&'ast self, file: &FileInfo<'ast>) -> Option<&'ast Span<'ast>>;
     fn symbol_str(&'ast self, api_id: SymbolId) -> &'ast str;
     fn resolve_method_target(&'ast self, id: ExprId) -> ItemId;
+    fn attrs(&'ast self, node: NodeId) -> &'ast [Attribute<'ast>];
+    fn derives(&'ast self, item: ItemId) -> &'ast [Derive<'ast>];
+    fn aliased_sem_ty(&'ast self, item: ItemId) -> Option<marker_api::sem::TyKind<'ast>>;
+    fn overloaded_op_fn(&'ast self, expr: ExprId) -> Option<ItemId>;
+    fn method_trait(&'ast self, expr: ExprId) -> Option<ItemId>;
+    fn is_dynamic_dispatch(&'ast self, expr: ExprId) -> bool;
+    fn async_output_ty(&'ast self, item: ItemId) -> Option<marker_api::sem::TyKind<'ast>>;
+    fn is_iterator_adapter(&'ast self, expr: ExprId) -> bool;
+    fn all_supertraits(&'ast self, item: ItemId) -> &'ast [TyDefId];
+    fn annotation_matches_init(&'ast self, stmt: StmtId) -> bool;
+    fn let_expr_is_irrefutable(&'ast self, expr: ExprId) -> bool;
 }
 
 extern "C" fn emit_diag<'a, 'ast>(data: &'ast MarkerContextData, diag: &Diagnostic<'a, 'ast>) {
@@ -165,12 +266,214 @@ extern "C" fn resolve_ty_ids<'ast>(
     unsafe { as_driver(data) }.resolve_ty_ids((&path).into()).into()
 }
 
+extern "C" fn crate_attrs<'ast>(data: &'ast MarkerContextData) -> ffi::FfiSlice<'ast, Attribute<'ast>> {
+    unsafe { as_driver(data) }.crate_attrs().into()
+}
+
+extern "C" fn manifest<'ast>(data: &'ast MarkerContextData) -> FfiOption<&'ast Manifest<'ast>> {
+    unsafe { as_driver(data) }.manifest().into()
+}
+
+extern "C" fn target<'ast>(data: &'ast MarkerContextData) -> &'ast TargetInfo<'ast> {
+    unsafe { as_driver(data) }.target()
+}
+
+extern "C" fn object_safety_violations<'ast>(
+    data: &'ast MarkerContextData,
+    trait_id: TyDefId,
+) -> FfiOption<ffi::FfiSlice<'ast, ObjectSafetyViolation<'ast>>> {
+    unsafe { as_driver(data) }
+        .object_safety_violations(trait_id)
+        .map(Into::into)
+        .into()
+}
+
+extern "C" fn enclosing_loop<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> FfiOption<ExprId> {
+    unsafe { as_driver(data) }.enclosing_loop(expr).into()
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn normalize_alias<'ast>(
+    data: &'ast MarkerContextData,
+    ty: DriverTyId,
+) -> FfiOption<marker_api::sem::TyKind<'ast>> {
+    unsafe { as_driver(data) }.normalize_alias(ty).into()
+}
+
+extern "C" fn is_param_used<'ast>(data: &'ast MarkerContextData, param: GenericId, item: ItemId) -> bool {
+    unsafe { as_driver(data) }.is_param_used(param, item)
+}
+
+extern "C" fn is_used_in_crate<'ast>(data: &'ast MarkerContextData, item: ItemId) -> bool {
+    unsafe { as_driver(data) }.is_used_in_crate(item)
+}
+
+extern "C" fn is_doc_hidden<'ast>(data: &'ast MarkerContextData, item: ItemId) -> bool {
+    unsafe { as_driver(data) }.is_doc_hidden(item)
+}
+
+extern "C" fn is_ty_doc_hidden<'ast>(data: &'ast MarkerContextData, ty: TyDefId) -> bool {
+    unsafe { as_driver(data) }.is_ty_doc_hidden(ty)
+}
+
+// False positive because `NodeId` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn node_at<'ast>(data: &'ast MarkerContextData, span: &Span<'ast>) -> ffi::FfiOption<NodeId> {
+    unsafe { as_driver(data) }.node_at(span).into()
+}
+
+// False positive because `PanicKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn panic_macro<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> ffi::FfiOption<PanicMacro<'ast>> {
+    unsafe { as_driver(data) }.panic_macro(expr).into()
+}
+
+extern "C" fn is_last_use(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.is_last_use(expr)
+}
+
+extern "C" fn needless_collect(data: &MarkerContextData, expr: ExprId) -> FfiOption<SpanId> {
+    unsafe { as_driver(data) }.needless_collect(expr).into()
+}
+
+extern "C" fn format_args<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> FfiOption<&'ast FormatArgs<'ast>> {
+    unsafe { as_driver(data) }.format_args(expr).into()
+}
+
+extern "C" fn try_eval_const<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> FfiOption<&'ast ConstValue<'ast>> {
+    unsafe { as_driver(data) }.try_eval_const(expr).into()
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn iterator_item_ty<'ast>(
+    data: &'ast MarkerContextData,
+    ty: DriverTyId,
+    owner: ItemId,
+) -> FfiOption<marker_api::sem::TyKind<'ast>> {
+    unsafe { as_driver(data) }.iterator_item_ty(ty, owner).into()
+}
+
+extern "C" fn ty_layout(data: &MarkerContextData, ty: DriverTyId, owner: ItemId) -> FfiOption<TyLayout> {
+    unsafe { as_driver(data) }.ty_layout(ty, owner).into()
+}
+
+extern "C" fn needs_drop(data: &MarkerContextData, ty: DriverTyId, owner: ItemId) -> bool {
+    unsafe { as_driver(data) }.needs_drop(ty, owner)
+}
+
+extern "C" fn has_drop_impl(data: &MarkerContextData, ty: DriverTyId) -> bool {
+    unsafe { as_driver(data) }.has_drop_impl(ty)
+}
+
+extern "C" fn parses_as_expr(data: &MarkerContextData, text: ffi::FfiStr<'_>) -> bool {
+    unsafe { as_driver(data) }.parses_as_expr((&text).into())
+}
+
+extern "C" fn const_fn_violation<'ast>(
+    data: &'ast MarkerContextData,
+    id: ItemId,
+) -> FfiOption<&'ast ConstFnViolation<'ast>> {
+    unsafe { as_driver(data) }.const_fn_violation(id).into()
+}
+
+extern "C" fn var_name(data: &MarkerContextData, var: VarId) -> SymbolId {
+    unsafe { as_driver(data) }.var_name(var)
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn var_ty<'ast>(data: &'ast MarkerContextData, var: VarId) -> marker_api::sem::TyKind<'ast> {
+    unsafe { as_driver(data) }.var_ty(var)
+}
+
+extern "C" fn var_decl_span(data: &MarkerContextData, var: VarId) -> SpanId {
+    unsafe { as_driver(data) }.var_decl_span(var)
+}
+
+extern "C" fn call_target_path<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> ffi::FfiOption<ffi::FfiStr<'ast>> {
+    unsafe { as_driver(data) }.call_target_path(expr).map(Into::into).into()
+}
+
+extern "C" fn is_in_async_context(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.is_in_async_context(expr)
+}
+
+// False positive because `NodeId` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn is_in_test_code(data: &MarkerContextData, node: NodeId) -> bool {
+    unsafe { as_driver(data) }.is_in_test_code(node)
+}
+
+extern "C" fn is_guarded_unwrap(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.is_guarded_unwrap(expr)
+}
+
+extern "C" fn is_identity_conversion(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.is_identity_conversion(expr)
+}
+
+extern "C" fn is_local_def(data: &MarkerContextData, id: TyDefId) -> bool {
+    unsafe { as_driver(data) }.is_local_def(id)
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn try_residual_conversion<'ast>(
+    data: &'ast MarkerContextData,
+    expr: ExprId,
+) -> FfiOption<TryConversion<'ast>> {
+    unsafe { as_driver(data) }.try_residual_conversion(expr).into()
+}
+
+extern "C" fn enum_discriminants<'ast>(
+    data: &'ast MarkerContextData,
+    enum_id: TyDefId,
+) -> FfiOption<ffi::FfiSlice<'ast, ConstInt<'ast>>> {
+    unsafe { as_driver(data) }
+        .enum_discriminants(enum_id)
+        .map(Into::into)
+        .into()
+}
+
+extern "C" fn transparent_inner_field<'ast>(
+    data: &'ast MarkerContextData,
+    struct_id: ItemId,
+) -> FfiOption<&'ast ItemField<'ast>> {
+    unsafe { as_driver(data) }.transparent_inner_field(struct_id).into()
+}
+
+extern "C" fn derivable_trait<'ast>(
+    data: &'ast MarkerContextData,
+    impl_id: ItemId,
+) -> ffi::FfiOption<ffi::FfiStr<'ast>> {
+    unsafe { as_driver(data) }
+        .derivable_trait(impl_id)
+        .map(Into::into)
+        .into()
+}
+
+extern "C" fn auto_trait_impl<'ast>(
+    data: &'ast MarkerContextData,
+    impl_id: ItemId,
+) -> ffi::FfiOption<ffi::FfiStr<'ast>> {
+    unsafe { as_driver(data) }
+        .auto_trait_impl(impl_id)
+        .map(Into::into)
+        .into()
+}
+
 // False positive because `SemTyKind` is non-exhaustive
 #[allow(improper_ctypes_definitions)]
 extern "C" fn expr_ty<'ast>(data: &'ast MarkerContextData, expr: ExprId) -> marker_api::sem::TyKind<'ast> {
     unsafe { as_driver(data) }.expr_ty(expr)
 }
 
+extern "C" fn var_init(data: &MarkerContextData, var: VarId) -> FfiOption<ExprId> {
+    unsafe { as_driver(data) }.var_init(var).into()
+}
+
 extern "C" fn span<'ast>(data: &'ast MarkerContextData, span_id: SpanId) -> &'ast Span<'ast> {
     unsafe { as_driver(data) }.span(span_id)
 }
@@ -196,6 +499,10 @@ extern "C" fn span_pos_to_file_loc<'ast>(
     unsafe { as_driver(data) }.span_pos_to_file_loc(file, pos).into()
 }
 
+extern "C" fn file_span<'ast>(data: &'ast MarkerContextData, file: &FileInfo<'ast>) -> FfiOption<&'ast Span<'ast>> {
+    unsafe { as_driver(data) }.file_span(file).into()
+}
+
 extern "C" fn span_expn_info<'ast>(data: &'ast MarkerContextData, expn_id: ExpnId) -> FfiOption<&'ast ExpnInfo<'ast>> {
     unsafe { as_driver(data) }.span_expn_info(expn_id).into()
 }
@@ -208,6 +515,62 @@ extern "C" fn resolve_method_target<'ast>(data: &'ast MarkerContextData, id: Exp
     unsafe { as_driver(data) }.resolve_method_target(id)
 }
 
+// False positive because `NodeId` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn attrs<'ast>(data: &'ast MarkerContextData, node: NodeId) -> ffi::FfiSlice<'ast, Attribute<'ast>> {
+    unsafe { as_driver(data) }.attrs(node).into()
+}
+
+extern "C" fn derives<'ast>(data: &'ast MarkerContextData, item: ItemId) -> ffi::FfiSlice<'ast, Derive<'ast>> {
+    unsafe { as_driver(data) }.derives(item).into()
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn aliased_sem_ty<'ast>(
+    data: &'ast MarkerContextData,
+    item: ItemId,
+) -> FfiOption<marker_api::sem::TyKind<'ast>> {
+    unsafe { as_driver(data) }.aliased_sem_ty(item).into()
+}
+
+extern "C" fn overloaded_op_fn(data: &MarkerContextData, expr: ExprId) -> FfiOption<ItemId> {
+    unsafe { as_driver(data) }.overloaded_op_fn(expr).into()
+}
+
+extern "C" fn method_trait(data: &MarkerContextData, expr: ExprId) -> FfiOption<ItemId> {
+    unsafe { as_driver(data) }.method_trait(expr).into()
+}
+
+extern "C" fn is_dynamic_dispatch(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.is_dynamic_dispatch(expr)
+}
+
+// False positive because `SemTyKind` is non-exhaustive
+#[allow(improper_ctypes_definitions)]
+extern "C" fn async_output_ty<'ast>(
+    data: &'ast MarkerContextData,
+    item: ItemId,
+) -> FfiOption<marker_api::sem::TyKind<'ast>> {
+    unsafe { as_driver(data) }.async_output_ty(item).into()
+}
+
+extern "C" fn is_iterator_adapter(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.is_iterator_adapter(expr)
+}
+
+extern "C" fn all_supertraits<'ast>(data: &'ast MarkerContextData, item: ItemId) -> ffi::FfiSlice<'ast, TyDefId> {
+    unsafe { as_driver(data) }.all_supertraits(item).into()
+}
+
+extern "C" fn annotation_matches_init(data: &MarkerContextData, stmt: StmtId) -> bool {
+    unsafe { as_driver(data) }.annotation_matches_init(stmt)
+}
+
+extern "C" fn let_expr_is_irrefutable(data: &MarkerContextData, expr: ExprId) -> bool {
+    unsafe { as_driver(data) }.let_expr_is_irrefutable(expr)
+}
+
 /// # Safety
 /// The `data` must be a valid pointer to a [`MarkerContextWrapper`]
 unsafe fn as_driver<'ast>(data: &'ast MarkerContextData) -> &'ast dyn MarkerContextDriver<'ast> {
@@ -5,23 +5,42 @@
 
 mod error;
 mod loader;
+mod manifest;
 
 pub mod context;
 
 pub use error::{Error, Result};
 pub use loader::LintCrateInfo;
+pub use manifest::{DependencyInfo, FeatureInfo, ManifestInfo};
 
 use loader::LintCrateRegistry;
-use marker_api::Lint;
 use marker_api::{
-    ast::{Body, Crate, EnumVariant, ExprKind, ItemField, ItemKind, StmtKind},
+    ast::{
+        Body, Crate, EnumVariant, ExprKind, GenericParams, ItemData, ItemField, ItemKind, ModItem, StmtKind,
+        WhereClauseKind,
+    },
+    common::ItemId,
     context::MarkerContext,
-    LintPass, LintPassInfo,
+    LintPass,
 };
+use marker_api::{Lint, LintSupersession};
 use marker_utils::visitor::{self, Visitor};
 use std::{cell::RefCell, ops::ControlFlow};
 
 pub const LINT_CRATES_ENV: &str = "MARKER_LINT_CRATES";
+pub const LINT_FILTERS_ENV: &str = "MARKER_LINT_FILTERS";
+pub const LINT_CONFIGS_ENV: &str = "MARKER_LINT_CONFIGS";
+pub const MANIFESTS_ENV: &str = "MARKER_MANIFESTS";
+pub const SHOW_LINT_SOURCE_ENV: &str = "MARKER_SHOW_LINT_SOURCE";
+/// Glob patterns, separated by `;`. Diagnostics in files matching one of the
+/// patterns are not emitted.
+pub const IGNORE_PATHS_ENV: &str = "MARKER_IGNORE_PATHS";
+/// Glob patterns, separated by `;`. If set, diagnostics are only emitted in
+/// files matching one of the patterns.
+pub const INCLUDE_PATHS_ENV: &str = "MARKER_INCLUDE_PATHS";
+/// The maximum number of diagnostics, which are emitted per lint. Further
+/// diagnostics of the lint are suppressed and only counted.
+pub const MAX_PER_LINT_ENV: &str = "MARKER_MAX_PER_LINT";
 
 /// This struct is the interface used by lint drivers to load lint crates, pass
 /// `marker_api` objects to external lint passes and all other magic you can think of.
@@ -34,11 +53,26 @@ pub struct Adapter {
     /// The effects of the mutability should never reach the driver anyways and
     /// this just makes it way easier to handle the adapter in drivers.
     inner: RefCell<AdapterInner>,
+    /// The lints, which have been disabled by the lint filters. These are stored
+    /// outside of [`Adapter::inner`], since they're accessed while the lint
+    /// crates are being called.
+    disabled_lints: Vec<&'static Lint>,
+    /// The enabled lints and the names of the lint crates, which define them.
+    lint_sources: Vec<(&'static Lint, String)>,
+    /// The lint supersessions declared by the loaded lint crates.
+    lint_supersessions: Vec<LintSupersession>,
 }
 
 #[derive(Debug)]
 struct AdapterInner {
     external_lint_crates: LintCrateRegistry,
+    /// The module processed by [`Adapter::process_module`] or
+    /// [`Adapter::process_root_module`]. Nested modules are only checked as
+    /// items, while this is set.
+    module: Option<ItemId>,
+    /// The number of nested modules, which are currently being traversed. Nodes
+    /// are only checked, if this is `0`.
+    nested_mod_depth: usize,
 }
 
 impl Adapter {
@@ -50,22 +84,48 @@ impl Adapter {
     /// loading process.
     pub fn new(lint_crates: &[LintCrateInfo]) -> Result<Self> {
         let external_lint_crates = LintCrateRegistry::new(lint_crates)?;
+        let disabled_lints = external_lint_crates.disabled_lints().to_vec();
+        let lint_sources = external_lint_crates.lint_sources();
+        let lint_supersessions = external_lint_crates.lint_supersessions();
         Ok(Self {
-            inner: RefCell::new(AdapterInner { external_lint_crates }),
+            inner: RefCell::new(AdapterInner {
+                external_lint_crates,
+                module: None,
+                nested_mod_depth: 0,
+            }),
+            disabled_lints,
+            lint_sources,
+            lint_supersessions,
         })
     }
 
-    pub fn marker_lints(&self) -> Vec<&'static Lint> {
-        self.lint_pass_infos()
-            .iter()
-            .flat_map(marker_api::LintPassInfo::lints)
-            .copied()
-            .collect()
+    /// Returns the lints, which have been disabled by the lint filters of the
+    /// [`LintCrateInfo`]s. Diagnostics of these lints should not be emitted.
+    #[must_use]
+    pub fn disabled_lints(&self) -> &[&'static Lint] {
+        &self.disabled_lints
     }
 
+    /// Returns the lint supersessions declared by the loaded lint crates. A
+    /// diagnostic of a superseded lint should not be emitted, if the superseding
+    /// lint has been emitted at the same node.
     #[must_use]
-    fn lint_pass_infos(&self) -> Vec<LintPassInfo> {
-        self.inner.borrow().external_lint_crates.collect_lint_pass_info()
+    pub fn lint_supersessions(&self) -> &[LintSupersession] {
+        &self.lint_supersessions
+    }
+
+    /// Returns the name of the lint crate, which defines the given lint. The
+    /// name is the one used by [`LintCrateInfo::name`].
+    #[must_use]
+    pub fn lint_source(&self, lint: &'static Lint) -> Option<&str> {
+        self.lint_sources
+            .iter()
+            .find(|(source_lint, _)| std::ptr::eq(*source_lint, lint))
+            .map(|(_, name)| name.as_str())
+    }
+
+    pub fn marker_lints(&self) -> Vec<&'static Lint> {
+        self.inner.borrow().external_lint_crates.collect_lints()
     }
 
     pub fn process_krate<'ast>(&self, cx: &'ast MarkerContext<'ast>, krate: &'ast Crate<'ast>) {
@@ -76,20 +136,111 @@ impl Adapter {
         inner.external_lint_crates.check_crate(cx, krate);
         visitor::traverse_item::<()>(cx, inner, ItemKind::Mod(krate.root_mod()));
     }
+
+    /// Processes the root module of the given crate, like [`Adapter::process_krate`].
+    /// Unlike [`Adapter::process_krate`], the content of nested modules is skipped,
+    /// they're only checked as items. Nested modules can be processed separately
+    /// with [`Adapter::process_module`]. This allows drivers to cache the results
+    /// of every module.
+    pub fn process_root_module<'ast>(&self, cx: &'ast MarkerContext<'ast>, krate: &'ast Crate<'ast>) {
+        let inner = &mut *self.inner.borrow_mut();
+
+        inner.external_lint_crates.set_ast_context(cx);
+        inner.module = Some(krate.root_mod().id());
+
+        inner.external_lint_crates.check_crate(cx, krate);
+        visitor::traverse_item::<()>(cx, inner, ItemKind::Mod(krate.root_mod()));
+
+        inner.module = None;
+    }
+
+    /// Processes the items of the given module, the content of nested modules
+    /// is skipped. The module itself is checked as an item of its parent. See
+    /// [`Adapter::process_root_module`] for the root module.
+    pub fn process_module<'ast>(&self, cx: &'ast MarkerContext<'ast>, module: &'ast ModItem<'ast>) {
+        let inner = &mut *self.inner.borrow_mut();
+
+        inner.external_lint_crates.set_ast_context(cx);
+        inner.module = Some(module.id());
+
+        for item in module.items() {
+            visitor::traverse_item::<()>(cx, inner, *item);
+        }
+
+        inner.module = None;
+    }
+}
+
+impl AdapterInner {
+    /// Calls [`LintPass::check_generic_bound`] for all bounds declared by the
+    /// given item.
+    fn check_generic_bounds<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+        let (generics, bounds): (Option<&GenericParams<'ast>>, &[_]) = match item {
+            ItemKind::Fn(item) => (Some(item.generics()), &[]),
+            ItemKind::Struct(item) => (Some(item.generics()), &[]),
+            ItemKind::Enum(item) => (Some(item.generics()), &[]),
+            ItemKind::Union(item) => (Some(item.generics()), &[]),
+            ItemKind::Impl(item) => (Some(item.generics()), &[]),
+            ItemKind::Trait(item) => (Some(item.generics()), item.supertraits()),
+            ItemKind::TyAlias(item) => (Some(item.generics()), item.bounds()),
+            _ => (None, &[]),
+        };
+
+        let clause_bounds = generics
+            .into_iter()
+            .flat_map(GenericParams::clauses)
+            .filter_map(|clause| match clause {
+                WhereClauseKind::Ty(clause) => Some(clause.bounds()),
+                _ => None,
+            })
+            .flatten();
+        for bound in bounds.iter().chain(clause_bounds) {
+            self.external_lint_crates.check_generic_bound(cx, bound, item.id());
+        }
+    }
 }
 
 impl Visitor<()> for AdapterInner {
     fn scope(&self) -> visitor::VisitorScope {
-        visitor::VisitorScope::AllBodies
+        // The bodies of skipped modules are not requested, this prevents their
+        // conversion and keeps the module independent of their content.
+        if self.nested_mod_depth > 0 {
+            visitor::VisitorScope::NoBodies
+        } else {
+            visitor::VisitorScope::AllBodies
+        }
     }
 
     fn visit_item<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) -> ControlFlow<()> {
+        if self.nested_mod_depth > 0 {
+            if let ItemKind::Mod(_) = item {
+                self.nested_mod_depth += 1;
+            }
+            return ControlFlow::Continue(());
+        }
+
         self.external_lint_crates.check_item(cx, item);
+        self.check_generic_bounds(cx, item);
+
+        if let ItemKind::Mod(module) = item {
+            if self.module.is_some_and(|id| id != module.id()) {
+                self.nested_mod_depth += 1;
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_item_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) -> ControlFlow<()> {
+        if matches!(item, ItemKind::Mod(_)) && self.nested_mod_depth > 0 {
+            self.nested_mod_depth -= 1;
+        }
         ControlFlow::Continue(())
     }
 
     fn visit_field<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, field: &'ast ItemField<'ast>) -> ControlFlow<()> {
-        self.external_lint_crates.check_field(cx, field);
+        if self.nested_mod_depth == 0 {
+            self.external_lint_crates.check_field(cx, field);
+        }
         ControlFlow::Continue(())
     }
 
@@ -98,22 +249,35 @@ impl Visitor<()> for AdapterInner {
         cx: &'ast MarkerContext<'ast>,
         variant: &'ast EnumVariant<'ast>,
     ) -> ControlFlow<()> {
-        self.external_lint_crates.check_variant(cx, variant);
+        if self.nested_mod_depth == 0 {
+            self.external_lint_crates.check_variant(cx, variant);
+        }
         ControlFlow::Continue(())
     }
 
     fn visit_body<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, body: &'ast Body<'ast>) -> ControlFlow<()> {
-        self.external_lint_crates.check_body(cx, body);
+        if self.nested_mod_depth == 0 {
+            self.external_lint_crates.check_body(cx, body);
+        }
         ControlFlow::Continue(())
     }
 
     fn visit_stmt<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) -> ControlFlow<()> {
+        if self.nested_mod_depth > 0 {
+            return ControlFlow::Continue(());
+        }
+
         self.external_lint_crates.check_stmt(cx, stmt);
+        if let StmtKind::Let(local) = stmt {
+            self.external_lint_crates.check_local(cx, local);
+        }
         ControlFlow::Continue(())
     }
 
     fn visit_expr<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
-        self.external_lint_crates.check_expr(cx, expr);
+        if self.nested_mod_depth == 0 {
+            self.external_lint_crates.check_expr(cx, expr);
+        }
         ControlFlow::Continue(())
     }
 }
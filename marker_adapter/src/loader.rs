@@ -2,10 +2,12 @@ use crate::error::prelude::*;
 use camino::Utf8PathBuf;
 use itertools::Itertools;
 use libloading::Library;
-use marker_api::{LintCrateBindings, MarkerContext};
-use marker_api::{LintPass, LintPassInfo, MARKER_API_VERSION};
+use marker_api::{Lint, LintCrateBindings, MarkerContext};
+use marker_api::{LintPass, LintPassInfo, LintSupersession, MARKER_API_VERSION};
+use serde::Deserialize;
+use std::collections::HashMap;
 
-use super::LINT_CRATES_ENV;
+use super::{LINT_CONFIGS_ENV, LINT_CRATES_ENV, LINT_FILTERS_ENV};
 
 /// A struct describing a lint crate that can be loaded.
 #[derive(Debug, Clone)]
@@ -14,11 +16,31 @@ pub struct LintCrateInfo {
     pub name: String,
     /// The absolute path of the compiled dynamic library, which can be loaded as a lint crate.
     pub path: Utf8PathBuf,
+    /// The names of the lints, which should be enabled. All lints are enabled,
+    /// if this is `None`.
+    pub enabled_lints: Option<Vec<String>>,
+    /// The names of the lints, which should be disabled. Disabled lints are not
+    /// registered and their diagnostics are not emitted.
+    pub disabled_lints: Vec<String>,
+    /// The configuration of the lint crate as a JSON string. It's passed to
+    /// the lint crate before any of its lint pass functions are called.
+    pub config: Option<String>,
+}
+
+/// The lint filter of a single lint crate, as stored in [`LINT_FILTERS_ENV`].
+#[derive(Debug, Default, Deserialize)]
+struct LintFilter {
+    #[serde(default)]
+    enabled_lints: Option<Vec<String>>,
+    #[serde(default)]
+    disabled_lints: Vec<String>,
 }
 
 impl LintCrateInfo {
     /// This function tries to load the list of [`LintCrateInfo`]s from the
-    /// [`LINT_CRATES_ENV`] environment value.
+    /// [`LINT_CRATES_ENV`] environment value. The lint filters and configurations
+    /// are loaded from the optional [`LINT_FILTERS_ENV`] and [`LINT_CONFIGS_ENV`]
+    /// environment values.
     ///
     /// # Errors
     ///
@@ -29,6 +51,32 @@ impl LintCrateInfo {
         let Some(env_str) = std::env::var(LINT_CRATES_ENV).ok() else {
             return Ok(None);
         };
+        let filters = std::env::var(LINT_FILTERS_ENV).ok();
+        let configs = std::env::var(LINT_CONFIGS_ENV).ok();
+        Self::list_from(&env_str, filters.as_deref(), configs.as_deref()).map(Some)
+    }
+
+    /// Parses the [`LintCrateInfo`]s from the values of the [`LINT_CRATES_ENV`],
+    /// [`LINT_FILTERS_ENV`] and [`LINT_CONFIGS_ENV`] environment values.
+    fn list_from(env_str: &str, filters: Option<&str>, configs: Option<&str>) -> Result<Vec<LintCrateInfo>> {
+        let mut filters: HashMap<String, LintFilter> = match filters {
+            Some(filters_str) => serde_json::from_str(filters_str).context(|| {
+                format!(
+                    "The content of the `{LINT_FILTERS_ENV}` environment variable is malformed. \
+                    Dumped its content on the next line:\n---\n{filters_str}\n---",
+                )
+            })?,
+            None => HashMap::default(),
+        };
+        let mut configs: HashMap<String, serde_json::Value> = match configs {
+            Some(configs_str) => serde_json::from_str(configs_str).context(|| {
+                format!(
+                    "The content of the `{LINT_CONFIGS_ENV}` environment variable is malformed. \
+                    Dumped its content on the next line:\n---\n{configs_str}\n---",
+                )
+            })?,
+            None => HashMap::default(),
+        };
 
         let mut lint_crates = vec![];
         for item in env_str.split(';') {
@@ -39,12 +87,28 @@ impl LintCrateInfo {
                 )
             })?;
 
+            let filter = filters.remove(name).unwrap_or_default();
             lint_crates.push(LintCrateInfo {
                 name: name.to_string(),
                 path: path.into(),
+                enabled_lints: filter.enabled_lints,
+                disabled_lints: filter.disabled_lints,
+                config: configs.remove(name).map(|config| config.to_string()),
             });
         }
-        Ok(Some(lint_crates))
+        Ok(lint_crates)
+    }
+
+    /// Returns `true`, if the lint with the given name, without the
+    /// `marker::<lint_crate>::` prefix, is enabled by the lint filter. Lint
+    /// names are case insensitive. Disabling a lint takes precedence over
+    /// enabling it.
+    #[must_use]
+    pub fn is_lint_enabled(&self, lint: &str) -> bool {
+        let is_enabled = self.enabled_lints.as_ref().map_or(true, |enabled| {
+            enabled.iter().any(|name| name.eq_ignore_ascii_case(lint))
+        });
+        is_enabled && !self.disabled_lints.iter().any(|name| name.eq_ignore_ascii_case(lint))
     }
 }
 
@@ -53,6 +117,8 @@ impl LintCrateInfo {
 #[derive(Debug, Default)]
 pub struct LintCrateRegistry {
     passes: Vec<LoadedLintCrate>,
+    /// The lints, which have been disabled by the lint filters.
+    disabled_lints: Vec<&'static Lint>,
 }
 
 impl LintCrateRegistry {
@@ -60,14 +126,18 @@ impl LintCrateRegistry {
         let mut new_self = Self::default();
 
         for krate in lint_crates {
-            new_self.passes.push(LoadedLintCrate::try_from_info(krate.clone())?);
+            let pass = LoadedLintCrate::try_from_info(krate.clone())?;
+            pass.validate_lint_filter()?;
+            new_self.disabled_lints.extend(pass.disabled_lints());
+            // Lint crates, with only disabled lints, don't need to be called
+            if !pass.lints().is_empty() {
+                new_self.passes.push(pass);
+            }
         }
 
-        let lint_passes = new_self.collect_lint_pass_info();
-
-        let errors = lint_passes
-            .iter()
-            .flat_map(LintPassInfo::lints)
+        let errors = new_self
+            .collect_lints()
+            .into_iter()
             .into_group_map_by(|lint| lint.name.to_ascii_lowercase())
             .into_iter()
             .filter(|(_, lints)| lints.len() > 1)
@@ -88,8 +158,30 @@ impl LintCrateRegistry {
         }
     }
 
-    pub(crate) fn collect_lint_pass_info(&self) -> Vec<LintPassInfo> {
-        self.passes.iter().map(|pass| (pass.bindings.info)()).collect()
+    /// Returns all enabled lints of the loaded lint crates.
+    pub(crate) fn collect_lints(&self) -> Vec<&'static Lint> {
+        self.passes.iter().flat_map(LoadedLintCrate::lints).collect()
+    }
+
+    /// Returns all enabled lints, together with the name of the lint crate,
+    /// which defines them.
+    pub(crate) fn lint_sources(&self) -> Vec<(&'static Lint, String)> {
+        self.passes
+            .iter()
+            .flat_map(|pass| pass.lints().into_iter().map(|lint| (lint, pass.info.name.clone())))
+            .collect()
+    }
+
+    pub(crate) fn disabled_lints(&self) -> &[&'static Lint] {
+        &self.disabled_lints
+    }
+
+    /// Returns the lint supersessions declared by the loaded lint crates.
+    pub(crate) fn lint_supersessions(&self) -> Vec<LintSupersession> {
+        self.passes
+            .iter()
+            .flat_map(|pass| pass.pass_info.supersessions().iter().copied())
+            .collect()
     }
 }
 
@@ -139,17 +231,37 @@ impl LintPass for LintCrateRegistry {
         }
     }
 
+    fn check_local<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, local: &'ast marker_api::ast::LetStmt<'ast>) {
+        for lp in &self.passes {
+            (lp.bindings.check_local)(cx, local);
+        }
+    }
+
     fn check_expr<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: marker_api::ast::ExprKind<'ast>) {
         for lp in &self.passes {
             (lp.bindings.check_expr)(cx, expr);
         }
     }
+
+    fn check_generic_bound<'ast>(
+        &mut self,
+        cx: &'ast MarkerContext<'ast>,
+        bound: &'ast marker_api::ast::TyParamBound<'ast>,
+        owner: marker_api::common::ItemId,
+    ) {
+        for lp in &self.passes {
+            (lp.bindings.check_generic_bound)(cx, bound, owner);
+        }
+    }
 }
 
 struct LoadedLintCrate {
     _lib: &'static Library,
     info: LintCrateInfo,
     bindings: LintCrateBindings,
+    /// The [`LintPassInfo`] of the lint crate. It's requested once during
+    /// loading, as the lint crate allocates a new instance on every call.
+    pass_info: LintPassInfo,
 }
 
 #[allow(clippy::missing_fields_in_debug)]
@@ -191,14 +303,97 @@ impl LoadedLintCrate {
 
         let bindings = get_lint_crate_bindings();
 
+        // The configuration has to be set, before the lint pass is used
+        if let Some(config) = &info.config {
+            if let Some(err) = (bindings.set_config)(config.into()).get() {
+                return Err(Error::root(format!(
+                    "Failed to load the configuration of lint crate `{}`: {}",
+                    info.name,
+                    err.get()
+                )));
+            }
+        }
+
+        let pass_info = (bindings.info)();
+
         Ok(Self {
             _lib: lib,
             info,
             bindings,
+            pass_info,
         })
     }
 }
 
+impl LoadedLintCrate {
+    /// Returns the lints of this crate, with the lint filter applied.
+    fn lints(&self) -> Vec<&'static Lint> {
+        self.pass_info
+            .lints()
+            .iter()
+            .copied()
+            .filter(|lint| self.is_lint_enabled(lint))
+            .collect()
+    }
+
+    fn disabled_lints(&self) -> Vec<&'static Lint> {
+        self.pass_info
+            .lints()
+            .iter()
+            .copied()
+            .filter(|lint| !self.is_lint_enabled(lint))
+            .collect()
+    }
+
+    fn is_lint_enabled(&self, lint: &Lint) -> bool {
+        self.info.is_lint_enabled(short_lint_name(lint))
+    }
+
+    /// Checks that all lints named in the lint filter are defined by this crate
+    /// and that no lint is both enabled and disabled.
+    fn validate_lint_filter(&self) -> Result {
+        let known: Vec<_> = self
+            .pass_info
+            .lints()
+            .iter()
+            .map(|lint| short_lint_name(lint))
+            .collect();
+        let unknown = self
+            .info
+            .enabled_lints
+            .iter()
+            .flatten()
+            .chain(&self.info.disabled_lints)
+            .filter(|name| !known.iter().any(|known| known.eq_ignore_ascii_case(name)))
+            .map(|name| {
+                Error::root(format!(
+                    "The lint crate `{}` doesn't define a lint named `{name}`",
+                    self.info.name
+                ))
+            });
+        let conflicting = self
+            .info
+            .enabled_lints
+            .iter()
+            .flatten()
+            .filter(|name| !self.info.is_lint_enabled(name))
+            .map(|name| {
+                Error::root(format!(
+                    "The lint `{name}` of the lint crate `{}` is both enabled and disabled",
+                    self.info.name
+                ))
+            });
+
+        Error::try_many(unknown.chain(conflicting), "Found invalid lints in the lint filter")
+    }
+}
+
+/// Returns the name of the lint, without the `marker::<lint_crate>::` prefix.
+/// Lint names are case insensitive.
+fn short_lint_name(lint: &Lint) -> &'static str {
+    lint.name.rsplit("::").next().unwrap_or(lint.name)
+}
+
 /// SAFETY: inherits the same safety requirements from [`Library::get`].
 unsafe fn get_symbol<T>(
     lib: &'static Library,
@@ -216,3 +411,33 @@ unsafe fn get_symbol<T>(
         )
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_from() {
+        let configs = r#"{ "lints_a": { "max_params": 5, "names": ["x"] } }"#;
+        let infos = LintCrateInfo::list_from("lints_a:/a.so;lints_b:/b.so", None, Some(configs)).unwrap();
+        assert_eq!(infos[0].path, "/a.so");
+        assert_eq!(infos[0].config.as_deref(), Some(r#"{"max_params":5,"names":["x"]}"#));
+        assert_eq!(infos[1].config, None);
+
+        assert!(LintCrateInfo::list_from("lints_a:/a.so", None, Some("{")).is_err());
+    }
+
+    #[test]
+    fn test_is_lint_enabled() {
+        let filters = r#"{
+            "lints_a": { "disabled_lints": ["lint_b"] },
+            "lints_b": { "enabled_lints": ["LINT_C", "lint_d"], "disabled_lints": ["lint_d"] }
+        }"#;
+        let infos = LintCrateInfo::list_from("lints_a:/a.so;lints_b:/b.so", Some(filters), None).unwrap();
+        assert!(infos[0].is_lint_enabled("lint_a"));
+        assert!(!infos[0].is_lint_enabled("LINT_B"));
+        assert!(infos[1].is_lint_enabled("lint_c"));
+        assert!(!infos[1].is_lint_enabled("lint_d"));
+        assert!(!infos[1].is_lint_enabled("lint_e"));
+    }
+}
@@ -0,0 +1,97 @@
+use crate::error::prelude::*;
+use camino::{Utf8Path, Utf8PathBuf};
+use serde::Deserialize;
+
+use super::MANIFESTS_ENV;
+
+/// The manifest information of a single package, as serialized by `cargo-marker`
+/// into the [`MANIFESTS_ENV`] environment value.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ManifestInfo {
+    /// The absolute path of the directory containing the `Cargo.toml` file.
+    pub manifest_dir: Utf8PathBuf,
+    /// The edition, like `2021`.
+    pub edition: String,
+    pub features: Vec<FeatureInfo>,
+    pub dependencies: Vec<DependencyInfo>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct FeatureInfo {
+    pub name: String,
+    pub enables: Vec<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct DependencyInfo {
+    pub name: String,
+    pub rename: Option<String>,
+    /// The kind of the dependency, either `normal`, `dev` or `build`.
+    pub kind: String,
+    pub optional: bool,
+}
+
+impl ManifestInfo {
+    /// This function tries to load the [`ManifestInfo`] of the package, which
+    /// is currently being compiled, from the [`MANIFESTS_ENV`] environment value.
+    /// The package is identified by the `CARGO_MANIFEST_DIR` environment value,
+    /// which is set by Cargo.
+    ///
+    /// # Errors
+    ///
+    /// This function will return an error if the content of the environment
+    /// value is malformed. The `README.md` of this adapter contains the format
+    /// definition.
+    pub fn from_env() -> Result<Option<ManifestInfo>> {
+        let (Ok(env_str), Ok(manifest_dir)) = (std::env::var(MANIFESTS_ENV), std::env::var("CARGO_MANIFEST_DIR"))
+        else {
+            return Ok(None);
+        };
+
+        Self::find_in(&env_str, Utf8Path::new(&manifest_dir))
+    }
+
+    fn find_in(env_str: &str, manifest_dir: &Utf8Path) -> Result<Option<ManifestInfo>> {
+        let manifests: Vec<ManifestInfo> = serde_json::from_str(env_str).context(|| {
+            format!(
+                "The content of the `{MANIFESTS_ENV}` environment variable is malformed. \
+                Dumped its content on the next line:\n---\n{env_str}\n---",
+            )
+        })?;
+
+        Ok(manifests.into_iter().find(|info| info.manifest_dir == manifest_dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_in() {
+        let env_str = r#"[
+            {
+                "manifest_dir": "/ws/a",
+                "edition": "2021",
+                "features": [{ "name": "default", "enables": ["dep:serde"] }],
+                "dependencies": [{ "name": "serde", "rename": null, "kind": "normal", "optional": true }]
+            },
+            { "manifest_dir": "/ws/b", "edition": "2018", "features": [], "dependencies": [] }
+        ]"#;
+
+        let info = ManifestInfo::find_in(env_str, Utf8Path::new("/ws/a")).unwrap().unwrap();
+        assert_eq!(info.edition, "2021");
+        assert_eq!(info.features[0].enables, ["dep:serde"]);
+        assert!(info.dependencies[0].optional);
+
+        let info = ManifestInfo::find_in(env_str, Utf8Path::new("/ws/b")).unwrap().unwrap();
+        assert_eq!(info.edition, "2018");
+
+        assert!(
+            ManifestInfo::find_in(env_str, Utf8Path::new("/ws/c"))
+                .unwrap()
+                .is_none()
+        );
+        assert!(ManifestInfo::find_in("{", Utf8Path::new("/ws/a")).is_err());
+    }
+}
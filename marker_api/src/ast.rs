@@ -1,17 +1,23 @@
 //! A module containing the AST of Marker, which is the main syntactic
 //! representation of the written code.
 
+mod attr;
 mod common;
+mod doc;
 mod expr;
 mod generic;
 mod item;
+mod meta;
 mod pat;
 mod stmt;
 mod ty;
+pub use attr::*;
 pub use common::*;
+pub use doc::*;
 pub use expr::*;
 pub use generic::*;
 pub use item::*;
+pub use meta::*;
 pub use pat::*;
 pub use stmt::*;
 pub use ty::*;
@@ -35,4 +41,9 @@ impl<'ast> Crate<'ast> {
     pub fn root_mod(&self) -> &ModItem<'ast> {
         &self.root_mod
     }
+
+    /// Returns the attributes attached to the crate root, like `#![forbid(unsafe_code)]`.
+    pub fn attrs(&self) -> &'ast [Attribute<'ast>] {
+        self.root_mod.attrs()
+    }
 }
@@ -0,0 +1,231 @@
+use std::marker::PhantomData;
+
+use crate::{
+    common::{SpanId, SymbolId, TyDefId},
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice},
+    span::{Ident, Span},
+};
+
+use super::MetaItem;
+
+/// An attribute attached to a node, like `#[inline]`, `#[doc = "..."]` or
+/// `#![allow(dead_code)]`.
+///
+/// Doc comments, like `/// Doc` or `//! Doc` are also represented as attributes.
+/// Their path is `doc` and the comment content can be retrieved via
+/// [`Attribute::doc_comment`].
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct Attribute<'ast> {
+    span: SpanId,
+    style: AttrStyle,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    path: FfiSlice<'ast, Ident<'ast>>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    tokens: FfiSlice<'ast, Token<'ast>>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    doc_comment: FfiOption<SymbolId>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    meta_items: FfiOption<FfiSlice<'ast, MetaItem<'ast>>>,
+}
+
+impl<'ast> Attribute<'ast> {
+    /// The [`Span`] of the entire attribute, including the `#[` and `]` tokens.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    /// Returns the style of this attribute, either outer (`#[...]`) or inner (`#![...]`).
+    pub fn style(&self) -> AttrStyle {
+        self.style
+    }
+
+    /// The segments of the attribute path. For `#[marker::lint_crate_attr]`
+    /// this would be `marker` and `lint_crate_attr`.
+    pub fn path(&self) -> &[Ident<'ast>] {
+        self.path.get()
+    }
+
+    /// Returns `true`, if the path of this attribute matches the given path.
+    /// The segments have to be separated by `::`, like `"marker::lint_crate_attr"`.
+    pub fn has_path(&self, path: &str) -> bool {
+        let mut segments = path.split("::");
+        self.path
+            .get()
+            .iter()
+            .all(|ident| segments.next().map_or(false, |seg| ident.name() == seg))
+            && segments.next().is_none()
+    }
+
+    /// The tokens of the attribute input, following the path. For
+    /// `#[allow(unused, dead_code)]` this would be the `(`, `unused`, `,`,
+    /// `dead_code` and `)` tokens. Every token has its own [`Span`], which
+    /// maps back to the source code.
+    ///
+    /// Doc comments and attributes without input, like `#[inline]`, have
+    /// no tokens.
+    pub fn tokens(&self) -> &[Token<'ast>] {
+        self.tokens.get()
+    }
+
+    /// The input of this attribute, parsed into [`MetaItem`]s. For
+    /// `#[foo(bar, baz = "x")]` this would return the items `bar` and `baz = "x"`.
+    ///
+    /// ```ignore
+    /// if attr.has_path("marker::my_lint_crate::config") {
+    ///     for item in attr.meta_items().unwrap_or_default() {
+    ///         if item.has_path("threshold") {
+    ///             let threshold = item.value();
+    ///             // ...
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// `None` is returned, if the attribute has no parenthesized input or if
+    /// the input doesn't follow the [meta item grammar], like `#[foo = "x"]`
+    /// or `#[foo(a + b)]`. The input of these attributes is still available
+    /// via [`Attribute::tokens`].
+    ///
+    /// [meta item grammar]: https://doc.rust-lang.org/reference/attributes.html#meta-item-attribute-syntax
+    pub fn meta_items(&self) -> Option<&'ast [MetaItem<'ast>]> {
+        self.meta_items.get().map(FfiSlice::get)
+    }
+
+    /// Returns the content of the doc comment, if this attribute was written as
+    /// a doc comment, like `/// Doc`. The returned string doesn't include the
+    /// comment prefix.
+    ///
+    /// Attributes written as `#[doc = "..."]` will return `None`.
+    pub fn doc_comment(&self) -> Option<&'ast str> {
+        self.doc_comment
+            .get()
+            .map(|sym| with_cx(self, |cx| cx.symbol_str(*sym)))
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum AttrStyle {
+    /// An outer attribute, like `#[inline]`, which is applied to the following node.
+    Outer,
+    /// An inner attribute, like `#![allow(dead_code)]`, which is applied to the
+    /// enclosing node.
+    Inner,
+}
+
+/// A single token of an attribute input. See [`Attribute::tokens`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct Token<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    kind: TokenKind,
+    text: SymbolId,
+    span: SpanId,
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Token<'ast> {
+    pub fn new(kind: TokenKind, text: SymbolId, span: SpanId) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            kind,
+            text,
+            span,
+        }
+    }
+}
+
+impl<'ast> Token<'ast> {
+    pub fn kind(&self) -> TokenKind {
+        self.kind
+    }
+
+    /// The text of this token, as it would be written in the source code.
+    /// String literals will include their quotes, and number literals their
+    /// suffix, like `"text"` or `10_u32`.
+    pub fn text(&self) -> &'ast str {
+        with_cx(self, |cx| cx.symbol_str(self.text))
+    }
+
+    /// The [`Span`] of this token.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// An identifier or keyword, like `unused` or `true`
+    Ident,
+    /// A lifetime, like `'a`
+    Lifetime,
+    /// A literal, like `"text"`, `'c'` or `10`
+    Lit,
+    /// A punctuation token, like `=`, `,` or `::`
+    Punct,
+    /// An opening delimiter, like `(`, `[` or `{`
+    OpenDelim,
+    /// A closing delimiter, like `)`, `]` or `}`
+    CloseDelim,
+}
+
+/// A derive macro, which was applied to an item, like `Debug` in
+/// `#[derive(Debug)]`. See [`ItemData::derives`](crate::ast::ItemData::derives).
+///
+/// The `#[derive(...)]` attribute is removed during macro expansion. Derives
+/// are instead determined by the trait implementations, which they generate.
+/// Derive macros, which don't implement a trait for the item, are therefore
+/// not included.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Derive<'ast> {
+    _lifetime: PhantomData<&'ast ()>,
+    path: SymbolId,
+    span: SpanId,
+    trait_id: TyDefId,
+    is_std: bool,
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Derive<'ast> {
+    pub fn new(path: SymbolId, span: SpanId, trait_id: TyDefId, is_std: bool) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            path,
+            span,
+            trait_id,
+            is_std,
+        }
+    }
+}
+
+impl<'ast> Derive<'ast> {
+    /// The path of the derive macro, as it was written in the attribute, like
+    /// `Debug` or `serde::Serialize`.
+    pub fn path(&self) -> &'ast str {
+        with_cx(self, |cx| cx.symbol_str(self.path))
+    }
+
+    /// The [`Span`] of the path in the `#[derive(...)]` attribute.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    /// The [`TyDefId`] of the trait, implemented by this derive macro.
+    pub fn trait_id(&self) -> TyDefId {
+        self.trait_id
+    }
+
+    /// Returns `true`, if this is a derive macro provided by the standard
+    /// library, like `Debug`, `Clone` or `PartialEq`. Custom derive macros
+    /// from proc-macro crates will return `false`.
+    pub fn is_std(&self) -> bool {
+        self.is_std
+    }
+}
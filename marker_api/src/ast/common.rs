@@ -3,7 +3,11 @@ pub use ast_path::*;
 
 use std::fmt::Debug;
 
-use crate::common::ItemId;
+use crate::{
+    common::{ItemId, SpanId},
+    context::with_cx,
+    span::Span,
+};
 
 use super::generic::GenericArgs;
 
@@ -12,12 +16,17 @@ use super::generic::GenericArgs;
 pub struct TraitRef<'ast> {
     item_id: ItemId,
     generics: GenericArgs<'ast>,
+    span: SpanId,
 }
 
 #[cfg(feature = "driver-api")]
 impl<'ast> TraitRef<'ast> {
-    pub fn new(item_id: ItemId, generics: GenericArgs<'ast>) -> Self {
-        Self { item_id, generics }
+    pub fn new(item_id: ItemId, generics: GenericArgs<'ast>, span: SpanId) -> Self {
+        Self {
+            item_id,
+            generics,
+            span,
+        }
     }
 }
 
@@ -29,4 +38,9 @@ impl<'ast> TraitRef<'ast> {
     pub fn generics(&self) -> &GenericArgs<'ast> {
         &self.generics
     }
+
+    /// The [`Span`] of the trait path, including the generic arguments.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
 }
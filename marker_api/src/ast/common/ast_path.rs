@@ -264,12 +264,17 @@ impl<'ast> AstPath<'ast> {
 pub struct AstPathSegment<'ast> {
     ident: Ident<'ast>,
     generics: GenericArgs<'ast>,
+    target: AstPathTarget,
 }
 
 #[cfg(feature = "driver-api")]
 impl<'ast> AstPathSegment<'ast> {
-    pub fn new(ident: Ident<'ast>, generics: GenericArgs<'ast>) -> Self {
-        Self { ident, generics }
+    pub fn new(ident: Ident<'ast>, generics: GenericArgs<'ast>, target: AstPathTarget) -> Self {
+        Self {
+            ident,
+            generics,
+            target,
+        }
     }
 }
 
@@ -278,7 +283,21 @@ impl<'ast> AstPathSegment<'ast> {
         &self.ident
     }
 
+    /// This returns the [`GenericArgs`] specified on this segment. For the path
+    /// `HashMap::<u32, u8>::new`, the first segment has the type arguments
+    /// `u32` and `u8`, while the second segment has no generic arguments.
     pub fn generics(&self) -> &GenericArgs<'ast> {
         &self.generics
     }
+
+    /// This function resolves the target of this segment. For the path
+    /// `std::collections::HashMap::new`, the segment `HashMap` resolves to the
+    /// `HashMap` struct and the segment `new` to the associated function.
+    ///
+    /// [`AstPathTarget::Unresolved`] is returned for segments, which don't
+    /// point to a node in Marker's AST, like primitive types, or which can't be
+    /// resolved by the driver.
+    pub fn resolve(&self) -> AstPathTarget {
+        self.target
+    }
 }
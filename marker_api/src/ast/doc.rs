@@ -0,0 +1,230 @@
+//! This module extracts the fenced code blocks from doc comments. See
+//! [`ItemData::doc_examples`](super::ItemData::doc_examples).
+
+use crate::span::Span;
+
+use super::Attribute;
+
+/// A fenced code block in the doc comment of an item, like this:
+///
+/// ````text
+/// /// Returns the answer.
+/// ///
+/// /// ```rust,no_run
+/// /// //     ^^^^^^^^^^^ The info string, see `lang()`
+/// /// let answer = the_answer();
+/// /// assert_eq!(answer, 42);
+/// /// ```
+/// fn the_answer() -> u32 {
+///     42
+/// }
+/// ````
+#[derive(Debug)]
+pub struct DocCodeBlock<'ast> {
+    lang: String,
+    code: String,
+    span: Span<'ast>,
+}
+
+impl<'ast> DocCodeBlock<'ast> {
+    /// The info string, written after the opening fence. For `` ```rust,ignore ``
+    /// this is `rust,ignore`. Code blocks without an info string return an
+    /// empty string.
+    pub fn lang(&self) -> &str {
+        &self.lang
+    }
+
+    /// Returns `true`, if rustdoc treats this code block as Rust code. This is
+    /// the case for code blocks without an info string and code blocks, which
+    /// only specify `rust` and rustdoc attributes, like `ignore`, `no_run` or
+    /// `edition2021`.
+    pub fn is_rust(&self) -> bool {
+        self.lang
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|token| !token.is_empty())
+            .all(|token| {
+                matches!(
+                    token,
+                    "rust" | "ignore" | "should_panic" | "no_run" | "compile_fail" | "test_harness" | "standalone"
+                ) || token.starts_with("ignore-")
+                    || token.starts_with("edition")
+                    || (token.len() == 5 && token.starts_with('E') && token[1..].bytes().all(|b| b.is_ascii_digit()))
+            })
+    }
+
+    /// The code inside the block, without the fences. The indentation of the
+    /// opening fence is removed from every line. Lines hidden by rustdoc, like
+    /// `# use std::io;`, are included.
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The [`Span`] of the code inside the block. The span points into the doc
+    /// comment and can be used to emit diagnostics inside the example. Empty
+    /// code blocks return the span of the opening fence.
+    ///
+    /// For doc comments, whose content can't be mapped to the source code, like
+    /// comments created by macros, this is the span of the entire comments.
+    pub fn span(&self) -> &Span<'ast> {
+        &self.span
+    }
+}
+
+/// A single line of the doc comment and its [`Span`].
+struct DocLine<'ast> {
+    text: &'ast str,
+    span: Span<'ast>,
+    /// `true`, if the span only covers the text of this line.
+    mapped: bool,
+}
+
+impl<'ast> DocLine<'ast> {
+    /// Returns the span of the line, without the first `skip` bytes.
+    fn span_after(&self, skip: usize) -> Span<'ast> {
+        if self.mapped {
+            self.span.with_start(self.span.start().offset(skip))
+        } else {
+            self.span.clone()
+        }
+    }
+}
+
+/// The length of the doc comment prefixes `///`, `//!`, `/**` and `/*!`.
+const DOC_PREFIX_LEN: usize = 3;
+
+fn doc_lines<'ast>(attrs: &'ast [Attribute<'ast>]) -> Vec<DocLine<'ast>> {
+    let mut lines = vec![];
+    for attr in attrs {
+        let Some(doc) = attr.doc_comment() else { continue };
+        let span = attr.span();
+        let mapped = !span.is_from_expansion()
+            && span
+                .snippet()
+                .and_then(|snippet| snippet.get(DOC_PREFIX_LEN..))
+                .is_some_and(|content| content.starts_with(doc));
+
+        // Only block comments, like `/** Doc */`, can contain multiple lines
+        let is_block = doc.contains('\n');
+        let mut offset = DOC_PREFIX_LEN;
+        for line in doc.split('\n') {
+            let mut text = line.strip_suffix('\r').unwrap_or(line);
+            let mut skip = 0;
+            // Lines of block comments are usually prefixed with a `*`, which is
+            // removed by rustdoc
+            if is_block {
+                if let Some(rest) = text.trim_start().strip_prefix('*') {
+                    skip = text.len() - rest.len();
+                    text = rest;
+                }
+            }
+            let line_span = if mapped {
+                let start = span.start().offset(offset + skip);
+                span.with_start(start).with_end(start.offset(text.len()))
+            } else {
+                span.clone()
+            };
+            lines.push(DocLine {
+                text,
+                span: line_span,
+                mapped,
+            });
+            offset += line.len() + 1;
+        }
+    }
+    lines
+}
+
+/// Parses an opening code fence, like `` ```rust ``. The returned values are the
+/// indentation of the fence, the fence itself and the info string.
+fn parse_opening_fence(line: &str) -> Option<(usize, &str, &str)> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let fence_len = trimmed.chars().take_while(|c| *c == fence_char).count();
+    if fence_len < 3 {
+        return None;
+    }
+    let (fence, info) = trimmed.split_at(fence_len);
+    if fence_char == '`' && info.contains('`') {
+        return None;
+    }
+    Some((line.len() - trimmed.len(), fence, info.trim()))
+}
+
+fn is_closing_fence(line: &str, fence: &str) -> bool {
+    let trimmed = line.trim();
+    let fence_char = fence.as_bytes()[0];
+    trimmed.len() >= fence.len() && trimmed.bytes().all(|b| b == fence_char)
+}
+
+/// Removes up to `indent` whitespace characters from the start of the line.
+fn strip_indent(line: &str, indent: usize) -> &str {
+    let skip: usize = line
+        .chars()
+        .take(indent)
+        .take_while(|c| c.is_whitespace())
+        .map(char::len_utf8)
+        .sum();
+    &line[skip..]
+}
+
+pub(crate) fn doc_examples<'ast>(attrs: &'ast [Attribute<'ast>]) -> Vec<DocCodeBlock<'ast>> {
+    let lines = doc_lines(attrs);
+    let mut blocks = vec![];
+    let mut iter = lines.iter();
+    while let Some(fence_line) = iter.next() {
+        let Some((indent, fence, info)) = parse_opening_fence(fence_line.text) else {
+            continue;
+        };
+
+        // Unclosed code blocks continue until the end of the doc comment
+        let mut code = String::new();
+        let mut code_span: Option<Span<'ast>> = None;
+        for line in iter.by_ref() {
+            if is_closing_fence(line.text, fence) {
+                break;
+            }
+            let text = strip_indent(line.text, indent);
+            let line_span = line.span_after(line.text.len() - text.len());
+            code_span = Some(match code_span {
+                Some(span) => {
+                    code.push('\n');
+                    span.with_end(line_span.end())
+                },
+                None => line_span,
+            });
+            code.push_str(text);
+        }
+
+        blocks.push(DocCodeBlock {
+            lang: info.to_string(),
+            code,
+            span: code_span.unwrap_or_else(|| fence_line.span.clone()),
+        });
+    }
+    blocks
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_code_fences() {
+        assert_eq!(parse_opening_fence(" ```"), Some((1, "```", "")));
+        assert_eq!(parse_opening_fence("```rust,ignore "), Some((0, "```", "rust,ignore")));
+        assert_eq!(parse_opening_fence("  ~~~~text"), Some((2, "~~~~", "text")));
+        assert_eq!(parse_opening_fence(" `` "), None);
+        assert_eq!(parse_opening_fence("``` `code` "), None);
+        assert_eq!(parse_opening_fence(" text"), None);
+
+        assert!(is_closing_fence(" ```", "```"));
+        assert!(is_closing_fence("~~~~~ ", "~~~"));
+        assert!(!is_closing_fence("~~~", "```"));
+        assert!(!is_closing_fence("``", "```"));
+        assert!(!is_closing_fence("``` rust", "```"));
+
+        assert_eq!(strip_indent("   code", 2), " code");
+        assert_eq!(strip_indent(" code", 3), "code");
+        assert_eq!(strip_indent("code", 3), "code");
+    }
+}
@@ -13,6 +13,7 @@ mod block_expr;
 mod call_exprs;
 mod control_flow_expr;
 mod ctor_expr;
+mod format_args;
 mod lit_expr;
 mod op_exprs;
 mod path_expr;
@@ -22,6 +23,7 @@ pub use block_expr::*;
 pub use call_exprs::*;
 pub use control_flow_expr::*;
 pub use ctor_expr::*;
+pub use format_args::*;
 pub use lit_expr::*;
 pub use op_exprs::*;
 pub use path_expr::*;
@@ -250,6 +252,27 @@ pub enum ExprPrecedence {
     Unstable(i32),
 }
 
+/// The position of an expression, relative to its parent expression. This is
+/// used by [`MarkerContext::expr_needs_parens`](crate::MarkerContext::expr_needs_parens)
+/// to determine if parentheses are required.
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ExprPosition {
+    /// The left operand of a binary operator, like `a` in `a + b`. This also
+    /// includes the operand of an `as` cast, the start of a range and the place
+    /// of an assignment.
+    Left,
+    /// The right operand of a binary operator, like `b` in `a + b`. This also
+    /// includes the end of a range and the value of an assignment.
+    Right,
+    /// The operand of a prefix operator, like `a` in `-a`, `!a`, `*a` or `&a`.
+    Prefix,
+    /// The operand of a postfix operator, like the receiver of a method call, the
+    /// operand of a field access or index expression, the function of a call, or
+    /// the operand of `?` and `.await`.
+    Postfix,
+}
+
 macro_rules! impl_expr_kind_fn {
     (ExprKind: $method:ident () -> $return_ty:ty) => {
         impl_expr_kind_fn!((ExprKind) $method() -> $return_ty,
@@ -348,8 +371,8 @@ use impl_expr_data;
 /// An expression that is evaluated at compile time. These show up in array
 /// indices and constant generics.
 ///
-/// Marker currently doesn't provide a way to calculate the result of a constant
-/// expression.
+/// The result of a constant expression can be calculated with
+/// [`MarkerContext::try_eval_const`](crate::MarkerContext::try_eval_const).
 #[repr(C)]
 #[derive(Debug)]
 pub struct ConstExpr<'ast> {
@@ -396,13 +419,13 @@ mod test {
         assert_size_of::<AsExpr<'_>>(&expect!["48"]);
         assert_size_of::<PathExpr<'_>>(&expect!["96"]);
         assert_size_of::<CallExpr<'_>>(&expect!["48"]);
-        assert_size_of::<MethodExpr<'_>>(&expect!["80"]);
+        assert_size_of::<MethodExpr<'_>>(&expect!["96"]);
         assert_size_of::<ArrayExpr<'_>>(&expect!["56"]);
         assert_size_of::<TupleExpr<'_>>(&expect!["32"]);
         assert_size_of::<CtorExpr<'_>>(&expect!["136"]);
         assert_size_of::<RangeExpr<'_>>(&expect!["72"]);
         assert_size_of::<IndexExpr<'_>>(&expect!["48"]);
-        assert_size_of::<FieldExpr<'_>>(&expect!["48"]);
+        assert_size_of::<FieldExpr<'_>>(&expect!["72"]);
         assert_size_of::<IfExpr<'_>>(&expect!["72"]);
         assert_size_of::<LetExpr<'_>>(&expect!["48"]);
         assert_size_of::<MatchExpr<'_>>(&expect!["48"]);
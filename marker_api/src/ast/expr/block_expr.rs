@@ -1,11 +1,13 @@
 use crate::{
-    ast::{pat::PatKind, stmt::StmtKind, ty::TyKind},
-    common::{BodyId, Safety, SpanId, Syncness},
+    ast::{pat::PatKind, stmt::StmtKind, ty::TyKind, Body},
+    common::{BodyId, Constness, Safety, SpanId, Syncness},
+    context::with_cx,
     ffi::{FfiOption, FfiSlice},
+    sem::ClosureKind,
     span::Ident,
 };
 
-use super::{CommonExprData, ExprKind};
+use super::{CommonExprData, ExprData, ExprKind};
 
 /// A block expression is one of the most fundamental expressions in Rust. It
 /// is used by items and expressions to group statements together and express
@@ -37,7 +39,9 @@ use super::{CommonExprData, ExprKind};
 /// clear by linking directly to the [`BlockExpr`] struct or calling it a *block*.
 ///
 /// This expression also represents async blocks, the internal desugar used by
-/// rustc is resugared for this.
+/// rustc is resugared for this. Const blocks, like `const { 1 + 2 }`, are
+/// represented by this expression as well. The kind of the block can be
+/// checked with [`BlockExpr::block_kind()`].
 #[repr(C)]
 #[derive(Debug)]
 pub struct BlockExpr<'ast> {
@@ -47,6 +51,7 @@ pub struct BlockExpr<'ast> {
     label: FfiOption<Ident<'ast>>,
     safety: Safety,
     syncness: Syncness,
+    constness: Constness,
     capture_kind: CaptureKind,
 }
 
@@ -63,6 +68,8 @@ impl<'ast> BlockExpr<'ast> {
         self.expr.copy()
     }
 
+    /// The optional label of this block, like `'label` in `'label: { ... }`.
+    /// Labeled blocks can be targeted by [`BreakExpr`](super::BreakExpr)s.
     pub fn label(&self) -> Option<&Ident<'ast>> {
         self.label.get()
     }
@@ -75,6 +82,36 @@ impl<'ast> BlockExpr<'ast> {
         self.syncness
     }
 
+    pub fn constness(&self) -> Constness {
+        self.constness
+    }
+
+    /// Returns the kind of this block, based on the keyword in front of it.
+    /// Labels are not part of the kind, they can be accessed via
+    /// [`BlockExpr::label()`].
+    ///
+    /// ```
+    /// # #![feature(inline_const)]
+    /// # async fn foo() {
+    /// let _ = { 1 };           // BlockKind::Default
+    /// let _ = 'label: { 1 };   // BlockKind::Default
+    /// let _ = unsafe { 1 };    // BlockKind::Unsafe
+    /// let _ = async { 1 };     // BlockKind::Async
+    /// let _ = const { 1 };     // BlockKind::Const
+    /// # }
+    /// ```
+    pub fn block_kind(&self) -> BlockKind {
+        if self.constness.is_const() {
+            BlockKind::Const
+        } else if self.syncness.is_async() {
+            BlockKind::Async
+        } else if self.safety.is_unsafe() {
+            BlockKind::Unsafe
+        } else {
+            BlockKind::Default
+        }
+    }
+
     /// The capture kind of this block. For normal blocks, this will always be
     /// [`CaptureKind::Default`], which in this context means no capture at all.
     /// Async blocks are special, as they can capture values by move, indicated
@@ -99,6 +136,7 @@ super::impl_expr_data!(BlockExpr<'ast>, Block);
 
 #[cfg(feature = "driver-api")]
 impl<'ast> BlockExpr<'ast> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         data: CommonExprData<'ast>,
         stmts: &'ast [StmtKind<'ast>],
@@ -106,6 +144,7 @@ impl<'ast> BlockExpr<'ast> {
         label: Option<Ident<'ast>>,
         safety: Safety,
         syncness: Syncness,
+        constness: Constness,
         capture_kind: CaptureKind,
     ) -> Self {
         Self {
@@ -115,11 +154,27 @@ impl<'ast> BlockExpr<'ast> {
             label: label.into(),
             safety,
             syncness,
+            constness,
             capture_kind,
         }
     }
 }
 
+/// The kind of a [`BlockExpr`], see [`BlockExpr::block_kind()`].
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BlockKind {
+    /// A normal block, like `{ ... }` or `'label: { ... }`
+    Default,
+    /// An unsafe block, like `unsafe { ... }`
+    Unsafe,
+    /// An async block, like `async { ... }` or `async move { ... }`
+    Async,
+    /// A const block, like `const { ... }`
+    Const,
+}
+
 /// A closure expression
 ///
 /// ```
@@ -160,6 +215,28 @@ impl<'ast> ClosureExpr<'ast> {
     pub fn body_id(&self) -> BodyId {
         self.body_id
     }
+
+    /// Returns the [`Body`] of this closure. The expression of the body can be
+    /// walked like the body of a function item. This is a shorthand for
+    /// retrieving the body with [`AstMap::body`](crate::context::AstMap::body).
+    pub fn body(&self) -> &Body<'ast> {
+        with_cx(self, |cx| cx.ast().body(self.body_id))
+    }
+
+    /// Returns the inferred [`ClosureKind`] of this closure. The inferred
+    /// signature is available via the semantic [`ClosureTy`](crate::sem::ClosureTy)
+    /// of this expression.
+    ///
+    /// Async closures and coroutines don't have a semantic [`ClosureTy`] yet and
+    /// return `None`.
+    ///
+    /// [`ClosureTy`]: crate::sem::ClosureTy
+    pub fn closure_kind(&self) -> Option<ClosureKind> {
+        match self.ty() {
+            crate::sem::TyKind::Closure(closure) => Some(closure.kind()),
+            _ => None,
+        }
+    }
 }
 
 super::impl_expr_data!(ClosureExpr<'ast>, Closure);
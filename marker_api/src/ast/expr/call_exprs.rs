@@ -1,4 +1,10 @@
-use crate::{ast::AstPathSegment, ffi::FfiSlice};
+use crate::{
+    ast::AstPathSegment,
+    common::{ItemId, SpanId},
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    span::Span,
+};
 
 use super::{CommonExprData, ExprKind};
 
@@ -41,6 +47,14 @@ impl<'ast> CallExpr<'ast> {
     pub fn args(&self) -> &[ExprKind<'ast>] {
         self.args.get()
     }
+
+    /// Returns `true`, if this calls a method of the [`Iterator`] trait with a
+    /// path, which returns another iterator, like `Iterator::map(iter, f)`.
+    /// Consuming methods, like `Iterator::count(iter)`, are not adapters. See
+    /// [`MethodExpr::is_iterator_adapter`] for the method call syntax.
+    pub fn is_iterator_adapter(&self) -> bool {
+        with_cx(self, |cx| cx.is_iterator_adapter(self.data.id))
+    }
 }
 
 super::impl_expr_data!(CallExpr<'ast>, Call);
@@ -59,7 +73,9 @@ impl<'ast> MethodExpr<'ast> {
         self.receiver
     }
 
-    /// This is the identifier of the method, being called on the receiver.
+    /// This is the identifier of the method, being called on the receiver. The
+    /// segment includes the turbofish arguments, like `Vec<_>` in
+    /// `iter.collect::<Vec<_>>()`, and resolves to the called method.
     pub fn method(&self) -> &AstPathSegment<'ast> {
         &self.method
     }
@@ -77,10 +93,85 @@ impl<'ast> MethodExpr<'ast> {
     pub fn args(&self) -> &[ExprKind<'ast>] {
         self.args.get()
     }
+
+    /// Returns `true`, if this calls a method of the [`Iterator`] trait, which
+    /// returns another iterator, like `filter()` or `map()`. Consuming methods,
+    /// like `collect()` or `next()`, are not adapters.
+    ///
+    /// ```ignore
+    /// // Detects `iter.filter(..).map(..)`
+    /// if let ExprKind::Method(outer) = expr
+    ///     && outer.method().ident().name() == "map"
+    ///     && let ExprKind::Method(inner) = outer.receiver()
+    ///     && inner.method().ident().name() == "filter"
+    ///     && inner.is_iterator_adapter()
+    /// {
+    ///     // Suggest `filter_map`
+    /// }
+    /// ```
+    pub fn is_iterator_adapter(&self) -> bool {
+        with_cx(self, |cx| cx.is_iterator_adapter(self.data.id))
+    }
+
+    /// Returns `true`, if this calls `unwrap()` or `expect()` on an [`Option`]
+    /// or [`Result`]. See [`MarkerContext::is_in_test_code`] and
+    /// [`MarkerContext::is_guarded_unwrap`] for related checks.
+    ///
+    /// [`MarkerContext::is_in_test_code`]: crate::MarkerContext::is_in_test_code
+    /// [`MarkerContext::is_guarded_unwrap`]: crate::MarkerContext::is_guarded_unwrap
+    pub fn is_unwrap_or_expect(&self) -> bool {
+        matches!(self.method.ident().name(), "unwrap" | "expect")
+            && with_cx(self, |cx| cx.is_option_or_result(self.receiver.ty()))
+    }
+
+    /// Returns `true`, if the called method is defined by a trait, like
+    /// [`Iterator::map`]. Inherent methods, like [`Option::map`], return `false`.
+    ///
+    /// ```ignore
+    /// // Only targets `Iterator::map`, not inherent `map` methods
+    /// if method.method().ident().name() == "map"
+    ///     && method.is_trait_method()
+    ///     && cx.is_iterator_ty(method.receiver().ty(), body.owner())
+    /// {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn is_trait_method(&self) -> bool {
+        self.defining_trait().is_some()
+    }
+
+    /// Returns the [`ItemId`] of the trait, which defines the called method, or
+    /// `None` for inherent methods. For trait methods, this is the trait and not
+    /// the implementation, which is selected for the receiver type.
+    pub fn defining_trait(&self) -> Option<ItemId> {
+        with_cx(self, |cx| cx.method_trait(self.data.id))
+    }
+
+    /// Returns how the called method is dispatched. Calls on trait objects, like
+    /// `dyn Iterator`, are dispatched dynamically via the vtable. All other calls,
+    /// including trait methods called on generic types, are resolved statically.
+    pub fn dispatch(&self) -> DispatchKind {
+        if with_cx(self, |cx| cx.is_dynamic_dispatch(self.data.id)) {
+            DispatchKind::Dynamic
+        } else {
+            DispatchKind::Static
+        }
+    }
 }
 
 super::impl_expr_data!(MethodExpr<'ast>, Method);
 
+/// The way a method is dispatched, see [`MethodExpr::dispatch`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DispatchKind {
+    /// The called function is known at compile time. The call can be inlined.
+    Static,
+    /// The called function is selected at runtime, via the vtable of a trait
+    /// object.
+    Dynamic,
+}
+
 #[cfg(feature = "driver-api")]
 impl<'ast> MethodExpr<'ast> {
     pub fn new(
@@ -97,3 +188,79 @@ impl<'ast> MethodExpr<'ast> {
         }
     }
 }
+
+/// Information about a call of a panicking macro, as returned by
+/// [`MarkerContext::panic_macro`](crate::MarkerContext::panic_macro).
+///
+/// ```
+/// # fn example(value: u32) {
+/// //  vvvvv The kind of the macro
+///     todo!("support negative values");
+/// //        ^^^^^^^^^^^^^^^^^^^^^^^^^ The message
+///
+/// //  vvvvvvvvv The kind of the macro
+///     assert_eq!(value, 1, "the value should be {}", 1);
+/// //                       ^^^^^^^^^^^^^^^^^^^^^^^^ The message
+/// # }
+/// ```
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct PanicMacro<'ast> {
+    kind: PanicKind,
+    message: FfiOption<FfiStr<'ast>>,
+    span: SpanId,
+}
+
+impl<'ast> PanicMacro<'ast> {
+    pub fn kind(&self) -> PanicKind {
+        self.kind
+    }
+
+    /// The message passed to the macro, if it's a string literal. This is the
+    /// content of the literal, with escapes resolved. Format placeholders, like
+    /// `{}`, are not replaced.
+    ///
+    /// `None` is returned, if the macro was called without a message or if the
+    /// message is not a string literal, like in `panic!(message)`.
+    pub fn message(&self) -> Option<&'ast str> {
+        self.message.get().map(FfiStr::get)
+    }
+
+    /// The span of the macro call, like `todo!()`. The expression itself is
+    /// expanded from the macro. Lints, which don't report in macros, can use
+    /// this span to emit their diagnostic at the macro call instead.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> PanicMacro<'ast> {
+    pub fn new(kind: PanicKind, message: Option<&'ast str>, span: SpanId) -> Self {
+        Self {
+            kind,
+            message: message.map(Into::into).into(),
+            span,
+        }
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PanicKind {
+    /// A [`panic!`] call
+    Panic,
+    /// A [`todo!`] call
+    Todo,
+    /// An [`unimplemented!`] call
+    Unimplemented,
+    /// An [`unreachable!`] call
+    Unreachable,
+    /// An [`assert!`] or [`debug_assert!`] call
+    Assert,
+    /// An [`assert_eq!`] or [`debug_assert_eq!`] call
+    AssertEq,
+    /// An [`assert_ne!`] or [`debug_assert_ne!`] call
+    AssertNe,
+}
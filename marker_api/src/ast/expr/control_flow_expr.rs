@@ -3,10 +3,10 @@ use crate::{
     common::{ExprId, SpanId},
     context::with_cx,
     ffi::{FfiOption, FfiSlice},
-    span::{Ident, Span},
+    span::{HasSpan, Ident, Span, SpanSource},
 };
 
-use super::{CommonExprData, ExprKind};
+use super::{BinaryOpKind, BlockKind, CommonExprData, ExprKind};
 
 /// An if expression. If let expressions are expressed as an [`IfExpr`] with an
 /// [`LetExpr`] as the conditional expression.
@@ -115,6 +115,27 @@ impl<'ast> LetExpr<'ast> {
     pub fn scrutinee(&self) -> ExprKind<'ast> {
         self.scrutinee
     }
+
+    /// Returns `true`, if the pattern matches every value of the scrutinee type.
+    /// In this case, the `let` expression always evaluates to `true` and an `if let`
+    /// could be replaced by a simple `let` statement:
+    ///
+    /// ```
+    /// # struct Wrapper(i32);
+    /// # let value = Some(1);
+    /// # let wrapper = Wrapper(2);
+    /// if let x = value {}               // `true`, a binding without subpattern
+    /// if let Wrapper(inner) = wrapper {} // `true`, structs only have one constructor
+    /// if let (_, y) = (1, value) {}     // `true`
+    /// if let Some(x) = value {}         // `false`, `None` is not matched
+    /// ```
+    ///
+    /// This check is conservative. Patterns, which are only irrefutable due to
+    /// uninhabited types or a combination of or-patterns, like `true | false`,
+    /// are considered refutable.
+    pub fn is_irrefutable(&self) -> bool {
+        with_cx(self, |cx| cx.let_expr_is_irrefutable(self.data.id))
+    }
 }
 
 super::impl_expr_data!(LetExpr<'ast>, Let);
@@ -159,6 +180,227 @@ impl<'ast> MatchExpr<'ast> {
     pub fn arms(&self) -> &[MatchArm<'ast>] {
         self.arms.get()
     }
+
+    /// Returns `true`, if every arm of this match evaluates to a boolean literal.
+    /// Blocks, which only contain a boolean literal, are also accepted.
+    ///
+    /// ```
+    /// # let value = Some(1);
+    /// let _ = match value {
+    ///     Some(1 | 2) => true,
+    ///     _ => false,
+    /// };
+    /// ```
+    ///
+    /// Such matches can often be replaced by the [`matches!`] macro, see
+    /// [`MatchExpr::matches_suggestion`].
+    pub fn is_boolean_match(&self) -> bool {
+        self.arms().iter().all(|arm| bool_lit_value(arm.expr()).is_some())
+    }
+
+    /// Returns the [`matches!`] invocation, which is equivalent to this match.
+    /// The suggestion replaces the entire match expression.
+    ///
+    /// ```
+    /// # let value = Some(1);
+    /// // Suggests `matches!(value, Some(1) | Some(2))`
+    /// let _ = match value {
+    ///     Some(1) => true,
+    ///     Some(2) => true,
+    ///     _ => false,
+    /// };
+    ///
+    /// // Suggests `!matches!(value, Some(x) if x > 2)`
+    /// let _ = match value {
+    ///     Some(x) if x > 2 => false,
+    ///     _ => true,
+    /// };
+    /// ```
+    ///
+    /// The last arm has to be a [catch-all arm](MatchArm::is_catch_all), while all
+    /// other arms have to evaluate to the opposite boolean value. `matches!` only
+    /// supports a single guard. Matches with multiple arms, where one of them has a
+    /// guard, can't be converted. The patterns of multiple arms are combined into
+    /// an or-pattern, which requires them to bind the same variables. `None` is
+    /// returned in these cases, if this isn't a [boolean match](Self::is_boolean_match)
+    /// or if a snippet is unavailable.
+    pub fn matches_suggestion(&self) -> Option<String> {
+        let [arms @ .., last] = self.arms() else {
+            return None;
+        };
+        if self.span().is_from_expansion() {
+            return None;
+        }
+        if arms.is_empty() || !last.is_catch_all() {
+            return None;
+        }
+        let value = bool_lit_value(arms[0].expr())?;
+        if bool_lit_value(last.expr())? == value
+            || !arms.iter().all(|arm| bool_lit_value(arm.expr()) == Some(value))
+            || (arms.len() > 1 && arms.iter().any(|arm| arm.guard().is_some()))
+        {
+            return None;
+        }
+        if arms.len() > 1 {
+            let first = binding_names(arms[0].pat())?;
+            if !arms[1..]
+                .iter()
+                .all(|arm| binding_names(arm.pat()).as_ref() == Some(&first))
+            {
+                return None;
+            }
+        }
+
+        let pats = arms
+            .iter()
+            .map(|arm| callsite_snippet(arm.pat().span()))
+            .collect::<Option<Vec<_>>>()?
+            .join(" | ");
+        let guard = match arms[0].guard() {
+            Some(guard) => format!(" if {}", callsite_snippet(guard.span())?),
+            None => String::new(),
+        };
+        let negation = if value { "" } else { "!" };
+        let scrutinee = callsite_snippet(self.scrutinee.span())?;
+        Some(format!("{negation}matches!({scrutinee}, {pats}{guard})"))
+    }
+
+    /// Returns the `if let` expression, which is equivalent to this match. The
+    /// suggestion replaces the entire match expression.
+    ///
+    /// ```
+    /// # let value = Some(1);
+    /// // Suggests `if let Some(x) = value { println!("{x}") }`
+    /// match value {
+    ///     Some(x) => println!("{x}"),
+    ///     _ => {},
+    /// }
+    /// ```
+    ///
+    /// The match has to have two arms. The first arm has to be refutable and can't
+    /// have a guard, as `if let` guards are unstable. The second arm has to be a
+    /// [catch-all arm](MatchArm::is_catch_all) with an empty body, like `{}` or
+    /// `()`. Bindings of the first arm remain usable in the body of the `if let`.
+    /// `None` is returned, if these conditions aren't met, if the match comes from
+    /// a macro expansion or if a snippet is unavailable.
+    pub fn if_let_suggestion(&self) -> Option<String> {
+        let [arm, other] = self.arms() else {
+            return None;
+        };
+        if self.span().is_from_expansion() {
+            return None;
+        }
+        if arm.guard().is_some() || arm.is_catch_all() || !other.is_catch_all() || !is_empty_body(other.expr()) {
+            return None;
+        }
+
+        let pat = callsite_snippet(arm.pat().span())?;
+        let body = callsite_snippet(arm.expr().span())?;
+        let body = match arm.expr() {
+            // Macros, like `println!`, can also expand to blocks
+            ExprKind::Block(block) if !block.span().is_from_expansion() => body.to_string(),
+            _ => format!("{{ {body} }}"),
+        };
+        let scrutinee = callsite_snippet(self.scrutinee.span())?;
+        // Struct expressions and lazy boolean operators have to be wrapped in
+        // parentheses, to be accepted as the scrutinee of an `if let`.
+        let needs_parens = match self.scrutinee {
+            ExprKind::Ctor(_) => true,
+            ExprKind::BinaryOp(op) => matches!(op.kind(), BinaryOpKind::And | BinaryOpKind::Or),
+            _ => false,
+        };
+        let scrutinee = if needs_parens && !is_parenthesized(scrutinee) {
+            format!("({scrutinee})")
+        } else {
+            scrutinee.to_string()
+        };
+        Some(format!("if let {pat} = {scrutinee} {body}"))
+    }
+}
+
+/// Returns the value of a boolean literal, which can be wrapped in blocks.
+fn bool_lit_value(expr: ExprKind<'_>) -> Option<bool> {
+    match expr {
+        ExprKind::BoolLit(lit) => Some(lit.value()),
+        ExprKind::Block(block)
+            if block.stmts().is_empty() && block.label().is_none() && block.block_kind() == BlockKind::Default =>
+        {
+            block.expr().and_then(bool_lit_value)
+        },
+        _ => None,
+    }
+}
+
+/// Returns the sorted names of all variables bound by the pattern. `None` is
+/// returned, if the pattern contains unstable patterns, which can't be checked.
+fn binding_names(pat: PatKind<'_>) -> Option<Vec<&str>> {
+    fn collect<'ast>(pat: PatKind<'ast>, names: &mut Vec<&'ast str>) -> Option<()> {
+        match pat {
+            PatKind::Ident(ident) => {
+                names.push(ident.name());
+                if let Some(binding) = ident.binding_pat() {
+                    collect(binding, names)?;
+                }
+            },
+            PatKind::Ref(ref_pat) => collect(ref_pat.pat(), names)?,
+            PatKind::Struct(struct_pat) => {
+                for field in struct_pat.fields() {
+                    collect(field.pat(), names)?;
+                }
+            },
+            PatKind::Tuple(tuple) => tuple.elements().iter().try_for_each(|pat| collect(*pat, names))?,
+            PatKind::Slice(slice) => slice.elements().iter().try_for_each(|pat| collect(*pat, names))?,
+            // All alternatives of an or-pattern bind the same variables
+            PatKind::Or(or_pat) => collect(*or_pat.pats().first()?, names)?,
+            PatKind::Unstable(_) => return None,
+            _ => {},
+        }
+        Some(())
+    }
+
+    let mut names = vec![];
+    collect(pat, &mut names)?;
+    names.sort_unstable();
+    Some(names)
+}
+
+/// Returns the snippet of the span. Spans from macro expansions, like the
+/// arguments of `println!`, are mapped to the outermost macro call.
+fn callsite_snippet<'ast>(span: &Span<'ast>) -> Option<&'ast str> {
+    let mut span = span;
+    while let SpanSource::Macro(expn) = span.source() {
+        span = expn.call_site();
+    }
+    span.snippet()
+}
+
+/// Returns `true`, if the entire snippet is wrapped in a pair of parentheses.
+fn is_parenthesized(snippet: &str) -> bool {
+    let Some(inner) = snippet.strip_prefix('(').and_then(|rest| rest.strip_suffix(')')) else {
+        return false;
+    };
+    // The opening parenthesis could also be closed before the end, like in `(a) && (b)`
+    let mut depth = 0_usize;
+    inner.chars().all(|c| {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => return false,
+            ')' => depth -= 1,
+            _ => {},
+        }
+        true
+    })
+}
+
+/// Returns `true`, if the expression is `()` or an empty block.
+fn is_empty_body(expr: ExprKind<'_>) -> bool {
+    match expr {
+        ExprKind::Tuple(tuple) => tuple.elements().is_empty(),
+        ExprKind::Block(block) => {
+            block.stmts().is_empty() && block.expr().is_none() && block.block_kind() == BlockKind::Default
+        },
+        _ => false,
+    }
 }
 
 super::impl_expr_data!(MatchExpr<'ast>, Match);
@@ -211,14 +453,50 @@ impl<'ast> MatchArm<'ast> {
         self.pat
     }
 
+    /// The optional guard expression of this arm, like `*x == 1` in
+    /// `[x] if *x == 1 => ...`.
     pub fn guard(&self) -> Option<ExprKind<'ast>> {
         self.guard.copy()
     }
 
+    /// The body of this arm, which is evaluated if the pattern matches and
+    /// the guard evaluates to `true`.
     pub fn expr(&self) -> ExprKind<'ast> {
         self.expr
     }
 
+    /// Returns `true`, if this arm matches every value, without a guard. This is
+    /// the case for wildcard patterns and bindings without a subpattern:
+    ///
+    /// ```
+    /// # let value = Some(1);
+    /// match value {
+    ///     Some(_) => {},    // `false`, the pattern is refutable
+    ///     x if false => {}, // `false`, the arm has a guard
+    ///     _ => {},          // `true`
+    /// }
+    ///
+    /// match value {
+    ///     None => {},       // `false`, the pattern is refutable
+    ///     other => {},      // `true`
+    /// }
+    /// ```
+    ///
+    /// Or patterns are catch-all arms, if one of their alternatives is one.
+    /// Other irrefutable patterns, like `(_, _)`, are not detected by this method.
+    pub fn is_catch_all(&self) -> bool {
+        fn is_catch_all_pat(pat: PatKind<'_>) -> bool {
+            match pat {
+                PatKind::Wildcard(_) => true,
+                PatKind::Ident(ident) => ident.binding_pat().map_or(true, is_catch_all_pat),
+                PatKind::Or(or) => or.pats().iter().any(|pat| is_catch_all_pat(*pat)),
+                _ => false,
+            }
+        }
+
+        self.guard.get().is_none() && is_catch_all_pat(self.pat)
+    }
+
     // FIXME(xFrednet): Add `fn attrs() -> ??? {}`, see rust-marker/marker#51
 }
 
@@ -533,7 +811,7 @@ impl<'ast> ForExpr<'ast> {
         self.pat
     }
 
-    pub fn iterable(&self) -> ExprKind {
+    pub fn iterable(&self) -> ExprKind<'ast> {
         self.iterable
     }
 
@@ -562,3 +840,63 @@ impl<'ast> ForExpr<'ast> {
         }
     }
 }
+
+/// Information about a loop, as returned by
+/// [`MarkerContext::enclosing_loop`](crate::MarkerContext::enclosing_loop).
+///
+/// ```
+/// //  vvvvvv The label of the loop
+///     'outer: for i in 0..10 {
+/// //          ^^^ The kind of the loop
+///         while i < 5 {
+///             break 'outer;
+///         //  ^^^^^^^^^^^^ A break expression, targeting the outer `for` loop
+///         }
+///     }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct LoopInfo<'ast> {
+    expr: ExprKind<'ast>,
+}
+
+impl<'ast> LoopInfo<'ast> {
+    /// Creates a new [`LoopInfo`] if the given expression is a loop.
+    pub(crate) fn new(expr: ExprKind<'ast>) -> Option<Self> {
+        matches!(expr, ExprKind::Loop(_) | ExprKind::While(_) | ExprKind::For(_)).then_some(Self { expr })
+    }
+
+    /// The loop expression. This is either a [`LoopExpr`], [`WhileExpr`] or
+    /// [`ForExpr`], as indicated by [`LoopInfo::kind`].
+    pub fn expr(&self) -> ExprKind<'ast> {
+        self.expr
+    }
+
+    pub fn kind(&self) -> LoopKind {
+        match self.expr {
+            ExprKind::Loop(_) => LoopKind::Loop,
+            ExprKind::While(_) => LoopKind::While,
+            ExprKind::For(_) => LoopKind::For,
+            _ => unreachable!("`LoopInfo` is only created for loop expressions"),
+        }
+    }
+
+    pub fn label(&self) -> Option<&'ast Ident<'ast>> {
+        match self.expr {
+            ExprKind::Loop(e) => e.label(),
+            ExprKind::While(e) => e.label(),
+            ExprKind::For(e) => e.label(),
+            _ => unreachable!("`LoopInfo` is only created for loop expressions"),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LoopKind {
+    /// An unconditional `loop {}`
+    Loop,
+    /// A `while` loop, including `while let` loops
+    While,
+    /// A `for` loop
+    For,
+}
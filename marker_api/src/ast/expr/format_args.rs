@@ -0,0 +1,337 @@
+use crate::{
+    common::{SpanId, SymbolId},
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    span::Span,
+};
+
+use super::ExprKind;
+
+/// The arguments of a formatting macro, like [`format!`] or [`println!`], as
+/// returned by [`MarkerContext::format_args`](crate::MarkerContext::format_args).
+///
+/// ```
+/// # let name = "marker";
+/// # let version = 1;
+/// println!("{name} v{:>3}", version);
+/// //        ^^^^^^                     A captured argument
+/// //              ^                    A literal piece
+/// //               ^^^^^               A placeholder with a width and an alignment
+/// //                        ^^^^^^^    An explicit argument
+/// ```
+///
+/// The template and arguments represent the macro call, as it was written by
+/// the user. Rustc might simplify the expansion, for example, by inlining
+/// literal arguments into the template.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatArgs<'ast> {
+    span: SpanId,
+    template: FfiSlice<'ast, FormatPiece<'ast>>,
+    arguments: FfiSlice<'ast, FormatArgument<'ast>>,
+}
+
+impl<'ast> FormatArgs<'ast> {
+    /// The span of the `format_args!` call, which contains the template and
+    /// the arguments. This span comes from the expansion of the macro.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    /// The pieces of the template string. Literal pieces have their escapes
+    /// resolved.
+    pub fn template(&self) -> &'ast [FormatPiece<'ast>] {
+        self.template.get()
+    }
+
+    /// The arguments of the macro call. Explicit arguments come first, followed
+    /// by arguments, which have been captured from the template, like `x` in
+    /// `"{x}"`. Placeholders refer to these arguments by their index.
+    pub fn arguments(&self) -> &'ast [FormatArgument<'ast>] {
+        self.arguments.get()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatArgs<'ast> {
+    pub fn new(span: SpanId, template: &'ast [FormatPiece<'ast>], arguments: &'ast [FormatArgument<'ast>]) -> Self {
+        Self {
+            span,
+            template: template.into(),
+            arguments: arguments.into(),
+        }
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum FormatPiece<'ast> {
+    /// A literal string between placeholders.
+    Literal(&'ast FormatLiteral<'ast>),
+    /// A placeholder like `{}` or `{name:?}`.
+    Placeholder(&'ast FormatPlaceholder),
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatLiteral<'ast> {
+    value: FfiStr<'ast>,
+}
+
+impl<'ast> FormatLiteral<'ast> {
+    /// The value of the literal, with resolved escapes.
+    pub fn value(&self) -> &'ast str {
+        self.value.get()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatLiteral<'ast> {
+    pub fn new(value: &'ast str) -> Self {
+        Self { value: value.into() }
+    }
+}
+
+/// A placeholder in the template of a formatting macro, like `{:>8.2}`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatPlaceholder {
+    argument: usize,
+    argument_kind: FormatArgPosKind,
+    span: FfiOption<SpanId>,
+    format_trait: FormatTrait,
+    width: FfiOption<FormatCount>,
+    precision: FfiOption<FormatCount>,
+    alignment: FfiOption<FormatAlignment>,
+    fill: FfiOption<char>,
+    flags: u8,
+}
+
+const FLAG_SIGN_PLUS: u8 = 1 << 0;
+const FLAG_SIGN_MINUS: u8 = 1 << 1;
+const FLAG_ALTERNATE: u8 = 1 << 2;
+const FLAG_ZERO_PAD: u8 = 1 << 3;
+
+impl FormatPlaceholder {
+    /// The index of the formatted argument in [`FormatArgs::arguments`].
+    pub fn argument(&self) -> usize {
+        self.argument
+    }
+
+    /// How the argument was referenced by this placeholder.
+    pub fn argument_kind(&self) -> FormatArgPosKind {
+        self.argument_kind
+    }
+
+    /// The span of the placeholder in the template string. This is `None`, if
+    /// the template was not written as a string literal, like in a macro.
+    pub fn span<'ast>(&self) -> Option<&Span<'ast>> {
+        with_cx(self, |cx| self.span.copy().map(|span| cx.span(span)))
+    }
+
+    /// The formatting trait used by this placeholder, like [`FormatTrait::Debug`]
+    /// for `{:?}`.
+    pub fn format_trait(&self) -> FormatTrait {
+        self.format_trait
+    }
+
+    /// The minimum width, like `8` in `{:8}`.
+    pub fn width(&self) -> Option<FormatCount> {
+        self.width.copy()
+    }
+
+    /// The precision, like `2` in `{:.2}`.
+    pub fn precision(&self) -> Option<FormatCount> {
+        self.precision.copy()
+    }
+
+    pub fn alignment(&self) -> Option<FormatAlignment> {
+        self.alignment.copy()
+    }
+
+    /// The fill character, like `-` in `{:->8}`.
+    pub fn fill(&self) -> Option<char> {
+        self.fill.copy()
+    }
+
+    /// Returns `true`, if the `+` flag is set, like in `{:+}`.
+    pub fn sign_plus(&self) -> bool {
+        self.flags & FLAG_SIGN_PLUS != 0
+    }
+
+    /// Returns `true`, if the `-` flag is set, like in `{:-}`.
+    pub fn sign_minus(&self) -> bool {
+        self.flags & FLAG_SIGN_MINUS != 0
+    }
+
+    /// Returns `true`, if the `#` flag is set, like in `{:#?}`.
+    pub fn alternate(&self) -> bool {
+        self.flags & FLAG_ALTERNATE != 0
+    }
+
+    /// Returns `true`, if the `0` flag is set, like in `{:08}`.
+    pub fn zero_pad(&self) -> bool {
+        self.flags & FLAG_ZERO_PAD != 0
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl FormatPlaceholder {
+    #[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+    pub fn new(
+        argument: usize,
+        argument_kind: FormatArgPosKind,
+        span: Option<SpanId>,
+        format_trait: FormatTrait,
+        width: Option<FormatCount>,
+        precision: Option<FormatCount>,
+        alignment: Option<FormatAlignment>,
+        fill: Option<char>,
+        sign_plus: bool,
+        sign_minus: bool,
+        alternate: bool,
+        zero_pad: bool,
+    ) -> Self {
+        let flags = [
+            (sign_plus, FLAG_SIGN_PLUS),
+            (sign_minus, FLAG_SIGN_MINUS),
+            (alternate, FLAG_ALTERNATE),
+            (zero_pad, FLAG_ZERO_PAD),
+        ]
+        .into_iter()
+        .filter(|(set, _)| *set)
+        .fold(0, |flags, (_, flag)| flags | flag);
+        Self {
+            argument,
+            argument_kind,
+            span: span.into(),
+            format_trait,
+            width: width.into(),
+            precision: precision.into(),
+            alignment: alignment.into(),
+            fill: fill.into(),
+            flags,
+        }
+    }
+}
+
+/// How a placeholder references its argument.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatArgPosKind {
+    /// The next argument is used implicitly, like in `{}` or `{:?}`.
+    Implicit,
+    /// The argument is referenced by its index, like in `{0}`.
+    Number,
+    /// The argument is referenced by its name, like in `{name}`.
+    Named,
+}
+
+/// The formatting trait of a placeholder.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatTrait {
+    /// `{}`, formatting with [`Display`](std::fmt::Display)
+    Display,
+    /// `{:?}`, formatting with [`Debug`](std::fmt::Debug)
+    Debug,
+    /// `{:e}`, formatting with [`LowerExp`](std::fmt::LowerExp)
+    LowerExp,
+    /// `{:E}`, formatting with [`UpperExp`](std::fmt::UpperExp)
+    UpperExp,
+    /// `{:o}`, formatting with [`Octal`](std::fmt::Octal)
+    Octal,
+    /// `{:p}`, formatting with [`Pointer`](std::fmt::Pointer)
+    Pointer,
+    /// `{:b}`, formatting with [`Binary`](std::fmt::Binary)
+    Binary,
+    /// `{:x}`, formatting with [`LowerHex`](std::fmt::LowerHex)
+    LowerHex,
+    /// `{:X}`, formatting with [`UpperHex`](std::fmt::UpperHex)
+    UpperHex,
+}
+
+/// The value of a width or precision.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatCount {
+    /// A literal value, like `8` in `{:8}`.
+    Literal(usize),
+    /// A value taken from an argument, like `width` in `{:width$}`. The value
+    /// is the index of the argument in [`FormatArgs::arguments`].
+    Argument(usize),
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatAlignment {
+    /// `<`
+    Left,
+    /// `>`
+    Right,
+    /// `^`
+    Center,
+}
+
+/// An argument of a formatting macro.
+#[repr(C)]
+#[derive(Debug)]
+pub struct FormatArgument<'ast> {
+    kind: FormatArgumentKind,
+    name: FfiOption<SymbolId>,
+    span: SpanId,
+    expr: FfiOption<ExprKind<'ast>>,
+}
+
+impl<'ast> FormatArgument<'ast> {
+    pub fn kind(&self) -> FormatArgumentKind {
+        self.kind
+    }
+
+    /// The name of named and captured arguments, like `name` in `name = value`.
+    pub fn name(&self) -> Option<&str> {
+        self.name.copy().map(|name| with_cx(self, |cx| cx.symbol_str(name)))
+    }
+
+    /// The span of the argument expression. For captured arguments, this is
+    /// the span of the identifier in the template string.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+
+    /// The argument expression. This can be `None` if rustc removed the
+    /// expression during the expansion, for example, when a literal argument
+    /// was inlined into the template.
+    pub fn expr(&self) -> Option<ExprKind<'ast>> {
+        self.expr.copy()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> FormatArgument<'ast> {
+    pub fn new(kind: FormatArgumentKind, name: Option<SymbolId>, span: SpanId, expr: Option<ExprKind<'ast>>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            span,
+            expr: expr.into(),
+        }
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum FormatArgumentKind {
+    /// A positional argument, like `value` in `format!("{}", value)`.
+    Normal,
+    /// A named argument, like `name = value` in `format!("{name}", name = value)`.
+    Named,
+    /// An argument captured from the template, like `x` in `format!("{x}")`.
+    Captured,
+}
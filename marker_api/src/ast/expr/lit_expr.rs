@@ -1,4 +1,7 @@
-use crate::{common::SymbolId, context::with_cx, ffi::FfiOption, ffi::FfiSlice};
+use crate::{
+    common::SymbolId, context::with_cx, ffi::FfiOption, ffi::FfiSlice, span::is_bidi_control_char,
+    span::is_zero_width_char,
+};
 
 use super::{CommonExprData, ExprPrecedence};
 
@@ -30,6 +33,7 @@ impl<'ast> BoolLitExpr<'ast> {
     }
 }
 
+/// A character literal like `'a'`, `'\n'` or `'\u{200B}'`.
 #[repr(C)]
 #[derive(Debug)]
 pub struct CharLitExpr<'ast> {
@@ -38,9 +42,44 @@ pub struct CharLitExpr<'ast> {
 }
 
 impl<'ast> CharLitExpr<'ast> {
+    /// The value of the literal, with all escapes resolved.
     pub fn value(&self) -> char {
         self.value
     }
+
+    /// Returns `true`, if the value is within the ASCII range.
+    pub fn is_ascii(&self) -> bool {
+        self.value.is_ascii()
+    }
+
+    /// Returns `true`, if the value has the general category for control
+    /// codes, like `'\n'` or `'\u{7f}'`.
+    pub fn is_control(&self) -> bool {
+        self.value.is_control()
+    }
+
+    /// Returns `true`, if the value is a combining character, like `'\u{301}'`
+    /// (combining acute accent). These characters modify the preceding character
+    /// and are often not visible on their own. Only the combining mark blocks of
+    /// Unicode are checked.
+    pub fn is_combining(&self) -> bool {
+        is_combining_char(self.value)
+    }
+
+    /// Returns `true`, if the value is a Unicode code point, that changes the
+    /// text direction, like `'\u{202E}'` (right-to-left override). These can
+    /// make the source code appear different from how the compiler reads it.
+    ///
+    /// See: <https://trojansource.codes/>
+    pub fn is_bidi_control(&self) -> bool {
+        is_bidi_control_char(self.value)
+    }
+
+    /// Returns `true`, if the value is an invisible zero-width code point, like
+    /// `'\u{200B}'` (zero width space) or `'\u{200D}'` (zero width joiner).
+    pub fn is_zero_width(&self) -> bool {
+        is_zero_width_char(self.value)
+    }
 }
 
 super::impl_expr_data!(
@@ -228,6 +267,21 @@ impl<'ast> StrLitExpr<'ast> {
             StrLitData::Bytes(bytes) => bytes.get(),
         }
     }
+
+    /// Returns `true`, if the string value contains a Unicode code point, that
+    /// changes the text direction. See [`CharLitExpr::is_bidi_control`] for
+    /// more information.
+    pub fn has_bidi_control(&self) -> bool {
+        self.str_value()
+            .map_or(false, |value| value.chars().any(is_bidi_control_char))
+    }
+
+    /// Returns `true`, if the string value contains an invisible zero-width
+    /// code point. See [`CharLitExpr::is_zero_width`] for more information.
+    pub fn has_zero_width(&self) -> bool {
+        self.str_value()
+            .map_or(false, |value| value.chars().any(is_zero_width_char))
+    }
 }
 
 super::impl_expr_data!(
@@ -245,6 +299,17 @@ impl<'ast> StrLitExpr<'ast> {
     }
 }
 
+fn is_combining_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{20D0}'..='\u{20FF}'
+            | '\u{FE20}'..='\u{FE2F}'
+    )
+}
+
 #[derive(Debug)]
 #[allow(clippy::exhaustive_enums)]
 #[cfg_attr(feature = "driver-api", visibility::make(pub))]
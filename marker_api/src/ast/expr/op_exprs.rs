@@ -1,10 +1,12 @@
 use crate::{
     ast::{pat::PatKind, ty::TyKind},
-    common::Mutability,
+    common::{ItemId, Mutability},
+    context::with_cx,
     ffi::FfiOption,
+    sem::TyKind as SemTyKind,
 };
 
-use super::{CommonExprData, ExprKind, ExprPrecedence};
+use super::{CommonExprData, ExprData, ExprKind, ExprPrecedence};
 
 #[repr(C)]
 #[derive(Debug)]
@@ -27,6 +29,55 @@ impl<'ast> BinaryOpExpr<'ast> {
     pub fn kind(&self) -> BinaryOpKind {
         self.kind
     }
+
+    /// Returns the [`ItemId`] of the trait method, which is called by this
+    /// operator, if it's overloaded. An example would be `a + b`, where `a`
+    /// is a user defined type, which implements [`Add`](std::ops::Add). The
+    /// id of [`Add::add`](std::ops::Add::add) would be returned in this case.
+    ///
+    /// `None` is returned for builtin operations on primitive types, like
+    /// `1 + 2`, and for the lazy boolean operators `&&` and `||`. These can't
+    /// be overloaded.
+    ///
+    /// The returned id belongs to the method of the trait and not the method
+    /// of the implementation. Traits from external crates, like the ones from
+    /// [`std::ops`], are therefore not available via the
+    /// [`AstMap`](crate::context::AstMap).
+    pub fn overloaded_fn(&self) -> Option<ItemId> {
+        with_cx(self, |cx| cx.overloaded_op_fn(self.data.id))
+    }
+
+    /// Returns `true`, if both operands are floats, like in `a == b` with `a`
+    /// and `b` being [`f64`] values. References are peeled, to also cover
+    /// operations like `&a < &b`. This is useful for lints that check float
+    /// comparisons or NaN handling. The type of the left operand can be used
+    /// to get the width of the floats:
+    ///
+    /// ```ignore
+    /// if op.is_float_op() {
+    ///     if let SemTyKind::Num(num) = op.left().ty().peel_refs() {
+    ///         let bits = num.numeric_kind().float_bits();
+    ///     }
+    /// }
+    /// ```
+    pub fn is_float_op(&self) -> bool {
+        self.left.ty().peel_refs().is_float() && self.right.ty().peel_refs().is_float()
+    }
+
+    /// Returns `true`, if this is a `==` or `!=` comparison of a `bool` expression
+    /// with a `bool` literal, like `x == true` or `false != x`. These comparisons
+    /// can be simplified with
+    /// [`MarkerContext::simplify_bool`](crate::MarkerContext::simplify_bool).
+    pub fn is_bool_literal_comparison(&self) -> bool {
+        if !matches!(self.kind, BinaryOpKind::Eq | BinaryOpKind::NotEq) {
+            return false;
+        }
+
+        match (self.left, self.right) {
+            (ExprKind::BoolLit(_), other) | (other, ExprKind::BoolLit(_)) => matches!(other.ty(), SemTyKind::Bool(_)),
+            _ => false,
+        }
+    }
 }
 
 super::impl_expr_data!(
@@ -215,6 +266,13 @@ impl<'ast> UnaryOpExpr<'ast> {
     pub fn kind(&self) -> UnaryOpKind {
         self.kind
     }
+
+    /// Returns `true`, if this is a logical negation of a `bool` expression,
+    /// like `!x`. The `!` operator on integers is a bitwise negation and
+    /// overloaded operators can have any meaning, these return `false`.
+    pub fn is_logical_not(&self) -> bool {
+        matches!(self.kind, UnaryOpKind::Not) && matches!(self.expr.ty(), SemTyKind::Bool(_))
+    }
 }
 
 super::impl_expr_data!(
@@ -264,6 +322,30 @@ impl<'ast> AsExpr<'ast> {
     pub fn cast_ty(&self) -> TyKind<'ast> {
         self.cast_ty
     }
+
+    /// Returns `true`, if this casts a fieldless enum to an integer, like
+    /// `Ordering::Less as i8`. The discriminants of the enum can be retrieved
+    /// with [`MarkerContext::enum_discriminants`](crate::MarkerContext::enum_discriminants).
+    pub fn is_enum_to_int(&self) -> bool {
+        matches!(self.ty(), SemTyKind::Num(num) if num.is_integer()) && is_fieldless_enum(self.expr.ty())
+    }
+
+    /// Returns `true`, if this casts an integer to a fieldless enum.
+    ///
+    /// Rust currently rejects these casts, since most integers are not valid
+    /// discriminants. Code like this would usually use [`std::mem::transmute`],
+    /// which is undefined behavior for invalid values. This function is provided
+    /// for completeness and to stay correct, if the cast is allowed in the future.
+    pub fn is_int_to_enum(&self) -> bool {
+        matches!(self.expr.ty(), SemTyKind::Num(num) if num.is_integer()) && is_fieldless_enum(self.ty())
+    }
+}
+
+fn is_fieldless_enum(ty: SemTyKind<'_>) -> bool {
+    match ty {
+        SemTyKind::Adt(adt) => with_cx(adt, |cx| cx.enum_discriminants(adt.def_id()).is_some()),
+        _ => false,
+    }
 }
 
 super::impl_expr_data!(AsExpr<'ast>, As);
@@ -275,6 +357,93 @@ impl<'ast> AsExpr<'ast> {
     }
 }
 
+/// An operation on a raw pointer, as returned by
+/// [`MarkerContext::raw_ptr_op`](crate::MarkerContext::raw_ptr_op).
+///
+/// ```
+/// # let value = 12_u32;
+/// # let address = &value as *const u32 as usize;
+/// //          vvvvvvvvvvvvvvvvvvvvvvvv A cast to a pointer, originating from a reference
+/// let ptr = &value as *const u32;
+///
+/// //          vvvvvvvvvvvvvvvvvvvvvvvvvv A cast to a pointer, originating from an integer
+/// let other = address as *const u32;
+///
+/// unsafe {
+/// //  vvvvvvvvvvvv Pointer arithmetic
+///     ptr.add(0);
+/// //  vvvv A dereference
+///     *ptr;
+/// }
+/// ```
+#[derive(Debug, Copy, Clone)]
+pub struct RawPtrOp<'ast> {
+    kind: RawPtrOpKind,
+    ptr: ExprKind<'ast>,
+    pointee: SemTyKind<'ast>,
+    origin: RawPtrOrigin,
+}
+
+impl<'ast> RawPtrOp<'ast> {
+    pub(crate) fn new(kind: RawPtrOpKind, ptr: ExprKind<'ast>, pointee: SemTyKind<'ast>, origin: RawPtrOrigin) -> Self {
+        Self {
+            kind,
+            ptr,
+            pointee,
+            origin,
+        }
+    }
+
+    pub fn kind(&self) -> RawPtrOpKind {
+        self.kind
+    }
+
+    /// The expression evaluating to the raw pointer. For casts, this is the
+    /// [`AsExpr`] itself.
+    pub fn ptr(&self) -> ExprKind<'ast> {
+        self.ptr
+    }
+
+    /// The semantic type, the pointer points to. For `*const u32` this would
+    /// be `u32`.
+    pub fn pointee(&self) -> SemTyKind<'ast> {
+        self.pointee
+    }
+
+    /// Where the pointer value came from, as far as it can be determined by
+    /// looking at the expression.
+    pub fn origin(&self) -> RawPtrOrigin {
+        self.origin
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RawPtrOpKind {
+    /// A dereference of a raw pointer, like `*ptr`
+    Deref,
+    /// Pointer arithmetic using methods like `ptr.add(1)` or `ptr.offset(-1)`
+    Arithmetic,
+    /// A cast to a raw pointer, like `value as *const T`
+    Cast,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RawPtrOrigin {
+    /// The pointer was created from a reference or place, like `&value as *const T`.
+    /// These pointers are initially valid.
+    Ref,
+    /// The pointer was created from an integer, like `0x1234 as *const T`. The
+    /// validity of these pointers can't be determined.
+    Int,
+    /// The origin of the pointer is unknown, for example, if it's a function
+    /// parameter or the result of a function call. Pointers stored in immutable
+    /// variables have the origin of the variable's initializer, pointers in
+    /// mutable variables have an unknown origin.
+    Unknown,
+}
+
 /// An expression assigning a value to an assignee expression.
 ///
 /// ```
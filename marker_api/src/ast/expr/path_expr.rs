@@ -1,4 +1,4 @@
-use crate::ast::AstQPath;
+use crate::ast::{AstPathSegment, AstQPath};
 
 use super::CommonExprData;
 
@@ -16,6 +16,16 @@ impl<'ast> PathExpr<'ast> {
     pub fn path(&self) -> &AstQPath<'ast> {
         &self.path
     }
+
+    /// Returns the [`AstPathSegment`]s of the path, including the segments of
+    /// the type for type relative paths, like `HashMap::<K, V>::new`. Each
+    /// segment provides its own generic arguments and resolution.
+    ///
+    /// This is a shorthand for [`AstQPath::segments()`], the documentation of
+    /// that function contains more details.
+    pub fn segments(&self) -> &[AstPathSegment<'ast>] {
+        self.path.segments()
+    }
 }
 
 super::impl_expr_data!(PathExpr<'ast>, Path);
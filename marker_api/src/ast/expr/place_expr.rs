@@ -1,4 +1,4 @@
-use crate::span::Ident;
+use crate::{ast::ItemField, common::FieldId, context::with_cx, ffi::FfiOption, sem::TyKind, span::Ident};
 
 use super::{CommonExprData, ExprKind};
 
@@ -31,6 +31,24 @@ impl<'ast> IndexExpr<'ast> {
     pub fn index(&self) -> ExprKind<'ast> {
         self.index
     }
+
+    /// Returns the value of the index, if it's an integer literal, like the
+    /// `2` in `array[2]`. Ranges and other expressions will return [`None`].
+    pub fn const_index(&self) -> Option<u128> {
+        match self.index {
+            ExprKind::IntLit(lit) => Some(lit.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the length of the indexed array, if the [`operand`](Self::operand)
+    /// is an array or a reference to an array with a known length.
+    pub fn operand_array_len(&self) -> Option<u128> {
+        match self.operand.ty().peel_refs() {
+            crate::sem::TyKind::Array(array) => array.try_len(),
+            _ => None,
+        }
+    }
 }
 
 super::impl_expr_data!(IndexExpr<'ast>, Index);
@@ -63,12 +81,18 @@ impl<'ast> IndexExpr<'ast> {
 ///     let _ = tuple.0;
 /// //                ^ The index of the tuple
 /// ```
+///
+/// The operand is automatically dereferenced, until a type with the accessed
+/// field is found. For example, `boxed.a` accesses the field `a` of the struct
+/// inside the `Box`, without an explicit `(*boxed).a`.
 #[repr(C)]
 #[derive(Debug)]
 pub struct FieldExpr<'ast> {
     data: CommonExprData<'ast>,
     operand: ExprKind<'ast>,
     field: Ident<'ast>,
+    field_id: FfiOption<FieldId>,
+    autoderefs: usize,
 }
 
 impl<'ast> FieldExpr<'ast> {
@@ -79,13 +103,46 @@ impl<'ast> FieldExpr<'ast> {
     pub fn field(&self) -> &Ident<'ast> {
         &self.field
     }
+
+    /// The semantic type of the operand, as it's written, before any automatic
+    /// dereferences. For `boxed.a` this is the `Box` type.
+    pub fn base_ty(&self) -> TyKind<'ast> {
+        self.operand.ty()
+    }
+
+    /// Returns the definition of the accessed field, after the automatic
+    /// dereferences of the operand. `None` is returned for fields of tuples
+    /// and for fields, which are defined outside the current crate.
+    pub fn resolved_field(&self) -> Option<&ItemField<'ast>> {
+        self.field_id
+            .copy()
+            .and_then(|id| with_cx(self, |cx| cx.ast().field(id)))
+    }
+
+    /// The number of automatic dereferences, which are applied to the operand,
+    /// to access the field. This is `1` for `boxed.a` and `0` for `(*boxed).a`.
+    pub fn autoderefs(&self) -> usize {
+        self.autoderefs
+    }
 }
 
 super::impl_expr_data!(FieldExpr<'ast>, Field);
 
 #[cfg(feature = "driver-api")]
 impl<'ast> FieldExpr<'ast> {
-    pub fn new(data: CommonExprData<'ast>, operand: ExprKind<'ast>, field: Ident<'ast>) -> Self {
-        Self { data, operand, field }
+    pub fn new(
+        data: CommonExprData<'ast>,
+        operand: ExprKind<'ast>,
+        field: Ident<'ast>,
+        field_id: Option<FieldId>,
+        autoderefs: usize,
+    ) -> Self {
+        Self {
+            data,
+            operand,
+            field,
+            field_id: field_id.into(),
+            autoderefs,
+        }
     }
 }
@@ -223,6 +223,48 @@ impl<'ast> GenericParams<'ast> {
     pub fn clauses(&self) -> &'ast [WhereClauseKind<'ast>] {
         self.clauses.get()
     }
+
+    /// Returns all outlives relations of these parameters, like `'a: 'b` and
+    /// `T: 'a`. Bounds declared inline with the parameter and bounds from the
+    /// `where` clause are both included, in the order of [`Self::clauses`]:
+    ///
+    /// ```
+    /// # use std::fmt::Debug;
+    /// fn example<'a, 'b: 'a, T: 'a + Debug>(a: &'a T, b: &'b str)
+    /// where
+    ///     'b: 'static,
+    ///     Vec<T>: 'b,
+    /// {}
+    /// // Results in these relations:
+    /// // - `'b: 'a`
+    /// // - `T: 'a`
+    /// // - `'b: 'static`
+    /// // - `Vec<T>: 'b`
+    /// ```
+    ///
+    /// Bounds of `impl Trait` parameters are not included.
+    pub fn lifetime_bounds(&self) -> impl Iterator<Item = LifetimeBound<'ast>> + 'ast {
+        self.clauses().iter().flat_map(|clause| -> Box<dyn Iterator<Item = _>> {
+            match clause {
+                WhereClauseKind::Lifetime(clause) => {
+                    let longer = OutlivesSubject::Lifetime(clause.lifetime());
+                    Box::new(
+                        clause
+                            .bounds()
+                            .iter()
+                            .map(move |shorter| LifetimeBound::new(longer, shorter)),
+                    )
+                },
+                WhereClauseKind::Ty(clause) => {
+                    let longer = OutlivesSubject::Ty(clause.ty());
+                    Box::new(clause.bounds().iter().filter_map(move |bound| match bound {
+                        TyParamBound::Lifetime(shorter) => Some(LifetimeBound::new(longer, shorter)),
+                        TyParamBound::TraitBound(_) => None,
+                    }))
+                },
+            }
+        })
+    }
 }
 
 #[cfg(feature = "driver-api")]
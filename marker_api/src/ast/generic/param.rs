@@ -306,6 +306,41 @@ impl<'ast> TyClause<'ast> {
     }
 }
 
+/// An outlives relation, which requires the [`longer`](LifetimeBound::longer)
+/// lifetime or type to outlive the [`shorter`](LifetimeBound::shorter) lifetime,
+/// like `'a: 'b` or `T: 'a`. These relations are collected from the generic
+/// parameters and `where` clauses by [`GenericParams::lifetime_bounds`].
+#[derive(Debug, Clone, Copy)]
+pub struct LifetimeBound<'ast> {
+    longer: OutlivesSubject<'ast>,
+    shorter: &'ast Lifetime<'ast>,
+}
+
+impl<'ast> LifetimeBound<'ast> {
+    pub(crate) fn new(longer: OutlivesSubject<'ast>, shorter: &'ast Lifetime<'ast>) -> Self {
+        Self { longer, shorter }
+    }
+
+    /// The lifetime or type, which has to outlive [`Self::shorter`]. This is
+    /// `'a` in `'a: 'b` and `T` in `T: 'b`.
+    pub fn longer(&self) -> OutlivesSubject<'ast> {
+        self.longer
+    }
+
+    /// The lifetime, which is outlived. This is `'b` in `'a: 'b` and `T: 'b`.
+    pub fn shorter(&self) -> &'ast Lifetime<'ast> {
+        self.shorter
+    }
+}
+
+/// The lifetime or type on the left side of a [`LifetimeBound`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum OutlivesSubject<'ast> {
+    Lifetime(&'ast Lifetime<'ast>),
+    Ty(TyKind<'ast>),
+}
+
 #[cfg(feature = "driver-api")]
 impl<'ast> TyClause<'ast> {
     pub fn new(params: Option<GenericParams<'ast>>, ty: TyKind<'ast>, bounds: &'ast [TyParamBound<'ast>]) -> Self {
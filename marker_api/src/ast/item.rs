@@ -1,7 +1,7 @@
 use std::fmt::Debug;
 
 use crate::{
-    common::{HasNodeId, ItemId, SpanId},
+    common::{CaseStyle, HasNodeId, ItemId, SpanId},
     context::with_cx,
     diagnostic::EmissionNode,
     ffi::FfiOption,
@@ -10,7 +10,7 @@ use crate::{
     CtorBlocker,
 };
 
-use super::expr::ExprKind;
+use super::{expr::ExprKind, Attribute, Derive, DocCodeBlock};
 
 // Item implementations
 mod extern_crate_item;
@@ -35,6 +35,8 @@ mod impl_item;
 pub use impl_item::*;
 mod extern_block_item;
 pub use extern_block_item::*;
+mod extern_type_item;
+pub use extern_type_item::*;
 mod unstable_item;
 pub use unstable_item::*;
 
@@ -53,18 +55,61 @@ pub trait ItemData<'ast>: Debug + EmissionNode<'ast> + HasSpan<'ast> + HasNodeId
     /// This function can return [`None`] if the item was generated and has no real name
     fn ident(&self) -> Option<&Ident<'ast>>;
 
+    /// Returns the [`CaseStyle`] of the item name, or [`None`], if the item has no
+    /// name. Names like `X` match several case styles, lints enforcing naming
+    /// conventions should therefore check the expected style with
+    /// [`CaseStyle::matches`]:
+    ///
+    /// ```ignore
+    /// let expected = match item {
+    ///     ItemKind::Mod(_) | ItemKind::Fn(_) => CaseStyle::SnakeCase,
+    ///     ItemKind::Const(_) | ItemKind::Static(_) => CaseStyle::ScreamingSnakeCase,
+    ///     ItemKind::Struct(_) | ItemKind::Enum(_) | ItemKind::Trait(_) => CaseStyle::PascalCase,
+    ///     _ => return,
+    /// };
+    /// let Some(ident) = item.ident() else { return };
+    /// if !expected.matches(ident.name()) {
+    ///     let sugg = cx.suggest_rename(ident.name(), expected);
+    ///     // ...
+    /// }
+    /// ```
+    fn name_case(&self) -> Option<CaseStyle> {
+        self.ident().map(|ident| CaseStyle::of(ident.name()))
+    }
+
+    /// Returns the fenced code blocks of the doc comments of this item. This can
+    /// be used to check the examples in the documentation. The [`Span`] of
+    /// each [`DocCodeBlock`] points into the doc comment.
+    ///
+    /// ```ignore
+    /// for example in item.doc_examples() {
+    ///     if example.is_rust() && example.code().contains(".unwrap()") {
+    ///         cx.emit_lint(LINT, item, "doc examples shouldn't use `unwrap()`")
+    ///             .span(example.span());
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Only doc comments, like `/// Doc`, are considered. Documentation written
+    /// as `#[doc = "..."]` attributes is ignored.
+    fn doc_examples(&self) -> Vec<DocCodeBlock<'ast>> {
+        super::doc::doc_examples(self.attrs())
+    }
+
     /// Returns this item wrapped in it's [`ExprKind`] variant.
     ///
     /// In function parameters, it's recommended to use `Into<ItemKind<'ast>>`
     /// as a bound to support all items and `ItemKind<'ast>` as parameters.
     fn as_item(&'ast self) -> ItemKind<'ast>;
 
-    /// The attributes attached to this item.
-    ///
-    /// Currently, it's only a placeholder until a proper representation is implemented.
-    /// rust-marker/marker#51 tracks the task of implementing this. You're welcome to
-    /// leave any comments in that issue.
-    fn attrs(&self); // FIXME: Add return type: -> &'ast [&'ast dyn Attribute<'ast>];
+    /// The attributes attached to this item. This includes inner attributes,
+    /// like `#![allow(dead_code)]` for modules, and doc comments.
+    fn attrs(&self) -> &'ast [Attribute<'ast>];
+
+    /// The derive macros applied to this item, like `Debug` and `Clone` for
+    /// `#[derive(Debug, Clone)]`. The derives are ordered as they were written.
+    /// Items which can't have derives, like functions, return an empty slice.
+    fn derives(&self) -> &'ast [Derive<'ast>];
 }
 
 #[repr(C)]
@@ -84,6 +129,7 @@ pub enum ItemKind<'ast> {
     Trait(&'ast TraitItem<'ast>),
     Impl(&'ast ImplItem<'ast>),
     ExternBlock(&'ast ExternBlockItem<'ast>),
+    ExternType(&'ast ExternTypeItem<'ast>),
     Unstable(&'ast UnstableItem<'ast>),
 }
 
@@ -92,7 +138,10 @@ impl<'ast> ItemKind<'ast> {
     impl_item_type_fn!(ItemKind: span() -> &Span<'ast>);
     impl_item_type_fn!(ItemKind: visibility() -> &Visibility<'ast>);
     impl_item_type_fn!(ItemKind: ident() -> Option<&Ident<'ast>>);
-    impl_item_type_fn!(ItemKind: attrs() -> ());
+    impl_item_type_fn!(ItemKind: name_case() -> Option<CaseStyle>);
+    impl_item_type_fn!(ItemKind: attrs() -> &'ast [Attribute<'ast>]);
+    impl_item_type_fn!(ItemKind: doc_examples() -> Vec<DocCodeBlock<'ast>>);
+    impl_item_type_fn!(ItemKind: derives() -> &'ast [Derive<'ast>]);
 }
 
 crate::span::impl_spanned_for!(ItemKind<'ast>);
@@ -111,7 +160,9 @@ impl<'ast> AssocItemKind<'ast> {
     impl_item_type_fn!(AssocItemKind: span() -> &Span<'ast>);
     impl_item_type_fn!(AssocItemKind: visibility() -> &Visibility<'ast>);
     impl_item_type_fn!(AssocItemKind: ident() -> Option<&Ident<'ast>>);
-    impl_item_type_fn!(AssocItemKind: attrs() -> ());
+    impl_item_type_fn!(AssocItemKind: attrs() -> &'ast [Attribute<'ast>]);
+    impl_item_type_fn!(AssocItemKind: doc_examples() -> Vec<DocCodeBlock<'ast>>);
+    impl_item_type_fn!(AssocItemKind: derives() -> &'ast [Derive<'ast>]);
     impl_item_type_fn!(AssocItemKind: as_item() -> ItemKind<'ast>);
     // FIXME: Potentially add a field to the items to optionally store the owner id
 }
@@ -134,6 +185,7 @@ impl<'ast> From<AssocItemKind<'ast>> for ItemKind<'ast> {
 pub enum ExternItemKind<'ast> {
     Static(&'ast StaticItem<'ast>, CtorBlocker),
     Fn(&'ast FnItem<'ast>, CtorBlocker),
+    Type(&'ast ExternTypeItem<'ast>, CtorBlocker),
 }
 
 impl<'ast> ExternItemKind<'ast> {
@@ -141,7 +193,9 @@ impl<'ast> ExternItemKind<'ast> {
     impl_item_type_fn!(ExternItemKind: span() -> &Span<'ast>);
     impl_item_type_fn!(ExternItemKind: visibility() -> &Visibility<'ast>);
     impl_item_type_fn!(ExternItemKind: ident() -> Option<&Ident<'ast>>);
-    impl_item_type_fn!(ExternItemKind: attrs() -> ());
+    impl_item_type_fn!(ExternItemKind: attrs() -> &'ast [Attribute<'ast>]);
+    impl_item_type_fn!(ExternItemKind: doc_examples() -> Vec<DocCodeBlock<'ast>>);
+    impl_item_type_fn!(ExternItemKind: derives() -> &'ast [Derive<'ast>]);
     impl_item_type_fn!(ExternItemKind: as_item() -> ItemKind<'ast>);
 }
 
@@ -153,6 +207,7 @@ impl<'ast> From<ExternItemKind<'ast>> for ItemKind<'ast> {
         match value {
             ExternItemKind::Static(item, ..) => ItemKind::Static(item),
             ExternItemKind::Fn(item, ..) => ItemKind::Fn(item),
+            ExternItemKind::Type(item, ..) => ItemKind::ExternType(item),
         }
     }
 }
@@ -163,7 +218,7 @@ macro_rules! impl_item_type_fn {
     (ItemKind: $method:ident () -> $return_ty:ty) => {
         impl_item_type_fn!((ItemKind) $method() -> $return_ty,
             Mod, ExternCrate, Use, Static, Const, Fn, TyAlias, Struct, Enum,
-            Union, Trait, Impl, ExternBlock, Unstable
+            Union, Trait, Impl, ExternBlock, ExternType, Unstable
         );
     };
     (AssocItemKind: $method:ident () -> $return_ty:ty) => {
@@ -173,7 +228,7 @@ macro_rules! impl_item_type_fn {
     };
     (ExternItemKind: $method:ident () -> $return_ty:ty) => {
         impl_item_type_fn!((ExternItemKind) $method() -> $return_ty,
-            Static, Fn
+            Static, Fn, Type
         );
     };
     (($self:ident) $method:ident () -> $return_ty:ty $(, $item:ident)+) => {
@@ -217,7 +272,13 @@ macro_rules! impl_item_data {
                 $crate::ast::item::ItemKind::$enum_name(self)
             }
 
-            fn attrs(&self) {}
+            fn attrs(&self) -> &'ast [crate::ast::Attribute<'ast>] {
+                $crate::context::with_cx(self, |cx| cx.attrs(self.data.id.into()))
+            }
+
+            fn derives(&self) -> &'ast [crate::ast::Derive<'ast>] {
+                $crate::context::with_cx(self, |cx| cx.derives(self.data.id))
+            }
         }
 
         impl<'ast> $crate::span::HasSpan<'ast> for $self_name<'ast> {
@@ -332,13 +393,13 @@ mod test {
         assert_size_of::<UseItem<'_>>(&expect!["88"]);
         assert_size_of::<StaticItem<'_>>(&expect!["104"]);
         assert_size_of::<ConstItem<'_>>(&expect!["96"]);
-        assert_size_of::<FnItem<'_>>(&expect!["168"]);
+        assert_size_of::<FnItem<'_>>(&expect!["184"]);
         assert_size_of::<TyAliasItem<'_>>(&expect!["136"]);
         assert_size_of::<StructItem<'_>>(&expect!["120"]);
         assert_size_of::<EnumItem<'_>>(&expect!["112"]);
         assert_size_of::<UnionItem<'_>>(&expect!["112"]);
         assert_size_of::<TraitItem<'_>>(&expect!["136"]);
-        assert_size_of::<ImplItem<'_>>(&expect!["168"]);
+        assert_size_of::<ImplItem<'_>>(&expect!["176"]);
         assert_size_of::<ExternBlockItem<'_>>(&expect!["88"]);
         assert_size_of::<UnstableItem<'_>>(&expect!["72"]);
     }
@@ -0,0 +1,30 @@
+use super::CommonItemData;
+
+/// An opaque type, declared in an `extern` block, like this:
+///
+/// ```ignore
+/// #![feature(extern_types)]
+///
+/// extern "C" {
+///     type Opaque;
+/// }
+/// ```
+///
+/// Extern types are unsized and have no fields or body. The type can only be
+/// used behind pointers, like `*const Opaque`.
+///
+/// * See <https://rust-lang.github.io/rfcs/1861-extern-types.html>
+#[repr(C)]
+#[derive(Debug)]
+pub struct ExternTypeItem<'ast> {
+    data: CommonItemData<'ast>,
+}
+
+super::impl_item_data!(ExternTypeItem, ExternType);
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ExternTypeItem<'ast> {
+    pub fn new(data: CommonItemData<'ast>) -> Self {
+        Self { data }
+    }
+}
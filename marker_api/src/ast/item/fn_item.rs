@@ -1,7 +1,9 @@
 use crate::{
     ast::{generic::GenericParams, pat::PatKind, ty::TyKind},
     common::{Abi, BodyId, Constness, Safety, SpanId, Syncness},
+    context::with_cx,
     ffi::{FfiOption, FfiSlice},
+    sem::TyKind as SemTyKind,
 };
 
 use super::CommonItemData;
@@ -33,8 +35,8 @@ use super::CommonItemData;
 /// Async functions in Rustc actually return a future with the defined output type.
 /// The return type `-> u8` gets desugared to `impl Future<Output = u8>`. Marker will
 /// resugar the type to what the user had originally written. In this case it would
-/// just return `u8`. The semantic return type of an async function can be retrieved
-/// from the expression of the [`Body`][super::Body].
+/// just return `u8`. The semantic output type of the returned future can be
+/// retrieved with [`FnItem::async_output_ty`].
 ///
 /// See: <https://doc.rust-lang.org/reference/items/functions.html>
 #[repr(C)]
@@ -46,7 +48,7 @@ pub struct FnItem<'ast> {
     syncness: Syncness,
     safety: Safety,
     is_extern: bool,
-    has_self: bool,
+    self_kind: FfiOption<SelfKind>,
     abi: Abi,
     params: FfiSlice<'ast, FnParam<'ast>>,
     return_ty: FfiOption<TyKind<'ast>>,
@@ -66,6 +68,25 @@ impl<'ast> FnItem<'ast> {
         self.body_id.copy()
     }
 
+    /// Returns `true`, if this function has a body. Methods in traits without a
+    /// body are required, while methods with a body provide a default
+    /// implementation. Extern functions never have a body.
+    ///
+    /// ```
+    /// trait Shape {
+    ///     // Required, this function has no body
+    ///     fn area(&self) -> f64;
+    ///
+    ///     // Provided, this function has a body
+    ///     fn is_empty(&self) -> bool {
+    ///         self.area() == 0.0
+    ///     }
+    /// }
+    /// ```
+    pub fn has_body(&self) -> bool {
+        self.body_id.is_some()
+    }
+
     /// Returns the [`Constness`] of this callable
     pub fn constness(&self) -> Constness {
         self.constness
@@ -104,7 +125,12 @@ impl<'ast> FnItem<'ast> {
     /// type of `self` can be retrieved from the first element of
     /// [`params()`](`Self::params`).
     pub fn has_self(&self) -> bool {
-        self.has_self
+        self.self_kind.is_some()
+    }
+
+    /// Returns the [`SelfKind`] of the `self` argument, if this callable has one.
+    pub fn self_kind(&self) -> Option<SelfKind> {
+        self.self_kind.copy()
     }
 
     /// Returns the parameters, that this callable accepts. The `self` argument
@@ -118,6 +144,49 @@ impl<'ast> FnItem<'ast> {
     pub fn return_ty(&self) -> Option<&TyKind<'ast>> {
         self.return_ty.get()
     }
+
+    /// Returns `true`, if a parameter of this callable has an `impl Trait`
+    /// type, like `fn foo(_: impl Trait)` or `fn foo(_: &impl Trait)`. These
+    /// types have the [`ImplTraitPosition::Arg`] position.
+    ///
+    /// [`ImplTraitPosition::Arg`]: crate::ast::ImplTraitPosition::Arg
+    pub fn has_impl_trait_params(&self) -> bool {
+        self.params()
+            .iter()
+            .any(|param| matches!(param.ty().peel_refs(), TyKind::ImplTrait(_)))
+    }
+
+    /// Returns `true`, if the return type of this callable is an `impl Trait`
+    /// type, like `fn foo() -> impl Trait`. For `async` functions, this checks
+    /// the declared return type and not the returned future.
+    pub fn returns_impl_trait(&self) -> bool {
+        matches!(self.return_ty(), Some(TyKind::ImplTrait(_)))
+    }
+
+    /// Returns the semantic `Output` type of the future returned by this
+    /// function, if it's `async`. This works the same for free functions,
+    /// associated functions in `impl` blocks and functions in traits, even
+    /// if they don't have a body. The output type is `()`, if no return type
+    /// was specified.
+    ///
+    /// ```
+    /// // The output type is `u8`
+    /// async fn foo() -> u8 {
+    ///     // ...
+    ///     # 16
+    /// }
+    ///
+    /// pub trait Trait {
+    ///     // The output type is `String`
+    ///     async fn bar(&self) -> String;
+    /// }
+    /// ```
+    pub fn async_output_ty(&self) -> Option<SemTyKind<'ast>> {
+        if !self.syncness.is_async() {
+            return None;
+        }
+        with_cx(self, |cx| cx.async_output_ty(self.data.id))
+    }
 }
 
 #[cfg(feature = "driver-api")]
@@ -130,7 +199,7 @@ impl<'ast> FnItem<'ast> {
         syncness: Syncness,
         safety: Safety,
         is_extern: bool,
-        has_self: bool,
+        self_kind: Option<SelfKind>,
         abi: Abi,
         params: &'ast [FnParam<'ast>],
         return_ty: Option<TyKind<'ast>>,
@@ -143,7 +212,7 @@ impl<'ast> FnItem<'ast> {
             syncness,
             safety,
             is_extern,
-            has_self,
+            self_kind: self_kind.into(),
             abi,
             params: params.into(),
             return_ty: return_ty.into(),
@@ -152,6 +221,38 @@ impl<'ast> FnItem<'ast> {
     }
 }
 
+/// The kind of the `self` parameter of a method, like:
+///
+/// ```
+/// # use std::pin::Pin;
+/// # struct Item;
+/// impl Item {
+///     fn value(self) {}
+///     fn reference(&self) {}
+///     fn mut_reference(&mut self) {}
+///     fn boxed(self: Box<Self>) {}
+///     fn pinned(self: Pin<&mut Self>) {}
+/// }
+/// ```
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SelfKind {
+    /// `self` is taken by value, like `self`, `mut self` or `self: Self`
+    Value,
+    /// `self` is taken by reference, like `&self` or `self: &Self`
+    Ref,
+    /// `self` is taken by mutable reference, like `&mut self` or `self: &mut Self`
+    MutRef,
+    /// `self` is a [`Box`], like `self: Box<Self>`
+    Box,
+    /// `self` is a [`Pin`](std::pin::Pin), like `self: Pin<&mut Self>`
+    Pin,
+    /// `self` has a different type, like `self: Rc<Self>`. The type can be
+    /// retrieved from the first element of [`FnItem::params()`].
+    Other,
+}
+
 /// A parameter for a [`FnItem`], like:
 ///
 /// ```
@@ -53,6 +53,9 @@ impl<'ast> ImplItem<'ast> {
         &self.generics
     }
 
+    /// Returns `true`, if this is a negative implementation, like
+    /// `impl !Send for Foo {}`. Negative implementations are unstable and
+    /// require the `negative_impls` feature.
     pub fn is_negated(&self) -> bool {
         self.is_negated
     }
@@ -1,7 +1,9 @@
 use crate::ast::generic::{GenericParams, TyParamBound};
+use crate::common::TyDefId;
+use crate::context::with_cx;
 use crate::ffi::FfiSlice;
 
-use super::{AssocItemKind, CommonItemData};
+use super::{AssocItemKind, CommonItemData, FnItem};
 
 /// A trait item like:
 ///
@@ -46,13 +48,54 @@ impl<'ast> TraitItem<'ast> {
     ///     // ...
     /// }
     /// ```
+    ///
+    /// Bounds declared in a where clause, like `where Self: Supertrait`, are
+    /// part of the [generics](`TraitItem::generics`). All supertraits, including
+    /// these bounds, can be retrieved with [`TraitItem::all_supertraits`].
     pub fn supertraits(&self) -> &'ast [TyParamBound<'ast>] {
         self.supertraits.get()
     }
 
+    /// Returns the [`TyDefId`]s of all supertraits of this trait. This includes
+    /// bounds on `Self` in the where clause and the supertraits of supertraits.
+    ///
+    /// ```
+    /// # use std::fmt::Display;
+    /// // The supertraits are `Error`, `Debug` and `Display`
+    /// trait CustomError: std::error::Error {}
+    ///
+    /// // The supertraits are `Display`, `Clone` and `Sized`, as a supertrait of `Clone`
+    /// trait Label: Display where Self: Clone {}
+    /// ```
+    ///
+    /// The order of the returned ids is unspecified.
+    pub fn all_supertraits(&self) -> &'ast [TyDefId] {
+        with_cx(self, |cx| cx.all_supertraits(self.data.id))
+    }
+
     pub fn items(&self) -> &[AssocItemKind<'ast>] {
         self.items.get()
     }
+
+    /// Returns the methods of this trait, which don't have a default
+    /// implementation. These have to be implemented by every implementor.
+    /// See [`FnItem::has_body`].
+    pub fn required_methods(&self) -> impl Iterator<Item = &'ast FnItem<'ast>> + '_ {
+        self.methods().filter(|method| !method.has_body())
+    }
+
+    /// Returns the methods of this trait, which provide a default implementation.
+    /// See [`FnItem::has_body`].
+    pub fn provided_methods(&self) -> impl Iterator<Item = &'ast FnItem<'ast>> + '_ {
+        self.methods().filter(|method| method.has_body())
+    }
+
+    fn methods(&self) -> impl Iterator<Item = &'ast FnItem<'ast>> + '_ {
+        self.items().iter().filter_map(|item| match item {
+            AssocItemKind::Fn(method, ..) => Some(*method),
+            _ => None,
+        })
+    }
 }
 
 #[cfg(feature = "driver-api")]
@@ -1,6 +1,8 @@
 use crate::ast::generic::{GenericParams, TyParamBound};
 use crate::ast::ty::TyKind;
+use crate::context::with_cx;
 use crate::ffi::{FfiOption, FfiSlice};
+use crate::sem::TyKind as SemTyKind;
 
 use super::CommonItemData;
 
@@ -39,9 +41,22 @@ impl<'ast> TyAliasItem<'ast> {
         self.bounds.get()
     }
 
+    /// The syntactic type, that this alias stands for. This is `None` for
+    /// associated types in traits, which don't provide a default.
     pub fn aliased_ty(&self) -> Option<TyKind> {
         self.aliased_ty.copy()
     }
+
+    /// The semantic type, that this alias stands for. Other type aliases used
+    /// in the [`aliased_ty`](Self::aliased_ty) are already resolved. This is
+    /// `None` for associated types in traits, which don't provide a default.
+    ///
+    /// See [`MarkerContext::normalize_alias`](crate::MarkerContext::normalize_alias)
+    /// to also expand associated types and `impl Trait` types.
+    pub fn aliased_sem_ty(&self) -> Option<SemTyKind<'ast>> {
+        self.aliased_ty.get()?;
+        with_cx(self, |cx| cx.aliased_sem_ty(self.data.id))
+    }
 }
 
 #[cfg(feature = "driver-api")]
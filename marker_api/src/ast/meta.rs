@@ -0,0 +1,333 @@
+//! This module parses the input of attributes into meta items. See
+//! [`Attribute::meta_items`](super::Attribute::meta_items).
+//!
+//! The input is parsed once by the driver, during the conversion of the
+//! attribute, using [`parse_meta_items`].
+
+use crate::{
+    common::SpanId,
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    span::Span,
+};
+
+#[cfg(any(feature = "driver-api", test))]
+use super::TokenKind;
+
+/// A single argument of an attribute, which follows the meta item grammar,
+/// like `bar`, `baz = "x"` or `all(a, b)` in:
+///
+/// ```text
+/// #[foo(bar, baz = "x", all(a, b))]
+/// ```
+///
+/// See <https://doc.rust-lang.org/reference/attributes.html#meta-item-attribute-syntax>
+#[repr(C)]
+#[derive(Debug)]
+pub struct MetaItem<'ast> {
+    path: FfiSlice<'ast, FfiStr<'ast>>,
+    kind: MetaItemKind,
+    value: FfiOption<FfiStr<'ast>>,
+    list: FfiSlice<'ast, MetaItem<'ast>>,
+    span: SpanId,
+}
+
+impl<'ast> MetaItem<'ast> {
+    /// The segments of the path of this item. For `clippy::pedantic` this
+    /// would be `clippy` and `pedantic`. Literals without a name, like `"x"`
+    /// in `#[foo("x")]`, have an empty path.
+    pub fn path(&self) -> impl Iterator<Item = &'ast str> {
+        self.path.get().iter().map(FfiStr::get)
+    }
+
+    /// Returns the name of this item, if the path consists of a single segment,
+    /// like `feature` in `feature = "x"`.
+    pub fn name(&self) -> Option<&'ast str> {
+        match self.path.get() {
+            [name] => Some(name.get()),
+            _ => None,
+        }
+    }
+
+    /// Returns `true`, if the path of this item matches the given path. The
+    /// segments have to be separated by `::`, like `"clippy::pedantic"`.
+    pub fn has_path(&self, path: &str) -> bool {
+        !self.path.get().is_empty() && self.path().eq(path.split("::"))
+    }
+
+    /// The form of this item. It determines, if the item has a
+    /// [`value`](Self::value) or nested items in its [`list`](Self::list).
+    pub fn kind(&self) -> MetaItemKind {
+        self.kind
+    }
+
+    /// Returns the literal value of a name-value item or a literal item, like
+    /// `"x"` for `baz = "x"`. The text is returned as it's written in the
+    /// source code, string literals therefore include their quotes.
+    pub fn value(&self) -> Option<&'ast str> {
+        self.value.get().map(FfiStr::get)
+    }
+
+    /// Returns the nested items of a list item, like `a` and `b` for `all(a, b)`.
+    /// Other items return an empty slice.
+    pub fn list(&self) -> &'ast [MetaItem<'ast>] {
+        self.list.get()
+    }
+
+    /// The [`Span`] of the entire item, including the value or nested items.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+/// The form of a [`MetaItem`].
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MetaItemKind {
+    /// A path without arguments, like `bar` in `#[foo(bar)]`
+    Word,
+    /// A path with a literal value, like `baz = "x"` in `#[foo(baz = "x")]`.
+    /// See [`MetaItem::value`].
+    NameValue,
+    /// A path with nested items, like `all(a, b)` in `#[cfg(all(a, b))]`.
+    /// See [`MetaItem::list`].
+    List,
+    /// A literal without a name, like `"x"` in `#[foo("x")]`. The path of
+    /// these items is empty. See [`MetaItem::value`].
+    Lit,
+}
+
+/// The driver functionality, which is required by [`parse_meta_items`], to
+/// store the parsed items.
+#[cfg(feature = "driver-api")]
+pub trait MetaItemStorage<'ast> {
+    /// Returns the span, covering the attribute input tokens from `start` to
+    /// `end`. The `end` index is exclusive.
+    fn span(&self, start: usize, end: usize) -> SpanId;
+
+    fn alloc_path(&self, path: &[&'ast str]) -> &'ast [FfiStr<'ast>];
+
+    fn alloc_items(&self, items: Vec<MetaItem<'ast>>) -> &'ast [MetaItem<'ast>];
+}
+
+/// Parses the input tokens of an attribute into meta items. `None` is returned,
+/// if the attribute has no input or if the input doesn't follow the meta item
+/// grammar.
+#[cfg(feature = "driver-api")]
+pub fn parse_meta_items<'ast>(
+    tokens: &[(TokenKind, &'ast str)],
+    storage: &impl MetaItemStorage<'ast>,
+) -> Option<&'ast [MetaItem<'ast>]> {
+    let raw = parse_attr_input(tokens)?;
+    Some(to_meta_items(raw, storage))
+}
+
+#[cfg(feature = "driver-api")]
+fn to_meta_items<'ast>(raw: Vec<RawMeta<'ast>>, storage: &impl MetaItemStorage<'ast>) -> &'ast [MetaItem<'ast>] {
+    let items = raw
+        .into_iter()
+        .map(|raw| {
+            let (kind, value, list) = match raw.kind {
+                RawMetaKind::Word => (MetaItemKind::Word, None, &[][..]),
+                RawMetaKind::NameValue(value) => (MetaItemKind::NameValue, Some(value.into()), &[][..]),
+                RawMetaKind::List(items) => (MetaItemKind::List, None, to_meta_items(items, storage)),
+                RawMetaKind::Lit(value) => (MetaItemKind::Lit, Some(value.into()), &[][..]),
+            };
+            MetaItem {
+                path: storage.alloc_path(&raw.path).into(),
+                kind,
+                value: value.into(),
+                list: list.into(),
+                span: storage.span(raw.start, raw.end),
+            }
+        })
+        .collect();
+    storage.alloc_items(items)
+}
+
+/// A meta item, referencing the range of its tokens, which is used to
+/// determine the span.
+#[cfg(any(feature = "driver-api", test))]
+#[derive(Debug, PartialEq, Eq)]
+struct RawMeta<'a> {
+    path: Vec<&'a str>,
+    kind: RawMetaKind<'a>,
+    start: usize,
+    end: usize,
+}
+
+#[cfg(any(feature = "driver-api", test))]
+#[derive(Debug, PartialEq, Eq)]
+enum RawMetaKind<'a> {
+    Word,
+    NameValue(&'a str),
+    List(Vec<RawMeta<'a>>),
+    Lit(&'a str),
+}
+
+/// Parses an attribute input like `(bar, baz = "x")`. Inputs like `= "x"` are
+/// not lists and return `None`.
+#[cfg(any(feature = "driver-api", test))]
+fn parse_attr_input<'a>(tokens: &[(TokenKind, &'a str)]) -> Option<Vec<RawMeta<'a>>> {
+    let mut pos = 0;
+    let items = parse_list(tokens, &mut pos)?;
+    (pos == tokens.len()).then_some(items)
+}
+
+#[cfg(any(feature = "driver-api", test))]
+fn parse_list<'a>(tokens: &[(TokenKind, &'a str)], pos: &mut usize) -> Option<Vec<RawMeta<'a>>> {
+    if tokens.get(*pos) != Some(&(TokenKind::OpenDelim, "(")) {
+        return None;
+    }
+    *pos += 1;
+
+    let mut items = vec![];
+    loop {
+        if tokens.get(*pos) == Some(&(TokenKind::CloseDelim, ")")) {
+            *pos += 1;
+            return Some(items);
+        }
+        items.push(parse_item(tokens, pos)?);
+        match tokens.get(*pos)? {
+            (TokenKind::Punct, ",") => *pos += 1,
+            (TokenKind::CloseDelim, ")") => {},
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(any(feature = "driver-api", test))]
+fn parse_item<'a>(tokens: &[(TokenKind, &'a str)], pos: &mut usize) -> Option<RawMeta<'a>> {
+    let start = *pos;
+    if let (TokenKind::Lit, value) = tokens.get(start)? {
+        *pos += 1;
+        return Some(RawMeta {
+            path: vec![],
+            kind: RawMetaKind::Lit(value),
+            start,
+            end: *pos,
+        });
+    }
+
+    let mut path = vec![];
+    if tokens[*pos] == (TokenKind::Punct, "::") {
+        *pos += 1;
+    }
+    loop {
+        let (TokenKind::Ident, segment) = tokens.get(*pos)? else {
+            return None;
+        };
+        path.push(*segment);
+        *pos += 1;
+        if tokens.get(*pos) != Some(&(TokenKind::Punct, "::")) {
+            break;
+        }
+        *pos += 1;
+    }
+
+    let kind = match tokens.get(*pos) {
+        Some((TokenKind::Punct, "=")) => {
+            *pos += 1;
+            let value = match tokens.get(*pos)? {
+                (TokenKind::Lit, value) | (TokenKind::Ident, value @ ("true" | "false")) => *value,
+                _ => return None,
+            };
+            *pos += 1;
+            RawMetaKind::NameValue(value)
+        },
+        Some((TokenKind::OpenDelim, "(")) => RawMetaKind::List(parse_list(tokens, pos)?),
+        _ => RawMetaKind::Word,
+    };
+    Some(RawMeta {
+        path,
+        kind,
+        start,
+        end: *pos,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokens(input: &str) -> Vec<(TokenKind, &str)> {
+        input
+            .split_whitespace()
+            .map(|text| {
+                let kind = match text {
+                    "(" | "[" | "{" => TokenKind::OpenDelim,
+                    ")" | "]" | "}" => TokenKind::CloseDelim,
+                    "," | "=" | "::" => TokenKind::Punct,
+                    _ if text.starts_with('"') || text.starts_with(|c: char| c.is_ascii_digit()) => TokenKind::Lit,
+                    _ => TokenKind::Ident,
+                };
+                (kind, text)
+            })
+            .collect()
+    }
+
+    fn word(path: &[&'static str], start: usize) -> RawMeta<'static> {
+        RawMeta {
+            path: path.to_vec(),
+            kind: RawMetaKind::Word,
+            start,
+            end: start + path.len() * 2 - 1,
+        }
+    }
+
+    #[test]
+    fn test_meta_items_derive() {
+        // #[derive(A, B)]
+        let items = parse_attr_input(&tokens("( A , B )")).unwrap();
+        assert_eq!(items, [word(&["A"], 1), word(&["B"], 3)]);
+
+        // #[derive(serde::Serialize,)]
+        let items = parse_attr_input(&tokens("( serde :: Serialize , )")).unwrap();
+        assert_eq!(items, [word(&["serde", "Serialize"], 1)]);
+    }
+
+    #[test]
+    fn test_meta_items_cfg() {
+        // #[cfg(feature = "x")]
+        let items = parse_attr_input(&tokens(r#"( feature = "x" )"#)).unwrap();
+        assert_eq!(
+            items,
+            [RawMeta {
+                path: vec!["feature"],
+                kind: RawMetaKind::NameValue(r#""x""#),
+                start: 1,
+                end: 4,
+            }]
+        );
+
+        // #[cfg(all(unix, not(test)))]
+        let items = parse_attr_input(&tokens("( all ( unix , not ( test ) ) )")).unwrap();
+        let [all] = items.as_slice() else { panic!() };
+        let RawMetaKind::List(nested) = &all.kind else { panic!() };
+        assert_eq!((all.start, all.end), (1, 10));
+        assert_eq!(nested[0], word(&["unix"], 3));
+        assert_eq!(nested[1].path, ["not"]);
+        assert_eq!(nested[1].kind, RawMetaKind::List(vec![word(&["test"], 7)]));
+    }
+
+    #[test]
+    fn test_meta_items_fallback() {
+        // #[must_use = "x"]
+        assert_eq!(parse_attr_input(&tokens(r#"= "x""#)), None);
+        // #[foo[bar]]
+        assert_eq!(parse_attr_input(&tokens("[ bar ]")), None);
+        // #[foo(a b)]
+        assert_eq!(parse_attr_input(&tokens("( a b )")), None);
+        // #[foo(a = b)]
+        assert_eq!(parse_attr_input(&tokens("( a = b )")), None);
+        // #[foo(a = 1,]
+        assert_eq!(parse_attr_input(&tokens("( a = 1 ,")), None);
+        // #[foo("x", b = true)]
+        let items = parse_attr_input(&tokens(r#"( "x" , b = true )"#)).unwrap();
+        assert_eq!(items[0].kind, RawMetaKind::Lit(r#""x""#));
+        assert_eq!(items[1].kind, RawMetaKind::NameValue("true"));
+        // Attributes without input
+        assert_eq!(parse_attr_input(&[]), None);
+    }
+}
@@ -3,12 +3,13 @@ use std::marker::PhantomData;
 
 use crate::{
     common::{HasNodeId, SpanId, StmtId},
+    context::with_cx,
     ffi::FfiOption,
     private::Sealed,
     span::{HasSpan, Span},
 };
 
-use super::{expr::ExprKind, item::ItemKind, pat::PatKind, ty::TyKind};
+use super::{attr::Attribute, expr::ExprKind, item::ItemKind, pat::PatKind, ty::TyKind};
 
 /// This trait combines methods, which all statements have in common.
 ///
@@ -46,11 +47,9 @@ impl<'ast> StmtKind<'ast> {
     }
 
     /// Returns the attributes attached to this statement.
-    ///
-    /// Currently, it's only a placeholder until a proper representation is implemented.
-    /// rust-marker/marker#51 tracks the task of implementing this. You're welcome to
-    /// leave any comments in that issue.
-    pub fn attrs(&self) {}
+    pub fn attrs(&self) -> &'ast [Attribute<'ast>] {
+        with_cx(self, |cx| cx.attrs(self.id().into()))
+    }
 }
 
 crate::span::impl_spanned_for!(StmtKind<'ast>);
@@ -123,6 +122,31 @@ impl<'ast> LetStmt<'ast> {
         self.init.copy()
     }
 
+    /// Checks if the type annotation is the same type, as the type of the
+    /// initialization expression. Lifetimes are ignored in this comparison and
+    /// placeholders like `_` are compared with their inferred type. This can be
+    /// used to detect redundant type annotations:
+    ///
+    /// ```
+    /// let a: Vec<i32> = Vec::<i32>::new(); // `Some(true)`
+    /// let b: &[i32] = &Vec::<i32>::new();  // `Some(false)`, the init is coerced
+    /// let c: Vec<i32> = Vec::new();        // `Some(false)`, the init is inferred
+    /// let d: Vec<i32>;                     // `None`, no init
+    /// let e = Vec::<i32>::new();           // `None`, no type annotation
+    /// # d = vec![];
+    /// ```
+    ///
+    /// `Some(false)` is also returned, if the type of the init expression might
+    /// be inferred from the annotation, as the annotation is required in these
+    /// cases. This includes unsuffixed literals, like `1`, and calls, which don't
+    /// specify all generic arguments, like `Vec::new()` or `value.into()`.
+    pub fn annotation_matches_init(&self) -> Option<bool> {
+        if self.ty().is_none() || self.init().is_none() {
+            return None;
+        }
+        Some(with_cx(self, |cx| cx.annotation_matches_init(self.data.id)))
+    }
+
     /// This returns the optional `else` expression of the let statement.
     ///
     /// `els` is an abbreviation for `else`, which is a reserved keyword in Rust.
@@ -2,11 +2,28 @@ use crate::{ast::generic::TyParamBound, ffi::FfiSlice};
 
 use super::CommonSynTyData;
 
+/// The syntactic representation of an [`impl Trait`] type.
+///
+/// ```
+/// # use std::fmt::Debug;
+/// //              vvvvvvvvvvvvvv An `impl Trait` type in argument position
+/// fn debug(value: impl Debug) -> impl Debug {
+/// //                             ^^^^^^^^^^ An `impl Trait` type in return position
+///     value
+/// }
+/// ```
+///
+/// Arguments with an `impl Trait` type are desugared to anonymous generic
+/// parameters by rustc. These parameters are not included in the
+/// [`GenericParams`](crate::ast::generic::GenericParams) of the item.
+///
+/// [`impl Trait`]: https://doc.rust-lang.org/reference/types/impl-trait.html
 #[repr(C)]
 #[derive(Debug)]
 pub struct ImplTraitTy<'ast> {
     data: CommonSynTyData<'ast>,
     trait_bound: FfiSlice<'ast, TyParamBound<'ast>>,
+    position: ImplTraitPosition,
 }
 
 super::impl_ty_data!(ImplTraitTy<'ast>, ImplTrait);
@@ -15,18 +32,46 @@ impl<'ast> ImplTraitTy<'ast> {
     pub fn trait_bounds(&self) -> &[TyParamBound<'ast>] {
         self.trait_bound.get()
     }
+
+    /// Returns the position of this `impl Trait` type. Argument position
+    /// `impl Trait` (APIT) is an anonymous generic parameter chosen by the
+    /// caller, while return position `impl Trait` (RPIT) is an opaque type
+    /// chosen by the function.
+    pub fn position(&self) -> ImplTraitPosition {
+        self.position
+    }
 }
 
 #[cfg(feature = "driver-api")]
 impl<'ast> ImplTraitTy<'ast> {
-    pub fn new(data: CommonSynTyData<'ast>, trait_bound: &'ast [TyParamBound<'ast>]) -> Self {
+    pub fn new(
+        data: CommonSynTyData<'ast>,
+        trait_bound: &'ast [TyParamBound<'ast>],
+        position: ImplTraitPosition,
+    ) -> Self {
         Self {
             data,
             trait_bound: trait_bound.into(),
+            position,
         }
     }
 }
 
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ImplTraitPosition {
+    /// An `impl Trait` type in the parameter of a function, like
+    /// `fn foo(_: impl Trait)`
+    Arg,
+    /// An `impl Trait` type in the return type of a function, like
+    /// `fn foo() -> impl Trait`
+    Return,
+    /// An `impl Trait` type used as the value of a type alias. This is
+    /// currently unstable.
+    TyAlias,
+}
+
 /// The syntactic representation of a [trait object].
 ///
 /// [trait object]: https://doc.rust-lang.org/reference/types/trait-object.html
@@ -1,9 +1,14 @@
 //! This module provides types, which are used by the semantic and syntactic
 //! representations in Marker.
 
+mod case;
 mod id;
+mod target;
+pub use case::*;
 pub use id::*;
+pub use target::*;
 
+#[repr(C)]
 #[non_exhaustive]
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Edition {
@@ -138,6 +143,16 @@ impl NumKind {
     pub fn is_integer(&self) -> bool {
         !self.is_float()
     }
+
+    /// Returns the number of bits of a float type, `32` for [`f32`] and `64`
+    /// for [`f64`]. Integer types will return `None`.
+    pub fn float_bits(&self) -> Option<u32> {
+        match self {
+            NumKind::F32 => Some(32),
+            NumKind::F64 => Some(64),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for NumKind {
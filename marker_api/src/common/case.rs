@@ -0,0 +1,216 @@
+/// The case style of a name, like the name of an item or variable.
+///
+/// The classification follows the rules of rustc's `non_camel_case_types`,
+/// `non_snake_case` and `non_upper_case_globals` lints. Some names match
+/// several styles, like `X`, which is both `SCREAMING_SNAKE_CASE` and
+/// `PascalCase`. [`CaseStyle::of`] returns the first matching style in the
+/// order of the variants. Use [`CaseStyle::matches`] to check if a name
+/// follows a specific style.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum CaseStyle {
+    /// Lowercase words separated by underscores, like `snake_case`. This is
+    /// the convention for functions, variables and modules.
+    SnakeCase,
+    /// Uppercase words separated by underscores, like `SCREAMING_SNAKE_CASE`.
+    /// This is the convention for constants and statics.
+    ScreamingSnakeCase,
+    /// Capitalized words without separators, like `PascalCase`. This is the
+    /// convention for types, traits and enum variants.
+    PascalCase,
+    /// The name doesn't follow any of the other styles, like `mixed_Case` or
+    /// `camelCase`.
+    Mixed,
+}
+
+impl CaseStyle {
+    /// Returns the case style of the given name. Leading and trailing
+    /// underscores are ignored.
+    ///
+    /// ```
+    /// # use marker_api::common::CaseStyle;
+    /// assert_eq!(CaseStyle::of("snake_case"), CaseStyle::SnakeCase);
+    /// assert_eq!(CaseStyle::of("MAX_SIZE"), CaseStyle::ScreamingSnakeCase);
+    /// assert_eq!(CaseStyle::of("HttpServer"), CaseStyle::PascalCase);
+    /// assert_eq!(CaseStyle::of("camelCase"), CaseStyle::Mixed);
+    /// ```
+    #[must_use]
+    pub fn of(name: &str) -> Self {
+        [Self::SnakeCase, Self::ScreamingSnakeCase, Self::PascalCase]
+            .into_iter()
+            .find(|style| style.matches(name))
+            .unwrap_or(Self::Mixed)
+    }
+
+    /// Returns `true`, if the given name follows this case style. [`CaseStyle::Mixed`]
+    /// matches every name.
+    #[must_use]
+    pub fn matches(self, name: &str) -> bool {
+        match self {
+            Self::SnakeCase => is_snake_case(name),
+            Self::ScreamingSnakeCase => !name.chars().any(char::is_lowercase),
+            Self::PascalCase => is_pascal_case(name),
+            Self::Mixed => true,
+        }
+    }
+
+    /// Converts the name into this case style. See
+    /// [`MarkerContext::suggest_rename`](crate::MarkerContext::suggest_rename).
+    pub(crate) fn convert(self, name: &str) -> String {
+        match self {
+            Self::SnakeCase => to_snake_case(name),
+            Self::ScreamingSnakeCase => to_snake_case(name).to_uppercase(),
+            Self::PascalCase => to_pascal_case(name),
+            Self::Mixed => name.to_string(),
+        }
+    }
+}
+
+fn char_has_case(c: char) -> bool {
+    c.is_lowercase() || c.is_uppercase()
+}
+
+fn is_snake_case(name: &str) -> bool {
+    let name = name.trim_matches('_');
+    let mut allow_underscore = true;
+    name.chars().all(|c| match c {
+        '_' if !allow_underscore => false,
+        '_' => {
+            allow_underscore = false;
+            true
+        },
+        // Some characters don't have a lowercase form
+        c => {
+            allow_underscore = true;
+            !c.is_uppercase()
+        },
+    })
+}
+
+fn is_pascal_case(name: &str) -> bool {
+    let name = name.trim_matches('_');
+    let Some(first) = name.chars().next() else {
+        return true;
+    };
+    let chars: Vec<_> = name.chars().collect();
+    // Scripts without a concept of upper and lowercase are accepted
+    !first.is_lowercase()
+        && !name.contains("__")
+        && !chars
+            .windows(2)
+            .any(|pair| (char_has_case(pair[0]) && pair[1] == '_') || (char_has_case(pair[1]) && pair[0] == '_'))
+}
+
+/// This conversion is equivalent to the suggestion of rustc's `non_snake_case` lint.
+/// Leading underscores are preserved and a new word starts at every uppercase
+/// letter following a non-uppercase one.
+fn to_snake_case(name: &str) -> String {
+    let mut words = vec![];
+    let trimmed = name.trim_start_matches('_');
+    words.extend((0..name.len() - trimmed.len()).map(|_| String::new()));
+
+    for part in trimmed.split('_').filter(|part| !part.is_empty()) {
+        let mut last_upper = false;
+        let mut buf = String::new();
+        for c in part.chars() {
+            if !buf.is_empty() && c.is_uppercase() && !last_upper {
+                words.push(std::mem::take(&mut buf));
+            }
+            last_upper = c.is_uppercase();
+            buf.extend(c.to_lowercase());
+        }
+        words.push(buf);
+    }
+    words.join("_")
+}
+
+/// This conversion is equivalent to the suggestion of rustc's `non_camel_case_types`
+/// lint. Acronyms are treated as a single word, like `HTTP_SERVER` which becomes
+/// `HttpServer`. Components are separated by an underscore, if the boundary can't
+/// be expressed by the case, like between two digits in `v1_2`, which becomes `V1_2`.
+fn to_pascal_case(name: &str) -> String {
+    let components = name
+        .trim_matches('_')
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut component = String::new();
+            let mut new_word = true;
+            let mut prev_is_lowercase = true;
+            for c in part.chars() {
+                // An uppercase letter following a lowercase one starts a new word,
+                // to convert `camelCase` to `CamelCase`.
+                if prev_is_lowercase && c.is_uppercase() {
+                    new_word = true;
+                }
+                if new_word {
+                    component.extend(c.to_uppercase());
+                } else {
+                    component.extend(c.to_lowercase());
+                }
+                prev_is_lowercase = c.is_lowercase();
+                new_word = false;
+            }
+            component
+        });
+
+    let mut result = String::new();
+    let mut prev_last: Option<char> = None;
+    for component in components {
+        let needs_separator = match (prev_last, component.chars().next()) {
+            (Some(last), Some(first)) => !char_has_case(last) && !char_has_case(first),
+            _ => false,
+        };
+        if needs_separator {
+            result.push('_');
+        }
+        prev_last = component.chars().last();
+        result.push_str(&component);
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_case_style_of() {
+        assert_eq!(CaseStyle::of("snake_case"), CaseStyle::SnakeCase);
+        assert_eq!(CaseStyle::of("_unused"), CaseStyle::SnakeCase);
+        assert_eq!(CaseStyle::of("v2"), CaseStyle::SnakeCase);
+        assert_eq!(CaseStyle::of("MAX_SIZE"), CaseStyle::ScreamingSnakeCase);
+        assert_eq!(CaseStyle::of("X"), CaseStyle::ScreamingSnakeCase);
+        assert_eq!(CaseStyle::of("PascalCase"), CaseStyle::PascalCase);
+        assert_eq!(CaseStyle::of("HTTPServer"), CaseStyle::PascalCase);
+        assert_eq!(CaseStyle::of("camelCase"), CaseStyle::Mixed);
+        assert_eq!(CaseStyle::of("Mixed_Case"), CaseStyle::Mixed);
+        assert_eq!(CaseStyle::of("double__underscore"), CaseStyle::Mixed);
+
+        assert!(CaseStyle::PascalCase.matches("X"));
+        assert!(!CaseStyle::PascalCase.matches("MAX_SIZE"));
+    }
+
+    #[test]
+    fn test_case_style_convert() {
+        let snake = |name| CaseStyle::SnakeCase.convert(name);
+        assert_eq!(snake("CamelCase"), "camel_case");
+        assert_eq!(snake("camelCase"), "camel_case");
+        assert_eq!(snake("HTTPServer"), "httpserver");
+        assert_eq!(snake("__Private"), "__private");
+        assert_eq!(snake("Version2Name"), "version2_name");
+
+        let screaming = |name| CaseStyle::ScreamingSnakeCase.convert(name);
+        assert_eq!(screaming("maxSize"), "MAX_SIZE");
+        assert_eq!(screaming("max_size"), "MAX_SIZE");
+
+        let pascal = |name| CaseStyle::PascalCase.convert(name);
+        assert_eq!(pascal("snake_case"), "SnakeCase");
+        assert_eq!(pascal("camelCase"), "CamelCase");
+        assert_eq!(pascal("HTTP_SERVER"), "HttpServer");
+        assert_eq!(pascal("__private_type"), "PrivateType");
+        assert_eq!(pascal("v1_2"), "V1_2");
+        assert_eq!(pascal("foo_2bar"), "Foo2bar");
+    }
+}
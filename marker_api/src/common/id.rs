@@ -196,6 +196,12 @@ pub trait HasNodeId: Sealed {
     fn node_id(&self) -> NodeId;
 }
 
+impl HasNodeId for NodeId {
+    fn node_id(&self) -> NodeId {
+        *self
+    }
+}
+
 impl<N: HasNodeId> HasNodeId for &N {
     fn node_id(&self) -> NodeId {
         (*self).node_id()
@@ -0,0 +1,78 @@
+use crate::ffi::FfiStr;
+
+/// Information about the target, which the crate is being compiled for. This
+/// is the actual compilation target, which can differ from the host, when
+/// cross-compiling. See [`MarkerContext::target`](crate::MarkerContext::target).
+#[repr(C)]
+#[derive(Debug)]
+pub struct TargetInfo<'ast> {
+    triple: FfiStr<'ast>,
+    pointer_width: u32,
+    endianness: Endianness,
+    os: FfiStr<'ast>,
+    arch: FfiStr<'ast>,
+}
+
+impl<'ast> TargetInfo<'ast> {
+    /// The target triple, like `x86_64-unknown-linux-gnu`.
+    pub fn triple(&self) -> &'ast str {
+        self.triple.get()
+    }
+
+    /// The width of a pointer in bits, like `64`. This is the size of `usize`
+    /// and `isize` on the target.
+    pub fn pointer_width(&self) -> u32 {
+        self.pointer_width
+    }
+
+    /// The byte order of the target.
+    pub fn endianness(&self) -> Endianness {
+        self.endianness
+    }
+
+    /// The operating system of the target, like `linux` or `windows`. This is
+    /// the value used by `#[cfg(target_os = "...")]`.
+    pub fn os(&self) -> &'ast str {
+        self.os.get()
+    }
+
+    /// The architecture of the target, like `x86_64` or `aarch64`. This is the
+    /// value used by `#[cfg(target_arch = "...")]`.
+    pub fn arch(&self) -> &'ast str {
+        self.arch.get()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> TargetInfo<'ast> {
+    pub fn new(triple: &'ast str, pointer_width: u32, endianness: Endianness, os: &'ast str, arch: &'ast str) -> Self {
+        Self {
+            triple: triple.into(),
+            pointer_width,
+            endianness,
+            os: os.into(),
+            arch: arch.into(),
+        }
+    }
+}
+
+/// The byte order of a target.
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+impl Endianness {
+    #[must_use]
+    pub fn is_little(&self) -> bool {
+        matches!(self, Self::Little)
+    }
+
+    #[must_use]
+    pub fn is_big(&self) -> bool {
+        matches!(self, Self::Big)
+    }
+}
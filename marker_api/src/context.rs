@@ -8,16 +8,28 @@
 use std::{cell::RefCell, mem::transmute};
 
 use crate::{
-    common::{ExpnId, ExprId, ItemId, Level, MacroReport, SpanId, SymbolId, TyDefId},
-    diagnostic::{Diagnostic, DiagnosticBuilder, EmissionNode},
+    ast::{
+        AstPathTarget, Attribute, ConstExpr, Derive, ExprData, ExprKind, ExprPosition, ExprPrecedence, FormatArgs,
+        GenericParamKind, ItemField, LoopInfo, PanicKind, PanicMacro, RawPtrOp, RawPtrOpKind, RawPtrOrigin,
+        UnaryOpKind,
+    },
+    common::{
+        CaseStyle, DriverTyId, ExpnId, ExprId, GenericId, HasNodeId, ItemId, Level, MacroReport, NodeId, SpanId,
+        StmtId, SymbolId, TargetInfo, TyDefId, VarId,
+    },
+    diagnostic::{Diagnostic, DiagnosticBuilder, EmissionNode, SuggestionBuilder},
     ffi,
-    sem::TyKind,
-    span::{ExpnInfo, FileInfo, FilePos, Span, SpanPos, SpanSource},
+    manifest::Manifest,
+    sem::{ConstFnViolation, ConstInt, ConstValue, ObjectSafetyViolation, ObjectSafetyViolationKind, TyKind, TyLayout},
+    span::{ExpnInfo, FileInfo, FilePos, HasSpan, Span, SpanPos, SpanSource},
     Lint,
 };
 
+mod divergence;
 mod map;
 pub use map::*;
+mod parens;
+mod simplify_bool;
 
 thread_local! {
     /// **Warning**
@@ -253,6 +265,1199 @@ impl<'ast> MarkerContext<'ast> {
     pub fn resolve_ty_ids(&self, path: &str) -> &[TyDefId] {
         (self.callbacks.resolve_ty_ids)(self.callbacks.data, path.into()).get()
     }
+
+    /// Returns the attributes attached to the root of the currently linted crate,
+    /// like `#![forbid(unsafe_code)]`. These are the same attributes, as the ones
+    /// returned by [`Crate::attrs`](crate::ast::Crate::attrs).
+    ///
+    /// Crate-global checks are usually implemented in
+    /// [`LintPass::check_crate`](crate::LintPass::check_crate). Lints about the crate
+    /// itself should use the root module as their [`EmissionNode`]:
+    /// ```ignore
+    /// fn check_crate<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, krate: &'ast Crate<'ast>) {
+    ///     let forbids_unsafe = cx.crate_attrs().iter().any(|attr| {
+    ///         attr.has_path("forbid") && attr.tokens().iter().any(|token| token.text() == "unsafe_code")
+    ///     });
+    ///     if !forbids_unsafe {
+    ///         cx.emit_lint(LINT, krate.root_mod(), "crate is missing `#![forbid(unsafe_code)]`");
+    ///     }
+    /// }
+    /// ```
+    pub fn crate_attrs(&self) -> &'ast [Attribute<'ast>] {
+        (self.callbacks.crate_attrs)(self.callbacks.data).get()
+    }
+
+    /// Returns the `Cargo.toml` manifest of the package, which is currently being
+    /// checked. This provides access to the edition, features and dependencies
+    /// of the package.
+    ///
+    /// The manifest is provided by `cargo-marker`. `None` is returned, if the
+    /// crate is checked without Cargo, like when linting a single file.
+    ///
+    /// ```ignore
+    /// if let Some(manifest) = cx.manifest()
+    ///     && let Some(dep) = manifest.dependency("serde")
+    ///     && dep.is_optional()
+    /// {
+    ///     // Usages of `serde` should be gated behind a feature
+    /// }
+    /// ```
+    pub fn manifest(&self) -> Option<&'ast Manifest<'ast>> {
+        (self.callbacks.manifest)(self.callbacks.data).copy()
+    }
+
+    /// Returns information about the target, which the crate is compiled for.
+    /// This is the actual compilation target, selected with `--target`, which
+    /// can differ from the host when cross-compiling.
+    ///
+    /// ```ignore
+    /// if cx.target().pointer_width() < 64 {
+    ///     // Casts from `u64` to `usize` can truncate on this target
+    /// }
+    /// ```
+    pub fn target(&self) -> &'ast TargetInfo<'ast> {
+        (self.callbacks.target)(self.callbacks.data)
+    }
+
+    /// This function emits a lint at the given span of the `Cargo.toml` manifest.
+    /// The span should be retrieved from the [`Manifest`], for example, with
+    /// [`Dependency::span`](crate::manifest::Dependency::span). The level of the
+    /// lint is determined by the lint attributes of the crate root.
+    ///
+    /// ```ignore
+    /// if let Some(manifest) = cx.manifest() {
+    ///     for dep in manifest.dependencies() {
+    ///         if let Some(span) = dep.span() {
+    ///             cx.emit_manifest_lint(LINT, span, "this dependency is unpinned");
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// The diagnostic is displayed with the path of the manifest:
+    /// ```text
+    ///  warning: this dependency is unpinned
+    ///  --> Cargo.toml:8:1
+    ///   |
+    /// 8 | serde = "*"
+    ///   | ^^^^^
+    /// ```
+    ///
+    /// No diagnostic is emitted, if the manifest is unavailable. See
+    /// [`MarkerContext::manifest`].
+    pub fn emit_manifest_lint(
+        &self,
+        lint: &'static Lint,
+        span: &Span<'ast>,
+        msg: impl Into<String>,
+    ) -> DiagnosticBuilder<'ast> {
+        let Some(manifest) = self.manifest() else {
+            return DiagnosticBuilder::dummy();
+        };
+        let id = NodeId::Item(manifest.crate_root());
+        if self.ast().lint_level_at(lint, id) == Level::Allow {
+            return DiagnosticBuilder::dummy();
+        }
+
+        DiagnosticBuilder::new(lint, id, msg.into(), span.clone())
+    }
+
+    /// Returns `true`, if the trait with the given [`TyDefId`] is [object safe].
+    /// This means that the trait can be used as a trait object, like `dyn Trait`.
+    /// The function returns `false`, if the id doesn't belong to a trait.
+    ///
+    /// The reasons, why a trait is not object safe, can be retrieved with
+    /// [`MarkerContext::object_safety_violations`].
+    ///
+    /// [object safe]: https://doc.rust-lang.org/reference/items/traits.html#object-safety
+    pub fn is_object_safe(&self, trait_id: TyDefId) -> bool {
+        self.object_safety_violations(trait_id).map_or(false, |violations| {
+            violations
+                .iter()
+                .all(|violation| violation.kind() == ObjectSafetyViolationKind::WhereClauseReferencesSelf)
+        })
+    }
+
+    /// Returns the reasons, why the trait with the given [`TyDefId`] is not
+    /// [object safe]. The slice is empty, if the trait is object safe. `None`
+    /// is returned, if the id doesn't belong to a trait.
+    ///
+    /// ```ignore
+    /// if let Some(violations) = cx.object_safety_violations(trait_id) {
+    ///     for violation in violations {
+    ///         if violation.kind() == ObjectSafetyViolationKind::GenericMethod {
+    ///             // ...
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [object safe]: https://doc.rust-lang.org/reference/items/traits.html#object-safety
+    pub fn object_safety_violations(&self, trait_id: TyDefId) -> Option<&'ast [ObjectSafetyViolation<'ast>]> {
+        (self.callbacks.object_safety_violations)(self.callbacks.data, trait_id)
+            .copy()
+            .map(|violations| violations.get())
+    }
+
+    /// Returns `true`, if the given semantic type is defined in the current crate.
+    /// This is the case for ADTs, like structs and enums, trait objects of local
+    /// traits and closures, which are defined in the current crate.
+    ///
+    /// All other types, like primitives, references and generic parameters, are
+    /// not considered local. References and other wrappers need to be peeled
+    /// manually, if their inner type is relevant.
+    ///
+    /// Together with [`MarkerContext::is_local_trait`], this can be used to check,
+    /// if an implementation of a trait for a type relies on the type or the trait
+    /// being defined in the current crate:
+    ///
+    /// ```ignore
+    /// if !cx.is_local_trait(bound.trait_id()) && cx.is_local_ty(ty) {
+    ///     // Moving `ty` into a different crate would violate the orphan rules
+    /// }
+    /// ```
+    pub fn is_local_ty(&self, ty: TyKind<'ast>) -> bool {
+        let def_id = match ty {
+            TyKind::Adt(adt) => adt.def_id(),
+            TyKind::Closure(closure) => closure.def_id(),
+            TyKind::TraitObj(obj) => match obj.bounds().first() {
+                Some(bound) => bound.trait_id(),
+                None => return false,
+            },
+            _ => return false,
+        };
+        (self.callbacks.is_local_def)(self.callbacks.data, def_id)
+    }
+
+    /// Returns the discriminants of the fieldless enum with the given [`TyDefId`],
+    /// in the order of the declared variants. These are the only values, which
+    /// are valid for the enum. `None` is returned, if the id doesn't belong to an
+    /// enum, or if a variant of the enum has fields.
+    ///
+    /// ```
+    /// enum Level {
+    ///     Low = 1,
+    ///     Medium,    // 2
+    ///     High = 10,
+    /// }
+    /// // The discriminants of `Level` are `[1, 2, 10]`
+    /// ```
+    ///
+    /// The discriminants are returned as [`ConstInt`]s, with the type of the
+    /// enum's `repr` attribute or `isize` by default. This allows discriminants
+    /// of `#[repr(u128)]` enums, which are larger than [`i128::MAX`], to be
+    /// represented without wrapping around.
+    pub fn enum_discriminants(&self, enum_id: TyDefId) -> Option<&'ast [ConstInt<'ast>]> {
+        (self.callbacks.enum_discriminants)(self.callbacks.data, enum_id)
+            .get()
+            .map(ffi::FfiSlice::get)
+    }
+
+    /// Converts the given name into the target [`CaseStyle`]. The conversion is
+    /// the same as in the suggestions of rustc's naming lints:
+    ///
+    /// ```
+    /// # use marker_api::common::CaseStyle;
+    /// # fn example(cx: &marker_api::MarkerContext<'_>) {
+    /// // Acronyms are treated as a single word
+    /// assert_eq!(cx.suggest_rename("HTTP_SERVER", CaseStyle::PascalCase), "HttpServer");
+    /// assert_eq!(cx.suggest_rename("maxSize", CaseStyle::ScreamingSnakeCase), "MAX_SIZE");
+    /// assert_eq!(cx.suggest_rename("LoadConfig", CaseStyle::SnakeCase), "load_config");
+    /// // Digits are separated by an underscore, if the boundary can't be expressed
+    /// // by the case of the letters
+    /// assert_eq!(cx.suggest_rename("v1_2", CaseStyle::PascalCase), "V1_2");
+    /// # }
+    /// ```
+    ///
+    /// Leading underscores are kept for [`CaseStyle::SnakeCase`] and
+    /// [`CaseStyle::ScreamingSnakeCase`], since they suppress the `unused` lints.
+    /// The name is returned unchanged for [`CaseStyle::Mixed`].
+    #[allow(clippy::unused_self)] // `self` is used for consistency with the other utilities
+    pub fn suggest_rename(&self, name: &str, target_case: CaseStyle) -> String {
+        target_case.convert(name)
+    }
+
+    /// Returns the field of the struct with the given [`ItemId`], which determines
+    /// the layout of the struct, if it's declared as `#[repr(transparent)]`.
+    ///
+    /// All other fields have to be zero-sized with an alignment of `1`, like
+    /// [`PhantomData`](std::marker::PhantomData) or `()`. Fields with a generic
+    /// type are never considered to be zero-sized. `None` is returned, if the struct
+    /// has several fields, which are not zero-sized, if all fields are zero-sized,
+    /// or if the item isn't a struct of the current crate.
+    ///
+    /// ```
+    /// # use std::marker::PhantomData;
+    /// struct Meters(f64);                       // `Some(0)`
+    /// struct Tagged<T>(u32, PhantomData<T>);    // `Some(0)`
+    /// struct Wrapper<T>(T, ());                 // `Some(0)`
+    /// struct Pair(u32, u32);                    // `None`
+    /// struct Marker;                            // `None`
+    /// ```
+    ///
+    /// The result doesn't depend on the `repr` of the struct. It can be used to
+    /// check that a `#[repr(transparent)]` struct wraps the expected field, or
+    /// to suggest adding the attribute to newtypes.
+    pub fn transparent_inner_field(&self, struct_id: ItemId) -> Option<&'ast ItemField<'ast>> {
+        (self.callbacks.transparent_inner_field)(self.callbacks.data, struct_id).copy()
+    }
+
+    /// Returns the name of the derive macro, which would generate an equivalent
+    /// impl to the trait impl with the given [`ItemId`]. A lint can use this to
+    /// suggest replacing the manual impl with `#[derive(...)]`:
+    ///
+    /// ```
+    /// struct Config {
+    ///     name: String,
+    ///     retries: u32,
+    /// }
+    ///
+    /// // `Some("Default")`
+    /// impl Default for Config {
+    ///     fn default() -> Self {
+    ///         Self {
+    ///             name: Default::default(),
+    ///             retries: 0,
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// // `Some("Clone")`
+    /// impl Clone for Config {
+    ///     fn clone(&self) -> Self {
+    ///         Self {
+    ///             name: self.name.clone(),
+    ///             retries: self.retries,
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// Currently, `Default` and `Clone` impls of ADTs from the current crate are
+    /// supported. A `Default` impl is derivable, if it sets every field to
+    /// `Default::default()` or a literal with the default value, like `0` or `false`.
+    /// The `default()` function of enums has to return a unit variant, which can be
+    /// marked with `#[default]`. A `Clone` impl of a struct is derivable, if it clones
+    /// every field of `self`, or returns `*self` for `Copy` types. Impls with type
+    /// or const parameters are not derivable, since derive macros add bounds to them.
+    pub fn impl_is_derivable(&self, impl_id: ItemId) -> Option<&'ast str> {
+        (self.callbacks.derivable_trait)(self.callbacks.data, impl_id)
+            .copy()
+            .map(|name| name.get())
+    }
+
+    /// Returns the name of the auto trait, which is implemented by the impl with
+    /// the given [`ItemId`]. `None` is returned, if the item isn't an impl of an
+    /// auto trait, like `Send` and `Sync`.
+    ///
+    /// ```
+    /// # struct Handle(*mut u8);
+    /// // `Some("Send")`
+    /// unsafe impl Send for Handle {}
+    ///
+    /// // `None`, `Clone` isn't an auto trait
+    /// impl Clone for Handle {
+    ///     // ...
+    /// #     fn clone(&self) -> Self { Handle(self.0) }
+    /// }
+    /// ```
+    ///
+    /// Together with [`ImplItem::is_unsafe`](crate::ast::ImplItem::is_unsafe), this
+    /// can be used to find manual `unsafe impl`s of `Send` and `Sync`. Negative
+    /// impls, like `impl !Send for Handle {}`, are included as well. They can be
+    /// detected with [`ImplItem::is_negated`](crate::ast::ImplItem::is_negated).
+    pub fn impl_is_auto_trait(&self, impl_id: ItemId) -> Option<&'ast str> {
+        (self.callbacks.auto_trait_impl)(self.callbacks.data, impl_id)
+            .copy()
+            .map(|name| name.get())
+    }
+
+    /// Returns `true`, if the trait with the given [`TyDefId`] is defined in the
+    /// current crate. See [`MarkerContext::is_local_ty`] for an example.
+    pub fn is_local_trait(&self, trait_id: TyDefId) -> bool {
+        (self.callbacks.is_local_def)(self.callbacks.data, trait_id)
+    }
+
+    /// Returns `true`, if the function with the given [`ItemId`] could be declared
+    /// as `const fn`. This is also `true` for functions, which already are `const`.
+    ///
+    /// The check is a conservative approximation of rustc's const checking. It
+    /// might return `false` for functions, which rustc would accept. The reason
+    /// can be retrieved with [`MarkerContext::const_fn_violation`].
+    pub fn could_be_const_fn(&self, id: ItemId) -> bool {
+        self.const_fn_violation(id).is_none()
+    }
+
+    /// Returns the first reason, why the function with the given [`ItemId`] can't
+    /// be declared as `const fn`. The span of the violation can be used to explain
+    /// the reason in a diagnostic:
+    ///
+    /// ```ignore
+    /// if !func.constness().is_const() {
+    ///     if let Some(violation) = cx.const_fn_violation(func.id()) {
+    ///         // The function can't be `const`
+    ///     } else {
+    ///         cx.emit_lint(LINT, func, "this function could be `const`");
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// `None` is returned, if the function could be `const` or already is `const`.
+    /// Items, which are not functions, return a violation of the kind
+    /// [`ConstFnViolationKind::Unsupported`](crate::sem::ConstFnViolationKind::Unsupported).
+    pub fn const_fn_violation(&self, id: ItemId) -> Option<&'ast ConstFnViolation<'ast>> {
+        (self.callbacks.const_fn_violation)(self.callbacks.data, id).copy()
+    }
+
+    /// Returns the name of the local variable with the given [`VarId`].
+    ///
+    /// Variables are declared by [`IdentPat`](crate::ast::IdentPat)s, like
+    /// in `let` statements or function parameters. The [`VarId`] of a variable
+    /// can also be retrieved from the [`AstPathTarget::Var`](crate::ast::AstPathTarget::Var)
+    /// target of a path expression, which uses it. This makes it possible to
+    /// inspect the declaration of a variable from its use site:
+    ///
+    /// ```ignore
+    /// if let ExprKind::Path(path) = expr
+    ///     && let AstPathTarget::Var(var) = path.path().resolve()
+    /// {
+    ///     let decl = cx.var_decl_span(var);
+    ///     // ...
+    /// }
+    /// ```
+    ///
+    /// Parameters of trait functions without a body don't declare variables.
+    /// Their ids are invalid and shouldn't be passed to this function.
+    pub fn var_name(&self, var: VarId) -> &'ast str {
+        self.symbol_str((self.callbacks.var_name)(self.callbacks.data, var))
+    }
+
+    /// Returns the semantic type of the local variable with the given [`VarId`].
+    /// For bindings by reference, like `ref x`, this is the type of the reference.
+    /// See [`MarkerContext::var_name`] for more information about variables.
+    pub fn var_ty(&self, var: VarId) -> TyKind<'ast> {
+        (self.callbacks.var_ty)(self.callbacks.data, var)
+    }
+
+    /// Returns the [`Span`] of the pattern, which declares the local variable with
+    /// the given [`VarId`], like `mut x` in `let mut x = 1;`. See
+    /// [`MarkerContext::var_name`] for more information about variables.
+    pub fn var_decl_span(&self, var: VarId) -> &'ast Span<'ast> {
+        self.span((self.callbacks.var_decl_span)(self.callbacks.data, var))
+    }
+
+    /// The functions, which are considered blocking by
+    /// [`MarkerContext::is_blocking_call`]. A path ending with `::*` matches all
+    /// functions and methods in the module or type.
+    pub const DEFAULT_BLOCKING_FNS: &'static [&'static str] = &[
+        "std::thread::sleep",
+        "std::thread::JoinHandle::join",
+        "std::fs::*",
+        "std::sync::Mutex::lock",
+        "std::sync::RwLock::read",
+        "std::sync::RwLock::write",
+        "std::sync::mpsc::Receiver::recv",
+    ];
+
+    /// Returns `true`, if the given expression calls a function, which is known to
+    /// block the current thread, like `std::thread::sleep` or `std::fs::read`. See
+    /// [`MarkerContext::DEFAULT_BLOCKING_FNS`] for the full list.
+    ///
+    /// Together with [`MarkerContext::is_in_async_context`], this can be used to
+    /// detect blocking calls in `async fn`s and `async` blocks:
+    ///
+    /// ```ignore
+    /// if cx.is_blocking_call(expr) && cx.is_in_async_context(expr) {
+    ///     cx.emit_lint(BLOCKING_IN_ASYNC, expr, "blocking call in async context");
+    /// }
+    /// ```
+    ///
+    /// Use [`MarkerContext::is_blocking_call_with`] to add custom blocking functions.
+    pub fn is_blocking_call(&self, expr: ExprKind<'ast>) -> bool {
+        self.is_blocking_call_with::<&str>(expr, &[])
+    }
+
+    /// Returns `true`, if the given expression calls a function from
+    /// [`MarkerContext::DEFAULT_BLOCKING_FNS`] or from the `additional` paths.
+    /// This allows lint crates to make the set configurable, by accepting a list
+    /// of paths in their [configuration](crate::export_lint_pass):
+    ///
+    /// ```ignore
+    /// #[derive(Default, serde::Deserialize)]
+    /// struct Config {
+    ///     /// Paths like `crate::db::query` or `sync_client::Client::*`
+    ///     blocking_fns: Vec<String>,
+    /// }
+    ///
+    /// // In the lint pass:
+    /// if cx.is_blocking_call_with(expr, &self.config.blocking_fns) { /* ... */ }
+    /// ```
+    ///
+    /// Paths are compared with the definition path of the called function, without
+    /// generic arguments. Methods are referenced by their type, like
+    /// `std::sync::Mutex::lock`. Trait methods by their trait, like
+    /// `std::io::Read::read`. Items of the linted crate start with `crate::`.
+    pub fn is_blocking_call_with<S: AsRef<str>>(&self, expr: ExprKind<'ast>, additional: &[S]) -> bool {
+        if !matches!(expr, ExprKind::Call(_) | ExprKind::Method(_)) {
+            return false;
+        }
+        let Some(path) = (self.callbacks.call_target_path)(self.callbacks.data, expr.id())
+            .get()
+            .map(ffi::FfiStr::get)
+        else {
+            return false;
+        };
+
+        Self::DEFAULT_BLOCKING_FNS
+            .iter()
+            .copied()
+            .chain(additional.iter().map(AsRef::as_ref))
+            .any(|pattern| match pattern.strip_suffix("::*") {
+                Some(prefix) => path.strip_prefix(prefix).map_or(false, |rest| rest.starts_with("::")),
+                None => path == pattern,
+            })
+    }
+
+    /// Returns `true`, if the given expression is evaluated in an async context,
+    /// meaning inside an `async fn`, `async` block or `async` closure. Closures
+    /// and items nested inside an async context are not async themselves. The
+    /// body of `|| std::thread::sleep(..)` inside an `async fn` is therefore not
+    /// an async context.
+    pub fn is_in_async_context(&self, expr: ExprKind<'ast>) -> bool {
+        (self.callbacks.is_in_async_context)(self.callbacks.data, expr.id())
+    }
+
+    /// Returns `true`, if the given expression dereferences a raw pointer, like
+    /// `*ptr` where `ptr` is a `*const T` or `*mut T`.
+    ///
+    /// More information about the operation can be retrieved with
+    /// [`MarkerContext::raw_ptr_op`].
+    pub fn is_raw_ptr_deref(&self, expr: ExprKind<'ast>) -> bool {
+        matches!(self.raw_ptr_op(expr), Some(op) if op.kind() == RawPtrOpKind::Deref)
+    }
+
+    /// Returns information about the raw pointer operation performed by the
+    /// given expression. This includes:
+    /// * Dereferences of raw pointers, like `*ptr`
+    /// * Pointer arithmetic with the `add`, `sub`, `offset` and related methods, like `ptr.add(2)`
+    /// * Casts to raw pointers, like `value as *const T`
+    ///
+    /// `None` is returned for all other expressions.
+    ///
+    /// ```ignore
+    /// if let Some(op) = cx.raw_ptr_op(expr) {
+    ///     if op.origin() == RawPtrOrigin::Int {
+    ///         cx.emit_lint(LINT, expr, "this operates on a pointer created from an integer");
+    ///     }
+    /// }
+    /// ```
+    pub fn raw_ptr_op(&self, expr: ExprKind<'ast>) -> Option<RawPtrOp<'ast>> {
+        let (kind, ptr, ptr_ty) = match expr {
+            ExprKind::UnaryOp(e) if e.kind() == UnaryOpKind::Deref => (RawPtrOpKind::Deref, e.expr(), e.expr().ty()),
+            ExprKind::Method(e) if PTR_ARITHMETIC_METHODS.contains(&e.method().ident().name()) => {
+                (RawPtrOpKind::Arithmetic, e.receiver(), e.receiver().ty())
+            },
+            ExprKind::As(e) => (RawPtrOpKind::Cast, expr, e.ty()),
+            _ => return None,
+        };
+        let TyKind::RawPtr(ptr_ty) = ptr_ty else {
+            return None;
+        };
+
+        Some(RawPtrOp::new(kind, ptr, ptr_ty.inner_ty(), raw_ptr_origin(self, ptr)))
+    }
+
+    /// Returns the loop, that the given expression is part of. For `break` and
+    /// `continue` expressions, this is the loop targeted by the expression,
+    /// which takes labels into account. `None` is returned, if the expression
+    /// is not inside a loop or if a `break` targets a labeled block.
+    ///
+    /// The search stops at closures, async blocks and items, as `break` and
+    /// `continue` can't cross these boundaries.
+    ///
+    /// ```ignore
+    /// if let ExprKind::Break(brk) = expr
+    ///     && let Some(loop_info) = cx.enclosing_loop(expr)
+    ///     && loop_info.kind() == LoopKind::Loop
+    /// {
+    ///     // `brk.expr()` is the value of the loop expression
+    /// }
+    /// ```
+    pub fn enclosing_loop(&self, expr: ExprKind<'ast>) -> Option<LoopInfo<'ast>> {
+        let loop_id = match expr {
+            ExprKind::Break(e) => Some(e.target_id()),
+            ExprKind::Continue(e) => Some(e.target_id()),
+            _ => (self.callbacks.enclosing_loop)(self.callbacks.data, expr.id()).copy(),
+        }?;
+        LoopInfo::new(self.ast().expr(loop_id))
+    }
+
+    /// Expands the type aliases in the given semantic type, until no further
+    /// expansion is possible. This resolves associated types with known
+    /// implementations and the hidden types of `impl Trait` types.
+    ///
+    /// Type aliases defined with the `type` keyword are usually already
+    /// resolved in semantic types. The type is returned unchanged, if it doesn't
+    /// contain any aliases or if they can't be expanded, for example, when
+    /// they depend on generic parameters.
+    ///
+    /// ```ignore
+    /// // `first` and `second` are `TyAliasItem`s
+    /// if let (Some(a), Some(b)) = (first.aliased_sem_ty(), second.aliased_sem_ty()) {
+    ///     let a = cx.normalize_alias(a);
+    ///     let b = cx.normalize_alias(b);
+    ///     // Compare `a` and `b` ...
+    /// }
+    /// ```
+    pub fn normalize_alias(&self, ty: TyKind<'ast>) -> TyKind<'ast> {
+        (self.callbacks.normalize_alias)(self.callbacks.data, ty.driver_id())
+            .copy()
+            .unwrap_or(ty)
+    }
+
+    /// Returns `true`, if the given generic parameter is used in the signature
+    /// or body of the item with the given [`ItemId`]. Usages in the bounds of
+    /// the parameter itself, like `T: Clone`, are not counted. Usages only in
+    /// [`PhantomData`](std::marker::PhantomData) count as used.
+    ///
+    /// ```ignore
+    /// if let ItemKind::Struct(item) = item {
+    ///     for param in item.generics().params() {
+    ///         if !cx.is_param_used(param, item.id()) {
+    ///             // Suggest removing the parameter
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn is_param_used(&self, param: &GenericParamKind<'ast>, item: ItemId) -> bool {
+        (self.callbacks.is_param_used)(self.callbacks.data, param.id(), item)
+    }
+
+    /// Returns `true`, if the item with the given [`ItemId`] is used inside the
+    /// current crate. Unlike rustc's `dead_code` lint, `pub` items are not
+    /// exempt. This can be used to find `pub` or `pub(crate)` items, which are
+    /// never used by the crate itself.
+    ///
+    /// Uses in macro expansions are counted, while `use` declarations, uses of
+    /// an item inside itself, like recursive calls or uses of a type inside its
+    /// own inherent impls, and uses in derived implementations are ignored. Items in trait
+    /// implementations are used, if the corresponding trait item is used or if the trait comes
+    /// from another crate. The entry point, items with `#[no_mangle]`, `#[export_name]`
+    /// or `#[used]`, impl blocks and macros are always considered used. Items
+    /// from other crates also return `true`.
+    ///
+    /// ```ignore
+    /// if let ItemKind::Fn(item) = item
+    ///     && item.visibility().is_pub()
+    ///     && !cx.is_used_in_crate(item.id())
+    /// {
+    ///     // The function is never called inside the crate
+    /// }
+    /// ```
+    pub fn is_used_in_crate(&self, item: ItemId) -> bool {
+        (self.callbacks.is_used_in_crate)(self.callbacks.data, item)
+    }
+
+    /// Returns `true`, if the given item is marked with `#[doc(hidden)]`. This
+    /// works for items of the current crate and items from dependencies.
+    ///
+    /// Hidden items of other crates are usually internals, which are only public
+    /// for technical reasons, like the use in macros. They're not covered by the
+    /// stability guarantees of the crate. For local items, this can be used to
+    /// skip naming or visibility lints on intentionally hidden items.
+    ///
+    /// Note that only the attribute of the item itself is checked. Items inside
+    /// a hidden module are not hidden themselves. Use [`MarkerContext::is_ty_doc_hidden`]
+    /// for types, identified by a [`TyDefId`].
+    ///
+    /// ```ignore
+    /// if let ExprKind::Path(path) = expr
+    ///     && let AstPathTarget::Item(id) = path.path().resolve()
+    ///     && cx.ast().item(id).is_none()
+    ///     && cx.is_doc_hidden(id)
+    /// {
+    ///     // The expression uses a hidden item of another crate
+    /// }
+    /// ```
+    pub fn is_doc_hidden(&self, item: ItemId) -> bool {
+        (self.callbacks.is_doc_hidden)(self.callbacks.data, item)
+    }
+
+    /// Returns `true`, if the type with the given [`TyDefId`] is marked with
+    /// `#[doc(hidden)]`. See [`MarkerContext::is_doc_hidden`] for more details.
+    pub fn is_ty_doc_hidden(&self, ty: TyDefId) -> bool {
+        (self.callbacks.is_ty_doc_hidden)(self.callbacks.data, ty)
+    }
+
+    /// Returns the [`NodeId`] of the smallest AST node, which contains the given
+    /// [`Span`]. This can be used to map a position in the source code back to
+    /// the AST, for example, to show information about the node under the cursor.
+    ///
+    /// Only expressions, statements, items, fields and enum variants are considered.
+    /// Nodes, which are not represented in Marker's AST, like some desugared
+    /// expressions, are skipped in favor of their parent nodes. `None` is returned,
+    /// if no node contains the span.
+    ///
+    /// ```ignore
+    /// // A span with the length zero, pointing to the start of `span`
+    /// let pos = span.with_end(span.start());
+    /// if let Some(NodeId::Expr(id)) = cx.node_at(&pos) {
+    ///     let expr = cx.ast().expr(id);
+    ///     // ...
+    /// }
+    /// ```
+    pub fn node_at(&self, span: &Span<'ast>) -> Option<NodeId> {
+        (self.callbacks.node_at)(self.callbacks.data, span).copy()
+    }
+
+    /// Returns the lines of the given source file, together with their line
+    /// number and [`Span`]. This allows lints to check the raw source code,
+    /// like the length of lines or their indentation, independent of the AST.
+    ///
+    /// Line numbers start at `1`, like the ones of [`FilePos`]. The line
+    /// endings, `\n` and `\r\n`, are not part of the text or span of a line.
+    /// The iterator is empty, if the source of the file is unavailable.
+    ///
+    /// ```ignore
+    /// if let SpanSource::File(file) = item.span().source() {
+    ///     for (line, text, span) in cx.source_lines(file) {
+    ///         if text.chars().count() > 100 {
+    ///             cx.emit_lint(LINE_TOO_LONG, item, format!("line {line} is too long"))
+    ///                 .span(&span);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn source_lines(&self, file: &FileInfo<'ast>) -> impl Iterator<Item = (usize, &'ast str, Span<'ast>)> {
+        let file_span: Option<&'ast Span<'ast>> = (self.callbacks.file_span)(self.callbacks.data, file).copy();
+        file_span.into_iter().flat_map(|span| {
+            let mut pos = span.start();
+            span.snippet()
+                .unwrap_or_default()
+                .split_inclusive('\n')
+                .enumerate()
+                .map(move |(index, raw_line)| {
+                    let line = raw_line.strip_suffix('\n').unwrap_or(raw_line);
+                    let line = line.strip_suffix('\r').unwrap_or(line);
+                    let line_span = span.with_start(pos).with_end(pos.offset(line.len()));
+                    pos = pos.offset(raw_line.len());
+                    (index + 1, line, line_span)
+                })
+        })
+    }
+
+    /// Returns the [`PanicKind`] of the panicking macro, that the given expression
+    /// was expanded from. This detects [`panic!`], [`todo!`], [`unimplemented!`],
+    /// [`unreachable!`] and the [`assert!`] family, including the `debug_assert`
+    /// variants. The macros are identified by their definition and not by their
+    /// name.
+    ///
+    /// Only the outermost expression of the expansion is detected. `None` is
+    /// returned for the arguments of the macro and for all other expressions.
+    /// The span of the detected expression comes from the macro expansion. The
+    /// span of the macro call is available via [`PanicMacro::span`].
+    ///
+    /// More information, like the message, can be retrieved with
+    /// [`MarkerContext::panic_macro`].
+    pub fn is_panic_macro(&self, expr: ExprKind<'ast>) -> Option<PanicKind> {
+        self.panic_macro(expr).map(|info| info.kind())
+    }
+
+    /// Returns information about the panicking macro, that the given expression
+    /// was expanded from. See [`MarkerContext::is_panic_macro`] for the detected
+    /// macros.
+    ///
+    /// ```ignore
+    /// if let Some(info) = cx.panic_macro(expr)
+    ///     && (info.kind() == PanicKind::Todo || info.message().is_some_and(|msg| msg.contains("TODO")))
+    /// {
+    ///     cx.emit_lint(LINT, expr, "this code is unfinished").span(info.span());
+    /// }
+    /// ```
+    pub fn panic_macro(&self, expr: ExprKind<'ast>) -> Option<PanicMacro<'ast>> {
+        (self.callbacks.panic_macro)(self.callbacks.data, expr.id()).copy()
+    }
+
+    /// Returns `true`, if the given expression is the last use of a local
+    /// variable. The variable isn't read afterwards and no reference to it is
+    /// still in use. Dropping the variable at the end of its scope doesn't
+    /// count as a use. This can be used to detect redundant clones:
+    ///
+    /// ```ignore
+    /// let a = String::from("marker");
+    /// let b = a.clone();
+    /// //      ^ The last use of `a`, the clone is redundant
+    /// consume(b);
+    /// ```
+    ///
+    /// The check is conservative and based on the MIR of the enclosing function.
+    /// It returns `false`, if the expression isn't a path to a local variable, or
+    /// if the MIR is unavailable, like for constants, closures and async blocks.
+    pub fn is_last_use(&self, expr: ExprKind<'ast>) -> bool {
+        (self.callbacks.is_last_use)(self.callbacks.data, expr.id())
+    }
+
+    /// Checks if the given expression is a `collect()` call into a [`Vec`], which
+    /// is only used to create a new iterator. The returned [`Span`] covers the
+    /// code, which can be removed, to iterate over the original iterator directly.
+    ///
+    /// ```
+    /// # let items = [1, 2, 3];
+    /// let doubled = items.iter().map(|x| x * 2).collect::<Vec<_>>().into_iter();
+    /// //                                       ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    /// for item in items.iter().map(|x| x * 2).collect::<Vec<_>>() {}
+    /// //                                     ^^^^^^^^^^^^^^^^^^^^
+    /// ```
+    ///
+    /// Collections, which are stored in a local variable, are only considered
+    /// needless, if the variable is used exactly once, to create the iterator via
+    /// `into_iter()` or a `for` loop. In this case, the span only covers the
+    /// `collect()` call, as the variable also has to be inlined at its use.
+    ///
+    /// ```
+    /// # let items = [1, 2, 3];
+    /// let doubled: Vec<_> = items.iter().map(|x| x * 2).collect();
+    /// //                                               ^^^^^^^^^^
+    /// for item in doubled {}
+    /// ```
+    ///
+    /// `None` is returned, if the collection is used in any other way, like
+    /// multiple times or by calling methods, which aren't available on iterators.
+    /// Iterating over references with `iter()` is also not needless, as the
+    /// iterator yields different items. Variables, which are used inside a loop
+    /// or closure, are not considered needless either, since inlining the
+    /// iterator would change how often and when it's evaluated.
+    pub fn is_needless_collect(&self, expr: ExprKind<'ast>) -> Option<&'ast Span<'ast>> {
+        (self.callbacks.needless_collect)(self.callbacks.data, expr.id())
+            .copy()
+            .map(|span| self.span(span))
+    }
+
+    /// Returns the [`FormatArgs`] of a formatting macro, like [`format!`],
+    /// [`println!`] or [`write!`]. The given expression has to be the outermost
+    /// expression of the macro expansion. The formatting macro can also be called
+    /// by another macro, in which case, the outermost expression of that macro
+    /// has to be used. `None` is returned for all other expressions.
+    ///
+    /// ```ignore
+    /// if let Some(args) = cx.format_args(expr) {
+    ///     for (index, arg) in args.arguments().iter().enumerate() {
+    ///         let is_used = args.template().iter().any(|piece| {
+    ///             matches!(piece, FormatPiece::Placeholder(placeholder) if placeholder.argument() == index)
+    ///         });
+    ///         // ...
+    ///     }
+    /// }
+    /// ```
+    pub fn format_args(&self, expr: ExprKind<'ast>) -> Option<&'ast FormatArgs<'ast>> {
+        (self.callbacks.format_args)(self.callbacks.data, expr.id()).copy()
+    }
+
+    /// Returns the error conversion, performed by the given
+    /// [`TryExpr`](crate::ast::TryExpr). The `?` operator calls
+    /// [`From::from`] to convert the error, if the error type of the expression
+    /// differs from the error type of the function or `try` block. The returned
+    /// tuple contains the source error type, the target error type and the
+    /// [`ItemId`] of the invoked `From` impl.
+    ///
+    /// ```
+    /// # #[derive(Debug)] struct AppError;
+    /// # impl From<std::num::ParseIntError> for AppError {
+    /// #     fn from(_: std::num::ParseIntError) -> Self { AppError }
+    /// # }
+    /// fn parse(text: &str) -> Result<u32, AppError> {
+    ///     // Converts `ParseIntError` to `AppError` using
+    ///     // `impl From<ParseIntError> for AppError`
+    ///     let value = text.parse::<u32>()?;
+    ///     Ok(value)
+    /// }
+    /// ```
+    ///
+    /// The [`ItemId`] belongs to the `From` trait, if the impl depends on generic
+    /// parameters and can't be resolved. `None` is returned, if the expression
+    /// isn't a `?` expression, or if no conversion occurs, like for [`Option`]s
+    /// or matching error types.
+    pub fn try_from_residual_conversion(&self, expr: ExprKind<'ast>) -> Option<(TyKind<'ast>, TyKind<'ast>, ItemId)> {
+        if !matches!(expr, ExprKind::Try(_)) {
+            return None;
+        }
+        (self.callbacks.try_residual_conversion)(self.callbacks.data, expr.id())
+            .copy()
+            .map(|conversion| (conversion.source, conversion.target, conversion.from_impl))
+    }
+
+    /// Tries to calculate the value of the given [`ConstExpr`]. `None` is
+    /// returned, if the evaluation fails, if the value depends on generic
+    /// parameters or if the value can't be represented by [`ConstValue`], like
+    /// floats or structs.
+    ///
+    /// ```ignore
+    /// if let Some(len) = array_ty.len()
+    ///     && let Some(ConstValue::Int(int)) = cx.try_eval_const(len)
+    ///     && int.to_u128() == Some(0)
+    /// {
+    ///     // Zero sized array
+    /// }
+    /// ```
+    pub fn try_eval_const(&self, expr: &ConstExpr<'ast>) -> Option<&'ast ConstValue<'ast>> {
+        (self.callbacks.try_eval_const)(self.callbacks.data, expr.expr().id()).copy()
+    }
+
+    /// Returns `true`, if the given type is an [`Option`] or [`Result`]. Type
+    /// aliases, like `std::io::Result<T>`, are resolved in semantic types and
+    /// are therefore also detected.
+    pub fn is_option_or_result(&self, ty: TyKind<'ast>) -> bool {
+        let TyKind::Adt(adt) = ty else {
+            return false;
+        };
+        ["core::option::Option", "core::result::Result"]
+            .iter()
+            .any(|path| self.resolve_ty_ids(path).contains(&adt.def_id()))
+    }
+
+    /// Returns `true`, if the given node is part of test code. This is the case
+    /// for nodes inside `#[test]` functions and inside items marked with
+    /// `#[cfg(test)]`, like `mod tests`. This can be used to suppress lints in
+    /// tests:
+    ///
+    /// ```ignore
+    /// if let ExprKind::Method(call) = expr
+    ///     && call.is_unwrap_or_expect()
+    ///     && !cx.is_in_test_code(expr)
+    ///     && !cx.is_guarded_unwrap(expr)
+    /// {
+    ///     cx.emit_lint(UNWRAP_USED, expr, "used `unwrap()` or `expect()`");
+    /// }
+    /// ```
+    ///
+    /// Other configurations, like `#[cfg(any(test, feature = "testing"))]`,
+    /// are not considered to be test code.
+    pub fn is_in_test_code(&self, node: impl HasNodeId) -> bool {
+        (self.callbacks.is_in_test_code)(self.callbacks.data, node.node_id())
+    }
+
+    /// Returns `true`, if the given expression is an `unwrap()` or `expect()`
+    /// call on a local [`Option`] or [`Result`] variable, which is guaranteed to
+    /// succeed, due to a previous check. The following checks are detected:
+    ///
+    /// ```ignore
+    /// if x.is_some() {
+    ///     x.unwrap();
+    /// }
+    ///
+    /// if x.is_none() {
+    ///     return;
+    /// }
+    /// x.unwrap();
+    /// ```
+    ///
+    /// Results are checked with `is_ok()` and `is_err()`. Checks can be combined
+    /// with other conditions using `&&` and negated with `!`. Mutable variables
+    /// are never considered guarded, as they could be modified after the check.
+    pub fn is_guarded_unwrap(&self, expr: ExprKind<'ast>) -> bool {
+        (self.callbacks.is_guarded_unwrap)(self.callbacks.data, expr.id())
+    }
+
+    /// Returns `true`, if the given expression is a conversion, which returns a
+    /// value of the type it was called with. These conversions can be removed.
+    /// The following conversions are detected:
+    ///
+    /// ```ignore
+    /// // `a`, `b` and `c` are `String`s
+    /// let _: String = a.into();        // `Into<String> for String`
+    /// let _ = String::from(b);         // `From<String> for String`
+    /// let _: &str = "marker".as_ref(); // `AsRef<str> for str`
+    /// let _ = c.to_owned();            // `ToOwned` with `Owned = String`,
+    ///                                  // if `c` isn't used afterwards
+    /// ```
+    ///
+    /// The type of the converted value is compared before any auto-deref or
+    /// auto-ref adjustments. Generic conversions are detected, if the inferred
+    /// target type matches the source type, like `value.into()` returning `T` in
+    /// a function, which takes a `value: T`. `AsMut` is detected in the same way
+    /// as `AsRef`.
+    ///
+    /// `to_owned()` clones the value. It's only an identity conversion, if the
+    /// receiver is a local variable, which isn't used afterwards, as the value
+    /// can then be moved instead. See [`MarkerContext::is_last_use`]. Other
+    /// cloning methods, like `to_string()`, are not identity conversions.
+    pub fn is_identity_conversion(&self, expr: ExprKind<'ast>) -> bool {
+        (self.callbacks.is_identity_conversion)(self.callbacks.data, expr.id())
+    }
+
+    /// Returns `true`, if the given type implements [`Iterator`]. Generic
+    /// parameters in the type are checked with the bounds of the `owner` item.
+    /// This should be the item, which contains the type, like the function of
+    /// the checked expression. It can be retrieved with [`Body::owner`](crate::ast::Body::owner).
+    ///
+    /// The `Item` type of the iterator can be retrieved with
+    /// [`MarkerContext::iterator_item_ty`].
+    pub fn is_iterator_ty(&self, ty: TyKind<'ast>, owner: ItemId) -> bool {
+        self.iterator_item_ty(ty, owner).is_some()
+    }
+
+    /// Returns the `Item` type of the given type, if it implements [`Iterator`].
+    /// The type is normalized if possible. It can remain an alias type, if the
+    /// item type depends on generic parameters, like `<I as Iterator>::Item`.
+    /// See [`MarkerContext::is_iterator_ty`] for the `owner` item.
+    ///
+    /// `None` is returned, if the type uses generic parameters, which are not
+    /// declared by the `owner` item or its parents.
+    ///
+    /// ```ignore
+    /// // `collect()` was called on an iterator of `Result`s
+    /// if let Some(TyKind::Adt(item)) = cx.iterator_item_ty(receiver.ty(), body.owner())
+    ///     && cx.resolve_ty_ids("core::result::Result").contains(&item.def_id())
+    /// {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn iterator_item_ty(&self, ty: TyKind<'ast>, owner: ItemId) -> Option<TyKind<'ast>> {
+        (self.callbacks.iterator_item_ty)(self.callbacks.data, ty.driver_id(), owner).copy()
+    }
+
+    /// Returns the memory layout of the given type, including its size and
+    /// niche information. Generic parameters in the type are checked with the
+    /// bounds of the `owner` item, see [`MarkerContext::is_iterator_ty`]. This
+    /// can be used to check if `Option<T>` is larger than `T`:
+    ///
+    /// ```ignore
+    /// if let Some(layout) = cx.ty_layout(ty, body.owner()) && !layout.is_option_free() {
+    ///     // `Option<T>` requires additional space for the discriminant
+    /// }
+    /// ```
+    ///
+    /// `None` is returned, if the layout isn't known. This is the case for
+    /// unsized types and types, whose layout depends on generic parameters, like
+    /// `T` or `Option<T>`. References to sized generic types and containers, like
+    /// `Vec<T>`, have a known layout. `None` is also returned, if the type uses
+    /// generic parameters, which are not declared by the `owner` item or its
+    /// parents.
+    pub fn ty_layout(&self, ty: TyKind<'ast>, owner: ItemId) -> Option<TyLayout> {
+        (self.callbacks.ty_layout)(self.callbacks.data, ty.driver_id(), owner).copy()
+    }
+
+    /// Returns `true`, if the given type has a niche, which can be used by enums
+    /// to store their discriminant. See [`TyLayout::has_niche`] for more information
+    /// and [`MarkerContext::ty_layout`] for the `owner` item.
+    ///
+    /// `false` is returned, if the layout of the type isn't known.
+    pub fn ty_has_niche(&self, ty: TyKind<'ast>, owner: ItemId) -> bool {
+        self.ty_layout(ty, owner).map_or(false, |layout| layout.has_niche())
+    }
+
+    /// Returns the number of bytes, which a value of the given type occupies on
+    /// the stack. This can be used to find large local variables and array
+    /// literals, which should be moved to the heap with a [`Box`] or [`Vec`].
+    /// The threshold can be taken from the [configuration](crate::export_lint_pass)
+    /// of the lint crate:
+    ///
+    /// ```ignore
+    /// #[derive(Default, serde::Deserialize)]
+    /// struct Config {
+    ///     max_stack_size: Option<u64>,
+    /// }
+    ///
+    /// // In `LintPass::check_local`, `LintPass::check_expr` for array
+    /// // expressions works the same way:
+    /// let max = self.config.max_stack_size.unwrap_or(512 * 1024);
+    /// if let Some(init) = local.init()
+    ///     && let Some(size) = cx.stack_size_of(init.ty(), body.owner())
+    ///     && size > max
+    /// {
+    ///     cx.emit_lint(LARGE_STACK_ALLOC, local, format!("this allocates {size} bytes on the stack"))
+    ///         .decorate(|diag| diag.help("consider allocating it on the heap with `Box`"));
+    /// }
+    /// ```
+    ///
+    /// `None` is returned, if the size isn't known, like for unsized types and
+    /// types, whose layout depends on generic parameters. See
+    /// [`MarkerContext::ty_layout`] for more information and the `owner` item.
+    pub fn stack_size_of(&self, ty: TyKind<'ast>, owner: ItemId) -> Option<u64> {
+        self.ty_layout(ty, owner).map(|layout| layout.size())
+    }
+
+    /// Returns `true`, if dropping a value of the given type runs any code. This
+    /// is the case for types with a [`Drop`] implementation and types, which
+    /// contain such a type, like `Vec<String>` or `(u32, Box<u8>)`. This can be
+    /// used to find calls of [`std::mem::forget`], which skip a meaningful drop.
+    ///
+    /// Generic parameters in the type are checked with the bounds of the `owner`
+    /// item, see [`MarkerContext::is_iterator_ty`]. Types with generic parameters,
+    /// whose bounds don't guarantee a trivial drop, are assumed to need a drop.
+    /// See [`MarkerContext::has_drop_impl`], to check if the type itself
+    /// implements [`Drop`].
+    pub fn needs_drop(&self, ty: TyKind<'ast>, owner: ItemId) -> bool {
+        (self.callbacks.needs_drop)(self.callbacks.data, ty.driver_id(), owner)
+    }
+
+    /// Returns `true`, if the given type implements [`Drop`] itself. In contrast
+    /// to [`MarkerContext::needs_drop`], this doesn't consider the fields of the
+    /// type. `String` implements [`Drop`] indirectly via its `Vec<u8>` field,
+    /// this would therefore return `false` for it.
+    ///
+    /// ```ignore
+    /// // `Copy` and `Drop` can't be implemented for the same type. A lint can
+    /// // check this before suggesting a `#[derive(Copy)]`
+    /// if cx.has_drop_impl(ty) {
+    ///     return;
+    /// }
+    /// ```
+    pub fn has_drop_impl(&self, ty: TyKind<'ast>) -> bool {
+        (self.callbacks.has_drop_impl)(self.callbacks.data, ty.driver_id())
+    }
+
+    /// Returns a simplified version of the given boolean expression, if it
+    /// can be simplified. The result is intended to be used as a suggestion.
+    ///
+    /// ```ignore
+    /// x == true              // -> `x`
+    /// !(a && b)              // -> `!a || !b`
+    /// !(x < 5 || y == z)     // -> `x >= 5 && y != z`
+    /// !!flag && true         // -> `flag`
+    /// ```
+    ///
+    /// The simplification preserves the evaluation of operands, which could have
+    /// side effects. `x && false` is therefore not simplified to `false`.
+    /// Comparisons are only inverted, if the operand type has a total order, like
+    /// integers. For floats, `!(a < b)` is kept, as `NaN` is neither smaller
+    /// nor greater or equal. Parentheses are added where required to preserve
+    /// the operator precedence.
+    ///
+    /// `None` is returned, if the expression can't be simplified, or if the
+    /// snippet of an operand is unavailable.
+    pub fn simplify_bool(&self, expr: ExprKind<'ast>) -> Option<String> {
+        simplify_bool::simplify_bool(expr)
+    }
+
+    /// Returns `true`, if the evaluation of the given expression never completes
+    /// normally. This is the case for expressions of the never type `!`, like
+    /// `panic!()` calls and infinite loops, as well as for `return`, `break` and
+    /// `continue` expressions. Blocks diverge, if one of their statements or
+    /// the tail expression diverges, `if` expressions, if both branches diverge, and `match`
+    /// expressions, if all arms diverge. Labeled blocks and loops only diverge,
+    /// if they have the type `!`, as a `break` can target them. The labeled
+    /// block `'a: { break 'a 1; }` therefore doesn't diverge.
+    ///
+    /// This can be used to recognize early returns, like in
+    /// `if x.is_err() { return Err(...) }`, which could be replaced by `x?`:
+    ///
+    /// ```ignore
+    /// if let ExprKind::If(if_expr) = expr
+    ///     && if_expr.els().is_none()
+    ///     && cx.expr_diverges(if_expr.then())
+    /// {
+    ///     // The `then` branch is an early exit
+    /// }
+    /// ```
+    pub fn expr_diverges(&self, expr: ExprKind<'ast>) -> bool {
+        divergence::expr_diverges(expr)
+    }
+
+    /// Checks if an expression with the `inner` precedence requires parentheses,
+    /// when it's placed at the given [`ExprPosition`] of an expression with the
+    /// `parent` precedence. This is intended for suggestions, which substitute an
+    /// expression into a new context.
+    ///
+    /// ```text
+    /// // `a + b` as the left operand of `* c`      -> `(a + b) * c`
+    /// // `a * b` as the left operand of `+ c`      -> `a * b + c`
+    /// // `a - b` as the right operand of `c - `    -> `c - (a - b)`
+    /// // `a + b` as the receiver of `.pow(2)`      -> `(a + b).pow(2)`
+    /// ```
+    ///
+    /// Parentheses are also required, if the combination would otherwise be
+    /// rejected by the parser, like `a as usize < b` or chained comparisons.
+    /// `true` is returned for [`ExprPrecedence::Unstable`] precedences.
+    pub fn expr_needs_parens(&self, inner: ExprPrecedence, parent: ExprPrecedence, position: ExprPosition) -> bool {
+        parens::expr_needs_parens(inner, parent, position)
+    }
+
+    /// Returns the given expression text, wrapped in parentheses, if they're
+    /// required according to [`MarkerContext::expr_needs_parens`].
+    ///
+    /// ```ignore
+    /// let left = cx.render_with_parens(
+    ///     &snippet,
+    ///     expr.precedence(),
+    ///     ExprPrecedence::Mul,
+    ///     ExprPosition::Left,
+    /// );
+    /// let suggestion = format!("{left} * 2");
+    /// ```
+    pub fn render_with_parens(
+        &self,
+        expr_text: &str,
+        inner: ExprPrecedence,
+        parent: ExprPrecedence,
+        position: ExprPosition,
+    ) -> String {
+        parens::render_with_parens(expr_text, inner, parent, position)
+    }
+
+    /// Creates a [`SuggestionBuilder`], which replaces the code of the given
+    /// span with the suggestion.
+    ///
+    /// ```ignore
+    /// cx.emit_lint(LINT, expr, "this can be simplified").decorate(|diag| {
+    ///     cx.suggest_replace(expr, "x.is_empty()").emit(diag);
+    /// });
+    /// ```
+    pub fn suggest_replace(&self, span: impl HasSpan<'ast>, sugg: impl Into<String>) -> SuggestionBuilder<'ast> {
+        SuggestionBuilder::new(span.span().clone(), sugg.into())
+    }
+
+    /// Creates a [`SuggestionBuilder`], which inserts the text directly before
+    /// the given span, like `&` before an argument.
+    pub fn suggest_insert_before(&self, span: impl HasSpan<'ast>, text: impl Into<String>) -> SuggestionBuilder<'ast> {
+        let span = span.span();
+        SuggestionBuilder::new(span.with_end(span.start()), text.into())
+    }
+
+    /// Creates a [`SuggestionBuilder`], which inserts the text directly after
+    /// the given span, like `?` after a call.
+    pub fn suggest_insert_after(&self, span: impl HasSpan<'ast>, text: impl Into<String>) -> SuggestionBuilder<'ast> {
+        let span = span.span();
+        SuggestionBuilder::new(span.with_start(span.end()), text.into())
+    }
+}
+
+/// Inherent methods of raw pointers, which perform pointer arithmetic.
+const PTR_ARITHMETIC_METHODS: &[&str] = &[
+    "add",
+    "sub",
+    "offset",
+    "wrapping_add",
+    "wrapping_sub",
+    "wrapping_offset",
+    "byte_add",
+    "byte_sub",
+    "byte_offset",
+    "wrapping_byte_add",
+    "wrapping_byte_sub",
+    "wrapping_byte_offset",
+];
+
+fn raw_ptr_origin<'ast>(cx: &MarkerContext<'ast>, ptr: ExprKind<'ast>) -> RawPtrOrigin {
+    match ptr {
+        // `&raw const value` and coerced references
+        ExprKind::Ref(_) => RawPtrOrigin::Ref,
+        ExprKind::As(e) => match e.expr().ty() {
+            TyKind::Ref(_) => RawPtrOrigin::Ref,
+            TyKind::Num(num) if num.is_integer() => RawPtrOrigin::Int,
+            // Pointer to pointer casts keep the origin
+            TyKind::RawPtr(_) => raw_ptr_origin(cx, e.expr()),
+            _ => RawPtrOrigin::Unknown,
+        },
+        // Immutable variables keep the origin of their initializer
+        ExprKind::Path(e) => match e.path().resolve() {
+            AstPathTarget::Var(var) => cx
+                .var_init(var)
+                .map_or(RawPtrOrigin::Unknown, |init| raw_ptr_origin(cx, init)),
+            _ => RawPtrOrigin::Unknown,
+        },
+        _ => RawPtrOrigin::Unknown,
+    }
 }
 
 impl<'ast> MarkerContext<'ast> {
@@ -260,6 +1465,14 @@ impl<'ast> MarkerContext<'ast> {
         self.callbacks.call_expr_ty(expr)
     }
 
+    /// Returns the initializer of the given variable, if it's declared by an
+    /// immutable binding, which is the entire pattern of a `let` statement,
+    /// like `x` in `let x = value;`.
+    pub(crate) fn var_init(&self, var: VarId) -> Option<ExprKind<'ast>> {
+        let init = (self.callbacks.var_init)(self.callbacks.data, var).copy()?;
+        Some(self.ast().expr(init))
+    }
+
     // FIXME: This function should probably be removed in favor of a better
     // system to deal with spans. See rust-marker/marker#175
     pub(crate) fn span_snipped(&self, span: &Span<'ast>) -> Option<&'ast str> {
@@ -286,12 +1499,82 @@ impl<'ast> MarkerContext<'ast> {
         self.callbacks.call_symbol_str(sym)
     }
 
+    pub(crate) fn attrs(&self, node: NodeId) -> &'ast [Attribute<'ast>] {
+        (self.callbacks.attrs)(self.callbacks.data, node).get()
+    }
+
+    pub(crate) fn derives(&self, item: ItemId) -> &'ast [Derive<'ast>] {
+        (self.callbacks.derives)(self.callbacks.data, item).get()
+    }
+
+    pub(crate) fn aliased_sem_ty(&self, item: ItemId) -> Option<TyKind<'ast>> {
+        (self.callbacks.aliased_sem_ty)(self.callbacks.data, item).copy()
+    }
+
+    pub(crate) fn overloaded_op_fn(&self, expr: ExprId) -> Option<ItemId> {
+        (self.callbacks.overloaded_op_fn)(self.callbacks.data, expr).copy()
+    }
+
+    pub(crate) fn parses_as_expr(&self, text: &str) -> bool {
+        (self.callbacks.parses_as_expr)(self.callbacks.data, text.into())
+    }
+
+    pub(crate) fn method_trait(&self, expr: ExprId) -> Option<ItemId> {
+        (self.callbacks.method_trait)(self.callbacks.data, expr).copy()
+    }
+
+    pub(crate) fn is_dynamic_dispatch(&self, expr: ExprId) -> bool {
+        (self.callbacks.is_dynamic_dispatch)(self.callbacks.data, expr)
+    }
+
+    pub(crate) fn async_output_ty(&self, item: ItemId) -> Option<TyKind<'ast>> {
+        (self.callbacks.async_output_ty)(self.callbacks.data, item).copy()
+    }
+
+    pub(crate) fn all_supertraits(&self, item: ItemId) -> &'ast [TyDefId] {
+        (self.callbacks.all_supertraits)(self.callbacks.data, item).get()
+    }
+
+    pub(crate) fn is_iterator_adapter(&self, expr: ExprId) -> bool {
+        (self.callbacks.is_iterator_adapter)(self.callbacks.data, expr)
+    }
+
+    pub(crate) fn annotation_matches_init(&self, stmt: StmtId) -> bool {
+        (self.callbacks.annotation_matches_init)(self.callbacks.data, stmt)
+    }
+
+    pub(crate) fn let_expr_is_irrefutable(&self, expr: ExprId) -> bool {
+        (self.callbacks.let_expr_is_irrefutable)(self.callbacks.data, expr)
+    }
+
     #[allow(unused)] // Will be used later(or removed)
     pub(crate) fn resolve_method_target(&self, expr: ExprId) -> ItemId {
         self.callbacks.resolve_method_target(expr)
     }
 }
 
+/// The FFI representation of the result of
+/// [`MarkerContext::try_from_residual_conversion`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "driver-api", visibility::make(pub))]
+struct TryConversion<'ast> {
+    source: TyKind<'ast>,
+    target: TyKind<'ast>,
+    from_impl: ItemId,
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> TryConversion<'ast> {
+    pub fn new(source: TyKind<'ast>, target: TyKind<'ast>, from_impl: ItemId) -> Self {
+        Self {
+            source,
+            target,
+            from_impl,
+        }
+    }
+}
+
 /// This struct holds function pointers to driver implementations of required
 /// functions. These can roughly be split into two categories:
 ///
@@ -319,17 +1602,73 @@ struct MarkerContextCallbacks<'ast> {
 
     // Public utility
     pub resolve_ty_ids: extern "C" fn(&'ast MarkerContextData, path: ffi::FfiStr<'_>) -> ffi::FfiSlice<'ast, TyDefId>,
+    pub crate_attrs: extern "C" fn(&'ast MarkerContextData) -> ffi::FfiSlice<'ast, Attribute<'ast>>,
+    pub manifest: extern "C" fn(&'ast MarkerContextData) -> ffi::FfiOption<&'ast Manifest<'ast>>,
+    pub target: extern "C" fn(&'ast MarkerContextData) -> &'ast TargetInfo<'ast>,
+    pub object_safety_violations: extern "C" fn(
+        &'ast MarkerContextData,
+        TyDefId,
+    )
+        -> ffi::FfiOption<ffi::FfiSlice<'ast, ObjectSafetyViolation<'ast>>>,
+    pub enclosing_loop: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<ExprId>,
+    pub normalize_alias: extern "C" fn(&'ast MarkerContextData, DriverTyId) -> ffi::FfiOption<TyKind<'ast>>,
+    pub is_param_used: extern "C" fn(&'ast MarkerContextData, GenericId, ItemId) -> bool,
+    pub is_used_in_crate: extern "C" fn(&'ast MarkerContextData, ItemId) -> bool,
+    pub is_doc_hidden: extern "C" fn(&'ast MarkerContextData, ItemId) -> bool,
+    pub is_ty_doc_hidden: extern "C" fn(&'ast MarkerContextData, TyDefId) -> bool,
+    pub node_at: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> ffi::FfiOption<NodeId>,
+    pub panic_macro: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<PanicMacro<'ast>>,
+    pub is_last_use: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
+    pub needless_collect: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<SpanId>,
+    pub format_args: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<&'ast FormatArgs<'ast>>,
+    pub try_eval_const: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<&'ast ConstValue<'ast>>,
+    pub iterator_item_ty: extern "C" fn(&'ast MarkerContextData, DriverTyId, ItemId) -> ffi::FfiOption<TyKind<'ast>>,
+    pub ty_layout: extern "C" fn(&'ast MarkerContextData, DriverTyId, ItemId) -> ffi::FfiOption<TyLayout>,
+    pub needs_drop: extern "C" fn(&'ast MarkerContextData, DriverTyId, ItemId) -> bool,
+    pub has_drop_impl: extern "C" fn(&'ast MarkerContextData, DriverTyId) -> bool,
+    pub parses_as_expr: extern "C" fn(&'ast MarkerContextData, ffi::FfiStr<'_>) -> bool,
+    pub const_fn_violation:
+        extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiOption<&'ast ConstFnViolation<'ast>>,
+    pub var_name: extern "C" fn(&'ast MarkerContextData, VarId) -> SymbolId,
+    pub var_ty: extern "C" fn(&'ast MarkerContextData, VarId) -> TyKind<'ast>,
+    pub var_decl_span: extern "C" fn(&'ast MarkerContextData, VarId) -> SpanId,
+    pub call_target_path: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<ffi::FfiStr<'ast>>,
+    pub is_in_async_context: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
+    pub is_in_test_code: extern "C" fn(&'ast MarkerContextData, NodeId) -> bool,
+    pub is_guarded_unwrap: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
+    pub is_identity_conversion: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
+    pub is_local_def: extern "C" fn(&'ast MarkerContextData, TyDefId) -> bool,
+    pub try_residual_conversion: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<TryConversion<'ast>>,
+    pub derivable_trait: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiOption<ffi::FfiStr<'ast>>,
+    pub auto_trait_impl: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiOption<ffi::FfiStr<'ast>>,
+    pub transparent_inner_field:
+        extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiOption<&'ast ItemField<'ast>>,
+    pub enum_discriminants:
+        extern "C" fn(&'ast MarkerContextData, TyDefId) -> ffi::FfiOption<ffi::FfiSlice<'ast, ConstInt<'ast>>>,
 
     // Internal utility
     pub expr_ty: extern "C" fn(&'ast MarkerContextData, ExprId) -> TyKind<'ast>,
+    pub var_init: extern "C" fn(&'ast MarkerContextData, VarId) -> ffi::FfiOption<ExprId>,
     pub span: extern "C" fn(&'ast MarkerContextData, SpanId) -> &'ast Span<'ast>,
     pub span_snippet: extern "C" fn(&'ast MarkerContextData, &Span<'ast>) -> ffi::FfiOption<ffi::FfiStr<'ast>>,
     pub span_source: extern "C" fn(&'ast MarkerContextData, &Span<'_>) -> SpanSource<'ast>,
     pub span_pos_to_file_loc:
         extern "C" fn(&'ast MarkerContextData, &FileInfo<'ast>, SpanPos) -> ffi::FfiOption<FilePos<'ast>>,
+    pub file_span: extern "C" fn(&'ast MarkerContextData, &FileInfo<'ast>) -> ffi::FfiOption<&'ast Span<'ast>>,
     pub span_expn_info: extern "C" fn(&'ast MarkerContextData, ExpnId) -> ffi::FfiOption<&'ast ExpnInfo<'ast>>,
     pub symbol_str: extern "C" fn(&'ast MarkerContextData, SymbolId) -> ffi::FfiStr<'ast>,
     pub resolve_method_target: extern "C" fn(&'ast MarkerContextData, ExprId) -> ItemId,
+    pub attrs: extern "C" fn(&'ast MarkerContextData, NodeId) -> ffi::FfiSlice<'ast, Attribute<'ast>>,
+    pub derives: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiSlice<'ast, Derive<'ast>>,
+    pub aliased_sem_ty: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiOption<TyKind<'ast>>,
+    pub overloaded_op_fn: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<ItemId>,
+    pub method_trait: extern "C" fn(&'ast MarkerContextData, ExprId) -> ffi::FfiOption<ItemId>,
+    pub is_dynamic_dispatch: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
+    pub async_output_ty: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiOption<TyKind<'ast>>,
+    pub is_iterator_adapter: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
+    pub all_supertraits: extern "C" fn(&'ast MarkerContextData, ItemId) -> ffi::FfiSlice<'ast, TyDefId>,
+    pub annotation_matches_init: extern "C" fn(&'ast MarkerContextData, StmtId) -> bool,
+    pub let_expr_is_irrefutable: extern "C" fn(&'ast MarkerContextData, ExprId) -> bool,
 }
 
 impl<'ast> MarkerContextCallbacks<'ast> {
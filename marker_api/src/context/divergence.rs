@@ -0,0 +1,40 @@
+//! The divergence check used by
+//! [`MarkerContext::expr_diverges`](super::MarkerContext::expr_diverges).
+
+use crate::{
+    ast::{ExprKind, StmtKind},
+    sem::TyKind,
+};
+
+pub(crate) fn expr_diverges(expr: ExprKind<'_>) -> bool {
+    if matches!(expr.ty(), TyKind::Never(_)) {
+        return true;
+    }
+
+    match expr {
+        ExprKind::Return(_) | ExprKind::Break(_) | ExprKind::Continue(_) => true,
+        // A `break` inside a labeled block can target the block itself. The
+        // block therefore only diverges, if its type is `!`, which is checked above.
+        ExprKind::Block(block) if block.label().is_some() => false,
+        ExprKind::Block(block) => {
+            block.stmts().iter().any(|stmt| stmt_diverges(*stmt)) || block.expr().is_some_and(expr_diverges)
+        },
+        ExprKind::If(if_expr) => {
+            expr_diverges(if_expr.condition())
+                || (expr_diverges(if_expr.then()) && if_expr.els().is_some_and(expr_diverges))
+        },
+        ExprKind::Match(match_expr) => {
+            expr_diverges(match_expr.scrutinee())
+                || (!match_expr.arms().is_empty() && match_expr.arms().iter().all(|arm| expr_diverges(arm.expr())))
+        },
+        _ => false,
+    }
+}
+
+fn stmt_diverges(stmt: StmtKind<'_>) -> bool {
+    match stmt {
+        StmtKind::Let(local) => local.init().is_some_and(expr_diverges),
+        StmtKind::Expr(expr) => expr_diverges(expr.expr()),
+        _ => false,
+    }
+}
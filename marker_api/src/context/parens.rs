@@ -0,0 +1,109 @@
+//! The parentheses checks used by
+//! [`MarkerContext::expr_needs_parens`](super::MarkerContext::expr_needs_parens).
+//!
+//! The [`ExprPrecedence`] values are grouped into binding levels. The levels
+//! mostly follow the values of [`ExprPrecedence`], with a few adjustments, where
+//! the table doesn't reflect how the expression is parsed.
+
+use crate::ast::{ExprPosition, ExprPrecedence};
+
+/// The binding level of an expression. A higher value binds stronger.
+type Level = u8;
+const LEVEL_JUMP: Level = 0;
+const LEVEL_ASSIGN: Level = 1;
+const LEVEL_RANGE: Level = 2;
+const LEVEL_OR: Level = 3;
+const LEVEL_AND: Level = 4;
+const LEVEL_CMP: Level = 5;
+const LEVEL_BIT_OR: Level = 6;
+const LEVEL_BIT_XOR: Level = 7;
+const LEVEL_BIT_AND: Level = 8;
+const LEVEL_SHIFT: Level = 9;
+const LEVEL_ADD: Level = 10;
+const LEVEL_MUL: Level = 11;
+const LEVEL_AS: Level = 12;
+const LEVEL_PREFIX: Level = 13;
+const LEVEL_POSTFIX: Level = 14;
+const LEVEL_ATOM: Level = 15;
+
+/// Returns the binding level of the given precedence, or `None` for unstable
+/// precedences.
+fn level(precedence: ExprPrecedence) -> Option<Level> {
+    let level = match precedence {
+        ExprPrecedence::Lit
+        | ExprPrecedence::Block
+        | ExprPrecedence::Ctor
+        | ExprPrecedence::For
+        | ExprPrecedence::Loop
+        | ExprPrecedence::While
+        | ExprPrecedence::Path => LEVEL_ATOM,
+        // All postfix operators bind equally strong, they're evaluated from left
+        // to right. The table only distinguishes them, for historic reasons.
+        ExprPrecedence::Await
+        | ExprPrecedence::Method
+        | ExprPrecedence::Call
+        | ExprPrecedence::If
+        | ExprPrecedence::Let
+        | ExprPrecedence::Match
+        | ExprPrecedence::Field
+        | ExprPrecedence::Fn
+        | ExprPrecedence::Index
+        | ExprPrecedence::Try => LEVEL_POSTFIX,
+        ExprPrecedence::Neg | ExprPrecedence::Not | ExprPrecedence::Deref | ExprPrecedence::Ref => LEVEL_PREFIX,
+        ExprPrecedence::As => LEVEL_AS,
+        ExprPrecedence::Mul | ExprPrecedence::Div | ExprPrecedence::Rem => LEVEL_MUL,
+        ExprPrecedence::Add | ExprPrecedence::Sub => LEVEL_ADD,
+        ExprPrecedence::Shr | ExprPrecedence::Shl => LEVEL_SHIFT,
+        ExprPrecedence::BitAnd => LEVEL_BIT_AND,
+        ExprPrecedence::BitXor => LEVEL_BIT_XOR,
+        ExprPrecedence::BitOr => LEVEL_BIT_OR,
+        ExprPrecedence::Comparison => LEVEL_CMP,
+        ExprPrecedence::And => LEVEL_AND,
+        ExprPrecedence::Or => LEVEL_OR,
+        ExprPrecedence::Range => LEVEL_RANGE,
+        // Assignments are listed with literals, but they bind weaker than any
+        // operator, like `a = b + c`.
+        ExprPrecedence::Assign | ExprPrecedence::AssignOp => LEVEL_ASSIGN,
+        ExprPrecedence::Closure | ExprPrecedence::Break | ExprPrecedence::Return | ExprPrecedence::Continue => {
+            LEVEL_JUMP
+        },
+        ExprPrecedence::Unstable(_) => return None,
+    };
+    Some(level)
+}
+
+pub(crate) fn expr_needs_parens(inner: ExprPrecedence, parent: ExprPrecedence, position: ExprPosition) -> bool {
+    // Unstable precedences can change at any time, parentheses are the safe choice
+    let (Some(inner_level), Some(parent_level)) = (level(inner), level(parent)) else {
+        return true;
+    };
+
+    match position {
+        ExprPosition::Left => {
+            // `a as usize < b` is parsed as the start of generic arguments
+            let as_before_lt = inner_level == LEVEL_AS && matches!(parent_level, LEVEL_CMP | LEVEL_SHIFT);
+            inner_level < parent_level
+                || as_before_lt
+                // Assignments are right associative, comparisons and ranges can't be chained
+                || (inner_level == parent_level && matches!(parent_level, LEVEL_ASSIGN | LEVEL_RANGE | LEVEL_CMP))
+        },
+        ExprPosition::Right => {
+            // All binary operators, except assignments, are left associative
+            inner_level < parent_level || (inner_level == parent_level && parent_level != LEVEL_ASSIGN)
+        },
+        ExprPosition::Prefix | ExprPosition::Postfix => inner_level < parent_level,
+    }
+}
+
+pub(crate) fn render_with_parens(
+    expr_text: &str,
+    inner: ExprPrecedence,
+    parent: ExprPrecedence,
+    position: ExprPosition,
+) -> String {
+    if expr_needs_parens(inner, parent, position) {
+        format!("({expr_text})")
+    } else {
+        expr_text.to_string()
+    }
+}
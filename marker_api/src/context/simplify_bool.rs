@@ -0,0 +1,269 @@
+//! The boolean simplification used by
+//! [`MarkerContext::simplify_bool`](super::MarkerContext::simplify_bool).
+//!
+//! The expression is first converted into a [`BoolExpr`] tree. Negations are then
+//! pushed inwards, using De Morgan's laws, and constant operands are folded. The
+//! result is rendered with the minimal amount of parentheses, required to keep
+//! the meaning of the expression. The parentheses are determined by
+//! [`expr_needs_parens`].
+
+use super::parens::expr_needs_parens;
+use crate::{
+    ast::{BinaryOpKind, ExprKind, ExprPosition, ExprPrecedence},
+    sem::TyKind,
+};
+
+#[derive(Debug, Clone)]
+enum BoolExpr<'ast> {
+    Const(bool),
+    /// An expression, which isn't simplified further, like a variable or call.
+    Atom {
+        snippet: &'ast str,
+        precedence: ExprPrecedence,
+    },
+    Not(Box<BoolExpr<'ast>>),
+    And(Box<BoolExpr<'ast>>, Box<BoolExpr<'ast>>),
+    Or(Box<BoolExpr<'ast>>, Box<BoolExpr<'ast>>),
+    Cmp(Cmp<'ast>),
+}
+
+#[derive(Debug, Clone)]
+struct Cmp<'ast> {
+    left: &'ast str,
+    op: BinaryOpKind,
+    right: &'ast str,
+    /// Orderings can only be inverted, if the type has a total order. For
+    /// floats, `!(a < b)` is not the same as `a >= b`, due to `NaN`.
+    total_order: bool,
+}
+
+/// Returns the simplified form of the given boolean expression, if it can be
+/// simplified.
+pub(crate) fn simplify_bool(expr: ExprKind<'_>) -> Option<String> {
+    let mut cx = Simplifier { changed: false };
+    let bool_expr = cx.convert(expr)?;
+    let simplified = cx.simplify(bool_expr);
+    cx.changed.then(|| render(&simplified, None))
+}
+
+struct Simplifier {
+    /// Set to `true`, once a rule has been applied, which changes the expression.
+    changed: bool,
+}
+
+impl Simplifier {
+    fn convert<'ast>(&mut self, expr: ExprKind<'ast>) -> Option<BoolExpr<'ast>> {
+        let bool_expr = match expr {
+            ExprKind::BoolLit(lit) => BoolExpr::Const(lit.value()),
+            ExprKind::UnaryOp(op) if op.is_logical_not() => BoolExpr::Not(Box::new(self.convert(op.expr())?)),
+            ExprKind::BinaryOp(op) => match op.kind() {
+                BinaryOpKind::And => {
+                    BoolExpr::And(Box::new(self.convert(op.left())?), Box::new(self.convert(op.right())?))
+                },
+                BinaryOpKind::Or => {
+                    BoolExpr::Or(Box::new(self.convert(op.left())?), Box::new(self.convert(op.right())?))
+                },
+                BinaryOpKind::Eq | BinaryOpKind::NotEq if op.is_bool_literal_comparison() => {
+                    let (value, other) = match (op.left(), op.right()) {
+                        (ExprKind::BoolLit(lit), other) | (other, ExprKind::BoolLit(lit)) => (lit.value(), other),
+                        _ => unreachable!("one operand is a bool literal"),
+                    };
+                    let other = self.convert(other)?;
+                    self.changed = true;
+                    // `x == true` and `x != false` are the same as `x`
+                    if value == matches!(op.kind(), BinaryOpKind::Eq) {
+                        other
+                    } else {
+                        BoolExpr::Not(Box::new(other))
+                    }
+                },
+                BinaryOpKind::Eq
+                | BinaryOpKind::NotEq
+                | BinaryOpKind::Greater
+                | BinaryOpKind::GreaterEq
+                | BinaryOpKind::Lesser
+                | BinaryOpKind::LesserEq => BoolExpr::Cmp(Cmp {
+                    left: op.left().span().snippet()?,
+                    op: op.kind(),
+                    right: op.right().span().snippet()?,
+                    total_order: has_total_order(op.left().ty()),
+                }),
+                _ => atom(expr)?,
+            },
+            _ => atom(expr)?,
+        };
+        Some(bool_expr)
+    }
+
+    fn simplify<'ast>(&mut self, expr: BoolExpr<'ast>) -> BoolExpr<'ast> {
+        match expr {
+            BoolExpr::Not(inner) => {
+                let inner = self.simplify(*inner);
+                self.negate(inner)
+            },
+            BoolExpr::And(left, right) => match (self.simplify(*left), self.simplify(*right)) {
+                (BoolExpr::Const(true), other) | (other, BoolExpr::Const(true)) => {
+                    self.changed = true;
+                    other
+                },
+                // The right operand is never evaluated. `x && false` is kept,
+                // since `x` might have side effects.
+                (BoolExpr::Const(false), _) => {
+                    self.changed = true;
+                    BoolExpr::Const(false)
+                },
+                (left, right) => BoolExpr::And(Box::new(left), Box::new(right)),
+            },
+            BoolExpr::Or(left, right) => match (self.simplify(*left), self.simplify(*right)) {
+                (BoolExpr::Const(false), other) | (other, BoolExpr::Const(false)) => {
+                    self.changed = true;
+                    other
+                },
+                (BoolExpr::Const(true), _) => {
+                    self.changed = true;
+                    BoolExpr::Const(true)
+                },
+                (left, right) => BoolExpr::Or(Box::new(left), Box::new(right)),
+            },
+            BoolExpr::Const(_) | BoolExpr::Atom { .. } | BoolExpr::Cmp(_) => expr,
+        }
+    }
+
+    /// Negates the given simplified expression.
+    fn negate<'ast>(&mut self, expr: BoolExpr<'ast>) -> BoolExpr<'ast> {
+        match expr {
+            BoolExpr::Const(value) => {
+                self.changed = true;
+                BoolExpr::Const(!value)
+            },
+            BoolExpr::Not(inner) => {
+                self.changed = true;
+                *inner
+            },
+            BoolExpr::And(left, right) => {
+                self.changed = true;
+                let left = self.negate(*left);
+                let right = self.negate(*right);
+                BoolExpr::Or(Box::new(left), Box::new(right))
+            },
+            BoolExpr::Or(left, right) => {
+                self.changed = true;
+                let left = self.negate(*left);
+                let right = self.negate(*right);
+                BoolExpr::And(Box::new(left), Box::new(right))
+            },
+            BoolExpr::Cmp(cmp) => match inverted_cmp_op(&cmp) {
+                Some(op) => {
+                    self.changed = true;
+                    BoolExpr::Cmp(Cmp { op, ..cmp })
+                },
+                None => BoolExpr::Not(Box::new(BoolExpr::Cmp(cmp))),
+            },
+            BoolExpr::Atom { .. } => BoolExpr::Not(Box::new(expr)),
+        }
+    }
+}
+
+fn atom(expr: ExprKind<'_>) -> Option<BoolExpr<'_>> {
+    let snippet = expr.span().snippet()?;
+    // The parentheses of the snippet make it bind like a literal
+    let precedence = if is_parenthesized(snippet) {
+        ExprPrecedence::Lit
+    } else {
+        expr.precedence()
+    };
+    Some(BoolExpr::Atom { snippet, precedence })
+}
+
+fn has_total_order(ty: TyKind<'_>) -> bool {
+    match ty {
+        TyKind::Bool(_) => true,
+        TyKind::Num(num) => num.is_integer(),
+        TyKind::Text(text) => text.is_char(),
+        _ => false,
+    }
+}
+
+fn inverted_cmp_op(cmp: &Cmp<'_>) -> Option<BinaryOpKind> {
+    let op = match cmp.op {
+        BinaryOpKind::Eq => BinaryOpKind::NotEq,
+        BinaryOpKind::NotEq => BinaryOpKind::Eq,
+        _ if !cmp.total_order => return None,
+        BinaryOpKind::Greater => BinaryOpKind::LesserEq,
+        BinaryOpKind::GreaterEq => BinaryOpKind::Lesser,
+        BinaryOpKind::Lesser => BinaryOpKind::GreaterEq,
+        BinaryOpKind::LesserEq => BinaryOpKind::Greater,
+        _ => return None,
+    };
+    Some(op)
+}
+
+/// Returns `true`, if the snippet is wrapped in a single pair of parentheses,
+/// like `(a || b)`, but not `(a) || (b)`.
+fn is_parenthesized(snippet: &str) -> bool {
+    if !snippet.starts_with('(') || !snippet.ends_with(')') {
+        return false;
+    }
+
+    let mut depth = 0;
+    for (index, c) in snippet.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return index == snippet.len() - 1;
+                }
+            },
+            _ => {},
+        }
+    }
+    false
+}
+
+/// Renders the expression. The `parent` is the precedence and position of the
+/// operator, which uses the expression as an operand, if there is one.
+fn render(expr: &BoolExpr<'_>, parent: Option<(ExprPrecedence, ExprPosition)>) -> String {
+    let (text, precedence) = match expr {
+        BoolExpr::Const(value) => (value.to_string(), ExprPrecedence::Lit),
+        BoolExpr::Atom { snippet, precedence } => ((*snippet).to_string(), *precedence),
+        BoolExpr::Not(inner) => (
+            format!("!{}", render(inner, Some((ExprPrecedence::Not, ExprPosition::Prefix)))),
+            ExprPrecedence::Not,
+        ),
+        BoolExpr::And(left, right) => (
+            render_binary(left, "&&", right, ExprPrecedence::And),
+            ExprPrecedence::And,
+        ),
+        BoolExpr::Or(left, right) => (render_binary(left, "||", right, ExprPrecedence::Or), ExprPrecedence::Or),
+        BoolExpr::Cmp(cmp) => (
+            format!("{} {} {}", cmp.left, cmp_op_str(cmp.op), cmp.right),
+            ExprPrecedence::Comparison,
+        ),
+    };
+
+    match parent {
+        Some((parent, position)) if expr_needs_parens(precedence, parent, position) => format!("({text})"),
+        _ => text,
+    }
+}
+
+fn render_binary(left: &BoolExpr<'_>, op: &str, right: &BoolExpr<'_>, precedence: ExprPrecedence) -> String {
+    format!(
+        "{} {op} {}",
+        render(left, Some((precedence, ExprPosition::Left))),
+        render(right, Some((precedence, ExprPosition::Right)))
+    )
+}
+
+fn cmp_op_str(op: BinaryOpKind) -> &'static str {
+    match op {
+        BinaryOpKind::Eq => "==",
+        BinaryOpKind::NotEq => "!=",
+        BinaryOpKind::Greater => ">",
+        BinaryOpKind::GreaterEq => ">=",
+        BinaryOpKind::Lesser => "<",
+        BinaryOpKind::LesserEq => "<=",
+        _ => unreachable!("only comparison operators are stored in `Cmp`"),
+    }
+}
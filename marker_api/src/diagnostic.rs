@@ -1,12 +1,28 @@
 //! This module is responsible for the construction of diagnostic messages. The
 //! [`DiagnosticBuilder`] is the public stable interface, to construct messages.
+//!
+//! A builder is created by [`MarkerContext::emit_lint`] and emitted, when it's
+//! dropped. Additional parts are added in [`DiagnosticBuilder::decorate`]:
+//!
+//! ```ignore
+//! cx.emit_lint(LINT, expr, "this comparison can be simplified")
+//!     .decorate(|diag| {
+//!         diag.span_note("the value is defined here", def_span);
+//!         diag.span_suggestion("try", expr, "x", Applicability::MachineApplicable);
+//!         diag.help("comparisons with `true` are redundant");
+//!     });
+//! ```
+//!
+//! Suggestions with [`Applicability::MachineApplicable`] are applied by
+//! `cargo marker --fix`. The [`SuggestionBuilder`] provides a shorter way to
+//! create suggestions, which determines the applicability automatically.
 
 use std::fmt::Debug;
 
 use crate::{
-    common::{HasNodeId, NodeId},
+    common::{HasNodeId, Level, NodeId},
     context::{with_cx, MarkerContext},
-    ffi::{FfiSlice, FfiStr},
+    ffi::{FfiOption, FfiSlice, FfiStr},
     lint::Lint,
     prelude::{HasSpan, Span},
 };
@@ -26,7 +42,8 @@ struct DiagnosticBuilderInner<'ast> {
     node: NodeId,
     msg: String,
     span: Span<'ast>,
-    parts: Vec<DiagnosticPart<String, Span<'ast>>>,
+    level: Option<Level>,
+    parts: Vec<OwnedDiagnosticPart<'ast>>,
 }
 
 impl<'ast> DiagnosticBuilder<'ast> {
@@ -42,6 +59,7 @@ impl<'ast> DiagnosticBuilder<'ast> {
                 msg,
                 node,
                 span,
+                level: None,
                 parts: vec![],
             }),
         }
@@ -68,6 +86,41 @@ impl<'ast> DiagnosticBuilder<'ast> {
         self
     }
 
+    /// This function overrides the [`Level`] of the created diagnostic. By default,
+    /// the diagnostic is emitted with the level, that the user configured for the
+    /// [`Lint`] at the [`EmissionNode`]. This can be used to scale the severity of
+    /// a diagnostic, based on the linted code. For example, a lint could warn about
+    /// long functions, but emit an error for very long ones:
+    ///
+    /// ```
+    /// # use marker_api::prelude::*;
+    /// # use marker_api::common::Level;
+    /// # marker_api::declare_lint!{
+    /// #     /// Dummy
+    /// #     LINT,
+    /// #     Warn,
+    /// # }
+    /// # fn value_provider<'ast>(cx: &MarkerContext<'ast>, node: ExprKind<'ast>, lines: usize) {
+    ///     cx.emit_lint(LINT, node, "this function is too long").decorate(|diag| {
+    ///         if lines > 200 {
+    ///             diag.with_level(Level::Deny);
+    ///         }
+    ///     });
+    /// # }
+    /// ```
+    ///
+    /// The override only applies if the lint is enabled at the [`EmissionNode`].
+    /// A lint, which has been allowed by the user, for example via `#[allow]`, will
+    /// still be suppressed. The level is also limited by lint caps, like rustc's
+    /// `--cap-lints` argument.
+    pub fn with_level(&mut self, level: Level) -> &mut Self {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.level = Some(level);
+        }
+
+        self
+    }
+
     /// This function adds a note to the diagnostic message. Notes are intended
     /// to provide additional context or explanations about the diagnostic.
     ///
@@ -111,6 +164,7 @@ impl<'ast> DiagnosticBuilder<'ast> {
     /// ```
     ///
     /// [`Self::note`] can be used to add text notes without a span.
+    /// [`Self::span_label`] can be used to label a [`Span`] in the main snippet.
     pub fn span_note(&mut self, msg: impl Into<String>, span: impl HasSpan<'ast>) -> &mut Self {
         if let Some(inner) = self.inner.as_mut() {
             inner.parts.push(DiagnosticPart::NoteSpan {
@@ -122,6 +176,34 @@ impl<'ast> DiagnosticBuilder<'ast> {
         self
     }
 
+    /// This function adds a label to a secondary [`Span`] of the diagnostic.
+    /// Labels are intended to reference other relevant locations, like the first
+    /// definition of a duplicated item. The labeled span can be in a different
+    /// file than the main span.
+    ///
+    /// From rustc a labeled span would be displayed like this:
+    /// ```text
+    ///  warning: <lint message>
+    ///  --> path/file.rs:2:1
+    ///   |
+    /// 1 | context
+    ///   | ------- <text>             <-- The label added by this function
+    /// 2 | expression
+    ///   | ^^^^^^^^^^
+    /// ```
+    ///
+    /// [`Self::span_note`] can be used to add a separate note for a [`Span`].
+    pub fn span_label(&mut self, msg: impl Into<String>, span: impl HasSpan<'ast>) -> &mut Self {
+        if let Some(inner) = self.inner.as_mut() {
+            inner.parts.push(DiagnosticPart::Label {
+                msg: msg.into(),
+                span: span.span().clone(),
+            });
+        }
+
+        self
+    }
+
     /// This function adds a help message. Help messages are intended to provide
     /// additional information about how the issue can be solved.
     ///
@@ -213,6 +295,52 @@ impl<'ast> DiagnosticBuilder<'ast> {
         self
     }
 
+    /// This function adds a help message with a suggestion, which consists of
+    /// multiple parts. Each part replaces the code of its [`Span`]. The parts
+    /// belong together and should only be applied at once. They can be located
+    /// in different files, for example, to rename an item and all of its uses.
+    ///
+    /// The parts should not overlap. Tools like `cargo marker --fix` reject
+    /// suggestions with overlapping parts. A suggestion without parts is ignored.
+    ///
+    /// ```ignore
+    /// cx.emit_lint(LINT, node, "<text>").decorate(|diag| {
+    ///     diag.multipart_suggestion(
+    ///         "rename the function",
+    ///         [(def_span, "new_name"), (use_span, "new_name")],
+    ///         Applicability::MachineApplicable,
+    ///     );
+    /// });
+    /// ```
+    ///
+    /// Rustc only displays multipart suggestions, if all parts are located in
+    /// the same file. The suggestion is still included in the JSON output.
+    pub fn multipart_suggestion<S: HasSpan<'ast>, T: Into<String>>(
+        &mut self,
+        msg: impl Into<String>,
+        parts: impl IntoIterator<Item = (S, T)>,
+        app: Applicability,
+    ) -> &mut Self {
+        if let Some(inner) = self.inner.as_mut() {
+            let parts: Vec<_> = parts
+                .into_iter()
+                .map(|(span, sugg)| SuggestionPart {
+                    span: span.span().clone(),
+                    sugg: sugg.into(),
+                })
+                .collect();
+            if !parts.is_empty() {
+                inner.parts.push(DiagnosticPart::MultipartSuggestion {
+                    msg: msg.into(),
+                    parts,
+                    app,
+                });
+            }
+        }
+
+        self
+    }
+
     /// The `decorate` parameter accepts a closure, that is only executed, when the
     /// lint will actually be emitted in the end. Having them in a conditional closure
     /// will speedup the linting process if the lint is suppressed.
@@ -275,12 +403,29 @@ impl<'ast> DiagnosticBuilder<'ast> {
 
     pub(crate) fn emit<'builder>(&'builder self, cx: &MarkerContext<'ast>) {
         if let Some(inner) = &self.inner {
-            let parts: Vec<_> = inner.parts.iter().map(DiagnosticPart::to_ffi_part).collect();
+            // The parts of multipart suggestions have to outlive the FFI parts
+            let sugg_parts: Vec<Vec<_>> = inner
+                .parts
+                .iter()
+                .map(|part| match part {
+                    DiagnosticPart::MultipartSuggestion { parts, .. } => {
+                        parts.iter().map(SuggestionPart::to_ffi_part).collect()
+                    },
+                    _ => vec![],
+                })
+                .collect();
+            let parts: Vec<_> = inner
+                .parts
+                .iter()
+                .zip(&sugg_parts)
+                .map(|(part, sugg_parts)| part.to_ffi_part(sugg_parts))
+                .collect();
             let diag = Diagnostic {
                 lint: inner.lint,
                 msg: inner.msg.as_str().into(),
                 node: inner.node,
                 span: &inner.span,
+                level: inner.level.into(),
                 parts: parts.as_slice().into(),
             };
             cx.emit_diagnostic(&diag);
@@ -294,6 +439,133 @@ impl<'ast> Drop for DiagnosticBuilder<'ast> {
     }
 }
 
+/// This builder bundles the common operations to create a suggestion. It's
+/// created by [`MarkerContext::suggest_replace`], [`MarkerContext::suggest_insert_before`]
+/// or [`MarkerContext::suggest_insert_after`] and added to a diagnostic with
+/// [`SuggestionBuilder::emit`]:
+///
+/// ```ignore
+/// cx.emit_lint(LINT, expr, "this comparison can be simplified")
+///     .decorate(|diag| {
+///         let snippet = operand.span().snippet_or("..");
+///         cx.suggest_replace(expr, format!("!{snippet}"))
+///             .validate_expr()
+///             .emit(diag);
+///     });
+/// ```
+///
+/// Additional parts can be added with [`SuggestionBuilder::replace`] and the
+/// insertion methods. The parts are then emitted as a single
+/// [multipart suggestion](DiagnosticBuilder::multipart_suggestion).
+///
+/// The [`Applicability`] is determined automatically, unless it's set with
+/// [`SuggestionBuilder::applicability`]. Suggestions are [`MachineApplicable`],
+/// if all parts are located outside of macro expansions and all validated
+/// replacements could be parsed. Otherwise, they're [`MaybeIncorrect`].
+/// Snippets with placeholders should use [`Span::snippet_with_applicability`]
+/// and pass the resulting applicability to the builder.
+///
+/// [`MachineApplicable`]: Applicability::MachineApplicable
+/// [`MaybeIncorrect`]: Applicability::MaybeIncorrect
+#[derive(Debug)]
+pub struct SuggestionBuilder<'ast> {
+    msg: String,
+    parts: Vec<SuggestionPart<String, Span<'ast>>>,
+    app: Option<Applicability>,
+    validate_expr: bool,
+}
+
+impl<'ast> SuggestionBuilder<'ast> {
+    pub(crate) fn new(span: Span<'ast>, sugg: String) -> Self {
+        Self {
+            msg: "try".to_string(),
+            parts: vec![SuggestionPart { span, sugg }],
+            app: None,
+            validate_expr: false,
+        }
+    }
+
+    /// Sets the message of the suggestion. The default message is `try`.
+    #[must_use]
+    pub fn msg(mut self, msg: impl Into<String>) -> Self {
+        self.msg = msg.into();
+        self
+    }
+
+    /// Sets the [`Applicability`] of the suggestion, instead of determining it
+    /// automatically.
+    #[must_use]
+    pub fn applicability(mut self, app: Applicability) -> Self {
+        self.app = Some(app);
+        self
+    }
+
+    /// Adds a part, which replaces the code of the given span.
+    #[must_use]
+    pub fn replace(mut self, span: impl HasSpan<'ast>, sugg: impl Into<String>) -> Self {
+        self.parts.push(SuggestionPart {
+            span: span.span().clone(),
+            sugg: sugg.into(),
+        });
+        self
+    }
+
+    /// Adds a part, which inserts the text directly before the given span.
+    #[must_use]
+    pub fn insert_before(self, span: impl HasSpan<'ast>, text: impl Into<String>) -> Self {
+        let span = span.span();
+        self.replace(span.with_end(span.start()), text)
+    }
+
+    /// Adds a part, which inserts the text directly after the given span.
+    #[must_use]
+    pub fn insert_after(self, span: impl HasSpan<'ast>, text: impl Into<String>) -> Self {
+        let span = span.span();
+        self.replace(span.with_start(span.end()), text)
+    }
+
+    /// Checks, that the replacements of all parts can be parsed as expressions.
+    /// The suggestion is emitted as [`MaybeIncorrect`](Applicability::MaybeIncorrect)
+    /// otherwise. This should only be used, if every part replaces an entire
+    /// expression.
+    #[must_use]
+    pub fn validate_expr(mut self) -> Self {
+        self.validate_expr = true;
+        self
+    }
+
+    /// Returns the [`Applicability`], which will be used for this suggestion.
+    pub fn resolved_applicability(&self) -> Applicability {
+        if let Some(app) = self.app {
+            return app;
+        }
+
+        let from_expansion = self.parts.iter().any(|part| part.span.is_from_expansion());
+        let invalid =
+            self.validate_expr && with_cx(self, |cx| self.parts.iter().any(|part| !cx.parses_as_expr(&part.sugg)));
+        if from_expansion || invalid {
+            Applicability::MaybeIncorrect
+        } else {
+            Applicability::MachineApplicable
+        }
+    }
+
+    /// Adds the suggestion to the given diagnostic.
+    pub fn emit(self, diag: &mut DiagnosticBuilder<'ast>) {
+        if diag.inner.is_none() {
+            return;
+        }
+
+        let app = self.resolved_applicability();
+        match <[_; 1]>::try_from(self.parts) {
+            Ok([part]) => diag.span_suggestion(self.msg, &part.span, part.sugg, app),
+            Err(parts) => {
+                diag.multipart_suggestion(self.msg, parts.into_iter().map(|part| (part.span, part.sugg)), app)
+            },
+        };
+    }
+}
+
 /// Every lint emission is bound to a specific node. The node is used to
 /// determine the lint level and [`Span`] that is used for the main diagnostic
 /// message.
@@ -305,7 +577,7 @@ impl<'ast, N: Debug + HasSpan<'ast> + HasNodeId> EmissionNode<'ast> for N {}
 #[non_exhaustive]
 #[derive(Debug, Clone)]
 #[cfg_attr(feature = "driver-api", visibility::make(pub))]
-pub(crate) enum DiagnosticPart<St, Sp> {
+pub(crate) enum DiagnosticPart<St, Sp, Pa> {
     Help {
         msg: St,
     },
@@ -326,10 +598,26 @@ pub(crate) enum DiagnosticPart<St, Sp> {
         sugg: St,
         app: Applicability,
     },
+    Label {
+        msg: St,
+        span: Sp,
+    },
+    MultipartSuggestion {
+        msg: St,
+        parts: Pa,
+        app: Applicability,
+    },
 }
 
-impl<'ast> DiagnosticPart<String, Span<'ast>> {
-    fn to_ffi_part<'part>(&'part self) -> DiagnosticPart<FfiStr<'part>, &'part Span<'ast>> {
+type OwnedDiagnosticPart<'ast> = DiagnosticPart<String, Span<'ast>, Vec<SuggestionPart<String, Span<'ast>>>>;
+type FfiDiagnosticPart<'part, 'ast> =
+    DiagnosticPart<FfiStr<'part>, &'part Span<'ast>, FfiSlice<'part, SuggestionPart<FfiStr<'part>, &'part Span<'ast>>>>;
+
+impl<'ast> OwnedDiagnosticPart<'ast> {
+    fn to_ffi_part<'part>(
+        &'part self,
+        sugg_parts: &'part [SuggestionPart<FfiStr<'part>, &'part Span<'ast>>],
+    ) -> FfiDiagnosticPart<'part, 'ast> {
         match self {
             DiagnosticPart::Help { msg } => DiagnosticPart::Help { msg: msg.into() },
             DiagnosticPart::HelpSpan { msg, span } => DiagnosticPart::HelpSpan { msg: msg.into(), span },
@@ -341,6 +629,30 @@ impl<'ast> DiagnosticPart<String, Span<'ast>> {
                 sugg: sugg.into(),
                 app: *app,
             },
+            DiagnosticPart::Label { msg, span } => DiagnosticPart::Label { msg: msg.into(), span },
+            DiagnosticPart::MultipartSuggestion { msg, app, .. } => DiagnosticPart::MultipartSuggestion {
+                msg: msg.into(),
+                parts: sugg_parts.into(),
+                app: *app,
+            },
+        }
+    }
+}
+
+/// A single replacement of a [`DiagnosticBuilder::multipart_suggestion`].
+#[repr(C)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "driver-api", visibility::make(pub))]
+pub(crate) struct SuggestionPart<St, Sp> {
+    pub span: Sp,
+    pub sugg: St,
+}
+
+impl<'ast> SuggestionPart<String, Span<'ast>> {
+    fn to_ffi_part<'part>(&'part self) -> SuggestionPart<FfiStr<'part>, &'part Span<'ast>> {
+        SuggestionPart {
+            span: &self.span,
+            sugg: self.sugg.as_str().into(),
         }
     }
 }
@@ -386,7 +698,8 @@ pub(crate) struct Diagnostic<'builder, 'ast> {
     pub msg: FfiStr<'builder>,
     pub node: NodeId,
     pub span: &'builder Span<'ast>,
-    pub parts: FfiSlice<'builder, DiagnosticPart<FfiStr<'builder>, &'builder Span<'ast>>>,
+    pub level: FfiOption<Level>,
+    pub parts: FfiSlice<'builder, FfiDiagnosticPart<'builder, 'ast>>,
 }
 
 impl<'builder, 'ast> Diagnostic<'builder, 'ast> {
@@ -1,7 +1,11 @@
 //! A module responsible for generating and exposing an interface from lint crates.
 //! [`export_lint_pass`](crate::export_lint_pass) is the main macro, from this module.
 
-use crate::{context::MarkerContext, ffi::FfiSlice, lint::Lint};
+use crate::{
+    context::MarkerContext,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    lint::Lint,
+};
 
 /// **!Unstable!**
 /// This struct is used to connect lint crates to drivers.
@@ -9,6 +13,10 @@ use crate::{context::MarkerContext, ffi::FfiSlice, lint::Lint};
 #[doc(hidden)]
 pub struct LintCrateBindings {
     pub set_ast_context: for<'ast> extern "C" fn(cx: &'ast MarkerContext<'ast>),
+    /// Passes the JSON configuration of the lint crate to it. This has to be
+    /// called before any other lint pass function. The returned value contains
+    /// an error message, if the configuration couldn't be deserialized.
+    pub set_config: for<'a> extern "C" fn(config: FfiStr<'a>) -> FfiOption<FfiStr<'static>>,
 
     // lint pass functions
     pub info: for<'ast> extern "C" fn() -> LintPassInfo,
@@ -18,7 +26,10 @@ pub struct LintCrateBindings {
     pub check_variant: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::EnumVariant<'ast>),
     pub check_body: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::Body<'ast>),
     pub check_stmt: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, crate::ast::StmtKind<'ast>),
+    pub check_local: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::LetStmt<'ast>),
     pub check_expr: for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, crate::ast::ExprKind<'ast>),
+    pub check_generic_bound:
+        for<'ast> extern "C" fn(&'ast MarkerContext<'ast>, &'ast crate::ast::TyParamBound<'ast>, crate::common::ItemId),
 }
 
 /// This macro marks the given struct as the main [`LintPass`](`crate::LintPass`)
@@ -41,6 +52,53 @@ pub struct LintCrateBindings {
 /// marker_api::export_lint_pass!(LintPassCustomValue, LintPassCustomValue::new(/* ... */));
 /// ```
 ///
+/// **Struct initialized with a configuration:**
+///
+/// Lint crates can accept a configuration from the `Cargo.toml` file of the
+/// linted workspace. The configuration type has to implement [`Default`] and
+/// `serde::Deserialize`. Marker deserializes the `config` table of the lint
+/// crate entry into it and passes it to the given constructor. The default
+/// value is used, if no configuration was specified. This requires the
+/// `config` feature of `marker_api`.
+#[cfg_attr(feature = "config", doc = "```")]
+#[cfg_attr(not(feature = "config"), doc = "```ignore")]
+/// # use marker_api::{LintPass, LintPassInfo, LintPassInfoBuilder};
+/// #[derive(Default, serde::Deserialize)]
+/// struct Config {
+///     max_params: usize,
+/// }
+///
+/// struct LintPassWithConfig {
+///     config: Config,
+/// }
+///
+/// impl LintPassWithConfig {
+///     fn new(config: Config) -> Self {
+///         Self { config }
+///     }
+/// }
+/// # impl LintPass for LintPassWithConfig {
+/// #     fn info(&self) -> LintPassInfo {
+/// #         LintPassInfoBuilder::new(Box::new([])).build()
+/// #     }
+/// # }
+///
+/// marker_api::export_lint_pass!(LintPassWithConfig, config = Config);
+/// # fn main() {}
+#[doc = "```"]
+///
+/// A custom constructor can be passed as the third argument, like
+/// `export_lint_pass!(LintPassWithConfig, config = Config, LintPassWithConfig::with_config)`.
+///
+/// The configuration would then be specified like this:
+/// ```toml
+/// [workspace.metadata.marker.lints.lint_crate]
+/// path = "./lint_crate"
+///
+/// [workspace.metadata.marker.lints.lint_crate.config]
+/// max_params = 5
+/// ```
+///
 /// This macro will create some hidden items prefixed with two underscores. These
 /// are unstable and can change in the future.
 ///
@@ -52,7 +110,36 @@ macro_rules! export_lint_pass {
     ($pass_ty:ident) => {
         $crate::export_lint_pass!($pass_ty, $pass_ty::default());
     };
+    ($pass_ty:ident, config = $config_ty:ty) => {
+        $crate::export_lint_pass!($pass_ty, config = $config_ty, $pass_ty::new);
+    };
+    ($pass_ty:ident, config = $config_ty:ty, $pass_new:expr) => {
+        thread_local! {
+            #[doc(hidden)]
+            static __MARKER_CONFIG: std::cell::RefCell<Option<$config_ty>> = std::cell::RefCell::new(None);
+        }
+
+        #[doc(hidden)]
+        fn __marker_set_config(config: &str) -> Result<(), String> {
+            let config: $config_ty = $crate::__deserialize_config!(config)?;
+            __MARKER_CONFIG.with(|state| *state.borrow_mut() = Some(config));
+            Ok(())
+        }
+
+        $crate::export_lint_pass!(
+            @export $pass_ty,
+            ($pass_new)(__MARKER_CONFIG.with(|state| state.take()).unwrap_or_default())
+        );
+    };
     ($pass_ty:ident, $pass_init:expr) => {
+        #[doc(hidden)]
+        fn __marker_set_config(_config: &str) -> Result<(), String> {
+            Err("this lint crate doesn't accept a configuration".to_string())
+        }
+
+        $crate::export_lint_pass!(@export $pass_ty, $pass_init);
+    };
+    (@export $pass_ty:ident, $pass_init:expr) => {
         thread_local! {
             #[doc(hidden)]
             static __MARKER_STATE: std::cell::RefCell<$pass_ty> = std::cell::RefCell::new($pass_init);
@@ -76,6 +163,18 @@ macro_rules! export_lint_pass {
                 extern "C" fn set_ast_context<'ast>(cx: &'ast $crate::MarkerContext<'ast>) {
                     $crate::context::set_ast_cx(cx);
                 }
+                extern "C" fn set_config(
+                    config: $crate::ffi::FfiStr<'_>,
+                ) -> $crate::ffi::FfiOption<$crate::ffi::FfiStr<'static>> {
+                    match super::__marker_set_config(config.get()) {
+                        Ok(()) => $crate::ffi::FfiOption::None,
+                        Err(err) => {
+                            // The error is leaked, since it has to outlive this call
+                            let err: &'static str = Box::leak(err.into_boxed_str());
+                            $crate::ffi::FfiOption::Some(err.into())
+                        },
+                    }
+                }
                 extern "C" fn info() -> $crate::LintPassInfo {
                     super::__MARKER_STATE.with(|state| state.borrow_mut().info())
                 }
@@ -115,15 +214,29 @@ macro_rules! export_lint_pass {
                 ) {
                     super::__MARKER_STATE.with(|state| state.borrow_mut().check_stmt(cx, stmt));
                 }
+                extern "C" fn check_local<'ast>(
+                    cx: &'ast $crate::MarkerContext<'ast>,
+                    local: &'ast $crate::ast::LetStmt<'ast>,
+                ) {
+                    super::__MARKER_STATE.with(|state| state.borrow_mut().check_local(cx, local));
+                }
                 extern "C" fn check_expr<'ast>(
                     cx: &'ast $crate::MarkerContext<'ast>,
                     expr: $crate::ast::ExprKind<'ast>,
                 ) {
                     super::__MARKER_STATE.with(|state| state.borrow_mut().check_expr(cx, expr));
                 }
+                extern "C" fn check_generic_bound<'ast>(
+                    cx: &'ast $crate::MarkerContext<'ast>,
+                    bound: &'ast $crate::ast::TyParamBound<'ast>,
+                    owner: $crate::common::ItemId,
+                ) {
+                    super::__MARKER_STATE.with(|state| state.borrow_mut().check_generic_bound(cx, bound, owner));
+                }
 
                 $crate::LintCrateBindings {
                     set_ast_context,
+                    set_config,
                     info,
                     check_crate,
                     check_item,
@@ -131,16 +244,42 @@ macro_rules! export_lint_pass {
                     check_variant,
                     check_body,
                     check_stmt,
+                    check_local,
                     check_expr,
+                    check_generic_bound,
                 }
             }
         }
     };
 }
 
+/// Deserializes the configuration of a lint crate for [`export_lint_pass`].
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "config")]
+macro_rules! __deserialize_config {
+    ($config:expr) => {
+        $crate::__private::serde_json::from_str($config).map_err(|err| err.to_string())
+    };
+}
+
+/// Fallback of [`__deserialize_config`], which explains how the `config`
+/// argument of [`export_lint_pass`] can be enabled.
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "config"))]
+macro_rules! __deserialize_config {
+    ($config:expr) => {
+        compile_error!(
+            "`export_lint_pass!` only accepts the `config` argument, if the `config` feature of `marker_api` is enabled"
+        )
+    };
+}
+
 #[derive(Debug)]
 pub struct LintPassInfoBuilder {
     lints: &'static [&'static Lint],
+    supersessions: Vec<LintSupersession>,
 }
 
 impl LintPassInfoBuilder {
@@ -154,17 +293,59 @@ impl LintPassInfoBuilder {
             // is called. Ideally, it would be cool to just store the `Box` directly but
             // that is sadly not possible due to ABI constraints
             lints: Box::leak(lints),
+            supersessions: vec![],
         }
     }
 
+    /// Declares that `lint` supersedes the `superseded` lint. Diagnostics of the
+    /// `superseded` lint are dropped, if `lint` has been emitted at the same node.
+    ///
+    /// This is useful for lints with overlapping scopes, where both lints would
+    /// otherwise emit conflicting suggestions for the same node. The order in
+    /// which the lints are emitted doesn't matter. Diagnostics of `lint` which
+    /// have been allowed or expected don't suppress the `superseded` lint.
+    ///
+    /// ```ignore
+    /// LintPassInfoBuilder::new(Box::new([MANUAL_SWAP, NEEDLESS_TEMP]))
+    ///     .supersedes(MANUAL_SWAP, NEEDLESS_TEMP)
+    ///     .build()
+    /// ```
+    #[must_use]
+    pub fn supersedes(mut self, lint: &'static Lint, superseded: &'static Lint) -> Self {
+        self.supersessions.push(LintSupersession { lint, superseded });
+        self
+    }
+
     /// This method builds the [`LintPassInfo`], ready for consumption.
     pub fn build(self) -> LintPassInfo {
         LintPassInfo {
             lints: self.lints.into(),
+            supersessions: (&*Box::leak(self.supersessions.into_boxed_slice())).into(),
         }
     }
 }
 
+/// A relation between two lints, declared by [`LintPassInfoBuilder::supersedes`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct LintSupersession {
+    lint: &'static Lint,
+    superseded: &'static Lint,
+}
+
+impl LintSupersession {
+    /// The lint, which suppresses the [`superseded`](Self::superseded) lint.
+    pub fn lint(&self) -> &'static Lint {
+        self.lint
+    }
+
+    /// The lint, which is suppressed at nodes where [`lint`](Self::lint) has
+    /// been emitted.
+    pub fn superseded(&self) -> &'static Lint {
+        self.superseded
+    }
+}
+
 /// This struct provides basic information required by the driver. It can also
 /// be used to provide additional information. The struct is constructed using
 /// the [`LintPassInfoBuilder`].
@@ -176,6 +357,7 @@ impl LintPassInfoBuilder {
 #[non_exhaustive]
 pub struct LintPassInfo {
     lints: FfiSlice<'static, &'static Lint>,
+    supersessions: FfiSlice<'static, LintSupersession>,
 }
 
 #[cfg(feature = "driver-api")]
@@ -183,4 +365,72 @@ impl LintPassInfo {
     pub fn lints(&self) -> &[&'static Lint] {
         self.lints.get()
     }
+
+    pub fn supersessions(&self) -> &[LintSupersession] {
+        self.supersessions.get()
+    }
+}
+
+// Lints of the macro expansion are only reported inside this crate
+#[cfg(all(test, feature = "config"))]
+#[allow(improper_ctypes, improper_ctypes_definitions, unused_imports)]
+mod test {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Clone, Copy, Default, serde::Deserialize)]
+    struct TestConfig {
+        max_params: usize,
+    }
+
+    thread_local! {
+        static MAX_PARAMS: Cell<Option<usize>> = Cell::new(None);
+    }
+
+    struct TestPass;
+
+    impl TestPass {
+        fn with_config(config: TestConfig) -> Self {
+            MAX_PARAMS.with(|max_params| max_params.set(Some(config.max_params)));
+            Self
+        }
+    }
+
+    impl crate::LintPass for TestPass {
+        fn info(&self) -> LintPassInfo {
+            LintPassInfoBuilder::new(Box::new([])).build()
+        }
+    }
+
+    crate::export_lint_pass!(TestPass, config = TestConfig, TestPass::with_config);
+
+    extern "C" {
+        fn marker_lint_crate_bindings() -> LintCrateBindings;
+    }
+
+    fn bindings() -> LintCrateBindings {
+        unsafe { marker_lint_crate_bindings() }
+    }
+
+    // Every test runs in a new thread, the lint pass is therefore created with
+    // the configuration set by the test.
+    #[test]
+    fn test_set_config() {
+        let bindings = bindings();
+        assert!((bindings.set_config)(r#"{ "max_params": 5 }"#.into()).get().is_none());
+        (bindings.info)();
+        assert_eq!(MAX_PARAMS.with(Cell::get), Some(5));
+    }
+
+    #[test]
+    fn test_set_config_default() {
+        (bindings().info)();
+        assert_eq!(MAX_PARAMS.with(Cell::get), Some(0));
+    }
+
+    #[test]
+    fn test_set_config_invalid() {
+        let err = (bindings().set_config)(r#"{ "max_params": "five" }"#.into());
+        assert!(err.get().unwrap().get().starts_with("invalid type"));
+    }
 }
@@ -24,6 +24,7 @@ pub mod ast;
 pub mod common;
 pub mod context;
 pub mod diagnostic;
+pub mod manifest;
 pub mod prelude;
 pub mod sem;
 pub mod span;
@@ -31,8 +32,16 @@ pub mod span;
 #[doc(hidden)]
 pub mod ffi;
 
+/// Dependencies used by the macros of this crate. This is not part of the
+/// stable API.
+#[doc(hidden)]
+#[cfg(feature = "config")]
+pub mod __private {
+    pub use serde_json;
+}
+
 pub use context::MarkerContext;
-pub use interface::{LintPassInfo, LintPassInfoBuilder};
+pub use interface::{LintPassInfo, LintPassInfoBuilder, LintSupersession};
 
 /// A [`LintPass`] visits every node like a `Visitor`. The difference is that a
 /// [`LintPass`] provides some additional information about the implemented lints.
@@ -47,7 +56,23 @@ pub trait LintPass {
     fn check_variant<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _variant: &'ast ast::EnumVariant<'ast>) {}
     fn check_body<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _body: &'ast ast::Body<'ast>) {}
     fn check_stmt<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _stmt: ast::StmtKind<'ast>) {}
+    /// This is called for every `let` statement, including `let-else` statements.
+    /// It's called right after [`LintPass::check_stmt`] for the same statement.
+    fn check_local<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _local: &'ast ast::LetStmt<'ast>) {}
     fn check_expr<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _expr: ast::ExprKind<'ast>) {}
+    /// This is called for every bound declared by an item, like `Clone` in
+    /// `T: Clone` or `?Sized` in `where T: ?Sized`. Bounds of the generic
+    /// parameters are included in the `where` clauses. Supertraits and the
+    /// bounds of associated types are visited as well. The `owner` is the
+    /// item, which declares the bound. It's called right after
+    /// [`LintPass::check_item`] for the owner.
+    fn check_generic_bound<'ast>(
+        &mut self,
+        _cx: &'ast MarkerContext<'ast>,
+        _bound: &'ast ast::TyParamBound<'ast>,
+        _owner: common::ItemId,
+    ) {
+    }
 }
 
 /// This struct blocks the construction of enum variants, similar to the `#[non_exhaustive]`
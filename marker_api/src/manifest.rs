@@ -0,0 +1,218 @@
+//! This module contains a minimal model of the `Cargo.toml` manifest of the
+//! package, which is currently being checked. See [`MarkerContext::manifest`].
+//!
+//! The entries of the manifest provide [`Span`]s, which point into the
+//! `Cargo.toml` file. These can be used to emit diagnostics with
+//! [`MarkerContext::emit_manifest_lint`].
+//!
+//! [`MarkerContext::manifest`]: crate::MarkerContext::manifest
+//! [`MarkerContext::emit_manifest_lint`]: crate::MarkerContext::emit_manifest_lint
+
+use crate::{
+    common::{Edition, ItemId, SpanId},
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    span::Span,
+};
+
+/// The `Cargo.toml` manifest of the package, which is currently being checked.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Manifest<'ast> {
+    edition: FfiOption<Edition>,
+    features: FfiSlice<'ast, Feature<'ast>>,
+    dependencies: FfiSlice<'ast, Dependency<'ast>>,
+    package_span: FfiOption<SpanId>,
+    /// The root module of the crate. Lint levels of diagnostics in the manifest
+    /// are determined by the attributes of the crate root.
+    crate_root: ItemId,
+}
+
+impl<'ast> Manifest<'ast> {
+    /// The Rust edition of the package. This is `None`, if the edition is not
+    /// known to this version of Marker.
+    pub fn edition(&self) -> Option<Edition> {
+        self.edition.copy()
+    }
+
+    /// The span of the `package` key, in the `[package]` table header. This can
+    /// be used for diagnostics about the package as a whole, like a missing
+    /// field. It's `None` if the manifest couldn't be loaded by the driver.
+    pub fn package_span(&self) -> Option<&Span<'ast>> {
+        self.package_span.copy().map(|span| with_cx(self, |cx| cx.span(span)))
+    }
+
+    pub(crate) fn crate_root(&self) -> ItemId {
+        self.crate_root
+    }
+
+    /// The features declared in the `[features]` table of the manifest. This
+    /// also includes the implicit features of optional dependencies.
+    pub fn features(&self) -> &'ast [Feature<'ast>] {
+        self.features.get()
+    }
+
+    /// Returns the feature with the given name, if it's declared by the package.
+    pub fn feature(&self, name: &str) -> Option<&'ast Feature<'ast>> {
+        self.features().iter().find(|feature| feature.name() == name)
+    }
+
+    /// The dependencies of the package, including dev- and build-dependencies.
+    pub fn dependencies(&self) -> &'ast [Dependency<'ast>] {
+        self.dependencies.get()
+    }
+
+    /// Returns the dependency with the given name. The name is the one used in
+    /// the source code, which takes renames into account.
+    pub fn dependency(&self, name: &str) -> Option<&'ast Dependency<'ast>> {
+        self.dependencies().iter().find(|dep| dep.local_name() == name)
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Manifest<'ast> {
+    pub fn new(
+        edition: Option<Edition>,
+        features: &'ast [Feature<'ast>],
+        dependencies: &'ast [Dependency<'ast>],
+        package_span: Option<SpanId>,
+        crate_root: ItemId,
+    ) -> Self {
+        Self {
+            edition: edition.into(),
+            features: features.into(),
+            dependencies: dependencies.into(),
+            package_span: package_span.into(),
+            crate_root,
+        }
+    }
+}
+
+/// A feature declared in the `[features]` table of the manifest.
+///
+/// ```toml
+/// [features]
+/// #          vvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvvv The enabled features and dependencies
+/// default = ["std", "dep:serde", "regex/unicode"]
+/// # ^^^^^ The name of the feature
+/// ```
+#[repr(C)]
+#[derive(Debug)]
+pub struct Feature<'ast> {
+    name: FfiStr<'ast>,
+    enables: FfiSlice<'ast, FfiStr<'ast>>,
+    is_enabled: bool,
+    span: FfiOption<SpanId>,
+}
+
+impl<'ast> Feature<'ast> {
+    pub fn name(&self) -> &'ast str {
+        self.name.get()
+    }
+
+    /// The values of this feature, as written in the manifest. These can be
+    /// other features, like `std`, dependencies, like `dep:serde` or features
+    /// of dependencies, like `regex/unicode`.
+    pub fn enables(&self) -> impl Iterator<Item = &'ast str> {
+        self.enables.get().iter().map(FfiStr::get)
+    }
+
+    /// Returns `true`, if this feature is enabled for the current compilation.
+    pub fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    /// The span of the feature name in the `[features]` table. It's `None` for
+    /// implicit features of optional dependencies.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        self.span.copy().map(|span| with_cx(self, |cx| cx.span(span)))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Feature<'ast> {
+    pub fn new(name: &'ast str, enables: &'ast [FfiStr<'ast>], is_enabled: bool, span: Option<SpanId>) -> Self {
+        Self {
+            name: name.into(),
+            enables: enables.into(),
+            is_enabled,
+            span: span.into(),
+        }
+    }
+}
+
+/// A dependency of the package.
+#[repr(C)]
+#[derive(Debug)]
+pub struct Dependency<'ast> {
+    name: FfiStr<'ast>,
+    rename: FfiOption<FfiStr<'ast>>,
+    kind: DependencyKind,
+    is_optional: bool,
+    span: FfiOption<SpanId>,
+}
+
+impl<'ast> Dependency<'ast> {
+    /// The name of the package, this dependency refers to.
+    pub fn name(&self) -> &'ast str {
+        self.name.get()
+    }
+
+    /// The new name of the dependency, if it was renamed with the `package` key.
+    pub fn rename(&self) -> Option<&'ast str> {
+        self.rename.get().map(FfiStr::get)
+    }
+
+    /// The name under which this dependency is available in the source code.
+    /// This is the [`rename`](Self::rename) if specified and the
+    /// [`name`](Self::name) otherwise. Dashes are not replaced by underscores.
+    pub fn local_name(&self) -> &'ast str {
+        self.rename().unwrap_or_else(|| self.name())
+    }
+
+    pub fn kind(&self) -> DependencyKind {
+        self.kind
+    }
+
+    /// Returns `true`, if this dependency is marked as `optional = true`.
+    pub fn is_optional(&self) -> bool {
+        self.is_optional
+    }
+
+    /// The span of the dependency key in the manifest, like `serde` in
+    /// `serde = "1.0"` or `[dependencies.serde]`.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        self.span.copy().map(|span| with_cx(self, |cx| cx.span(span)))
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> Dependency<'ast> {
+    pub fn new(
+        name: &'ast str,
+        rename: Option<&'ast str>,
+        kind: DependencyKind,
+        is_optional: bool,
+        span: Option<SpanId>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            rename: rename.map(Into::into).into(),
+            kind,
+            is_optional,
+            span: span.into(),
+        }
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DependencyKind {
+    /// A dependency from the `[dependencies]` table
+    Normal,
+    /// A dependency from the `[dev-dependencies]` table
+    Dev,
+    /// A dependency from the `[build-dependencies]` table
+    Build,
+}
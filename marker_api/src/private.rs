@@ -26,3 +26,4 @@ impl Sealed for ast::TyKind<'_> {}
 impl Sealed for ast::TyParam<'_> {}
 impl Sealed for Span<'_> {}
 impl Sealed for Ident<'_> {}
+impl Sealed for crate::common::NodeId {}
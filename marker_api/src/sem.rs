@@ -4,9 +4,11 @@
 mod common;
 mod generic;
 mod item;
+mod layout;
 mod ty;
 
 pub use common::*;
 pub use generic::*;
 pub use item::*;
+pub use layout::*;
 pub use ty::*;
@@ -1,26 +1,215 @@
-use std::fmt::Debug;
 use std::marker::PhantomData;
 
-/// The semantic equivalent of a [`ConstExpr`][crate::ast::ConstExpr], at
-/// least theoretically. This part of the API is sadly not done yet, so this is
-/// just a placeholder.
+use crate::{
+    common::SymbolId,
+    context::with_cx,
+    ffi::{FfiOption, FfiSlice, FfiStr},
+    sem::TyKind,
+};
+
+/// The semantic equivalent of a [`ConstExpr`][crate::ast::ConstExpr]. A value,
+/// which has been calculated at compile time.
+///
+/// Constant values can be calculated with [`MarkerContext::try_eval_const`]. They
+/// are also used by semantic types, like the length of an [`ArrayTy`](crate::sem::ArrayTy)
+/// or the [`ConstArg`](crate::sem::ConstArg) of a generic argument list.
 ///
-/// See: rust-marker/marker#179
+/// [`MarkerContext::try_eval_const`]: crate::MarkerContext::try_eval_const
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum ConstValue<'ast> {
+    /// An integer value, like `4_usize` or `-1_i32`
+    Int(ConstInt<'ast>),
+    /// A boolean value, like `true`
+    Bool(bool),
+    /// A character value, like `'x'`
+    Char(char),
+    /// A string slice, like `"marker"`
+    Str(ConstStr<'ast>),
+    /// A byte slice or array, like `b"marker"` or `[1_u8, 2, 3]`
+    Bytes(ConstBytes<'ast>),
+    /// A value, which Marker can't represent yet or which depends on generic
+    /// parameters. [`MarkerContext::try_eval_const`] never returns this variant.
+    ///
+    /// [`MarkerContext::try_eval_const`]: crate::MarkerContext::try_eval_const
+    Unknown,
+}
+
+impl<'ast> ConstValue<'ast> {
+    /// Returns the [`ConstInt`], if this is an integer value.
+    pub fn as_int(&self) -> Option<&ConstInt<'ast>> {
+        match self {
+            ConstValue::Int(int) => Some(int),
+            _ => None,
+        }
+    }
+}
+
+/// An integer value of a [`ConstValue`], together with its semantic type.
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConstInt<'ast> {
+    /// The bits of the value. Values of signed types are sign extended.
+    bits: u128,
+    is_signed: bool,
+    ty: TyKind<'ast>,
+}
+
+impl<'ast> ConstInt<'ast> {
+    /// Returns the value as an `u128`, or `None`, if the value is negative.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.is_signed {
+            u128::try_from(self.bits as i128).ok()
+        } else {
+            Some(self.bits)
+        }
+    }
+
+    /// Returns the value as an `i128`, or `None`, if the value is too large.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn to_i128(&self) -> Option<i128> {
+        if self.is_signed {
+            Some(self.bits as i128)
+        } else {
+            i128::try_from(self.bits).ok()
+        }
+    }
+
+    /// Returns `true`, if the integer type is signed, like `i32`.
+    pub fn is_signed(&self) -> bool {
+        self.is_signed
+    }
+
+    /// The semantic type of the integer, like `usize`.
+    pub fn ty(&self) -> TyKind<'ast> {
+        self.ty
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstInt<'ast> {
+    pub fn new(bits: u128, is_signed: bool, ty: TyKind<'ast>) -> Self {
+        Self { bits, is_signed, ty }
+    }
+}
+
+/// A string slice of a [`ConstValue`].
 #[repr(C)]
-pub struct ConstValue<'ast> {
+#[derive(Debug)]
+pub struct ConstStr<'ast> {
+    value: FfiStr<'ast>,
+}
+
+impl<'ast> ConstStr<'ast> {
+    pub fn value(&self) -> &'ast str {
+        self.value.get()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstStr<'ast> {
+    pub fn new(value: &'ast str) -> Self {
+        Self { value: value.into() }
+    }
+}
+
+/// A byte slice or array of a [`ConstValue`].
+#[repr(C)]
+#[derive(Debug)]
+pub struct ConstBytes<'ast> {
+    value: FfiSlice<'ast, u8>,
+}
+
+impl<'ast> ConstBytes<'ast> {
+    pub fn value(&self) -> &'ast [u8] {
+        self.value.get()
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl<'ast> ConstBytes<'ast> {
+    pub fn new(value: &'ast [u8]) -> Self {
+        Self { value: value.into() }
+    }
+}
+
+/// The semantic representation of a lifetime, like `'static` or `'a`.
+///
+/// Semantic types only retain a coarse representation of lifetimes. Rustc erases
+/// most lifetimes during type checking, types of expressions therefore usually
+/// only contain [`LifetimeKind::Erased`] lifetimes. Types taken from item
+/// signatures, like the signature of a [`FnTy`](crate::sem::FnTy), retain their
+/// lifetimes.
+#[repr(C)]
+#[cfg_attr(feature = "driver-api", derive(Clone))]
+pub struct Lifetime<'ast> {
+    #[allow(clippy::struct_field_names)]
     _lifetime: PhantomData<&'ast ()>,
+    kind: LifetimeKind,
+    name: FfiOption<SymbolId>,
+}
+
+impl<'ast> Lifetime<'ast> {
+    pub fn kind(&self) -> LifetimeKind {
+        self.kind
+    }
+
+    /// Returns the name of the lifetime, like `'a`, if it's [`LifetimeKind::Named`].
+    pub fn name(&self) -> Option<&'ast str> {
+        self.name.get().map(|sym| with_cx(self, |cx| cx.symbol_str(*sym)))
+    }
+
+    pub fn is_static(&self) -> bool {
+        self.kind == LifetimeKind::Static
+    }
+
+    /// Returns `true`, if the lifetime is known to outlive the body of the current
+    /// function. This is the case for `'static` and lifetime parameters, since they
+    /// are chosen by the caller. Erased lifetimes return `false`, as it's unknown
+    /// what they originally were.
+    pub fn outlives_fn(&self) -> bool {
+        matches!(
+            self.kind,
+            LifetimeKind::Static | LifetimeKind::Named | LifetimeKind::Anonymous
+        )
+    }
 }
 
-impl<'ast> Debug for ConstValue<'ast> {
+impl<'ast> std::fmt::Debug for Lifetime<'ast> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("ConstValue {{ /* WIP: See rust-marker/marker#179 */}}")
+        f.debug_struct("Lifetime")
+            .field("kind", &self.kind)
+            .field("name", &self.name())
             .finish()
     }
 }
 
 #[cfg(feature = "driver-api")]
-impl<'ast> ConstValue<'ast> {
-    pub fn new() -> Self {
-        Self { _lifetime: PhantomData }
+impl<'ast> Lifetime<'ast> {
+    pub fn new(kind: LifetimeKind, name: Option<SymbolId>) -> Self {
+        Self {
+            _lifetime: PhantomData,
+            kind,
+            name: name.into(),
+        }
     }
 }
+
+/// The kind of a semantic [`Lifetime`].
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum LifetimeKind {
+    /// The `'static` lifetime
+    Static,
+    /// A lifetime parameter with a name, like `'a` in `fn foo<'a>(_: &'a str)`
+    Named,
+    /// A lifetime parameter without a name, like the elided lifetime in
+    /// `fn foo(_: &str)` or `'_`
+    Anonymous,
+    /// A lifetime, which has been erased by the compiler. This is the case for
+    /// most lifetimes in the types of expressions.
+    Erased,
+}
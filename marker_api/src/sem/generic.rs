@@ -3,7 +3,10 @@ mod param;
 pub use args::*;
 pub use param::*;
 
-use crate::{ffi::FfiSlice, sem::ty::TyKind};
+use crate::{
+    ffi::FfiSlice,
+    sem::{ty::TyKind, Lifetime},
+};
 
 /// The semantic representation of generic arguments for an item or path.
 ///
@@ -80,4 +83,12 @@ pub enum GenericArgKind<'ast> {
     /// //            ^
     /// ```
     Const(&'ast ConstArg<'ast>),
+    /// A lifetime as a generic argument, like this:
+    ///
+    /// ```
+    /// # use std::borrow::Cow;
+    /// let _bax: Cow<'static, str> = Cow::Borrowed("marker");
+    /// //            ^^^^^^^
+    /// ```
+    Lifetime(&'ast Lifetime<'ast>),
 }
@@ -1,6 +1,11 @@
 use std::marker::PhantomData;
 
-use crate::common::ItemId;
+use crate::{
+    common::{ItemId, SpanId, SymbolId},
+    context::with_cx,
+    ffi::FfiOption,
+    span::Span,
+};
 
 /// The declared visibility of an item or field.
 ///
@@ -151,3 +156,130 @@ enum VisibilityKind {
     /// a module, identified by the stored [`ItemId`]
     Default(ItemId),
 }
+
+/// A reason, why a trait is not [object safe]. See
+/// [`MarkerContext::object_safety_violations`](crate::MarkerContext::object_safety_violations)
+///
+/// [object safe]: https://doc.rust-lang.org/reference/items/traits.html#object-safety
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct ObjectSafetyViolation<'ast> {
+    #[cfg_attr(feature = "driver-api", builder(setter(skip), default))]
+    _lifetime: PhantomData<&'ast ()>,
+    kind: ObjectSafetyViolationKind,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    item_name: FfiOption<SymbolId>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into), default))]
+    span: FfiOption<SpanId>,
+}
+
+impl<'ast> ObjectSafetyViolation<'ast> {
+    pub fn kind(&self) -> ObjectSafetyViolationKind {
+        self.kind
+    }
+
+    /// The name of the associated item, which causes this violation. This is
+    /// `None` for violations, which are caused by the trait itself, like
+    /// [`ObjectSafetyViolationKind::SizedSelf`].
+    pub fn item_name(&self) -> Option<&'ast str> {
+        self.item_name.get().map(|sym| with_cx(self, |cx| cx.symbol_str(*sym)))
+    }
+
+    /// The [`Span`] of the code, which causes this violation, if available.
+    pub fn span(&self) -> Option<&Span<'ast>> {
+        self.span.get().map(|span| with_cx(self, |cx| cx.span(*span)))
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ObjectSafetyViolationKind {
+    /// The trait requires `Self: Sized`, like `trait Trait: Sized {}`
+    SizedSelf,
+    /// A supertrait or where clause uses `Self` as a type parameter, like
+    /// `trait Trait: PartialEq<Self> {}`
+    SupertraitSelf,
+    /// The trait has an associated const
+    AssocConst,
+    /// The trait has an associated type with generic parameters
+    GenericAssocTy,
+    /// The trait has an associated function without a `self` parameter.
+    /// This is allowed, if the function has a `where Self: Sized` bound.
+    StaticMethod,
+    /// A method uses `Self` as the type of a parameter, like `fn eq(&self, other: Self)`
+    SelfInParams,
+    /// A method uses `Self` in the return type, like `fn clone(&self) -> Self`
+    SelfInReturnTy,
+    /// A method returns `impl Trait`
+    ImplTraitReturnTy,
+    /// A method is an `async fn`
+    AsyncFn,
+    /// A method has a where clause referencing `Self`. This is currently only
+    /// reported as a warning by rustc. Traits with only this violation are
+    /// therefore considered object safe by
+    /// [`MarkerContext::is_object_safe`](crate::MarkerContext::is_object_safe).
+    WhereClauseReferencesSelf,
+    /// A method has generic type parameters
+    GenericMethod,
+    /// A method has a receiver, which can't be used for dynamic dispatch,
+    /// like `self: Rc<Self>` without the unstable `DispatchFromDyn` trait
+    UndispatchableReceiver,
+    /// A violation which is not yet represented by this enum
+    Other,
+}
+
+/// A reason, why a function can't be declared as `const fn`. See
+/// [`MarkerContext::const_fn_violation`](crate::MarkerContext::const_fn_violation)
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct ConstFnViolation<'ast> {
+    #[cfg_attr(feature = "driver-api", builder(setter(skip), default))]
+    _lifetime: PhantomData<&'ast ()>,
+    kind: ConstFnViolationKind,
+    span: SpanId,
+}
+
+impl<'ast> ConstFnViolation<'ast> {
+    pub fn kind(&self) -> ConstFnViolationKind {
+        self.kind
+    }
+
+    /// The [`Span`] of the code, which causes this violation. For
+    /// [`ConstFnViolationKind::Unsupported`], this is the span of the function
+    /// name.
+    pub fn span(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.span))
+    }
+}
+
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ConstFnViolationKind {
+    /// The function can't be `const`, regardless of its body. This is the case
+    /// for trait methods, `async` functions, the `main` function and functions
+    /// with a non-Rust ABI.
+    Unsupported,
+    /// A function is called, which isn't `const`. This includes trait methods,
+    /// closures and function pointers.
+    NonConstCall,
+    /// Memory is allocated on the heap, like in `vec![1, 2, 3]`
+    HeapAllocation,
+    /// A value with a destructor is dropped
+    NonConstDrop,
+    /// A mutable reference is created or used in the signature
+    MutRef,
+    /// Floating point arithmetic, like `a + 1.0`
+    FloatArithmetic,
+    /// A pointer is cast to an integer, like `ptr as usize`
+    PtrToIntCast,
+    /// A `static` item is accessed
+    StaticAccess,
+    /// Inline assembly is used
+    InlineAsm,
+    /// A violation which is not yet represented by this enum
+    Other,
+}
@@ -0,0 +1,66 @@
+use crate::ffi::FfiOption;
+
+/// The memory layout of a type, as computed by the compiler for the current
+/// target. It can be retrieved with [`MarkerContext::ty_layout`](crate::MarkerContext::ty_layout).
+///
+/// ```
+/// # use std::num::NonZeroU32;
+/// # use std::mem::size_of;
+/// // `NonZeroU32` has a niche, as the value `0` is invalid. `Option` uses
+/// // the niche to represent `None`, which makes them the same size.
+/// assert_eq!(size_of::<NonZeroU32>(), size_of::<Option<NonZeroU32>>());
+///
+/// // `u32` has no niche, the discriminant of `Option` requires extra space.
+/// assert!(size_of::<u32>() < size_of::<Option<u32>>());
+/// ```
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TyLayout {
+    size: u64,
+    align: u64,
+    has_niche: bool,
+    option_size: FfiOption<u64>,
+}
+
+impl TyLayout {
+    /// The size of the type in bytes.
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// The ABI required alignment of the type in bytes.
+    pub fn align(&self) -> u64 {
+        self.align
+    }
+
+    /// Returns `true`, if the type has a niche. A niche is an invalid bit pattern,
+    /// like `0` for references and [`NonNull`](std::ptr::NonNull), which can
+    /// be used by enums to store their discriminant.
+    pub fn has_niche(&self) -> bool {
+        self.has_niche
+    }
+
+    /// The size of `Option<T>` in bytes, where `T` is this type.
+    pub fn option_size(&self) -> Option<u64> {
+        self.option_size.copy()
+    }
+
+    /// Returns `true`, if `Option<T>` has the same size as this type. This is
+    /// the case, if the type has a [niche](Self::has_niche), which can store
+    /// the discriminant of `Option`.
+    pub fn is_option_free(&self) -> bool {
+        self.option_size() == Some(self.size)
+    }
+}
+
+#[cfg(feature = "driver-api")]
+impl TyLayout {
+    pub fn new(size: u64, align: u64, has_niche: bool, option_size: Option<u64>) -> Self {
+        Self {
+            size,
+            align,
+            has_niche,
+            option_size: option_size.into(),
+        }
+    }
+}
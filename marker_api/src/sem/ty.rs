@@ -14,7 +14,7 @@ pub use sequence_ty::*;
 pub use trait_ty::*;
 pub use user_ty::*;
 
-use crate::common::DriverTyId;
+use crate::{common::DriverTyId, context::with_cx, sem::GenericArgKind};
 use std::{fmt::Debug, marker::PhantomData};
 
 /// The semantic representation of a type.
@@ -76,6 +76,8 @@ pub enum TyKind<'ast> {
     /// semantic types. This kind is mainly used for type aliases, where the concrete
     /// type is not yet known, for example in traits.
     Alias(&'ast AliasTy<'ast>),
+    /// An extern type, declared in an `extern` block, like `type Opaque;`
+    Foreign(&'ast ForeignTy<'ast>),
     // ================================
     // Other types
     // ================================
@@ -113,6 +115,106 @@ impl<'ast> TyKind<'ast> {
         }
         ty
     }
+
+    /// Returns `true`, if this is a reference with the `'static` lifetime, like
+    /// `&'static str`. This returns `false` for the types of expressions, like
+    /// string literals, as their lifetimes are erased. See [`RefTy::is_static_ref`]
+    /// for more information.
+    pub fn is_static_ref(self) -> bool {
+        matches!(self, Self::Ref(ref_ty) if ref_ty.is_static_ref())
+    }
+
+    /// Returns `true`, if this is a float type, like [`f32`] or [`f64`]. The
+    /// width can be retrieved with [`NumKind::float_bits`].
+    ///
+    /// [`NumKind::float_bits`]: crate::common::NumKind::float_bits
+    pub fn is_float(self) -> bool {
+        matches!(self, Self::Num(num_ty) if num_ty.is_float())
+    }
+
+    /// Returns the [`FnSig`] of function items, function pointers and closures.
+    /// Other types will return `None`.
+    pub fn fn_sig(self) -> Option<&'ast FnSig<'ast>> {
+        match self {
+            Self::Fn(ty) => Some(ty.fn_sig()),
+            Self::Closure(ty) => Some(ty.fn_sig()),
+            Self::FnPtr(ty) => Some(ty.fn_sig()),
+            _ => None,
+        }
+    }
+
+    /// Returns the type `T`, if this is a [`Box<T>`](Box).
+    pub fn as_box(self) -> Option<TyKind<'ast>> {
+        self.std_adt_ty_arg("alloc::boxed::Box")
+    }
+
+    /// Returns the type `T`, if this is a [`Rc<T>`](std::rc::Rc).
+    pub fn as_rc(self) -> Option<TyKind<'ast>> {
+        self.std_adt_ty_arg("alloc::rc::Rc")
+    }
+
+    /// Returns the type `T`, if this is an [`Arc<T>`](std::sync::Arc).
+    pub fn as_arc(self) -> Option<TyKind<'ast>> {
+        self.std_adt_ty_arg("alloc::sync::Arc")
+    }
+
+    /// Returns the pointer type `P`, if this is a [`Pin<P>`](std::pin::Pin).
+    /// The pinned type can be retrieved from the pointer type, like this:
+    ///
+    /// ```ignore
+    /// // `ty` is a `Pin<Box<T>>`
+    /// let pinned = ty.as_pin().and_then(TyKind::as_box);
+    /// ```
+    pub fn as_pin(self) -> Option<TyKind<'ast>> {
+        self.std_adt_ty_arg("core::pin::Pin")
+    }
+
+    /// Returns the borrowed type `B`, if this is a [`Cow<'_, B>`](std::borrow::Cow).
+    /// For `Cow<'_, str>` this would return `str`.
+    pub fn as_cow(self) -> Option<TyKind<'ast>> {
+        self.std_adt_ty_arg("alloc::borrow::Cow")
+    }
+
+    /// Returns the first generic type argument, if this type is the ADT with the
+    /// given path. The paths of the defining crates are used, since they're also
+    /// available in `no_std` crates. Type aliases are already resolved in semantic
+    /// types, re-exports and aliases of the std types are therefore also detected.
+    fn std_adt_ty_arg(self, path: &str) -> Option<TyKind<'ast>> {
+        let Self::Adt(adt) = self else {
+            return None;
+        };
+        if !with_cx(&self, |cx| cx.resolve_ty_ids(path)).contains(&adt.def_id()) {
+            return None;
+        }
+        adt.generics().args().iter().find_map(|arg| match arg {
+            GenericArgKind::Ty(ty) => Some(*ty),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn driver_id(self) -> DriverTyId {
+        let data = match self {
+            Self::Bool(ty) => ty.data(),
+            Self::Num(ty) => ty.data(),
+            Self::Text(ty) => ty.data(),
+            Self::Never(ty) => ty.data(),
+            Self::Tuple(ty) => ty.data(),
+            Self::Array(ty) => ty.data(),
+            Self::Slice(ty) => ty.data(),
+            Self::Fn(ty) => ty.data(),
+            Self::Closure(ty) => ty.data(),
+            Self::Ref(ty) => ty.data(),
+            Self::RawPtr(ty) => ty.data(),
+            Self::FnPtr(ty) => ty.data(),
+            Self::TraitObj(ty) => ty.data(),
+            Self::Adt(ty) => ty.data(),
+            Self::Generic(ty) => ty.data(),
+            Self::Alias(ty) => ty.data(),
+            Self::Foreign(ty) => ty.data(),
+            Self::Unstable(ty) => ty.data(),
+        };
+        data.driver_id
+    }
 }
 
 #[repr(C)]
@@ -139,9 +241,9 @@ impl<'ast> CommonTyData<'ast> {
 
 macro_rules! impl_ty_data {
     ($self_ty:ty, $enum_name:ident) => {
-        #[cfg(feature = "driver_api")]
         impl<'ast> $self_ty {
-            pub fn data(&self) -> &$crate::sem::ty::CommonTyData<'ast> {
+            #[cfg_attr(feature = "driver-api", visibility::make(pub))]
+            pub(crate) fn data(&self) -> &$crate::sem::ty::CommonTyData<'ast> {
                 &self.data
             }
         }
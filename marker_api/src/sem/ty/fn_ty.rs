@@ -1,9 +1,10 @@
 use crate::{
-    common::{ItemId, TyDefId},
+    common::{Abi, ItemId, Safety, TyDefId},
+    ffi::FfiSlice,
     sem::generic::GenericArgs,
 };
 
-use super::CommonTyData;
+use super::{CommonTyData, TyKind};
 
 /// A [function item type](https://doc.rust-lang.org/reference/types/function-item.html)
 /// identifying a specific function and potentualy additional generics.
@@ -14,6 +15,7 @@ pub struct FnTy<'ast> {
     data: CommonTyData<'ast>,
     fn_id: ItemId,
     generics: GenericArgs<'ast>,
+    sig: FnSig<'ast>,
 }
 
 impl<'ast> FnTy<'ast> {
@@ -26,6 +28,12 @@ impl<'ast> FnTy<'ast> {
     pub fn generics(&self) -> &GenericArgs<'ast> {
         &self.generics
     }
+
+    /// The [`FnSig`] of the identified function. Generic parameters in the
+    /// signature are replaced by the [`GenericArgs`] of this type.
+    pub fn fn_sig(&self) -> &FnSig<'ast> {
+        &self.sig
+    }
 }
 
 super::impl_ty_data!(FnTy<'ast>, Fn);
@@ -42,6 +50,8 @@ pub struct ClosureTy<'ast> {
     data: CommonTyData<'ast>,
     def_id: TyDefId,
     generics: GenericArgs<'ast>,
+    kind: ClosureKind,
+    sig: FnSig<'ast>,
 }
 
 impl<'ast> ClosureTy<'ast> {
@@ -55,8 +65,106 @@ impl<'ast> ClosureTy<'ast> {
         &self.generics
     }
 
-    // FIXME: Add a method to get a different representation, which includes the
-    // parameters and return type.
+    /// This returns the [`ClosureKind`] inferred for this closure. The kind
+    /// defines which of the `Fn*` traits is implemented by the closure.
+    pub fn kind(&self) -> ClosureKind {
+        self.kind
+    }
+
+    /// The [`FnSig`] of this closure, as it has been inferred by the driver.
+    pub fn fn_sig(&self) -> &FnSig<'ast> {
+        &self.sig
+    }
+
+    /// The semantic types of the closure parameters, as they have been inferred
+    /// by the driver.
+    pub fn params(&self) -> &'ast [TyKind<'ast>] {
+        self.sig.params()
+    }
+
+    /// The semantic return type of the closure, as it has been inferred by the
+    /// driver.
+    pub fn return_ty(&self) -> TyKind<'ast> {
+        self.sig.return_ty()
+    }
 }
 
 super::impl_ty_data!(ClosureTy<'ast>, Closure);
+
+/// The semantic signature of a function, function pointer or closure.
+///
+/// The parameters of closures are not tupled, like it's done internally by rustc.
+/// A closure like `|a: u32, b: u32| a + b` will have two parameters.
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct FnSig<'ast> {
+    safety: Safety,
+    abi: Abi,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    params: FfiSlice<'ast, TyKind<'ast>>,
+    return_ty: TyKind<'ast>,
+}
+
+impl<'ast> FnSig<'ast> {
+    /// Returns [`Safety::Unsafe`], if the function has to be called in an `unsafe` context.
+    pub fn safety(&self) -> Safety {
+        self.safety
+    }
+
+    /// The [`Abi`] of the function. Closures always use the [`Abi::Default`].
+    pub fn abi(&self) -> Abi {
+        self.abi
+    }
+
+    /// The semantic types of the parameters.
+    pub fn params(&self) -> &'ast [TyKind<'ast>] {
+        self.params.get()
+    }
+
+    /// The semantic return type. Functions without an explicit return type
+    /// return the unit type `()`.
+    pub fn return_ty(&self) -> TyKind<'ast> {
+        self.return_ty
+    }
+}
+
+/// The kind of a closure, which determines the most general `Fn*` trait that
+/// the closure implements. The kind is inferred, based on how the closure uses
+/// the captured values.
+///
+/// ```
+/// let mut counter = 0;
+/// let text = String::from("Hello");
+///
+/// // `Fn`: The captured values are only read
+/// let fn_closure = || println!("{counter}");
+/// # fn_closure();
+///
+/// // `FnMut`: A captured value is mutated
+/// let mut fn_mut_closure = || counter += 1;
+/// # fn_mut_closure();
+///
+/// // `FnOnce`: A captured value is moved out of the closure
+/// let fn_once_closure = move || text;
+/// # fn_once_closure();
+/// ```
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ClosureKind {
+    /// The closure implements [`Fn`], [`FnMut`] and [`FnOnce`].
+    Fn,
+    /// The closure implements [`FnMut`] and [`FnOnce`].
+    FnMut,
+    /// The closure only implements [`FnOnce`] and can therefore only be called once.
+    FnOnce,
+}
+
+impl ClosureKind {
+    /// Returns `true`, if a closure of this kind can be called multiple times.
+    #[must_use]
+    pub fn is_callable_multiple_times(&self) -> bool {
+        !matches!(self, Self::FnOnce)
+    }
+}
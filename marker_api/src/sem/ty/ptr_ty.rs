@@ -1,26 +1,46 @@
-use crate::{
-    common::{Abi, Mutability, Safety},
-    ffi::FfiSlice,
-};
+use crate::common::{Abi, Mutability, Safety};
+use crate::sem::Lifetime;
 
-use super::{CommonTyData, TyKind};
+use super::{CommonTyData, FnSig, TyKind};
 
 /// The semantic representation of a reference like [`&T`](prim@reference)
 /// or [`&mut T`](prim@reference)
 ///
-/// Note that the semantic representation doesn't contain lifetime information.
-/// Marker currently doesn't support the analysis of lifetimes. Removing them
-/// from the type also simplifies type comparisons.
+/// The semantic representation only contains a coarse [`Lifetime`] of the
+/// reference. Marker currently doesn't support the analysis of lifetimes.
 #[repr(C)]
 #[derive(Debug)]
 #[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
 pub struct RefTy<'ast> {
     data: CommonTyData<'ast>,
+    lifetime: Lifetime<'ast>,
     mutability: Mutability,
     inner_ty: TyKind<'ast>,
 }
 
 impl<'ast> RefTy<'ast> {
+    /// The [`Lifetime`] of the reference. Note that the lifetimes of expression
+    /// types are usually [erased](crate::sem::LifetimeKind::Erased).
+    pub fn lifetime(&self) -> &Lifetime<'ast> {
+        &self.lifetime
+    }
+
+    /// Returns `true`, if this is a `&'static T` reference.
+    ///
+    /// This is only known for types with retained lifetimes, like the parameters
+    /// of item signatures. The types of expressions have erased lifetimes. This
+    /// function therefore returns `false` for literals, like `"marker"`, even
+    /// though their type is `&'static str`.
+    pub fn is_static_ref(&self) -> bool {
+        self.lifetime.is_static()
+    }
+
+    /// Returns `true`, if the reference is known to outlive the body of the
+    /// current function. See [`Lifetime::outlives_fn`].
+    pub fn outlives_fn(&self) -> bool {
+        self.lifetime.outlives_fn()
+    }
+
     /// This returns the [`Mutability`] of the referenced type.
     pub fn mutability(&self) -> Mutability {
         self.mutability
@@ -63,28 +83,29 @@ super::impl_ty_data!(RawPtrTy<'ast>, RawPtr);
 #[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
 pub struct FnPtrTy<'ast> {
     data: CommonTyData<'ast>,
-    safety: Safety,
-    abi: Abi,
-    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
-    params: FfiSlice<'ast, TyKind<'ast>>,
-    return_ty: TyKind<'ast>,
+    sig: FnSig<'ast>,
 }
 
 impl<'ast> FnPtrTy<'ast> {
+    /// The [`FnSig`] of this function pointer.
+    pub fn fn_sig(&self) -> &FnSig<'ast> {
+        &self.sig
+    }
+
     pub fn safety(&self) -> Safety {
-        self.safety
+        self.sig.safety()
     }
 
     pub fn abi(&self) -> Abi {
-        self.abi
+        self.sig.abi()
     }
 
     pub fn params(&self) -> &[TyKind<'ast>] {
-        self.params.get()
+        self.sig.params()
     }
 
     pub fn return_ty(&self) -> TyKind<'ast> {
-        self.return_ty
+        self.sig.return_ty()
     }
 }
 
@@ -1,4 +1,7 @@
-use crate::{ffi::FfiSlice, sem::ConstValue};
+use crate::{
+    ffi::{FfiOption, FfiSlice},
+    sem::ConstValue,
+};
 
 use super::{CommonTyData, TyKind};
 
@@ -62,6 +65,8 @@ pub struct ArrayTy<'ast> {
     data: CommonTyData<'ast>,
     inner_ty: TyKind<'ast>,
     len: ConstValue<'ast>,
+    #[cfg_attr(feature = "driver-api", builder(setter(into)))]
+    known_len: FfiOption<u128>,
 }
 
 impl<'ast> ArrayTy<'ast> {
@@ -72,6 +77,13 @@ impl<'ast> ArrayTy<'ast> {
     pub fn len(&self) -> &ConstValue<'ast> {
         &self.len
     }
+
+    /// Returns the length of this array, if it's known to the driver. This
+    /// will return [`None`], if the length depends on a generic parameter,
+    /// like `[T; N]` inside a function generic over `const N: usize`.
+    pub fn try_len(&self) -> Option<u128> {
+        self.known_len.copy()
+    }
 }
 
 super::impl_ty_data!(ArrayTy<'ast>, Array);
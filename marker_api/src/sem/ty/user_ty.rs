@@ -82,3 +82,29 @@ impl<'ast> AliasTy<'ast> {
 }
 
 super::impl_ty_data!(AliasTy<'ast>, Alias);
+
+/// The semantic representation of an extern type, declared in an `extern` block.
+///
+/// ```ignore
+/// extern "C" {
+///     type Opaque;
+/// }
+/// ```
+///
+/// See <https://github.com/rust-lang/rust/issues/43467>
+#[repr(C)]
+#[derive(Debug)]
+#[cfg_attr(feature = "driver-api", derive(typed_builder::TypedBuilder))]
+pub struct ForeignTy<'ast> {
+    data: CommonTyData<'ast>,
+    def_id: TyDefId,
+}
+
+impl<'ast> ForeignTy<'ast> {
+    /// This returns the [`TyDefId`] of the extern type.
+    pub fn def_id(&self) -> TyDefId {
+        self.def_id
+    }
+}
+
+super::impl_ty_data!(ForeignTy<'ast>, Foreign);
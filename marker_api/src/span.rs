@@ -33,6 +33,13 @@ pub struct SpanPos(
     u32,
 );
 
+impl SpanPos {
+    /// Returns the position, which is the given number of bytes after this one.
+    pub(crate) fn offset(self, bytes: usize) -> Self {
+        Self(self.0 + u32::try_from(bytes).expect("span positions fit into an `u32`"))
+    }
+}
+
 #[cfg(feature = "driver-api")]
 impl SpanPos {
     pub fn new(index: u32) -> Self {
@@ -142,7 +149,10 @@ pub struct ExpnInfo<'ast> {
     _lifetime: PhantomData<&'ast ()>,
     parent: ExpnId,
     call_site: SpanId,
+    def_site: SpanId,
     macro_id: MacroId,
+    name: SymbolId,
+    kind: MacroKind,
 }
 
 impl<'ast> ExpnInfo<'ast> {
@@ -158,24 +168,67 @@ impl<'ast> ExpnInfo<'ast> {
         with_cx(self, |cx| cx.span(self.call_site))
     }
 
+    /// The [`Span`] of the macro definition. For proc macros, this is the span
+    /// of the proc macro function.
+    #[must_use]
+    pub fn def_site(&self) -> &Span<'ast> {
+        with_cx(self, |cx| cx.span(self.def_site))
+    }
+
     pub fn macro_id(&self) -> MacroId {
         self.macro_id
     }
+
+    /// The name of the macro, like `vec` for `vec![]` or `Clone` for
+    /// `#[derive(Clone)]`. The name might contain the path of the macro, like
+    /// `tokio::main`, if it has been used in the macro call.
+    pub fn name(&self) -> &str {
+        with_cx(self, |cx| cx.symbol_str(self.name))
+    }
+
+    /// The kind of the macro, which created this expansion.
+    pub fn kind(&self) -> MacroKind {
+        self.kind
+    }
 }
 
 #[cfg(feature = "driver-api")]
 impl<'ast> ExpnInfo<'ast> {
     #[must_use]
-    pub fn new(parent: ExpnId, call_site: SpanId, macro_id: MacroId) -> Self {
+    pub fn new(
+        parent: ExpnId,
+        call_site: SpanId,
+        def_site: SpanId,
+        macro_id: MacroId,
+        name: SymbolId,
+        kind: MacroKind,
+    ) -> Self {
         Self {
             _lifetime: PhantomData,
             parent,
             call_site,
+            def_site,
             macro_id,
+            name,
+            kind,
         }
     }
 }
 
+/// The kind of a macro, used by [`ExpnInfo::kind`].
+#[repr(C)]
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum MacroKind {
+    /// A function-like macro, like `vec![]`. This includes `macro_rules!`
+    /// macros and function-like proc macros.
+    Bang,
+    /// An attribute macro, like `#[tokio::main]`.
+    Attr,
+    /// A derive macro, like `#[derive(Clone)]`.
+    Derive,
+}
+
 /// A region of code, used for snipping, lint emission, and the retrieval of
 /// context information.
 ///
@@ -258,6 +311,12 @@ impl<'ast> Span<'ast> {
     /// lit_span.snippet(); // -> Some("15_000")
     /// ```
     ///
+    /// Spans from macro expansions still return a snippet, if the macro is
+    /// defined in an available file. The snippet is then taken from the macro
+    /// definition and not from the call site. Lints, which quote the snippet in
+    /// their message or suggestion, should check [`is_from_expansion`](Self::is_from_expansion)
+    /// or use the span of the macro call from [`expansion_origin`](Self::expansion_origin).
+    ///
     /// There are several reasons, why a snippet might be unavailable. Also
     /// depend on the used driver. You can also checkout the other snippet
     /// methods to better deal with these cases:
@@ -389,6 +448,105 @@ impl<'ast> Span<'ast> {
     pub fn source(&self) -> SpanSource<'ast> {
         with_cx(self, |cx| cx.span_source(self))
     }
+
+    /// Returns the [`ExpnInfo`] of the macro expansion, which created this
+    /// [`Span`], or `None`, if the span doesn't come from a macro. The
+    /// expansions of the macros, which invoked this macro, are available via
+    /// [`ExpnInfo::parent`].
+    ///
+    /// ```ignore
+    /// // Ignore code generated by `#[derive(Serialize)]`
+    /// if let Some(expn) = span.expansion_origin()
+    ///     && expn.kind() == MacroKind::Derive
+    ///     && expn.name() == "Serialize"
+    /// {
+    ///     return;
+    /// }
+    /// ```
+    #[must_use]
+    pub fn expansion_origin(&self) -> Option<&'ast ExpnInfo<'ast>> {
+        match self.source() {
+            SpanSource::Macro(expn) => Some(expn),
+            _ => None,
+        }
+    }
+
+    /// Returns the file location of the start of this [`Span`], with a 1-indexed
+    /// line and column. The column counts characters, like the locations in
+    /// rustc's diagnostics. This can be used to report locations to external
+    /// tools:
+    ///
+    /// ```ignore
+    /// if let Some(loc) = span.start_loc() {
+    ///     println!("::warning file={},line={},col={}::{msg}", loc.file(), loc.line(), loc.column());
+    /// }
+    /// ```
+    ///
+    /// `None` is returned, if the span doesn't belong to a file, like spans from
+    /// macro expansions. The location of the macro call can be retrieved with the
+    /// [`call_site`](ExpnInfo::call_site) of the [`expansion_origin`](Self::expansion_origin).
+    #[must_use]
+    pub fn start_loc(&self) -> Option<FileLoc<'ast>> {
+        self.file_loc(self.start)
+    }
+
+    /// Returns the file location of the end of this [`Span`]. The end is
+    /// exclusive, the column points to the character after the span. See
+    /// [`Span::start_loc`] for more information.
+    #[must_use]
+    pub fn end_loc(&self) -> Option<FileLoc<'ast>> {
+        self.file_loc(self.end)
+    }
+
+    fn file_loc(&self, span_pos: SpanPos) -> Option<FileLoc<'ast>> {
+        let SpanSource::File(file) = self.source() else {
+            return None;
+        };
+        let pos = file.try_to_file_pos(span_pos)?;
+        Some(FileLoc { file, pos })
+    }
+
+    /// Returns `true`, if the source code of this [`Span`] contains a Unicode
+    /// code point, that changes the text direction, like `U+202E` (right-to-left
+    /// override). This also checks comments and the content of string literals.
+    /// These code points can make the source code appear different from how the
+    /// compiler reads it.
+    ///
+    /// This returns `false`, if the [snippet](Self::snippet) is unavailable,
+    /// for example, if the span crosses a file boundary. A byte order mark (BOM)
+    /// at the start of a file is never part of a span.
+    ///
+    /// See: <https://trojansource.codes/>
+    pub fn contains_bidi_control(&self) -> bool {
+        self.snippet()
+            .map_or(false, |snippet| snippet.chars().any(is_bidi_control_char))
+    }
+
+    /// Returns `true`, if the source code of this [`Span`] contains an invisible
+    /// zero-width code point, like `U+200B` (zero width space). Like
+    /// [`Span::contains_bidi_control`], this also checks comments and the content
+    /// of string literals. These code points can make identifiers or strings
+    /// appear identical, even if they're different.
+    ///
+    /// This returns `false`, if the [snippet](Self::snippet) is unavailable.
+    pub fn contains_zero_width(&self) -> bool {
+        self.snippet()
+            .map_or(false, |snippet| snippet.chars().any(is_zero_width_char))
+    }
+}
+
+/// Returns `true`, for the bidirectional formatting characters, which can be
+/// abused to reorder the displayed source code. This is the same list that
+/// rustc uses for the `text_direction_codepoint_in_*` lints.
+pub(crate) fn is_bidi_control_char(c: char) -> bool {
+    matches!(c, '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}')
+}
+
+/// Returns `true`, for the zero-width characters, which are invisible in most
+/// editors. This includes the zero-width space, joiners, direction marks, the
+/// word joiner and the zero-width no-break space (`U+FEFF`).
+pub(crate) fn is_zero_width_char(c: char) -> bool {
+    matches!(c, '\u{200B}'..='\u{200F}' | '\u{2060}' | '\u{FEFF}')
 }
 
 impl<'ast> HasSpan<'ast> for Span<'ast> {
@@ -480,19 +638,20 @@ pub struct FilePos<'ast> {
     /// The lifetime is not needed right now, but I want to have it, to potentualy
     /// add more behavior to this struct.
     _lifetime: PhantomData<&'ast ()>,
-    /// The 1-indexed line in bytes
+    /// The 1-indexed line
     line: usize,
-    /// The 1-indexed column in bytes
+    /// The 1-indexed column in characters
     column: usize,
 }
 
 impl<'ast> FilePos<'ast> {
-    /// Returns the 1-indexed line location in bytes
+    /// Returns the 1-indexed line location
     pub fn line(&self) -> usize {
         self.line
     }
 
-    /// Returns the 1-indexed column location in bytes
+    /// Returns the 1-indexed column location in characters. This matches the
+    /// columns in rustc's diagnostics.
     pub fn column(&self) -> usize {
         self.column
     }
@@ -509,6 +668,42 @@ impl<'ast> FilePos<'ast> {
     }
 }
 
+/// A [`FilePos`] together with the file it belongs to, as returned by
+/// [`Span::start_loc`] and [`Span::end_loc`].
+#[derive(Debug, Clone, Copy)]
+pub struct FileLoc<'ast> {
+    file: &'ast FileInfo<'ast>,
+    pos: FilePos<'ast>,
+}
+
+impl<'ast> FileLoc<'ast> {
+    /// Returns the name of the file, as it's displayed by rustc. This is
+    /// usually a path relative to the workspace root.
+    pub fn file(&self) -> &'ast str {
+        self.file.file.get()
+    }
+
+    /// Returns the [`FileInfo`] of the file.
+    pub fn file_info(&self) -> &'ast FileInfo<'ast> {
+        self.file
+    }
+
+    /// Returns the 1-indexed line location
+    pub fn line(&self) -> usize {
+        self.pos.line()
+    }
+
+    /// Returns the 1-indexed column location in characters
+    pub fn column(&self) -> usize {
+        self.pos.column()
+    }
+
+    /// Returns the [`FilePos`] of this location.
+    pub fn pos(&self) -> FilePos<'ast> {
+        self.pos
+    }
+}
+
 /// The [`Span`] belongs to something, which was generated by the Compiler. This
 /// could be the imports from the prelude or the testing harness.
 #[repr(C)]
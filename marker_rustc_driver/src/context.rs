@@ -1,22 +1,48 @@
 use std::cell::{OnceCell, RefCell};
 
-use marker_adapter::context::{AstMapWrapper, MarkerContextDriver, MarkerContextWrapper};
+use marker_adapter::{
+    context::{AstMapWrapper, MarkerContextDriver, MarkerContextWrapper},
+    Adapter, ManifestInfo,
+};
 use marker_api::{
-    common::{SpanId, SymbolId},
+    ast::{Derive, FormatArgs, ItemField, PanicMacro},
+    common::{DriverTyId, Edition, Endianness, NodeId, SpanId, SymbolId, TargetInfo},
+    context::TryConversion,
     diagnostic::Diagnostic,
+    manifest::{Dependency, DependencyKind, Feature, Manifest},
     prelude::*,
+    sem::{ConstFnViolation, ConstInt, TyLayout},
 };
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use rustc_hir as hir;
 use rustc_lint::LintStore;
-use rustc_middle::ty::TyCtxt;
+use rustc_middle::ty::print::{with_crate_prefix, with_no_trimmed_paths};
+use rustc_middle::ty::{self, TyCtxt, TypeVisitableExt};
 
 use crate::conversion::{marker::MarkerConverter, rustc::RustcConverter};
 
 use self::storage::Storage;
 
+mod annotation;
+mod const_fn;
+mod derivable;
+pub mod diag;
+pub mod format_args;
+mod identity_conversion;
+mod irrefutable;
+mod item_usage;
+mod iterator;
+mod last_use;
+mod manifest;
 mod map;
+mod needless_collect;
+mod node_at;
+mod panic_macro;
+mod param_usage;
 pub mod storage;
+mod test_code;
+mod try_conversion;
+mod unwrap_guard;
 
 /// This is the central context for the rustc driver and the struct providing the
 /// callback implementation for [`MarkerContext`](`marker_api::context::MarkerContext`).
@@ -40,10 +66,32 @@ pub struct RustcContext<'ast, 'tcx> {
     /// which makes it safe to access afterwards.
     ast_cx: OnceCell<&'ast MarkerContext<'ast>>,
     resolved_ty_ids: RefCell<FxHashMap<&'ast str, &'ast [TyDefId]>>,
+    derives: RefCell<FxHashMap<ItemId, &'ast [Derive<'ast>]>>,
+    manifest_info: Option<&'ast ManifestInfo>,
+    manifest: OnceCell<Option<&'ast Manifest<'ast>>>,
+    target: OnceCell<&'ast TargetInfo<'ast>>,
+    used_items: OnceCell<FxHashSet<hir::def_id::DefId>>,
+    buffered_diags: RefCell<Vec<diag::BufferedDiagnostic>>,
+    /// The lints, which have been disabled by the lint filters. Diagnostics of
+    /// these lints are not emitted.
+    disabled_lints: &'ast [&'static marker_api::Lint],
+    /// The files, in which diagnostics are emitted.
+    path_filter: diag::PathFilter,
+    /// The adapter is used to add the name of the lint crate to emitted
+    /// diagnostics. It's only set, if `--show-lint-source` was requested.
+    lint_source_adapter: Option<&'ast Adapter>,
 }
 
 impl<'ast, 'tcx> RustcContext<'ast, 'tcx> {
-    pub fn new(rustc_cx: TyCtxt<'tcx>, lint_store: &'tcx LintStore, storage: &'ast Storage<'ast>) -> &'ast Self {
+    pub fn new(
+        rustc_cx: TyCtxt<'tcx>,
+        lint_store: &'tcx LintStore,
+        storage: &'ast Storage<'ast>,
+        manifest_info: Option<&'ast ManifestInfo>,
+        disabled_lints: &'ast [&'static marker_api::Lint],
+        path_filter: diag::PathFilter,
+        lint_source_adapter: Option<&'ast Adapter>,
+    ) -> &'ast Self {
         // Create context
         let driver_cx = storage.alloc(Self {
             rustc_cx,
@@ -53,6 +101,15 @@ impl<'ast, 'tcx> RustcContext<'ast, 'tcx> {
             rustc_converter: RustcConverter::new(rustc_cx),
             ast_cx: OnceCell::new(),
             resolved_ty_ids: RefCell::default(),
+            derives: RefCell::default(),
+            manifest_info,
+            manifest: OnceCell::new(),
+            target: OnceCell::new(),
+            used_items: OnceCell::new(),
+            buffered_diags: RefCell::default(),
+            disabled_lints,
+            path_filter,
+            lint_source_adapter,
         });
 
         // Create and link `MarkerContext`
@@ -73,6 +130,43 @@ impl<'ast, 'tcx> RustcContext<'ast, 'tcx> {
         // The `OnceCell` is filled in the new function and can never be not set.
         self.ast_cx.get().unwrap()
     }
+
+    /// Returns `true`, if the type uses generic parameters, which are not declared
+    /// by the given item or its parents. Parameters of nested items, like the
+    /// parameters of a method in an impl block, can't be resolved with the
+    /// generics of the outer item.
+    fn has_foreign_param(&self, owner: hir::def_id::DefId, rustc_ty: ty::Ty<'tcx>) -> bool {
+        let param_count = self.rustc_cx.generics_of(owner).count();
+        rustc_ty.walk().any(|arg| {
+            matches!(arg.unpack(), ty::GenericArgKind::Type(arg_ty)
+                if matches!(arg_ty.kind(), ty::Param(param) if param.index as usize >= param_count))
+        })
+    }
+
+    fn alloc_const_value(
+        &'ast self,
+        valtree: ty::ValTree<'tcx>,
+        ty: ty::Ty<'tcx>,
+    ) -> Option<&'ast marker_api::sem::ConstValue<'ast>> {
+        let value = self.marker_converter.to_const_value(valtree, ty)?;
+        Some(self.storage.alloc(value))
+    }
+
+    /// Returns the [`hir::HirId`] of the given method call expression and the
+    /// [`DefId`](hir::def_id::DefId) of the called method. For trait methods, this
+    /// is the method of the trait and not the method of the implementation.
+    fn method_call_target(&self, expr: ExprId) -> Option<(hir::HirId, hir::def_id::DefId)> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        let hir::Node::Expr(hir::Expr {
+            kind: hir::ExprKind::MethodCall(..),
+            ..
+        }) = self.rustc_cx.hir().get(hir_id)
+        else {
+            return None;
+        };
+        let method = typeck_body_of(self.rustc_cx, hir_id).type_dependent_def_id(hir_id)?;
+        Some((hir_id, method))
+    }
 }
 
 impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
@@ -80,41 +174,27 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
         let Some(id) = self.rustc_converter.try_to_hir_id_from_emission_node(diag.node) else {
             return;
         };
+        if self.disabled_lints.iter().any(|lint| std::ptr::eq(*lint, diag.lint)) {
+            return;
+        }
+        if self.is_ignored_path(diag.span) {
+            return;
+        }
         let lint = self.rustc_converter.to_lint(diag.lint);
-        self.rustc_cx.struct_span_lint_hir(
-            lint,
-            id,
-            self.rustc_converter.to_span(diag.span),
-            diag.msg().to_string(),
-            |builder| {
-                for part in diag.parts.get() {
-                    match part {
-                        marker_api::diagnostic::DiagnosticPart::Help { msg } => {
-                            builder.help(msg.get().to_string());
-                        },
-                        marker_api::diagnostic::DiagnosticPart::HelpSpan { msg, span } => {
-                            builder.span_help(self.rustc_converter.to_span(span), msg.get().to_string());
-                        },
-                        marker_api::diagnostic::DiagnosticPart::Note { msg } => {
-                            builder.note(msg.get().to_string());
-                        },
-                        marker_api::diagnostic::DiagnosticPart::NoteSpan { msg, span } => {
-                            builder.span_note(self.rustc_converter.to_span(span), msg.get().to_string());
-                        },
-                        marker_api::diagnostic::DiagnosticPart::Suggestion { msg, span, sugg, app } => {
-                            builder.span_suggestion(
-                                self.rustc_converter.to_span(span),
-                                msg.get().to_string(),
-                                sugg.get().to_string(),
-                                self.rustc_converter.to_applicability(*app),
-                            );
-                        },
-                        _ => unreachable!(),
-                    }
-                }
-                builder
-            },
-        );
+        let (mut level, src) = self.rustc_cx.lint_level_at_node(lint, id);
+        // The level override is only applied to enabled lints, to respect `#[allow]`
+        // and `#[expect]` attributes. It's still limited by the lint cap.
+        if let Some(api_level) = diag.level.copy()
+            && matches!(
+                level,
+                rustc_lint::Level::Warn | rustc_lint::Level::Deny | rustc_lint::Level::Forbid
+            )
+        {
+            let cap = self.rustc_cx.sess.opts.lint_cap.unwrap_or(rustc_lint::Level::Forbid);
+            level = self.rustc_converter.to_lint_level(api_level).min(cap);
+        }
+        let diag = self.buffer_diag(diag, id, level, src);
+        self.buffered_diags.borrow_mut().push(diag);
     }
 
     fn resolve_ty_ids(&'ast self, path: &str) -> &'ast [TyDefId] {
@@ -190,11 +270,511 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
         ids
     }
 
+    fn crate_attrs(&'ast self) -> &'ast [marker_api::ast::Attribute<'ast>] {
+        self.marker_converter.attrs(hir::CRATE_HIR_ID)
+    }
+
+    fn manifest(&'ast self) -> Option<&'ast Manifest<'ast>> {
+        *self.manifest.get_or_init(|| {
+            let info = self.manifest_info?;
+            let edition = match info.edition.as_str() {
+                "2015" => Some(Edition::Edition2015),
+                "2018" => Some(Edition::Edition2018),
+                "2021" => Some(Edition::Edition2021),
+                _ => None,
+            };
+
+            let enabled_features: Vec<_> = self
+                .rustc_cx
+                .sess
+                .parse_sess
+                .config
+                .iter()
+                .filter(|(name, _)| *name == rustc_span::sym::feature)
+                .filter_map(|(_, value)| *value)
+                .collect();
+            let spans = manifest::load_manifest_spans(self.rustc_cx, &info.manifest_dir).unwrap_or_default();
+            let to_span_id = |span: Option<rustc_span::Span>| span.map(|span| self.marker_converter.to_span_id(span));
+            let features = self.storage.alloc_slice(info.features.iter().map(|feature| {
+                Feature::new(
+                    self.storage.alloc_str(&feature.name),
+                    self.storage
+                        .alloc_slice(feature.enables.iter().map(|value| self.storage.alloc_str(value).into())),
+                    enabled_features.iter().any(|name| name.as_str() == feature.name),
+                    to_span_id(spans.features.get(&feature.name).copied()),
+                )
+            }));
+            let dependencies = self.storage.alloc_slice(info.dependencies.iter().map(|dep| {
+                let kind = match dep.kind.as_str() {
+                    "dev" => DependencyKind::Dev,
+                    "build" => DependencyKind::Build,
+                    _ => DependencyKind::Normal,
+                };
+                let local_name = dep.rename.as_deref().unwrap_or(&dep.name);
+                Dependency::new(
+                    self.storage.alloc_str(&dep.name),
+                    dep.rename.as_deref().map(|name| self.storage.alloc_str(name)),
+                    kind,
+                    dep.optional,
+                    to_span_id(spans.dependency(kind, local_name)),
+                )
+            }));
+
+            Some(self.storage.alloc(Manifest::new(
+                edition,
+                features,
+                dependencies,
+                to_span_id(spans.package),
+                self.marker_converter.to_item_id(hir::def_id::CRATE_DEF_ID.to_def_id()),
+            )))
+        })
+    }
+
+    fn target(&'ast self) -> &'ast TargetInfo<'ast> {
+        self.target.get_or_init(|| {
+            let sess = self.rustc_cx.sess;
+            let target = &sess.target;
+            let endianness = match target.endian {
+                rustc_target::abi::Endian::Little => Endianness::Little,
+                rustc_target::abi::Endian::Big => Endianness::Big,
+            };
+            self.storage.alloc(TargetInfo::new(
+                self.storage.alloc_str(sess.opts.target_triple.triple()),
+                target.pointer_width,
+                endianness,
+                self.storage.alloc_str(&target.os),
+                self.storage.alloc_str(&target.arch),
+            ))
+        })
+    }
+
+    fn object_safety_violations(
+        &'ast self,
+        trait_id: TyDefId,
+    ) -> Option<&'ast [marker_api::sem::ObjectSafetyViolation<'ast>]> {
+        let def_id = self.rustc_converter.to_def_id(trait_id);
+        if !matches!(self.rustc_cx.def_kind(def_id), hir::def::DefKind::Trait) {
+            return None;
+        }
+
+        let violations = self.rustc_cx.object_safety_violations(def_id);
+        Some(self.marker_converter.to_object_safety_violations(violations))
+    }
+
+    fn enclosing_loop(&'ast self, expr: ExprId) -> Option<ExprId> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        for (parent_id, node) in self.rustc_cx.hir().parent_iter(hir_id) {
+            match node {
+                hir::Node::Expr(parent) => match parent.kind {
+                    // `for` and `while` loops use the id of the desugared loop
+                    hir::ExprKind::Loop(..) => return Some(self.marker_converter.to_expr_id(parent_id)),
+                    // Closures and async blocks can't be exited with `break` or `continue`
+                    hir::ExprKind::Closure(_) => return None,
+                    _ => {},
+                },
+                hir::Node::Item(_)
+                | hir::Node::TraitItem(_)
+                | hir::Node::ImplItem(_)
+                | hir::Node::ForeignItem(_)
+                | hir::Node::AnonConst(_)
+                | hir::Node::ConstBlock(_) => return None,
+                _ => {},
+            }
+        }
+        None
+    }
+
+    fn normalize_alias(&'ast self, ty: DriverTyId) -> Option<marker_api::sem::TyKind<'ast>> {
+        let rustc_ty = self.rustc_converter.to_driver_ty_id(ty);
+        if !rustc_ty.has_projections() {
+            return None;
+        }
+
+        let normalized = self
+            .rustc_cx
+            .try_normalize_erasing_regions(rustc_middle::ty::ParamEnv::reveal_all(), rustc_ty)
+            .ok()?;
+        // Generic parameters can't be converted without knowing their owner.
+        // The normalization in these cases is also unlikely to succeed, due to
+        // the missing bounds of the parameters.
+        if normalized == rustc_ty || normalized.has_param() {
+            return None;
+        }
+        Some(self.marker_converter.to_sem_ty(normalized))
+    }
+
+    fn is_param_used(&'ast self, param: GenericId, item: ItemId) -> bool {
+        let param = self.rustc_converter.to_def_id(param);
+        let Some(item) = self.rustc_converter.to_def_id(item).as_local() else {
+            return true;
+        };
+        param_usage::is_param_used(self.rustc_cx, param, item)
+    }
+
+    fn is_used_in_crate(&'ast self, item: ItemId) -> bool {
+        let Some(item) = self.rustc_converter.to_def_id(item).as_local() else {
+            return true;
+        };
+        let used = self.used_items.get_or_init(|| item_usage::used_items(self.rustc_cx));
+        item_usage::is_used_in_crate(self.rustc_cx, used, item)
+    }
+
+    fn is_doc_hidden(&'ast self, item: ItemId) -> bool {
+        self.rustc_cx.is_doc_hidden(self.rustc_converter.to_def_id(item))
+    }
+
+    fn is_ty_doc_hidden(&'ast self, ty: TyDefId) -> bool {
+        self.rustc_cx.is_doc_hidden(self.rustc_converter.to_def_id(ty))
+    }
+
+    fn node_at(&'ast self, span: &Span<'ast>) -> Option<NodeId> {
+        let span = self.rustc_converter.to_span(span);
+        node_at::node_at(self.rustc_cx, &self.marker_converter, span)
+    }
+
+    fn panic_macro(&'ast self, expr: ExprId) -> Option<PanicMacro<'ast>> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        let (kind, message, span) = panic_macro::panic_macro(self.rustc_cx, hir_id)?;
+        Some(PanicMacro::new(
+            kind,
+            message.map(|msg| self.storage.alloc_str(&msg)),
+            self.marker_converter.to_span_id(span),
+        ))
+    }
+
+    fn is_last_use(&'ast self, expr: ExprId) -> bool {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        last_use::is_last_use(self.rustc_cx, hir_id)
+    }
+
+    fn needless_collect(&'ast self, expr: ExprId) -> Option<SpanId> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        needless_collect::needless_collect(self.rustc_cx, hir_id).map(|span| self.marker_converter.to_span_id(span))
+    }
+
+    fn format_args(&'ast self, expr: ExprId) -> Option<&'ast FormatArgs<'ast>> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        self.format_args_of(hir_id)
+    }
+
+    fn try_eval_const(&'ast self, expr: ExprId) -> Option<&'ast marker_api::sem::ConstValue<'ast>> {
+        let tcx = self.rustc_cx;
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        let owner = tcx.hir().enclosing_body_owner(hir_id);
+        if tcx.hir().body_owned_by(owner).hir_id != hir_id {
+            return None;
+        }
+
+        let def_id = owner.to_def_id();
+        let unevaluated = match tcx.def_kind(def_id) {
+            // Anonymous constants don't inherit the generics of their parent. A
+            // constant, which only consists of a generic parameter, is therefore
+            // lowered to the parameter itself.
+            hir::def::DefKind::AnonConst => match ty::Const::from_anon_const(tcx, owner).kind() {
+                ty::ConstKind::Value(valtree) => {
+                    return self.alloc_const_value(valtree, tcx.type_of(def_id).instantiate_identity());
+                },
+                ty::ConstKind::Unevaluated(unevaluated) => unevaluated,
+                _ => return None,
+            },
+            hir::def::DefKind::InlineConst => {
+                ty::UnevaluatedConst::new(def_id, ty::GenericArgs::identity_for_item(tcx, def_id))
+            },
+            _ => return None,
+        };
+        let valtree = tcx
+            .const_eval_resolve_for_typeck(tcx.param_env(def_id), unevaluated, None)
+            .ok()??;
+        self.alloc_const_value(valtree, tcx.type_of(def_id).instantiate_identity())
+    }
+
+    fn iterator_item_ty(&'ast self, ty: DriverTyId, owner: ItemId) -> Option<marker_api::sem::TyKind<'ast>> {
+        let rustc_ty = self.rustc_converter.to_driver_ty_id(ty);
+        let owner = self.rustc_converter.to_def_id(owner);
+        if self.has_foreign_param(owner, rustc_ty) {
+            return None;
+        }
+        let param_env = self.rustc_cx.param_env(owner);
+        let item_ty = iterator::iterator_item_ty(self.rustc_cx, param_env, rustc_ty)?;
+        Some(self.marker_converter.item_sem_ty(owner, item_ty))
+    }
+
+    fn ty_layout(&'ast self, ty: DriverTyId, owner: ItemId) -> Option<TyLayout> {
+        let tcx = self.rustc_cx;
+        let rustc_ty = self.rustc_converter.to_driver_ty_id(ty);
+        let owner = self.rustc_converter.to_def_id(owner);
+        if self.has_foreign_param(owner, rustc_ty) {
+            return None;
+        }
+        let param_env = tcx.param_env(owner).with_reveal_all_normalized(tcx);
+
+        let layout = tcx.layout_of(param_env.and(rustc_ty)).ok()?;
+        if layout.abi.is_unsized() {
+            return None;
+        }
+        let option_size = tcx.lang_items().option_type().and_then(|option_id| {
+            let option_ty = ty::Ty::new_adt(tcx, tcx.adt_def(option_id), tcx.mk_args(&[rustc_ty.into()]));
+            let option_layout = tcx.layout_of(param_env.and(option_ty)).ok()?;
+            Some(option_layout.size.bytes())
+        });
+
+        Some(TyLayout::new(
+            layout.size.bytes(),
+            layout.align.abi.bytes(),
+            layout.largest_niche.is_some(),
+            option_size,
+        ))
+    }
+
+    fn needs_drop(&'ast self, ty: DriverTyId, owner: ItemId) -> bool {
+        let rustc_ty = self.rustc_converter.to_driver_ty_id(ty);
+        let owner = self.rustc_converter.to_def_id(owner);
+        // Without a parameter environment, generic types are assumed to need a drop
+        let param_env = if self.has_foreign_param(owner, rustc_ty) {
+            ty::ParamEnv::reveal_all()
+        } else {
+            self.rustc_cx.param_env(owner)
+        };
+        rustc_ty.needs_drop(self.rustc_cx, param_env)
+    }
+
+    fn has_drop_impl(&'ast self, ty: DriverTyId) -> bool {
+        let rustc_ty = self.rustc_converter.to_driver_ty_id(ty);
+        rustc_ty
+            .ty_adt_def()
+            .is_some_and(|adt| adt.destructor(self.rustc_cx).is_some())
+    }
+
+    fn parses_as_expr(&'ast self, text: &str) -> bool {
+        // The diagnostics of the parser are discarded, the text isn't part of
+        // the linted crate.
+        let sess = rustc_session::parse::ParseSess::with_silent_emitter(None);
+        let Ok(mut parser) = rustc_parse::maybe_new_parser_from_source_str(
+            &sess,
+            rustc_span::FileName::anon_source_code(text),
+            text.to_string(),
+        ) else {
+            return false;
+        };
+        let parsed = match parser.parse_expr() {
+            Ok(_) => parser.token.kind == rustc_ast::token::TokenKind::Eof,
+            Err(diag) => {
+                diag.cancel();
+                false
+            },
+        };
+        // The parser recovers from some errors, which are only reported
+        parsed && sess.span_diagnostic.has_errors().is_none()
+    }
+
+    fn const_fn_violation(&'ast self, id: ItemId) -> Option<&'ast ConstFnViolation<'ast>> {
+        let def_id = self.rustc_converter.to_def_id(id);
+        let (kind, span) = const_fn::const_fn_violation(self.rustc_cx, def_id)?;
+        Some(
+            self.storage.alloc(
+                ConstFnViolation::builder()
+                    .kind(kind)
+                    .span(self.marker_converter.to_span_id(span))
+                    .build(),
+            ),
+        )
+    }
+
+    fn var_name(&'ast self, var: VarId) -> SymbolId {
+        let hir_id = self.rustc_converter.to_hir_id(var);
+        self.marker_converter.to_symbol_id(self.rustc_cx.hir().name(hir_id))
+    }
+
+    fn var_ty(&'ast self, var: VarId) -> marker_api::sem::TyKind<'ast> {
+        // The type of a binding pattern is the type of the variable
+        let hir_id = self.rustc_converter.to_hir_id(var);
+        self.marker_converter.expr_ty(hir_id)
+    }
+
+    fn var_decl_span(&'ast self, var: VarId) -> SpanId {
+        let hir_id = self.rustc_converter.to_hir_id(var);
+        self.marker_converter.to_span_id(self.rustc_cx.hir().span(hir_id))
+    }
+
+    fn call_target_path(&'ast self, expr: ExprId) -> Option<&'ast str> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        let hir::Node::Expr(expr) = self.rustc_cx.hir().get(hir_id) else {
+            return None;
+        };
+        let typeck = typeck_body_of(self.rustc_cx, hir_id);
+        let def_id = match expr.kind {
+            hir::ExprKind::MethodCall(..) => typeck.type_dependent_def_id(hir_id)?,
+            hir::ExprKind::Call(callee, _) => match callee.kind {
+                hir::ExprKind::Path(ref qpath) => match typeck.qpath_res(qpath, callee.hir_id) {
+                    hir::def::Res::Def(hir::def::DefKind::Fn | hir::def::DefKind::AssocFn, def_id) => def_id,
+                    _ => return None,
+                },
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        // Methods are referenced by their type or trait. Printing the method
+        // directly would include generics, like `std::sync::Mutex::<T>::lock`
+        let tcx = self.rustc_cx;
+        let print = |def_id| with_no_trimmed_paths!(with_crate_prefix!(tcx.def_path_str(def_id)));
+        let path = match tcx.impl_of_method(def_id) {
+            Some(impl_id) => {
+                let name = tcx.item_name(def_id);
+                match tcx.trait_id_of_impl(impl_id) {
+                    Some(trait_id) => format!("{}::{name}", print(trait_id)),
+                    None => match tcx.type_of(impl_id).instantiate_identity().kind() {
+                        ty::Adt(adt, _) => format!("{}::{name}", print(adt.did())),
+                        _ => print(def_id),
+                    },
+                }
+            },
+            None => print(def_id),
+        };
+        Some(self.storage.alloc_str(&path))
+    }
+
+    fn try_residual_conversion(&'ast self, expr: ExprId) -> Option<TryConversion<'ast>> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        let (source, target, from_impl) = try_conversion::try_residual_conversion(self.rustc_cx, hir_id)?;
+        Some(TryConversion::new(
+            self.marker_converter.body_sem_ty(hir_id, source),
+            self.marker_converter.body_sem_ty(hir_id, target),
+            self.marker_converter.to_item_id(from_impl),
+        ))
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn enum_discriminants(&'ast self, enum_id: TyDefId) -> Option<&'ast [ConstInt<'ast>]> {
+        let def_id = self.rustc_converter.to_def_id(enum_id);
+        if !matches!(self.rustc_cx.def_kind(def_id), hir::def::DefKind::Enum) {
+            return None;
+        }
+        let adt = self.rustc_cx.adt_def(def_id);
+        if !adt.is_payloadfree() {
+            return None;
+        }
+
+        let discriminants: Vec<_> = adt
+            .discriminants(self.rustc_cx)
+            .map(|(_, discr)| {
+                // The value is stored as bits, signed values have to be sign extended.
+                let (size, signed) = discr.ty.int_size_and_signed(self.rustc_cx);
+                let bits = if signed { size.sign_extend(discr.val) } else { discr.val };
+                ConstInt::new(bits, signed, self.marker_converter.to_sem_ty(discr.ty))
+            })
+            .collect();
+        Some(self.storage.alloc_slice(discriminants))
+    }
+
+    fn transparent_inner_field(&'ast self, struct_id: ItemId) -> Option<&'ast ItemField<'ast>> {
+        let def_id = self.rustc_converter.to_def_id(struct_id).as_local()?;
+        let hir::Node::Item(hir::Item {
+            kind: hir::ItemKind::Struct(data, _),
+            ..
+        }) = self.rustc_cx.hir().find_by_def_id(def_id)?
+        else {
+            return None;
+        };
+
+        // This follows rustc's check for `#[repr(transparent)]`, fields with
+        // an unknown layout are never trivial.
+        let param_env = self.rustc_cx.param_env(def_id);
+        let mut non_trivial = data.fields().iter().filter(|field| {
+            let ty = self.rustc_cx.type_of(field.def_id).instantiate_identity();
+            !self
+                .rustc_cx
+                .layout_of(param_env.and(ty))
+                .is_ok_and(|layout| layout.is_1zst())
+        });
+        let field = non_trivial.next()?;
+        if non_trivial.next().is_some() {
+            return None;
+        }
+        self.marker_converter.field(field.hir_id)
+    }
+
+    fn derivable_trait(&'ast self, impl_id: ItemId) -> Option<&'ast str> {
+        let def_id = self.rustc_converter.to_def_id(impl_id).as_local()?;
+        derivable::derivable_trait(self.rustc_cx, def_id)
+    }
+
+    fn auto_trait_impl(&'ast self, impl_id: ItemId) -> Option<&'ast str> {
+        let def_id = self.rustc_converter.to_def_id(impl_id);
+        if !matches!(
+            self.rustc_cx.def_kind(def_id),
+            hir::def::DefKind::Impl { of_trait: true }
+        ) {
+            return None;
+        }
+        let trait_id = self.rustc_cx.trait_id_of_impl(def_id)?;
+        self.rustc_cx
+            .trait_is_auto(trait_id)
+            .then(|| self.storage.alloc_str(self.rustc_cx.item_name(trait_id).as_str()))
+    }
+
+    fn is_local_def(&'ast self, id: TyDefId) -> bool {
+        self.rustc_converter.to_def_id(id).is_local()
+    }
+
+    fn is_in_async_context(&'ast self, expr: ExprId) -> bool {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        for (_, node) in self.rustc_cx.hir().parent_iter(hir_id) {
+            match node {
+                // The bodies of `async fn`s and `async` closures are desugared
+                // to `async` blocks, which are represented as closures.
+                hir::Node::Expr(hir::Expr {
+                    kind: hir::ExprKind::Closure(closure),
+                    ..
+                }) => {
+                    return matches!(
+                        self.rustc_cx.hir().body(closure.body).coroutine_kind,
+                        Some(hir::CoroutineKind::Async(_))
+                    );
+                },
+                hir::Node::Item(_)
+                | hir::Node::TraitItem(_)
+                | hir::Node::ImplItem(_)
+                | hir::Node::ForeignItem(_)
+                | hir::Node::AnonConst(_)
+                | hir::Node::ConstBlock(_) => return false,
+                _ => {},
+            }
+        }
+        false
+    }
+
+    fn is_in_test_code(&'ast self, node: NodeId) -> bool {
+        self.rustc_converter
+            .try_to_hir_id_from_emission_node(node)
+            .is_some_and(|hir_id| test_code::is_in_test_code(self.rustc_cx, hir_id))
+    }
+
+    fn is_guarded_unwrap(&'ast self, expr: ExprId) -> bool {
+        unwrap_guard::is_guarded_unwrap(self.rustc_cx, self.rustc_converter.to_hir_id(expr))
+    }
+
+    fn is_identity_conversion(&'ast self, expr: ExprId) -> bool {
+        identity_conversion::is_identity_conversion(self.rustc_cx, self.rustc_converter.to_hir_id(expr))
+    }
+
     fn expr_ty(&'ast self, expr: ExprId) -> marker_api::sem::TyKind<'ast> {
         let hir_id = self.rustc_converter.to_hir_id(expr);
         self.marker_converter.expr_ty(hir_id)
     }
 
+    fn var_init(&'ast self, var: VarId) -> Option<ExprId> {
+        let hir_id = self.rustc_converter.to_hir_id(var);
+        if let Some(hir::Node::Local(local)) = self.rustc_cx.hir().find_parent(hir_id)
+            && local.pat.hir_id == hir_id
+            && let hir::PatKind::Binding(hir::BindingAnnotation::NONE, ..) = local.pat.kind
+            && let Some(init) = local.init
+        {
+            return Some(self.marker_converter.to_expr_id(init.hir_id));
+        }
+        None
+    }
+
     fn span(&'ast self, span_id: SpanId) -> &'ast Span<'ast> {
         let rustc_span = self.rustc_converter.to_span_from_id(span_id);
         self.storage.alloc(self.marker_converter.to_span(rustc_span))
@@ -222,6 +802,22 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
         )
     }
 
+    fn file_span(&'ast self, file: &marker_api::span::FileInfo<'ast>) -> Option<&'ast Span<'ast>> {
+        let files = self.rustc_cx.sess.source_map().files();
+        let src_file = files.iter().find(|src_file| match &src_file.name {
+            rustc_span::FileName::Real(
+                rustc_span::RealFileName::LocalPath(file_path)
+                | rustc_span::RealFileName::Remapped {
+                    virtual_name: file_path,
+                    ..
+                },
+            ) => file_path.to_string_lossy() == file.file(),
+            _ => false,
+        })?;
+        let span = rustc_span::Span::with_root_ctxt(src_file.start_pos, src_file.end_position());
+        Some(self.storage.alloc(self.marker_converter.to_span(span)))
+    }
+
     fn span_expn_info(
         &'ast self,
         expn_id: marker_api::common::ExpnId,
@@ -245,6 +841,230 @@ impl<'ast, 'tcx: 'ast> MarkerContextDriver<'ast> for RustcContext<'ast, 'tcx> {
     fn resolve_method_target(&'ast self, _id: ExprId) -> ItemId {
         todo!()
     }
+
+    fn attrs(&'ast self, node: NodeId) -> &'ast [marker_api::ast::Attribute<'ast>] {
+        self.rustc_converter
+            .try_to_hir_id_from_emission_node(node)
+            .map_or(&[], |hir_id| self.marker_converter.attrs(hir_id))
+    }
+
+    fn aliased_sem_ty(&'ast self, item: ItemId) -> Option<marker_api::sem::TyKind<'ast>> {
+        let def_id = self.rustc_converter.to_def_id(item);
+        let has_value = match self.rustc_cx.def_kind(def_id) {
+            hir::def::DefKind::TyAlias => true,
+            hir::def::DefKind::AssocTy => self.rustc_cx.defaultness(def_id).has_value(),
+            _ => false,
+        };
+        if !has_value {
+            return None;
+        }
+
+        let ty = self.rustc_cx.type_of(def_id).instantiate_identity();
+        Some(self.marker_converter.item_sem_ty(def_id, ty))
+    }
+
+    fn overloaded_op_fn(&'ast self, expr: ExprId) -> Option<ItemId> {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        // Rustc removes the method resolution of builtin operations on
+        // primitive types during writeback.
+        typeck_body_of(self.rustc_cx, hir_id)
+            .type_dependent_def_id(hir_id)
+            .map(|def_id| self.marker_converter.to_item_id(def_id))
+    }
+
+    fn method_trait(&'ast self, expr: ExprId) -> Option<ItemId> {
+        let (_, method) = self.method_call_target(expr)?;
+        self.rustc_cx
+            .trait_of_item(method)
+            .map(|trait_id| self.marker_converter.to_item_id(trait_id))
+    }
+
+    fn is_dynamic_dispatch(&'ast self, expr: ExprId) -> bool {
+        let Some((hir_id, method)) = self.method_call_target(expr) else {
+            return false;
+        };
+        if self.rustc_cx.trait_of_item(method).is_none() {
+            return false;
+        }
+        // The first generic argument of a trait method is the `Self` type. For
+        // calls on trait objects, this is the `dyn Trait` type, even if the
+        // method is defined by a supertrait.
+        let args = typeck_body_of(self.rustc_cx, hir_id).node_args(hir_id);
+        args.types()
+            .next()
+            .is_some_and(|self_ty| matches!(self_ty.kind(), ty::Dynamic(..)))
+    }
+
+    fn async_output_ty(&'ast self, item: ItemId) -> Option<marker_api::sem::TyKind<'ast>> {
+        let def_id = self.rustc_converter.to_def_id(item);
+        if !self.rustc_cx.asyncness(def_id).is_async() {
+            return None;
+        }
+
+        // Async functions return an opaque type, or a projection to an
+        // associated type in traits. Both have a `Future<Output = T>` bound,
+        // where `Output` is the only associated type of `Future`.
+        let sig = self.rustc_cx.fn_sig(def_id).instantiate_identity().skip_binder();
+        let rustc_middle::ty::Alias(_, alias) = sig.output().kind() else {
+            return None;
+        };
+        let future_trait = self.rustc_cx.lang_items().future_trait()?;
+        let output = self
+            .rustc_cx
+            .explicit_item_bounds(alias.def_id)
+            .instantiate_identity_iter_copied()
+            .find_map(|(clause, _span)| match clause.kind().skip_binder() {
+                rustc_middle::ty::ClauseKind::Projection(proj)
+                    if proj.projection_ty.trait_def_id(self.rustc_cx) == future_trait =>
+                {
+                    proj.term.ty()
+                },
+                _ => None,
+            })?;
+        Some(self.marker_converter.item_sem_ty(def_id, output))
+    }
+
+    fn is_iterator_adapter(&'ast self, expr: ExprId) -> bool {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        iterator::is_iterator_adapter(self.rustc_cx, hir_id)
+    }
+
+    fn all_supertraits(&'ast self, item: ItemId) -> &'ast [TyDefId] {
+        let def_id = self.rustc_converter.to_def_id(item);
+        if !matches!(self.rustc_cx.def_kind(def_id), hir::def::DefKind::Trait) {
+            return &[];
+        }
+        // The first id is the trait itself
+        let supertraits: Vec<_> = rustc_trait_selection::traits::supertrait_def_ids(self.rustc_cx, def_id)
+            .skip(1)
+            .map(|super_id| self.marker_converter.to_ty_def_id(super_id))
+            .collect();
+        self.storage.alloc_slice(supertraits)
+    }
+
+    fn annotation_matches_init(&'ast self, stmt: StmtId) -> bool {
+        let hir_id = self.rustc_converter.to_hir_id(stmt);
+        let Some(hir::Node::Stmt(hir::Stmt {
+            kind: hir::StmtKind::Local(local),
+            ..
+        })) = self.rustc_cx.hir().find(hir_id)
+        else {
+            return false;
+        };
+        let (Some(_), Some(init)) = (local.ty, local.init) else {
+            return false;
+        };
+
+        // The type of the local is the annotated type, with all placeholders
+        // inferred. The type of the init expression is taken before any
+        // coercions, which would otherwise hide required annotations.
+        let typeck = typeck_body_of(self.rustc_cx, hir_id);
+        let Some(local_ty) = typeck.node_type_opt(local.hir_id) else {
+            return false;
+        };
+        let init_ty = typeck.expr_ty(init);
+        !local_ty.references_error()
+            && self.rustc_cx.erase_regions(local_ty) == self.rustc_cx.erase_regions(init_ty)
+            && annotation::is_independent_of_annotation(self.rustc_cx, typeck, init)
+    }
+
+    fn let_expr_is_irrefutable(&'ast self, expr: ExprId) -> bool {
+        let hir_id = self.rustc_converter.to_hir_id(expr);
+        let Some(hir::Node::Expr(hir::Expr {
+            kind: hir::ExprKind::Let(let_expr),
+            ..
+        })) = self.rustc_cx.hir().find(hir_id)
+        else {
+            return false;
+        };
+        let typeck = typeck_body_of(self.rustc_cx, hir_id);
+        irrefutable::is_irrefutable(typeck, let_expr.pat)
+    }
+
+    fn derives(&'ast self, item: ItemId) -> &'ast [Derive<'ast>] {
+        let def_id = self.rustc_converter.to_def_id(item);
+        if !matches!(
+            self.rustc_cx.def_kind(def_id),
+            hir::def::DefKind::Struct | hir::def::DefKind::Enum | hir::def::DefKind::Union
+        ) {
+            return &[];
+        }
+        if let Some(derives) = self.derives.borrow().get(&item) {
+            return derives;
+        }
+
+        // The `#[derive(...)]` attribute is removed during expansion. The derives
+        // are therefore collected from the trait impls, that they generated. The
+        // output of a derive macro is placed next to the item, only the items of
+        // the same module have to be checked.
+        let Some(local_id) = def_id.as_local() else {
+            return &[];
+        };
+        let module = self.rustc_cx.parent_module_from_def_id(local_id);
+        let mut derive_expns: Vec<(rustc_span::ExpnId, rustc_span::ExpnData, hir::def_id::DefId)> = vec![];
+        for item_id in self.rustc_cx.hir_module_items(module).items() {
+            let impl_id = item_id.owner_id.to_def_id();
+            if !matches!(
+                self.rustc_cx.def_kind(impl_id),
+                hir::def::DefKind::Impl { of_trait: true }
+            ) {
+                continue;
+            }
+            let expn_id = self.rustc_cx.def_span(impl_id).ctxt().outer_expn();
+            let expn = expn_id.expn_data();
+            let rustc_span::ExpnKind::Macro(rustc_span::MacroKind::Derive, name) = expn.kind else {
+                continue;
+            };
+            let Some(trait_ref) = self.rustc_cx.impl_trait_ref(impl_id) else {
+                continue;
+            };
+            let trait_ref = trait_ref.instantiate_identity();
+            if trait_ref.self_ty().ty_adt_def().map(rustc_middle::ty::AdtDef::did) != Some(def_id) {
+                continue;
+            }
+
+            // Some derives, like `PartialEq`, implement multiple traits. The trait
+            // with the same name as the derive macro is preferred.
+            let trait_id = trait_ref.def_id;
+            let is_named_trait = name.as_str().rsplit("::").next() == Some(self.rustc_cx.item_name(trait_id).as_str());
+            match derive_expns.iter_mut().find(|(id, _, _)| *id == expn_id) {
+                Some(entry) if is_named_trait => entry.2 = trait_id,
+                Some(_) => {},
+                None => derive_expns.push((expn_id, expn, trait_id)),
+            }
+        }
+        derive_expns.sort_by_key(|(_, expn, _)| expn.call_site.lo());
+
+        let derives = self
+            .storage
+            .alloc_slice(derive_expns.into_iter().map(|(_, expn, trait_id)| {
+                let rustc_span::ExpnKind::Macro(_, name) = expn.kind else {
+                    unreachable!("only derive expansions are collected")
+                };
+                let is_std = expn.macro_def_id.map_or(false, |macro_id| {
+                    matches!(
+                        self.rustc_cx.crate_name(macro_id.krate),
+                        rustc_span::sym::core | rustc_span::sym::alloc | rustc_span::sym::std
+                    )
+                });
+                Derive::new(
+                    self.marker_converter.to_symbol_id(name),
+                    self.marker_converter.to_span_id(expn.call_site),
+                    self.marker_converter.to_ty_def_id(trait_id),
+                    is_std,
+                )
+            }));
+        self.derives.borrow_mut().insert(item, derives);
+        derives
+    }
+}
+
+/// Returns the [`TypeckResults`](ty::TypeckResults) of the body containing the
+/// given node. The owner of the [`HirId`](hir::HirId) can't be used for this, as
+/// anonymous constants, like array lengths, are type checked separately.
+fn typeck_body_of(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> &ty::TypeckResults<'_> {
+    let map = tcx.hir();
+    tcx.typeck_body(map.body_owned_by(map.enclosing_body_owner(hir_id)))
 }
 
 fn select_children_with_name(
@@ -0,0 +1,90 @@
+use rustc_ast::{LitFloatType, LitIntType, LitKind};
+use rustc_hir as hir;
+use rustc_middle::ty::{self, TyCtxt};
+
+/// Checks if the type of the expression is determined without the type
+/// annotation of the local, it's assigned to. This is a conservative check.
+/// Expressions, which might infer generic arguments or literal types from the
+/// annotation, like `Vec::new()` or `1`, return `false`.
+pub(super) fn is_independent_of_annotation<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx ty::TypeckResults<'tcx>,
+    expr: &'tcx hir::Expr<'tcx>,
+) -> bool {
+    let is_independent = |expr| is_independent_of_annotation(tcx, typeck, expr);
+    match expr.kind {
+        hir::ExprKind::Lit(lit) => !matches!(
+            lit.node,
+            LitKind::Int(_, LitIntType::Unsuffixed) | LitKind::Float(_, LitFloatType::Unsuffixed)
+        ),
+        hir::ExprKind::Path(_) => has_explicit_args(tcx, typeck, expr.hir_id),
+        hir::ExprKind::Call(
+            callee @ hir::Expr {
+                kind: hir::ExprKind::Path(_),
+                ..
+            },
+            _,
+        ) => has_explicit_args(tcx, typeck, callee.hir_id),
+        hir::ExprKind::MethodCall(_, receiver, _, _) => {
+            let Some(def_id) = typeck.type_dependent_def_id(expr.hir_id) else {
+                return false;
+            };
+            // Generic parameters of the method or trait, like `T` in `Into<T>`,
+            // might be inferred from the annotation.
+            let counts = tcx.generics_of(def_id).own_counts();
+            let trait_has_params = tcx.trait_of_item(def_id).is_some_and(|trait_id| {
+                let counts = tcx.generics_of(trait_id).own_counts();
+                // `Self` is the first type parameter of every trait
+                counts.types + counts.consts > 1
+            });
+            counts.types + counts.consts == 0 && !trait_has_params && is_independent(receiver)
+        },
+        hir::ExprKind::Struct(..) => match typeck.expr_ty(expr).kind() {
+            ty::Adt(_, args) => args.types().next().is_none() && args.consts().next().is_none(),
+            _ => false,
+        },
+        hir::ExprKind::AddrOf(_, _, inner) | hir::ExprKind::DropTemps(inner) => is_independent(inner),
+        hir::ExprKind::Block(block, None) => block.expr.is_some_and(is_independent),
+        hir::ExprKind::Tup(exprs) => exprs.iter().all(is_independent),
+        hir::ExprKind::Cast(_, ty) => !matches!(ty.kind, hir::TyKind::Infer),
+        _ => false,
+    }
+}
+
+/// Checks if all generic arguments of the path with the given [`HirId`](hir::HirId)
+/// have been written by the user. Lifetimes are ignored.
+fn has_explicit_args<'tcx>(tcx: TyCtxt<'tcx>, typeck: &'tcx ty::TypeckResults<'tcx>, hir_id: hir::HirId) -> bool {
+    let args = typeck.node_args(hir_id);
+    if args.types().next().is_none() && args.consts().next().is_none() {
+        return true;
+    }
+    let Some(user_ty) = typeck.user_provided_types().get(hir_id) else {
+        return false;
+    };
+    let ty::UserType::TypeOf(def_id, user_args) = user_ty.value else {
+        return false;
+    };
+    // The arguments of the impl are determined by the self type, like
+    // `Vec::<i32>` in `Vec::<i32>::new()`
+    let (own_args, self_ty) = match user_args.user_self_ty {
+        Some(self_ty) => (
+            &user_args.args[tcx.generics_of(def_id).parent_count..],
+            Some(self_ty.self_ty.into()),
+        ),
+        None => (&user_args.args[..], None),
+    };
+    // Inferred arguments are represented as bound canonical variables
+    !own_args
+        .iter()
+        .copied()
+        .chain(self_ty)
+        .any(|arg| arg.walk().any(is_inferred))
+}
+
+fn is_inferred(arg: ty::GenericArg<'_>) -> bool {
+    match arg.unpack() {
+        ty::GenericArgKind::Type(ty) => matches!(ty.kind(), ty::Bound(..)),
+        ty::GenericArgKind::Const(c) => matches!(c.kind(), ty::ConstKind::Bound(..)),
+        ty::GenericArgKind::Lifetime(_) => false,
+    }
+}
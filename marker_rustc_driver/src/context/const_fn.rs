@@ -0,0 +1,217 @@
+use marker_api::sem::ConstFnViolationKind;
+use rustc_hir as hir;
+use rustc_hir::def::DefKind;
+use rustc_hir::intravisit::{self, Visitor as _};
+use rustc_middle::mir::{
+    self, visit::Visitor, BasicBlock, BasicBlockData, Body, BorrowKind, CastKind, ConstOperand, Location, Rvalue,
+    Terminator, TerminatorKind,
+};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::Span;
+use rustc_target::spec::abi::Abi;
+
+/// Returns the first reason, why the function with the given [`DefId`](hir::def_id::DefId)
+/// can't be `const`. `None` is returned, if the function could be `const` or
+/// already is `const`.
+///
+/// This is a conservative approximation of rustc's const checking, which runs on
+/// the optimized MIR of the function. Cleanup blocks are ignored, like in rustc.
+/// Explicit calls are additionally checked in the HIR, since MIR optimizations
+/// can remove them, like `clone()` calls on `Copy` types.
+pub(super) fn const_fn_violation(tcx: TyCtxt<'_>, def_id: hir::def_id::DefId) -> Option<(ConstFnViolationKind, Span)> {
+    let unsupported = Some((
+        ConstFnViolationKind::Unsupported,
+        tcx.def_ident_span(def_id).unwrap_or_else(|| tcx.def_span(def_id)),
+    ));
+    if !matches!(tcx.def_kind(def_id), DefKind::Fn | DefKind::AssocFn) {
+        return unsupported;
+    }
+    if tcx.is_const_fn_raw(def_id) {
+        return None;
+    }
+
+    let in_trait = tcx.trait_of_item(def_id).is_some()
+        || tcx
+            .impl_of_method(def_id)
+            .and_then(|impl_id| tcx.trait_id_of_impl(impl_id))
+            .is_some();
+    let is_main = tcx.entry_fn(()).map(|(entry_id, _)| entry_id) == Some(def_id);
+    let sig = tcx.fn_sig(def_id).skip_binder();
+    if in_trait
+        || is_main
+        || tcx.asyncness(def_id).is_async()
+        || sig.abi() != Abi::Rust
+        || sig.c_variadic()
+        || !tcx.is_mir_available(def_id)
+    {
+        return unsupported;
+    }
+
+    let body = tcx.optimized_mir(def_id);
+    let mut checker = ConstChecker { tcx, body, first: None };
+
+    // Arguments and the return type of `const fn`s can't contain mutable references
+    for local in body.args_iter().chain([mir::RETURN_PLACE]) {
+        let decl = &body.local_decls[local];
+        let has_mut_ref = decl
+            .ty
+            .walk()
+            .filter_map(ty::GenericArg::as_type)
+            .any(|ty| matches!(ty.kind(), ty::Ref(_, _, mir::Mutability::Mut)));
+        if has_mut_ref {
+            checker.report(ConstFnViolationKind::MutRef, decl.source_info.span);
+        }
+    }
+
+    checker.visit_body(body);
+    if let Some(local_id) = def_id.as_local() {
+        let body_id = tcx.hir().body_owned_by(local_id);
+        let mut calls = CallVisitor {
+            typeck: tcx.typeck_body(body_id),
+            checker: &mut checker,
+        };
+        calls.visit_body(tcx.hir().body(body_id));
+    }
+    checker.first
+}
+
+/// Checks if the function can be called in a `const fn`. Unstable `const fn`s
+/// can be called, if the feature is enabled or if the call originates from
+/// a macro, which allows the feature, like `panic!`.
+fn check_callee(tcx: TyCtxt<'_>, callee_id: hir::def_id::DefId, span: Span) -> Option<ConstFnViolationKind> {
+    if Some(callee_id) == tcx.lang_items().exchange_malloc_fn() {
+        return Some(ConstFnViolationKind::HeapAllocation);
+    }
+    if !tcx.is_const_fn_raw(callee_id) {
+        return Some(ConstFnViolationKind::NonConstCall);
+    }
+
+    match tcx.lookup_const_stability(callee_id) {
+        Some(stability) if stability.is_const_unstable() => {
+            let enabled = span.allows_unstable(stability.feature)
+                || tcx
+                    .features()
+                    .declared_lib_features
+                    .iter()
+                    .any(|&(feature, _)| feature == stability.feature);
+            (!enabled).then_some(ConstFnViolationKind::NonConstCall)
+        },
+        _ => None,
+    }
+}
+
+struct ConstChecker<'a, 'tcx> {
+    tcx: TyCtxt<'tcx>,
+    body: &'a Body<'tcx>,
+    /// The violation with the lowest position in the source code
+    first: Option<(ConstFnViolationKind, Span)>,
+}
+
+impl<'a, 'tcx> ConstChecker<'a, 'tcx> {
+    fn report(&mut self, kind: ConstFnViolationKind, span: Span) {
+        // Violations in macro expansions are reported at the macro call
+        let span = span.source_callsite();
+        if self.first.map_or(true, |(_, first)| span.lo() < first.lo()) {
+            self.first = Some((kind, span));
+        }
+    }
+
+    fn report_at(&mut self, kind: ConstFnViolationKind, location: Location) {
+        self.report(kind, self.body.source_info(location).span);
+    }
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for ConstChecker<'a, 'tcx> {
+    fn visit_basic_block_data(&mut self, block: BasicBlock, data: &BasicBlockData<'tcx>) {
+        if !data.is_cleanup {
+            self.super_basic_block_data(block, data);
+        }
+    }
+
+    fn visit_rvalue(&mut self, rvalue: &Rvalue<'tcx>, location: Location) {
+        let kind = match rvalue {
+            Rvalue::Ref(_, BorrowKind::Mut { .. }, _) | Rvalue::AddressOf(mir::Mutability::Mut, _) => {
+                Some(ConstFnViolationKind::MutRef)
+            },
+            Rvalue::Cast(CastKind::PointerExposeAddress, ..) => Some(ConstFnViolationKind::PtrToIntCast),
+            Rvalue::ThreadLocalRef(_) => Some(ConstFnViolationKind::StaticAccess),
+            Rvalue::ShallowInitBox(..) => Some(ConstFnViolationKind::HeapAllocation),
+            Rvalue::BinaryOp(_, operands) | Rvalue::CheckedBinaryOp(_, operands)
+                if operands.0.ty(self.body, self.tcx).is_floating_point() =>
+            {
+                Some(ConstFnViolationKind::FloatArithmetic)
+            },
+            Rvalue::UnaryOp(mir::UnOp::Neg, operand) if operand.ty(self.body, self.tcx).is_floating_point() => {
+                Some(ConstFnViolationKind::FloatArithmetic)
+            },
+            _ => None,
+        };
+        if let Some(kind) = kind {
+            self.report_at(kind, location);
+        }
+
+        self.super_rvalue(rvalue, location);
+    }
+
+    fn visit_constant(&mut self, constant: &ConstOperand<'tcx>, location: Location) {
+        if constant.check_static_ptr(self.tcx).is_some() {
+            self.report_at(ConstFnViolationKind::StaticAccess, location);
+        }
+    }
+
+    fn visit_terminator(&mut self, terminator: &Terminator<'tcx>, location: Location) {
+        let span = terminator.source_info.span;
+        match &terminator.kind {
+            TerminatorKind::Call { func, .. } => {
+                let kind = match *func.ty(self.body, self.tcx).kind() {
+                    ty::FnDef(callee_id, _) => check_callee(self.tcx, callee_id, span),
+                    // Calls of function pointers
+                    _ => Some(ConstFnViolationKind::NonConstCall),
+                };
+                if let Some(kind) = kind {
+                    self.report(kind, span);
+                }
+            },
+            // Drops remaining after drop elaboration require a destructor
+            TerminatorKind::Drop { place, .. } => {
+                self.report(
+                    ConstFnViolationKind::NonConstDrop,
+                    self.body.local_decls[place.local].source_info.span,
+                );
+            },
+            TerminatorKind::InlineAsm { .. } => self.report(ConstFnViolationKind::InlineAsm, span),
+            TerminatorKind::Yield { .. } | TerminatorKind::CoroutineDrop => {
+                self.report(ConstFnViolationKind::Other, span);
+            },
+            _ => {},
+        }
+
+        self.super_terminator(terminator, location);
+    }
+}
+
+struct CallVisitor<'a, 'b, 'tcx> {
+    typeck: &'tcx ty::TypeckResults<'tcx>,
+    checker: &'a mut ConstChecker<'b, 'tcx>,
+}
+
+impl<'a, 'b, 'tcx> intravisit::Visitor<'tcx> for CallVisitor<'a, 'b, 'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        let callee_id = match expr.kind {
+            hir::ExprKind::MethodCall(..) => self.typeck.type_dependent_def_id(expr.hir_id),
+            hir::ExprKind::Call(callee, _) => match callee.kind {
+                hir::ExprKind::Path(ref qpath) => match self.typeck.qpath_res(qpath, callee.hir_id) {
+                    hir::def::Res::Def(DefKind::Fn | DefKind::AssocFn, def_id) => Some(def_id),
+                    _ => None,
+                },
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(kind) = callee_id.and_then(|callee_id| check_callee(self.checker.tcx, callee_id, expr.span)) {
+            self.checker.report(kind, expr.span);
+        }
+
+        intravisit::walk_expr(self, expr);
+    }
+}
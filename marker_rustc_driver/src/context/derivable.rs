@@ -0,0 +1,213 @@
+use rustc_ast::LitKind;
+use rustc_hir as hir;
+use rustc_hir::def::{CtorKind, CtorOf, DefKind, Res};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::sym;
+
+/// Returns the name of the derive macro, which would generate an equivalent
+/// impl to the trait impl with the given [`LocalDefId`](hir::def_id::LocalDefId).
+///
+/// Only `Default` and `Clone` impls of local ADTs are supported. Impls with type
+/// or const parameters are ignored, since the derive macros add bounds for them.
+pub(super) fn derivable_trait(tcx: TyCtxt<'_>, def_id: hir::def_id::LocalDefId) -> Option<&'static str> {
+    let hir::Node::Item(item) = tcx.hir().find_by_def_id(def_id)? else {
+        return None;
+    };
+    let hir::ItemKind::Impl(impl_) = item.kind else {
+        return None;
+    };
+    let trait_id = impl_.of_trait.as_ref()?.trait_def_id()?;
+    let only_lifetimes = impl_
+        .generics
+        .params
+        .iter()
+        .all(|param| matches!(param.kind, hir::GenericParamKind::Lifetime { .. }));
+    if item.span.from_expansion() || tcx.has_attr(def_id, sym::automatically_derived) || !only_lifetimes {
+        return None;
+    }
+    let ty::Adt(adt, _) = tcx.type_of(def_id).instantiate_identity().kind() else {
+        return None;
+    };
+    if !adt.did().is_local() {
+        return None;
+    }
+
+    // Derived impls only contain the required function
+    let [item_ref] = impl_.items else {
+        return None;
+    };
+    let hir::ImplItemKind::Fn(_, body_id) = tcx.hir().impl_item(item_ref.id).kind else {
+        return None;
+    };
+    let body = tcx.hir().body(body_id);
+    let checker = BodyChecker {
+        tcx,
+        typeck: tcx.typeck_body(body_id),
+        param_env: tcx.param_env(def_id),
+        adt: *adt,
+    };
+    let value = peel_blocks(body.value);
+
+    if Some(trait_id) == tcx.get_diagnostic_item(sym::Default) {
+        checker.is_derived_default(value).then_some("Default")
+    } else if Some(trait_id) == tcx.lang_items().clone_trait() {
+        let self_id = body.params.first()?.pat.hir_id;
+        checker.is_derived_clone(value, self_id).then_some("Clone")
+    } else {
+        None
+    }
+}
+
+struct BodyChecker<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx ty::TypeckResults<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    adt: ty::AdtDef<'tcx>,
+}
+
+impl<'tcx> BodyChecker<'tcx> {
+    /// Checks if the value is a constructor of the ADT, where every field is
+    /// set to its default value. For enums, this has to be a unit variant,
+    /// which can be marked with `#[default]`.
+    fn is_derived_default(&self, value: &'tcx hir::Expr<'tcx>) -> bool {
+        if self.adt.is_enum() {
+            return matches!(
+                self.ctor_res(value),
+                Some(Res::Def(DefKind::Ctor(CtorOf::Variant, CtorKind::Const), _))
+            );
+        }
+        self.adt.is_struct()
+            && self
+                .ctor_fields(value)
+                .is_some_and(|fields| fields.iter().all(|(_, field)| self.is_default_value(field)))
+    }
+
+    /// Checks if the value is `*self` for `Copy` types, or a constructor of the
+    /// struct, where every field is set to the clone of the same field of `self`.
+    fn is_derived_clone(&self, value: &'tcx hir::Expr<'tcx>, self_id: hir::HirId) -> bool {
+        if let hir::ExprKind::Unary(hir::UnOp::Deref, inner) = value.kind {
+            return is_local(inner, self_id) && self.is_copy(self.typeck.expr_ty(value));
+        }
+        self.adt.is_struct()
+            && self.ctor_fields(value).is_some_and(|fields| {
+                fields
+                    .iter()
+                    .all(|(name, field)| self.is_cloned_field(field, name, self_id))
+            })
+    }
+
+    /// Returns the field names and values of a struct constructor, like
+    /// `Self { a: 1 }`, `Self(1)` or `Self`.
+    fn ctor_fields(&self, value: &'tcx hir::Expr<'tcx>) -> Option<Vec<(String, &'tcx hir::Expr<'tcx>)>> {
+        match value.kind {
+            hir::ExprKind::Struct(_, fields, None) => Some(
+                fields
+                    .iter()
+                    .map(|field| (field.ident.name.to_string(), field.expr))
+                    .collect(),
+            ),
+            hir::ExprKind::Call(callee, args) if self.struct_ctor_kind(callee) == Some(CtorKind::Fn) => Some(
+                args.iter()
+                    .enumerate()
+                    .map(|(index, arg)| (index.to_string(), arg))
+                    .collect(),
+            ),
+            hir::ExprKind::Path(_) if self.struct_ctor_kind(value) == Some(CtorKind::Const) => Some(vec![]),
+            _ => None,
+        }
+        .filter(|_| self.typeck.expr_ty(value).ty_adt_def() == Some(self.adt))
+    }
+
+    /// Returns the kind of the struct constructor, which is referenced by the path.
+    /// `Self` constructors are resolved to the constructor of the struct.
+    fn struct_ctor_kind(&self, path: &'tcx hir::Expr<'tcx>) -> Option<CtorKind> {
+        match self.ctor_res(path)? {
+            Res::Def(DefKind::Ctor(CtorOf::Struct, kind), _) => Some(kind),
+            Res::SelfCtor(_) if self.adt.is_struct() => self.adt.non_enum_variant().ctor_kind(),
+            _ => None,
+        }
+    }
+
+    fn ctor_res(&self, expr: &'tcx hir::Expr<'tcx>) -> Option<Res> {
+        match &expr.kind {
+            hir::ExprKind::Path(qpath) => Some(self.typeck.qpath_res(qpath, expr.hir_id)),
+            _ => None,
+        }
+    }
+
+    /// Checks if the expression is a call of `Default::default()` or a literal
+    /// with the default value of its type.
+    fn is_default_value(&self, expr: &'tcx hir::Expr<'tcx>) -> bool {
+        match peel_blocks(expr).kind {
+            hir::ExprKind::Call(callee, []) => match self.ctor_res(callee) {
+                Some(Res::Def(DefKind::AssocFn, fn_id)) => {
+                    let trait_id = self.tcx.trait_of_item(fn_id).or_else(|| {
+                        self.tcx
+                            .impl_of_method(fn_id)
+                            .and_then(|impl_id| self.tcx.trait_id_of_impl(impl_id))
+                    });
+                    trait_id.is_some() && trait_id == self.tcx.get_diagnostic_item(sym::Default)
+                },
+                _ => false,
+            },
+            hir::ExprKind::Lit(lit) => match lit.node {
+                LitKind::Int(value, _) => value == 0,
+                LitKind::Float(value, _) => value.as_str().parse::<f64>().is_ok_and(|value| value == 0.0),
+                LitKind::Bool(value) => !value,
+                LitKind::Str(value, _) => value.is_empty(),
+                _ => false,
+            },
+            hir::ExprKind::Tup([]) => true,
+            _ => false,
+        }
+    }
+
+    /// Checks if the expression is `self.<name>.clone()` or `self.<name>`, if the
+    /// field is `Copy`.
+    fn is_cloned_field(&self, expr: &'tcx hir::Expr<'tcx>, name: &str, self_id: hir::HirId) -> bool {
+        let is_self_field = |expr: &'tcx hir::Expr<'tcx>| match expr.kind {
+            hir::ExprKind::Field(base, ident) => ident.as_str() == name && is_local(base, self_id),
+            _ => false,
+        };
+        match expr.kind {
+            hir::ExprKind::MethodCall(_, receiver, [], _) => {
+                let is_clone = self
+                    .typeck
+                    .type_dependent_def_id(expr.hir_id)
+                    .and_then(|fn_id| self.tcx.trait_of_item(fn_id))
+                    .is_some_and(|trait_id| Some(trait_id) == self.tcx.lang_items().clone_trait());
+                is_clone && is_self_field(receiver)
+            },
+            hir::ExprKind::Field(..) => is_self_field(expr) && self.is_copy(self.typeck.expr_ty(expr)),
+            _ => false,
+        }
+    }
+
+    fn is_copy(&self, ty: ty::Ty<'tcx>) -> bool {
+        ty.is_copy_modulo_regions(self.tcx, self.param_env)
+    }
+}
+
+/// Checks if the expression is a path to the local with the given [`HirId`](hir::HirId).
+fn is_local(expr: &hir::Expr<'_>, local_id: hir::HirId) -> bool {
+    matches!(
+        expr.kind,
+        hir::ExprKind::Path(hir::QPath::Resolved(None, path)) if path.res == Res::Local(local_id)
+    )
+}
+
+fn peel_blocks<'tcx>(mut expr: &'tcx hir::Expr<'tcx>) -> &'tcx hir::Expr<'tcx> {
+    while let hir::ExprKind::Block(
+        hir::Block {
+            stmts: [],
+            expr: Some(inner),
+            rules: hir::BlockCheckMode::DefaultBlock,
+            ..
+        },
+        None,
+    ) = expr.kind
+    {
+        expr = inner;
+    }
+    expr
+}
@@ -0,0 +1,270 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use marker_api::diagnostic::{Diagnostic, DiagnosticPart};
+use marker_api::span::Span;
+use marker_api::{Lint, LintSupersession};
+use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hir::HirId;
+use rustc_middle::lint::LintLevelSource;
+use rustc_span::FileName;
+
+use super::RustcContext;
+
+/// Glob patterns, which determine the files in which diagnostics are emitted.
+/// The paths are matched relative to the current directory, which is the
+/// workspace root when the driver is invoked by `cargo-marker`.
+#[derive(Debug, Default)]
+pub struct PathFilter {
+    /// See [`INCLUDE_PATHS_ENV`](marker_adapter::INCLUDE_PATHS_ENV). All files
+    /// are included, if this is empty.
+    pub include: Vec<glob::Pattern>,
+    /// See [`IGNORE_PATHS_ENV`](marker_adapter::IGNORE_PATHS_ENV).
+    pub ignore: Vec<glob::Pattern>,
+}
+
+/// A diagnostic, which has been emitted by a lint crate, but not yet by rustc.
+///
+/// Lint crates emit diagnostics in the order they visit the AST. This order
+/// can depend on the order in which the lint crates are loaded and on the
+/// iteration order of some internal collections. The diagnostics are therefore
+/// buffered and emitted at the end, sorted by their location. This ensures that
+/// the output is stable between runs.
+pub struct BufferedDiagnostic {
+    api_lint: &'static marker_api::Lint,
+    node: HirId,
+    lint: &'static rustc_lint::Lint,
+    level: rustc_lint::Level,
+    src: LintLevelSource,
+    span: rustc_span::Span,
+    msg: String,
+    parts: Vec<BufferedPart>,
+}
+
+enum BufferedPart {
+    Help(String),
+    HelpSpan(String, rustc_span::Span),
+    Note(String),
+    NoteSpan(String, rustc_span::Span),
+    Suggestion(String, rustc_span::Span, String, rustc_errors::Applicability),
+    Label(String, rustc_span::Span),
+    MultipartSuggestion(String, Vec<(rustc_span::Span, String)>, rustc_errors::Applicability),
+}
+
+impl<'ast, 'tcx: 'ast> RustcContext<'ast, 'tcx> {
+    pub(super) fn buffer_diag(
+        &self,
+        diag: &Diagnostic<'_, 'ast>,
+        node: HirId,
+        level: rustc_lint::Level,
+        src: LintLevelSource,
+    ) -> BufferedDiagnostic {
+        let mut parts: Vec<_> = diag
+            .parts
+            .get()
+            .iter()
+            .map(|part| match part {
+                DiagnosticPart::Help { msg } => BufferedPart::Help(msg.get().to_string()),
+                DiagnosticPart::HelpSpan { msg, span } => {
+                    BufferedPart::HelpSpan(msg.get().to_string(), self.rustc_converter.to_span(span))
+                },
+                DiagnosticPart::Note { msg } => BufferedPart::Note(msg.get().to_string()),
+                DiagnosticPart::NoteSpan { msg, span } => {
+                    BufferedPart::NoteSpan(msg.get().to_string(), self.rustc_converter.to_span(span))
+                },
+                DiagnosticPart::Suggestion { msg, span, sugg, app } => BufferedPart::Suggestion(
+                    msg.get().to_string(),
+                    self.rustc_converter.to_span(span),
+                    sugg.get().to_string(),
+                    self.rustc_converter.to_applicability(*app),
+                ),
+                DiagnosticPart::Label { msg, span } => {
+                    BufferedPart::Label(msg.get().to_string(), self.rustc_converter.to_span(span))
+                },
+                DiagnosticPart::MultipartSuggestion { msg, parts, app } => BufferedPart::MultipartSuggestion(
+                    msg.get().to_string(),
+                    parts
+                        .get()
+                        .iter()
+                        .map(|part| (self.rustc_converter.to_span(part.span), part.sugg.get().to_string()))
+                        .collect(),
+                    self.rustc_converter.to_applicability(*app),
+                ),
+                _ => unreachable!(),
+            })
+            .collect();
+        if let Some(source) = self
+            .lint_source_adapter
+            .and_then(|adapter| adapter.lint_source(diag.lint))
+        {
+            parts.push(BufferedPart::Note(format!("emitted by the lint crate `{source}`")));
+        }
+
+        BufferedDiagnostic {
+            api_lint: diag.lint,
+            node,
+            lint: self.rustc_converter.to_lint(diag.lint),
+            level,
+            src,
+            span: self.rustc_converter.to_span(diag.span),
+            msg: diag.msg().to_string(),
+            parts,
+        }
+    }
+
+    /// Returns `true`, if the file of the given span doesn't match any included
+    /// path pattern or matches one of the ignored path patterns. Paths are
+    /// matched relative to the current directory, which is the workspace root,
+    /// when the driver is invoked by Cargo.
+    pub(super) fn is_ignored_path(&self, span: &Span<'ast>) -> bool {
+        let filter = &self.path_filter;
+        if filter.include.is_empty() && filter.ignore.is_empty() {
+            return false;
+        }
+
+        let rustc_span = self.rustc_converter.to_span(span);
+        // Spans outside of real local files, like the ones of external macros,
+        // can't be matched and are only filtered by the include patterns.
+        let FileName::Real(name) = self.rustc_cx.sess.source_map().span_to_filename(rustc_span) else {
+            return !filter.include.is_empty();
+        };
+        let Some(path) = name.local_path() else {
+            return !filter.include.is_empty();
+        };
+        let cwd = std::env::current_dir().ok();
+        let path = cwd
+            .as_deref()
+            .and_then(|cwd| path.strip_prefix(cwd).ok())
+            .unwrap_or(path);
+
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..glob::MatchOptions::default()
+        };
+        let path = normalize_path(path);
+        let matches = |pattern: &glob::Pattern| pattern.matches_path_with(path.as_path(), options);
+        (!filter.include.is_empty() && !filter.include.iter().any(matches)) || filter.ignore.iter().any(matches)
+    }
+
+    /// Emits all buffered diagnostics, sorted by file, line, column and lint name.
+    /// Diagnostics with the same key retain their emission order.
+    ///
+    /// Diagnostics of superseded lints are dropped, if the superseding lint has
+    /// been emitted at the same node, see [`LintSupersession`].
+    ///
+    /// If `max_per_lint` is set, only the first diagnostics of each lint are
+    /// emitted. The number of suppressed diagnostics is reported in a note
+    /// per lint. Allowed and expected diagnostics don't count towards the limit.
+    pub fn emit_buffered_diags(&self, supersessions: &[LintSupersession], max_per_lint: Option<usize>) {
+        let mut diags = self.buffered_diags.take();
+        remove_superseded_diags(&mut diags, supersessions);
+        let source_map = self.rustc_cx.sess.source_map();
+        diags.sort_by_cached_key(|diag| {
+            let loc = source_map.lookup_char_pos(diag.span.lo());
+            (
+                loc.file.name.prefer_local().to_string(),
+                loc.line,
+                loc.col,
+                diag.lint.name_lower(),
+            )
+        });
+
+        let mut emitted: FxHashMap<&'static str, usize> = FxHashMap::default();
+        let mut suppressed: BTreeMap<String, usize> = BTreeMap::new();
+        for diag in diags {
+            if let Some(max) = max_per_lint
+                && matches!(
+                    diag.level,
+                    rustc_lint::Level::Warn
+                        | rustc_lint::Level::ForceWarn(_)
+                        | rustc_lint::Level::Deny
+                        | rustc_lint::Level::Forbid
+                )
+            {
+                let count = emitted.entry(diag.lint.name).or_default();
+                if *count >= max {
+                    *suppressed.entry(diag.lint.name_lower()).or_default() += 1;
+                    continue;
+                }
+                *count += 1;
+            }
+            self.emit_buffered_diag(diag);
+        }
+
+        if let Some(max) = max_per_lint {
+            for (lint, count) in suppressed {
+                self.rustc_cx.sess.note_without_error(format!(
+                    "{count} more diagnostics of `{lint}` were suppressed (`--max-per-lint {max}`)"
+                ));
+            }
+        }
+    }
+
+    fn emit_buffered_diag(&self, diag: BufferedDiagnostic) {
+        rustc_middle::lint::struct_lint_level(
+            self.rustc_cx.sess,
+            diag.lint,
+            diag.level,
+            diag.src,
+            Some(diag.span.into()),
+            diag.msg,
+            |builder| {
+                for part in diag.parts {
+                    match part {
+                        BufferedPart::Help(msg) => {
+                            builder.help(msg);
+                        },
+                        BufferedPart::HelpSpan(msg, span) => {
+                            builder.span_help(span, msg);
+                        },
+                        BufferedPart::Note(msg) => {
+                            builder.note(msg);
+                        },
+                        BufferedPart::NoteSpan(msg, span) => {
+                            builder.span_note(span, msg);
+                        },
+                        BufferedPart::Suggestion(msg, span, sugg, app) => {
+                            builder.span_suggestion(span, msg, sugg, app);
+                        },
+                        BufferedPart::Label(msg, span) => {
+                            builder.span_label(span, msg);
+                        },
+                        BufferedPart::MultipartSuggestion(msg, parts, app) => {
+                            builder.multipart_suggestion(msg, parts, app);
+                        },
+                    }
+                }
+                builder
+            },
+        );
+    }
+}
+
+/// Removes the diagnostics of superseded lints, if the superseding lint has been
+/// emitted at the same node. Allowed and expected diagnostics don't supersede
+/// other lints, as they're not visible to the user.
+fn remove_superseded_diags(diags: &mut Vec<BufferedDiagnostic>, supersessions: &[LintSupersession]) {
+    if supersessions.is_empty() {
+        return;
+    }
+
+    // Lints are static items, they're therefore identified by their address
+    let fired: FxHashSet<(*const Lint, HirId)> = diags
+        .iter()
+        .filter(|diag| !matches!(diag.level, rustc_lint::Level::Allow | rustc_lint::Level::Expect(_)))
+        .map(|diag| (diag.api_lint as *const Lint, diag.node))
+        .collect();
+    diags.retain(|diag| {
+        !supersessions.iter().any(|sup| {
+            std::ptr::eq(sup.superseded(), diag.api_lint) && fired.contains(&(sup.lint() as *const Lint, diag.node))
+        })
+    });
+}
+
+/// Lexically removes `.` components from the path, like the leading `./`, which
+/// would otherwise prevent patterns like `src/**` from matching.
+fn normalize_path(path: &Path) -> std::path::PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, std::path::Component::CurDir))
+        .collect()
+}
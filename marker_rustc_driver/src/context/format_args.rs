@@ -0,0 +1,227 @@
+use std::cell::RefCell;
+
+use marker_api::ast::{
+    FormatAlignment, FormatArgPosKind, FormatArgs, FormatArgument, FormatArgumentKind, FormatCount, FormatLiteral,
+    FormatPiece, FormatPlaceholder, FormatTrait,
+};
+use rustc_ast as ast;
+use rustc_hash::FxHashMap;
+use rustc_hir as hir;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_span::{ExpnId, Span};
+
+use super::RustcContext;
+
+thread_local! {
+    /// The `format_args!` expressions of the current crate, by their span. Rustc
+    /// only keeps the lowered version in the HIR, which no longer contains the
+    /// template. The expressions are collected by [`FormatArgsCollector`], before
+    /// the AST is lowered.
+    ///
+    /// The HIR expression, created from a `format_args!` expression, has the
+    /// same span as the original expression.
+    static FORMAT_ARGS: RefCell<FxHashMap<Span, ast::FormatArgs>> = RefCell::default();
+}
+
+/// An early lint pass, which collects all `format_args!` expressions into
+/// [`FORMAT_ARGS`].
+pub struct FormatArgsCollector;
+
+rustc_lint_defs::impl_lint_pass!(FormatArgsCollector => []);
+
+impl rustc_lint::EarlyLintPass for FormatArgsCollector {
+    fn check_expr(&mut self, _cx: &rustc_lint::EarlyContext<'_>, expr: &ast::Expr) {
+        if let ast::ExprKind::FormatArgs(args) = &expr.kind {
+            FORMAT_ARGS.with(|map| map.borrow_mut().insert(expr.span, (**args).clone()));
+        }
+    }
+}
+
+impl<'ast, 'tcx: 'ast> RustcContext<'ast, 'tcx> {
+    /// Returns the [`FormatArgs`] of the formatting macro, that the expression
+    /// with the given [`HirId`](hir::HirId) was expanded from. The expression has
+    /// to be the outermost expression of the outermost macro call.
+    pub(super) fn format_args_of(&self, hir_id: hir::HirId) -> Option<&'ast FormatArgs<'ast>> {
+        let map = self.rustc_cx.hir();
+        let hir::Node::Expr(expr) = map.get(hir_id) else {
+            return None;
+        };
+        if !expr.span.from_expansion() {
+            return None;
+        }
+
+        // Find the outermost macro call
+        let mut root_expn = expr.span.ctxt().outer_expn();
+        loop {
+            let call_site = root_expn.expn_data().call_site;
+            if !call_site.from_expansion() {
+                break;
+            }
+            root_expn = call_site.ctxt().outer_expn();
+        }
+
+        // Only the outermost expression of the expansion should be detected.
+        // Statements created by macros in statement position use the span of the
+        // expansion and are therefore skipped, together with blocks.
+        let parent = map
+            .parent_iter(hir_id)
+            .find(|(_, node)| !matches!(node, hir::Node::Stmt(_) | hir::Node::Block(_)));
+        if let Some((parent_id, _)) = parent
+            && map.span(parent_id).ctxt().outer_expn().is_descendant_of(root_expn)
+        {
+            return None;
+        }
+
+        let mut finder = FormatArgsFinder { root_expn, found: None };
+        finder.visit_expr(expr);
+        let (format_expr, args) = finder.found?;
+        self.to_format_args(format_expr, &args)
+    }
+
+    fn to_format_args(
+        &self,
+        format_expr: &'tcx hir::Expr<'tcx>,
+        args: &ast::FormatArgs,
+    ) -> Option<&'ast FormatArgs<'ast>> {
+        let converter = &self.marker_converter;
+        let mut template = Vec::with_capacity(args.template.len());
+        for piece in &args.template {
+            template.push(match piece {
+                ast::FormatArgsPiece::Literal(sym) => FormatPiece::Literal(
+                    self.storage
+                        .alloc(FormatLiteral::new(self.storage.alloc_str(sym.as_str()))),
+                ),
+                ast::FormatArgsPiece::Placeholder(placeholder) => {
+                    FormatPiece::Placeholder(self.storage.alloc(to_format_placeholder(self, placeholder)?))
+                },
+            });
+        }
+
+        let mut arg_exprs = ArgExprFinder {
+            spans: args
+                .arguments
+                .all_args()
+                .iter()
+                .map(|arg| (arg.expr.span, None))
+                .collect(),
+        };
+        arg_exprs.visit_expr(format_expr);
+        let arguments: Vec<_> = args
+            .arguments
+            .all_args()
+            .iter()
+            .map(|arg| {
+                let (kind, name) = match arg.kind {
+                    ast::FormatArgumentKind::Normal => (FormatArgumentKind::Normal, None),
+                    ast::FormatArgumentKind::Named(ident) => (FormatArgumentKind::Named, Some(ident)),
+                    ast::FormatArgumentKind::Captured(ident) => (FormatArgumentKind::Captured, Some(ident)),
+                };
+                let expr = arg_exprs
+                    .spans
+                    .get(&arg.expr.span)
+                    .copied()
+                    .flatten()
+                    .and_then(|arg_id| converter.expr(arg_id));
+                FormatArgument::new(
+                    kind,
+                    name.map(|ident| converter.to_symbol_id(ident.name)),
+                    converter.to_span_id(arg.expr.span),
+                    expr,
+                )
+            })
+            .collect();
+
+        Some(self.storage.alloc(FormatArgs::new(
+            converter.to_span_id(args.span),
+            self.storage.alloc_slice(template),
+            self.storage.alloc_slice(arguments),
+        )))
+    }
+}
+
+fn to_format_placeholder(cx: &RustcContext<'_, '_>, placeholder: &ast::FormatPlaceholder) -> Option<FormatPlaceholder> {
+    let options = &placeholder.format_options;
+    let to_count = |count: &ast::FormatCount| match count {
+        ast::FormatCount::Literal(value) => Some(FormatCount::Literal(*value)),
+        ast::FormatCount::Argument(pos) => pos.index.ok().map(FormatCount::Argument),
+    };
+    let width = match &options.width {
+        Some(width) => Some(to_count(width)?),
+        None => None,
+    };
+    let precision = match &options.precision {
+        Some(precision) => Some(to_count(precision)?),
+        None => None,
+    };
+
+    Some(FormatPlaceholder::new(
+        // Invalid indices are reported as errors by rustc
+        placeholder.argument.index.ok()?,
+        match placeholder.argument.kind {
+            ast::FormatArgPositionKind::Implicit => FormatArgPosKind::Implicit,
+            ast::FormatArgPositionKind::Number => FormatArgPosKind::Number,
+            ast::FormatArgPositionKind::Named => FormatArgPosKind::Named,
+        },
+        placeholder.span.map(|span| cx.marker_converter.to_span_id(span)),
+        match placeholder.format_trait {
+            ast::FormatTrait::Display => FormatTrait::Display,
+            ast::FormatTrait::Debug => FormatTrait::Debug,
+            ast::FormatTrait::LowerExp => FormatTrait::LowerExp,
+            ast::FormatTrait::UpperExp => FormatTrait::UpperExp,
+            ast::FormatTrait::Octal => FormatTrait::Octal,
+            ast::FormatTrait::Pointer => FormatTrait::Pointer,
+            ast::FormatTrait::Binary => FormatTrait::Binary,
+            ast::FormatTrait::LowerHex => FormatTrait::LowerHex,
+            ast::FormatTrait::UpperHex => FormatTrait::UpperHex,
+        },
+        width,
+        precision,
+        options.alignment.map(|alignment| match alignment {
+            ast::FormatAlignment::Left => FormatAlignment::Left,
+            ast::FormatAlignment::Right => FormatAlignment::Right,
+            ast::FormatAlignment::Center => FormatAlignment::Center,
+        }),
+        options.fill,
+        options.sign == Some(ast::FormatSign::Plus),
+        options.sign == Some(ast::FormatSign::Minus),
+        options.alternate,
+        options.zero_pad,
+    ))
+}
+
+/// Finds the first lowered `format_args!` expression, which was expanded from
+/// the root expansion.
+struct FormatArgsFinder<'tcx> {
+    root_expn: ExpnId,
+    found: Option<(&'tcx hir::Expr<'tcx>, ast::FormatArgs)>,
+}
+
+impl<'tcx> Visitor<'tcx> for FormatArgsFinder<'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if self.found.is_some() {
+            return;
+        }
+        if expr.span.ctxt().outer_expn().is_descendant_of(self.root_expn)
+            && let Some(args) = FORMAT_ARGS.with(|map| map.borrow().get(&expr.span).cloned())
+        {
+            self.found = Some((expr, args));
+            return;
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
+
+/// Maps the spans of the format arguments to the first HIR expression with
+/// the same span.
+struct ArgExprFinder {
+    spans: FxHashMap<Span, Option<hir::HirId>>,
+}
+
+impl<'tcx> Visitor<'tcx> for ArgExprFinder {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let Some(entry @ None) = self.spans.get_mut(&expr.span) {
+            *entry = Some(expr.hir_id);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
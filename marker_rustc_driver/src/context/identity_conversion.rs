@@ -0,0 +1,46 @@
+use rustc_hir as hir;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::sym;
+
+/// Returns `true`, if the given expression calls a conversion method, which
+/// returns a value of the type it was called with.
+///
+/// Calls of `Into`, `From`, `AsRef` and `AsMut` are identity conversions, if
+/// the type of the converted value matches the type of the call. The type of
+/// the value is taken before any adjustments, `(&string).into()` is therefore
+/// not an identity conversion. Method calls, like `x.into()`, and paths, like
+/// `String::from(x)`, are checked.
+///
+/// `ToOwned` clones the value. Method calls of it are only identity conversions,
+/// if the types match and the receiver is a local variable, which isn't used
+/// afterwards. The value can then be moved instead.
+pub(super) fn is_identity_conversion(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    let hir::Node::Expr(expr) = tcx.hir().get(hir_id) else {
+        return false;
+    };
+    let typeck = super::typeck_body_of(tcx, hir_id);
+    let (def_id, value) = match expr.kind {
+        hir::ExprKind::MethodCall(_, receiver, [], _) => {
+            let Some(def_id) = typeck.type_dependent_def_id(expr.hir_id) else {
+                return false;
+            };
+            (def_id, receiver)
+        },
+        hir::ExprKind::Call(callee, [value]) => match *typeck.expr_ty(callee).kind() {
+            ty::FnDef(def_id, _) => (def_id, value),
+            _ => return false,
+        },
+        _ => return false,
+    };
+    let is_conversion = match tcx
+        .trait_of_item(def_id)
+        .and_then(|trait_id| tcx.get_diagnostic_name(trait_id))
+    {
+        Some(sym::Into | sym::From | sym::AsRef | sym::AsMut) => true,
+        Some(sym::ToOwned) => {
+            matches!(expr.kind, hir::ExprKind::MethodCall(..)) && super::last_use::is_last_use(tcx, value.hir_id)
+        },
+        _ => false,
+    };
+    is_conversion && tcx.erase_regions(typeck.expr_ty(value)) == tcx.erase_regions(typeck.expr_ty(expr))
+}
@@ -0,0 +1,52 @@
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_middle::ty;
+
+/// Checks if the pattern matches every value of its type. This is a conservative
+/// check, which doesn't consider uninhabited types or the combination of
+/// alternatives in or-patterns.
+pub(super) fn is_irrefutable<'tcx>(typeck: &'tcx ty::TypeckResults<'tcx>, pat: &'tcx hir::Pat<'tcx>) -> bool {
+    let all = |pats: &'tcx [hir::Pat<'tcx>]| pats.iter().all(|pat| is_irrefutable(typeck, pat));
+    match pat.kind {
+        hir::PatKind::Wild => true,
+        hir::PatKind::Binding(_, _, _, sub) => sub.map_or(true, |sub| is_irrefutable(typeck, sub)),
+        hir::PatKind::Tuple(pats, _) => all(pats),
+        hir::PatKind::Box(inner) | hir::PatKind::Ref(inner, _) => is_irrefutable(typeck, inner),
+        hir::PatKind::Or(pats) => pats.iter().any(|pat| is_irrefutable(typeck, pat)),
+        hir::PatKind::Struct(ref qpath, fields, _) => {
+            is_single_ctor(typeck, pat, qpath) && fields.iter().all(|field| is_irrefutable(typeck, field.pat))
+        },
+        hir::PatKind::TupleStruct(ref qpath, pats, _) => is_single_ctor(typeck, pat, qpath) && all(pats),
+        hir::PatKind::Path(ref qpath) => is_single_ctor(typeck, pat, qpath),
+        hir::PatKind::Slice(before, rest, after) => {
+            // Arrays have a fixed length, slices can only be matched by a rest pattern
+            let fixed_len = matches!(typeck.pat_ty(pat).kind(), ty::Array(..));
+            let len_matches = fixed_len || (before.is_empty() && after.is_empty() && rest.is_some());
+            len_matches && all(before) && all(after) && rest.map_or(true, |rest| is_irrefutable(typeck, rest))
+        },
+        hir::PatKind::Lit(_) | hir::PatKind::Range(..) => false,
+    }
+}
+
+/// Checks if the path of the pattern references the only constructor of its type.
+fn is_single_ctor<'tcx>(
+    typeck: &'tcx ty::TypeckResults<'tcx>,
+    pat: &'tcx hir::Pat<'tcx>,
+    qpath: &hir::QPath<'tcx>,
+) -> bool {
+    if matches!(
+        typeck.qpath_res(qpath, pat.hir_id),
+        Res::Def(DefKind::Const | DefKind::AssocConst, _) | Res::Err
+    ) {
+        return false;
+    }
+
+    match typeck.pat_ty(pat).kind() {
+        ty::Adt(adt, _) if adt.is_enum() => {
+            // Variants can be added to non exhaustive enums of other crates
+            adt.variants().len() == 1 && (adt.did().is_local() || !adt.is_variant_list_non_exhaustive())
+        },
+        ty::Adt(..) => true,
+        _ => false,
+    }
+}
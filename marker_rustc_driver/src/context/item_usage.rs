@@ -0,0 +1,172 @@
+use rustc_hash::FxHashSet;
+use rustc_hir as hir;
+use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def_id::{DefId, LocalDefId};
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::hir::nested_filter;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::sym;
+
+/// Collects the [`DefId`]s of all items, which are used inside the current crate.
+/// This includes uses in macro expansions, but ignores `use` declarations,
+/// the self type of `impl` blocks, uses of an item inside itself, like recursive
+/// calls or uses of a type inside its own inherent impls, and uses in
+/// automatically derived implementations.
+pub(super) fn used_items(tcx: TyCtxt<'_>) -> FxHashSet<DefId> {
+    let mut visitor = UsageVisitor {
+        tcx,
+        typeck: None,
+        used: FxHashSet::default(),
+    };
+    tcx.hir().walk_toplevel_module(&mut visitor);
+    visitor.used
+}
+
+/// Checks if the local item is used inside the crate, based on the uses
+/// collected by [`used_items`].
+pub(super) fn is_used_in_crate(tcx: TyCtxt<'_>, used: &FxHashSet<DefId>, item: LocalDefId) -> bool {
+    let def_id = item.to_def_id();
+    if used.contains(&def_id) {
+        return true;
+    }
+
+    match tcx.def_kind(def_id) {
+        // Impl blocks are used through their type, and uses of macros can't be
+        // tracked after the expansion.
+        DefKind::Impl { .. } | DefKind::Macro(_) | DefKind::GlobalAsm => true,
+        DefKind::Fn | DefKind::Static(_) => {
+            tcx.entry_fn(()).is_some_and(|(entry, _)| entry == def_id)
+                || tcx.has_attr(def_id, sym::no_mangle)
+                || tcx.has_attr(def_id, sym::export_name)
+                || tcx.has_attr(def_id, sym::used)
+        },
+        DefKind::AssocFn | DefKind::AssocConst | DefKind::AssocTy => {
+            // Items in trait impls are used, if the trait item is used. Traits
+            // from other crates can use them in ways, which can't be tracked.
+            match tcx.associated_item(def_id).trait_item_def_id {
+                Some(trait_item) => !trait_item.is_local() || used.contains(&trait_item),
+                None => false,
+            }
+        },
+        _ => false,
+    }
+}
+
+struct UsageVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    typeck: Option<&'tcx rustc_middle::ty::TypeckResults<'tcx>>,
+    used: FxHashSet<DefId>,
+}
+
+impl<'tcx> UsageVisitor<'tcx> {
+    fn add_use(&mut self, def_id: DefId, hir_id: hir::HirId) {
+        let owner = hir_id.owner.def_id;
+        if self.is_derived(owner) {
+            return;
+        }
+
+        // Constructors and variants also use the type they belong to
+        let mut current = Some(def_id);
+        while let Some(def_id) = current {
+            if !self.is_inside(def_id, owner) {
+                self.used.insert(def_id);
+            }
+            current = matches!(self.tcx.def_kind(def_id), DefKind::Ctor(..) | DefKind::Variant)
+                .then(|| self.tcx.parent(def_id));
+        }
+    }
+
+    /// Checks if the owner is the given item, an item nested inside it or an
+    /// inherent impl of it. Uses of an item inside itself don't count.
+    fn is_inside(&self, def_id: DefId, owner: LocalDefId) -> bool {
+        std::iter::successors(Some(owner), |id| self.tcx.opt_local_parent(*id)).any(|id| {
+            id.to_def_id() == def_id
+                || matches!(self.tcx.def_kind(id), DefKind::Impl { of_trait: false })
+                    && matches!(
+                        self.tcx.type_of(id).instantiate_identity().kind(),
+                        ty::Adt(adt, _) if adt.did() == def_id
+                    )
+        })
+    }
+
+    fn add_res(&mut self, res: Res, hir_id: hir::HirId) {
+        if let Res::Def(_, def_id) = res {
+            self.add_use(def_id, hir_id);
+        }
+    }
+
+    fn is_derived(&self, owner: LocalDefId) -> bool {
+        self.tcx
+            .opt_local_parent(owner)
+            .is_some_and(|parent| self.tcx.is_automatically_derived(parent.to_def_id()))
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for UsageVisitor<'tcx> {
+    type NestedFilter = nested_filter::All;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_nested_body(&mut self, body_id: hir::BodyId) {
+        let old_typeck = self.typeck.replace(self.tcx.typeck_body(body_id));
+        self.visit_body(self.tcx.hir().body(body_id));
+        self.typeck = old_typeck;
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        match item.kind {
+            hir::ItemKind::Use(..) => {},
+            hir::ItemKind::Impl(imp) => {
+                self.visit_generics(imp.generics);
+                if let Some(trait_ref) = &imp.of_trait {
+                    self.visit_trait_ref(trait_ref);
+                }
+                for impl_item in imp.items {
+                    self.visit_nested_impl_item(impl_item.id);
+                }
+            },
+            _ => intravisit::walk_item(self, item),
+        }
+    }
+
+    fn visit_path(&mut self, path: &hir::Path<'tcx>, hir_id: hir::HirId) {
+        self.add_res(path.res, hir_id);
+        for segment in path.segments {
+            self.add_res(segment.res, hir_id);
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let Some(typeck) = self.typeck {
+            match expr.kind {
+                hir::ExprKind::MethodCall(..) => {
+                    if let Some(def_id) = typeck.type_dependent_def_id(expr.hir_id) {
+                        self.add_use(def_id, expr.hir_id);
+                    }
+                },
+                hir::ExprKind::Path(ref qpath @ hir::QPath::TypeRelative(..)) => {
+                    self.add_res(typeck.qpath_res(qpath, expr.hir_id), expr.hir_id);
+                },
+                _ => {},
+            }
+        }
+        intravisit::walk_expr(self, expr);
+    }
+
+    fn visit_pat(&mut self, pat: &'tcx hir::Pat<'tcx>) {
+        if let Some(typeck) = self.typeck {
+            match pat.kind {
+                hir::PatKind::Path(ref qpath @ hir::QPath::TypeRelative(..))
+                | hir::PatKind::TupleStruct(ref qpath @ hir::QPath::TypeRelative(..), ..)
+                | hir::PatKind::Struct(ref qpath @ hir::QPath::TypeRelative(..), ..) => {
+                    self.add_res(typeck.qpath_res(qpath, pat.hir_id), pat.hir_id);
+                },
+                _ => {},
+            }
+        }
+        intravisit::walk_pat(self, pat);
+    }
+}
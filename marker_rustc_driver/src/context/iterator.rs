@@ -0,0 +1,65 @@
+use rustc_hir as hir;
+use rustc_infer::infer::TyCtxtInferExt;
+use rustc_middle::ty::{self, Ty, TyCtxt, TypeVisitableExt};
+use rustc_trait_selection::infer::InferCtxtExt;
+
+/// Returns the `Item` type of the given type, if it implements [`Iterator`] in
+/// the given [`ParamEnv`](ty::ParamEnv). The item type is normalized if possible.
+pub(super) fn iterator_item_ty<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    param_env: ty::ParamEnv<'tcx>,
+    ty: Ty<'tcx>,
+) -> Option<Ty<'tcx>> {
+    let iterator_trait = tcx.lang_items().iterator_trait()?;
+    let ty = tcx.erase_regions(ty);
+    if ty.has_escaping_bound_vars() {
+        return None;
+    }
+
+    let infcx = tcx.infer_ctxt().build();
+    if !infcx
+        .type_implements_trait(iterator_trait, [ty], param_env)
+        .must_apply_modulo_regions()
+    {
+        return None;
+    }
+
+    let item = tcx.get_diagnostic_item(rustc_span::sym::IteratorItem)?;
+    let projection = Ty::new_projection(tcx, item, [ty]);
+    Some(
+        tcx.try_normalize_erasing_regions(param_env, projection)
+            .unwrap_or(projection),
+    )
+}
+
+/// Checks if the given method call or call expression calls a method of the
+/// [`Iterator`] trait, which returns another iterator. Calls are resolved
+/// through the type of the callee, like `Iterator::map(iter, f)`.
+pub(super) fn is_iterator_adapter(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    let Some(iterator_trait) = tcx.lang_items().iterator_trait() else {
+        return false;
+    };
+    let hir::Node::Expr(expr) = tcx.hir().get(hir_id) else {
+        return false;
+    };
+    let owner = tcx.hir().enclosing_body_owner(hir_id);
+    let typeck = super::typeck_body_of(tcx, hir_id);
+    let method = match expr.kind {
+        hir::ExprKind::MethodCall(..) => typeck.type_dependent_def_id(hir_id),
+        // `type_dependent_def_id` is only set for method calls
+        hir::ExprKind::Call(callee, _) => match *typeck.expr_ty(callee).kind() {
+            ty::FnDef(def_id, _) => Some(def_id),
+            _ => None,
+        },
+        _ => None,
+    };
+    let Some(method) = method else {
+        return false;
+    };
+    if tcx.trait_of_item(method) != Some(iterator_trait) {
+        return false;
+    }
+
+    let param_env = tcx.param_env(tcx.typeck_root_def_id(owner.to_def_id()));
+    iterator_item_ty(tcx, param_env, typeck.node_type(hir_id)).is_some()
+}
@@ -0,0 +1,239 @@
+use rustc_hash::FxHashSet;
+use rustc_hir as hir;
+use rustc_middle::mir::{
+    self,
+    visit::{MutatingUseContext, PlaceContext, Visitor},
+    BasicBlock, Local, Location,
+};
+use rustc_middle::ty::{TyCtxt, TypeVisitableExt};
+
+/// Checks if the given expression is a path to a local variable, which isn't
+/// used afterwards. The check is done on the optimized MIR of the enclosing
+/// function. Uses of references, which might point to the variable, also
+/// count as uses of the variable. Drops of the variable itself are ignored.
+///
+/// This returns `false` in all cases, where the MIR can't be mapped back to
+/// the expression.
+pub(super) fn is_last_use(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    let hir::Node::Expr(expr) = tcx.hir().get(hir_id) else {
+        return false;
+    };
+    let hir::ExprKind::Path(hir::QPath::Resolved(None, path)) = expr.kind else {
+        return false;
+    };
+    let hir::def::Res::Local(var_id) = path.res else {
+        return false;
+    };
+
+    // Closures and coroutines access captured variables through their upvars,
+    // these can't be mapped back to the variable.
+    let owner = tcx.hir().enclosing_body_owner(hir_id);
+    if !matches!(tcx.def_kind(owner), hir::def::DefKind::Fn | hir::def::DefKind::AssocFn)
+        || tcx.hir().enclosing_body_owner(var_id) != owner
+        || super::typeck_body_of(tcx, hir_id).tainted_by_errors.is_some()
+    {
+        return false;
+    }
+    let body = tcx.optimized_mir(owner);
+
+    let Some(local) = find_var_local(tcx, body, var_id) else {
+        return false;
+    };
+    let mut finder = UseFinder {
+        body,
+        local,
+        span: expr.span,
+        found: None,
+    };
+    finder.visit_body(body);
+    let Some((location, _span)) = finder.found else {
+        return false;
+    };
+
+    // A borrow created by the use itself, like the autoref of `x.clone()`,
+    // is part of the use.
+    let use_borrow = body.stmt_at(location).left().and_then(|stmt| match &stmt.kind {
+        mir::StatementKind::Assign(assign) if matches!(assign.1, mir::Rvalue::Ref(..)) => assign.0.as_local(),
+        _ => None,
+    });
+    let mut locals = possible_borrowers(body, local);
+    if let Some(use_borrow) = use_borrow {
+        locals.remove(&use_borrow);
+    }
+    locals.insert(local);
+
+    !is_used_after(body, local, &locals, location)
+}
+
+fn find_var_local(tcx: TyCtxt<'_>, body: &mir::Body<'_>, var_id: hir::HirId) -> Option<Local> {
+    let name = tcx.hir().name(var_id);
+    let span = tcx.hir().span(var_id);
+    body.var_debug_info.iter().find_map(|info| match &info.value {
+        mir::VarDebugInfoContents::Place(place)
+            if info.name == name && info.source_info.span == span && info.composite.is_none() =>
+        {
+            place.as_local()
+        },
+        _ => None,
+    })
+}
+
+/// Finds the location of the smallest statement or terminator, which uses the
+/// local and contains the span of the expression.
+struct UseFinder<'a, 'tcx> {
+    body: &'a mir::Body<'tcx>,
+    local: Local,
+    span: rustc_span::Span,
+    found: Option<(Location, rustc_span::Span)>,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for UseFinder<'a, 'tcx> {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, location: Location) {
+        if local != self.local || !context.is_use() {
+            return;
+        }
+
+        let span = self.body.source_info(location).span;
+        if !span.contains(self.span) {
+            return;
+        }
+        if let Some((_, found_span)) = self.found
+            && (span == found_span || !found_span.contains(span))
+        {
+            return;
+        }
+        self.found = Some((location, span));
+    }
+}
+
+/// Returns all locals, which might hold a reference to the given local. This
+/// includes direct borrows and all values, which are derived from them and
+/// can contain references. The check is flow-insensitive and therefore
+/// conservative.
+fn possible_borrowers(body: &mir::Body<'_>, local: Local) -> FxHashSet<Local> {
+    let mut borrowers = FxHashSet::default();
+    loop {
+        let mut changed = false;
+        for (block, data) in body.basic_blocks.iter_enumerated() {
+            for (statement_index, stmt) in data.statements.iter().enumerate() {
+                let mir::StatementKind::Assign(assign) = &stmt.kind else {
+                    continue;
+                };
+                let (dest, rvalue) = &**assign;
+                let is_borrow = match rvalue {
+                    mir::Rvalue::Ref(_, _, place) | mir::Rvalue::AddressOf(_, place) => place.local == local,
+                    _ => {
+                        let mut mentions = MentionsVisitor {
+                            locals: &borrowers,
+                            found: false,
+                        };
+                        mentions.visit_rvalue(rvalue, Location { block, statement_index });
+                        mentions.found && body.local_decls[dest.local].ty.has_erased_regions()
+                    },
+                };
+                if is_borrow {
+                    changed |= borrowers.insert(dest.local);
+                }
+            }
+
+            if let mir::TerminatorKind::Call {
+                func,
+                args,
+                destination,
+                ..
+            } = &data.terminator().kind
+            {
+                let location = body.terminator_loc(block);
+                let mut mentions = MentionsVisitor {
+                    locals: &borrowers,
+                    found: false,
+                };
+                mentions.visit_operand(func, location);
+                for arg in args {
+                    mentions.visit_operand(arg, location);
+                }
+                if mentions.found && body.local_decls[destination.local].ty.has_erased_regions() {
+                    changed |= borrowers.insert(destination.local);
+                }
+            }
+        }
+
+        if !changed {
+            return borrowers;
+        }
+    }
+}
+
+struct MentionsVisitor<'a> {
+    locals: &'a FxHashSet<Local>,
+    found: bool,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for MentionsVisitor<'a> {
+    fn visit_local(&mut self, local: Local, _context: PlaceContext, _location: Location) {
+        self.found |= self.locals.contains(&local);
+    }
+}
+
+/// Checks if any of the given locals is used, in a block reachable from the
+/// given location. Drops of `var` itself are not counted as uses.
+fn is_used_after(body: &mir::Body<'_>, var: Local, locals: &FxHashSet<Local>, location: Location) -> bool {
+    let mut visitor = UsedVisitor {
+        var,
+        locals,
+        used: false,
+    };
+
+    let data = &body.basic_blocks[location.block];
+    for statement_index in (location.statement_index + 1)..data.statements.len() {
+        let loc = Location {
+            block: location.block,
+            statement_index,
+        };
+        visitor.visit_statement(&data.statements[statement_index], loc);
+    }
+    if location.statement_index < data.statements.len() {
+        visitor.visit_terminator(data.terminator(), body.terminator_loc(location.block));
+    }
+
+    let mut stack: Vec<BasicBlock> = data.terminator().successors().collect();
+    let mut visited = FxHashSet::default();
+    while !visitor.used
+        && let Some(block) = stack.pop()
+    {
+        if visited.insert(block) {
+            visitor.visit_basic_block_data(block, &body.basic_blocks[block]);
+            stack.extend(body.basic_blocks[block].terminator().successors());
+        }
+    }
+    visitor.used
+}
+
+struct UsedVisitor<'a> {
+    var: Local,
+    locals: &'a FxHashSet<Local>,
+    used: bool,
+}
+
+impl<'a, 'tcx> Visitor<'tcx> for UsedVisitor<'a> {
+    fn visit_local(&mut self, local: Local, context: PlaceContext, _location: Location) {
+        if !self.locals.contains(&local) || !context.is_use() {
+            return;
+        }
+        let is_use = match context {
+            // Assigning a new value, is not a use of the old one
+            PlaceContext::MutatingUse(
+                MutatingUseContext::Store
+                | MutatingUseContext::Call
+                | MutatingUseContext::AsmOutput
+                | MutatingUseContext::Yield
+                | MutatingUseContext::Deinit,
+            ) => false,
+            // The drop of the variable itself doesn't require the value.
+            // References might still be used in `Drop` implementations.
+            PlaceContext::MutatingUse(MutatingUseContext::Drop) => local != self.var,
+            _ => true,
+        };
+        self.used |= is_use;
+    }
+}
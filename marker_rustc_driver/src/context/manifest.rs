@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+
+use camino::Utf8Path;
+use marker_api::manifest::DependencyKind;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{BytePos, Span};
+use serde::Deserialize;
+use toml::Spanned;
+
+/// The spans of the entries in the `Cargo.toml` file of a package.
+#[derive(Debug, Default)]
+pub struct ManifestSpans {
+    pub package: Option<Span>,
+    pub features: BTreeMap<String, Span>,
+    pub dependencies: Vec<(DependencyKind, String, Span)>,
+}
+
+impl ManifestSpans {
+    /// Returns the span of the dependency with the given name, as written in
+    /// the manifest. Target specific dependencies are included.
+    pub fn dependency(&self, kind: DependencyKind, name: &str) -> Option<Span> {
+        self.dependencies
+            .iter()
+            .find(|(dep_kind, dep_name, _)| *dep_kind == kind && dep_name == name)
+            .map(|(_, _, span)| *span)
+    }
+}
+
+type SpannedTable = BTreeMap<Spanned<String>, toml::Value>;
+
+/// The parts of the manifest, which should have spans. Only the keys are
+/// spanned, since `toml` doesn't support spans of dotted key tables, like
+/// `serde.version = "1.0"`. The dependency tables are repeated, instead of
+/// flattened, as `#[serde(flatten)]` drops the spans.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawManifest {
+    #[serde(default)]
+    features: SpannedTable,
+    #[serde(default)]
+    dependencies: SpannedTable,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: SpannedTable,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: SpannedTable,
+    #[serde(default)]
+    target: BTreeMap<String, RawTargetDependencies>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawTargetDependencies {
+    #[serde(default)]
+    dependencies: SpannedTable,
+    #[serde(default, alias = "dev_dependencies")]
+    dev_dependencies: SpannedTable,
+    #[serde(default, alias = "build_dependencies")]
+    build_dependencies: SpannedTable,
+}
+
+/// Loads the `Cargo.toml` file in the given directory into the source map and
+/// returns the spans of its entries. `None` is returned, if the file couldn't
+/// be loaded or parsed.
+///
+/// The file is loaded with a path relative to the current directory, if possible.
+/// Cargo invokes the driver from the workspace root, this keeps the rendered
+/// paths consistent with the paths of Rust files.
+pub fn load_manifest_spans(tcx: TyCtxt<'_>, manifest_dir: &Utf8Path) -> Option<ManifestSpans> {
+    let path = manifest_dir.join("Cargo.toml");
+    let path = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| path.strip_prefix(cwd).ok().map(ToOwned::to_owned))
+        .unwrap_or(path);
+
+    let file = tcx.sess.source_map().load_file(path.as_std_path()).ok()?;
+    let src = file.src.as_ref()?;
+    // Source files are limited to `u32::MAX` bytes by rustc
+    #[allow(clippy::cast_possible_truncation)]
+    let to_span = |key: &Spanned<String>| {
+        let range = key.span();
+        Span::with_root_ctxt(
+            file.start_pos + BytePos(range.start as u32),
+            file.start_pos + BytePos(range.end as u32),
+        )
+    };
+
+    let top_level: SpannedTable = toml::from_str(src).ok()?;
+    let raw: RawManifest = toml::from_str(src).ok()?;
+
+    let tables = [
+        (DependencyKind::Normal, &raw.dependencies),
+        (DependencyKind::Dev, &raw.dev_dependencies),
+        (DependencyKind::Build, &raw.build_dependencies),
+    ]
+    .into_iter()
+    .chain(raw.target.values().flat_map(|target| {
+        [
+            (DependencyKind::Normal, &target.dependencies),
+            (DependencyKind::Dev, &target.dev_dependencies),
+            (DependencyKind::Build, &target.build_dependencies),
+        ]
+    }));
+    let dependencies = tables
+        .flat_map(|(kind, table)| table.keys().map(move |key| (kind, key.get_ref().clone(), to_span(key))))
+        .collect();
+
+    Some(ManifestSpans {
+        package: top_level.keys().find(|key| key.get_ref() == "package").map(to_span),
+        features: raw
+            .features
+            .keys()
+            .map(|key| (key.get_ref().clone(), to_span(key)))
+            .collect(),
+        dependencies,
+    })
+}
@@ -9,16 +9,22 @@ use super::RustcContext;
 
 impl<'ast, 'tcx: 'ast> AstMapDriver<'ast> for RustcContext<'ast, 'tcx> {
     fn item(&'ast self, id: ItemId) -> Option<ItemKind<'ast>> {
+        // Items of other crates, like traits from the standard library, have
+        // no AST in this compilation unit.
+        if !self.rustc_converter.to_def_id(id).is_local() {
+            return None;
+        }
         let rustc_id = self.rustc_converter.to_item_id(id);
         self.marker_converter.item(rustc_id)
     }
 
     fn variant(&'ast self, id: VariantId) -> Option<&'ast EnumVariant<'ast>> {
-        self.marker_converter.variant(id)
+        let def_id = self.rustc_converter.to_def_id(id).as_local()?;
+        self.marker_converter.variant(def_id)
     }
 
     fn field(&'ast self, id: FieldId) -> Option<&'ast ItemField<'ast>> {
-        self.marker_converter.field(id)
+        self.marker_converter.field(self.rustc_converter.to_hir_id(id))
     }
 
     fn body(&'ast self, id: BodyId) -> &'ast ast::Body<'ast> {
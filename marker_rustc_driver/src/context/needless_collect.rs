@@ -0,0 +1,130 @@
+use rustc_hir as hir;
+use rustc_hir::def::Res;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::{sym, DesugaringKind, Span};
+
+/// Returns the span, which can be removed, if the given expression is a `collect()`
+/// call into a `Vec`, which is only used to create a new iterator. This is the
+/// case, if the collection is directly turned into an iterator, or if it's stored
+/// in a local variable, which is only used once to create an iterator.
+pub(super) fn needless_collect(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> Option<Span> {
+    let hir::Node::Expr(collect) = tcx.hir().find(hir_id)? else {
+        return None;
+    };
+    let hir::ExprKind::MethodCall(_, collect_recv, [], _) = collect.kind else {
+        return None;
+    };
+    if collect.span.from_expansion() || collect_recv.span.from_expansion() {
+        return None;
+    }
+    let owner = tcx.hir().enclosing_body_owner(hir_id);
+    let typeck = super::typeck_body_of(tcx, hir_id);
+    let is_collect = typeck.type_dependent_def_id(collect.hir_id).is_some_and(|fn_id| {
+        tcx.item_name(fn_id).as_str() == "collect"
+            && tcx
+                .trait_of_item(fn_id)
+                .is_some_and(|trait_id| tcx.is_diagnostic_item(sym::Iterator, trait_id))
+    });
+    let is_vec = matches!(
+        typeck.expr_ty(collect).kind(),
+        ty::Adt(adt, _) if tcx.is_diagnostic_item(sym::Vec, adt.did())
+    );
+    if !is_collect || !is_vec {
+        return None;
+    }
+
+    // The removed code starts after the receiver of `collect()`
+    let removal_start = collect_recv.span.shrink_to_hi();
+    match tcx.hir().get_parent(collect.hir_id) {
+        hir::Node::Expr(parent) => {
+            let end = iter_creation_end(tcx, typeck, parent, collect)?;
+            Some(removal_start.with_hi(end.hi()))
+        },
+        hir::Node::Local(local) if local.els.is_none() => {
+            let hir::PatKind::Binding(_, var_id, _, None) = local.pat.kind else {
+                return None;
+            };
+            let mut finder = UseFinder { var_id, uses: vec![] };
+            finder.visit_body(tcx.hir().body(tcx.hir().body_owned_by(owner)));
+            let [var_use] = finder.uses[..] else {
+                return None;
+            };
+            // Inlining the iterator into a loop or closure would change how often
+            // and when the iterator is evaluated.
+            let local_block = tcx.hir().parent_id(tcx.hir().parent_id(local.hir_id));
+            let in_loop_or_closure = tcx
+                .hir()
+                .parent_iter(var_use.hir_id)
+                .take_while(|(id, _)| *id != local_block)
+                .any(|(_, node)| {
+                    matches!(
+                        node,
+                        hir::Node::Expr(hir::Expr {
+                            kind: hir::ExprKind::Loop(..) | hir::ExprKind::Closure(..),
+                            ..
+                        })
+                    )
+                });
+            if in_loop_or_closure {
+                return None;
+            }
+            let hir::Node::Expr(parent) = tcx.hir().get_parent(var_use.hir_id) else {
+                return None;
+            };
+            iter_creation_end(tcx, typeck, parent, var_use)?;
+            Some(removal_start.with_hi(collect.span.hi()))
+        },
+        _ => None,
+    }
+}
+
+/// Checks if `parent` creates an iterator from the `Vec` given by `expr`. This
+/// is the case for calls of `into_iter()` and the head of `for` loops. `iter()`
+/// is not accepted, as it yields references instead of the collected values.
+/// The returned span marks the end of the code, which can be removed.
+fn iter_creation_end<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    typeck: &'tcx ty::TypeckResults<'tcx>,
+    parent: &'tcx hir::Expr<'tcx>,
+    expr: &'tcx hir::Expr<'tcx>,
+) -> Option<Span> {
+    match parent.kind {
+        hir::ExprKind::MethodCall(segment, recv, [], _) if recv.hir_id == expr.hir_id => {
+            if parent.span.from_expansion() {
+                return None;
+            }
+            let fn_id = typeck.type_dependent_def_id(parent.hir_id)?;
+            let is_into_iter = segment.ident.name == sym::into_iter
+                && tcx
+                    .trait_of_item(fn_id)
+                    .is_some_and(|trait_id| tcx.is_diagnostic_item(sym::IntoIterator, trait_id));
+            is_into_iter.then_some(parent.span)
+        },
+        hir::ExprKind::Call(
+            hir::Expr {
+                kind: hir::ExprKind::Path(hir::QPath::LangItem(hir::LangItem::IntoIterIntoIter, ..)),
+                ..
+            },
+            [arg],
+        ) if arg.hir_id == expr.hir_id && parent.span.is_desugaring(DesugaringKind::ForLoop) => Some(expr.span),
+        _ => None,
+    }
+}
+
+/// Collects all uses of a local variable.
+struct UseFinder<'tcx> {
+    var_id: hir::HirId,
+    uses: Vec<&'tcx hir::Expr<'tcx>>,
+}
+
+impl<'tcx> Visitor<'tcx> for UseFinder<'tcx> {
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::Path(hir::QPath::Resolved(None, path)) = expr.kind
+            && path.res == Res::Local(self.var_id)
+        {
+            self.uses.push(expr);
+        }
+        intravisit::walk_expr(self, expr);
+    }
+}
@@ -0,0 +1,114 @@
+use marker_api::common::NodeId;
+use rustc_hir as hir;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::hir::nested_filter;
+
+use crate::conversion::marker::MarkerConverter;
+
+/// Returns the [`NodeId`] of the smallest node, which contains the given span
+/// and is represented in Marker's AST. Only the items containing the span are
+/// traversed, other items are skipped based on their span.
+pub(super) fn node_at<'tcx>(
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+    converter: &MarkerConverter<'_, 'tcx>,
+    span: rustc_span::Span,
+) -> Option<NodeId> {
+    let mut visitor = NodeAtVisitor {
+        tcx,
+        converter,
+        span,
+        found: None,
+    };
+    tcx.hir().walk_toplevel_module(&mut visitor);
+    visitor.found.map(|(_len, id)| id)
+}
+
+struct NodeAtVisitor<'a, 'ast, 'tcx> {
+    tcx: rustc_middle::ty::TyCtxt<'tcx>,
+    converter: &'a MarkerConverter<'ast, 'tcx>,
+    span: rustc_span::Span,
+    /// The length of the span and the id of the smallest node found so far
+    found: Option<(u32, NodeId)>,
+}
+
+impl<'a, 'ast, 'tcx> NodeAtVisitor<'a, 'ast, 'tcx> {
+    /// Checks if the given node contains the searched span. Returns `false`, if
+    /// the children of the node can be skipped, as they can't contain the span
+    /// either.
+    fn check_node(&mut self, span: rustc_span::Span, node: hir::Node<'tcx>) -> bool {
+        if !span.contains(self.span) {
+            // Nodes created by macros can have spans outside of their parent
+            return span.from_expansion();
+        }
+
+        let len = (span.hi() - span.lo()).0;
+        // Children are visited after their parents. The child is preferred,
+        // if both nodes have the same span.
+        if self.found.map_or(true, |(found_len, _)| len <= found_len)
+            && let Some(id) = self.converter.node_id(node)
+        {
+            self.found = Some((len, id));
+        }
+        true
+    }
+}
+
+impl<'a, 'ast, 'tcx> Visitor<'tcx> for NodeAtVisitor<'a, 'ast, 'tcx> {
+    type NestedFilter = nested_filter::All;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_item(&mut self, item: &'tcx hir::Item<'tcx>) {
+        // The span of `mod name;` items doesn't contain the module content.
+        // Modules are therefore also visited, if their inner span contains
+        // the searched span.
+        let contains = self.check_node(item.span, hir::Node::Item(item));
+        if contains || matches!(item.kind, hir::ItemKind::Mod(module) if module.spans.inner_span.contains(self.span)) {
+            intravisit::walk_item(self, item);
+        }
+    }
+
+    fn visit_trait_item(&mut self, item: &'tcx hir::TraitItem<'tcx>) {
+        if self.check_node(item.span, hir::Node::TraitItem(item)) {
+            intravisit::walk_trait_item(self, item);
+        }
+    }
+
+    fn visit_impl_item(&mut self, item: &'tcx hir::ImplItem<'tcx>) {
+        if self.check_node(item.span, hir::Node::ImplItem(item)) {
+            intravisit::walk_impl_item(self, item);
+        }
+    }
+
+    fn visit_foreign_item(&mut self, item: &'tcx hir::ForeignItem<'tcx>) {
+        if self.check_node(item.span, hir::Node::ForeignItem(item)) {
+            intravisit::walk_foreign_item(self, item);
+        }
+    }
+
+    fn visit_field_def(&mut self, field: &'tcx hir::FieldDef<'tcx>) {
+        if self.check_node(field.span, hir::Node::Field(field)) {
+            intravisit::walk_field_def(self, field);
+        }
+    }
+
+    fn visit_variant(&mut self, variant: &'tcx hir::Variant<'tcx>) {
+        if self.check_node(variant.span, hir::Node::Variant(variant)) {
+            intravisit::walk_variant(self, variant);
+        }
+    }
+
+    fn visit_stmt(&mut self, stmt: &'tcx hir::Stmt<'tcx>) {
+        if self.check_node(stmt.span, hir::Node::Stmt(stmt)) {
+            intravisit::walk_stmt(self, stmt);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if self.check_node(expr.span, hir::Node::Expr(expr)) {
+            intravisit::walk_expr(self, expr);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use marker_api::ast::PanicKind;
+use rustc_hir as hir;
+use rustc_lexer::{unescape, LiteralKind, TokenKind};
+use rustc_middle::ty::TyCtxt;
+use rustc_span::{sym, ExpnId};
+
+/// Returns the [`PanicKind`], message and call site of the panicking macro, that the
+/// expression with the given [`HirId`](hir::HirId) was expanded from. Nested
+/// panicking macros, like the `panic!` inside an `assert!`, are ignored in
+/// favor of the outermost one.
+pub(super) fn panic_macro(
+    tcx: TyCtxt<'_>,
+    hir_id: hir::HirId,
+) -> Option<(PanicKind, Option<String>, rustc_span::Span)> {
+    let map = tcx.hir();
+    let span = map.span(hir_id);
+
+    let mut found = None;
+    let mut expn = span.ctxt().outer_expn();
+    while expn != ExpnId::root() {
+        let data = expn.expn_data();
+        if let Some(kind) = data.macro_def_id.and_then(|def_id| panic_kind(tcx, def_id)) {
+            found = Some((expn, kind, data.call_site));
+        }
+        expn = data.call_site.ctxt().outer_expn();
+    }
+    let (expn, kind, call_site) = found?;
+
+    // Only the outermost expression of the expansion should be detected.
+    // Statements created by macros in statement position use the span of the
+    // expansion and are therefore skipped, together with blocks.
+    let parent = map
+        .parent_iter(hir_id)
+        .find(|(_, node)| !matches!(node, hir::Node::Stmt(_) | hir::Node::Block(_)));
+    if let Some((parent_id, _)) = parent
+        && map.span(parent_id).ctxt().outer_expn().is_descendant_of(expn)
+    {
+        return None;
+    }
+
+    let message_index = match kind {
+        PanicKind::Assert => 1,
+        PanicKind::AssertEq | PanicKind::AssertNe => 2,
+        PanicKind::Panic | PanicKind::Todo | PanicKind::Unimplemented | PanicKind::Unreachable => 0,
+        _ => unreachable!(),
+    };
+    let message = tcx
+        .sess
+        .source_map()
+        .span_to_snippet(call_site)
+        .ok()
+        .and_then(|snippet| macro_str_arg(&snippet, message_index));
+    Some((kind, message, call_site))
+}
+
+fn panic_kind(tcx: TyCtxt<'_>, def_id: hir::def_id::DefId) -> Option<PanicKind> {
+    let kind = match tcx.get_diagnostic_name(def_id)? {
+        sym::core_panic_macro
+        | sym::core_panic_2015_macro
+        | sym::core_panic_2021_macro
+        | sym::std_panic_macro
+        | sym::std_panic_2015_macro => PanicKind::Panic,
+        sym::todo_macro => PanicKind::Todo,
+        sym::unimplemented_macro => PanicKind::Unimplemented,
+        sym::unreachable_macro | sym::unreachable_2015_macro => PanicKind::Unreachable,
+        sym::assert_macro | sym::debug_assert_macro => PanicKind::Assert,
+        sym::assert_eq_macro | sym::debug_assert_eq_macro => PanicKind::AssertEq,
+        sym::assert_ne_macro | sym::debug_assert_ne_macro => PanicKind::AssertNe,
+        _ => return None,
+    };
+    Some(kind)
+}
+
+/// Returns the content of the macro argument with the given index, if it's a
+/// string literal. The arguments are read from the source of the macro call,
+/// as rustc doesn't keep them for format macros.
+fn macro_str_arg(snippet: &str, index: usize) -> Option<String> {
+    let args_start = snippet.find(['(', '[', '{'])? + 1;
+    let args = snippet.get(args_start..snippet.len() - 1)?;
+
+    let mut pos = 0;
+    let mut depth = 0;
+    let mut arg_index = 0;
+    let mut arg_tokens = vec![];
+    for token in rustc_lexer::tokenize(args) {
+        let text = &args[pos..pos + token.len as usize];
+        pos += token.len as usize;
+        match token.kind {
+            TokenKind::Whitespace | TokenKind::LineComment { .. } | TokenKind::BlockComment { .. } => continue,
+            TokenKind::Comma if depth == 0 => {
+                arg_index += 1;
+                continue;
+            },
+            TokenKind::OpenParen | TokenKind::OpenBracket | TokenKind::OpenBrace => depth += 1,
+            TokenKind::CloseParen | TokenKind::CloseBracket | TokenKind::CloseBrace => depth -= 1,
+            _ => {},
+        }
+        if arg_index == index {
+            arg_tokens.push((token.kind, text));
+        }
+    }
+
+    let [(TokenKind::Literal { kind, suffix_start }, text)] = arg_tokens[..] else {
+        return None;
+    };
+    if suffix_start as usize != text.len() {
+        return None;
+    }
+    match kind {
+        LiteralKind::Str { terminated: true } => {
+            let mut content = String::new();
+            let mut is_valid = true;
+            unescape::unescape_literal(&text[1..text.len() - 1], unescape::Mode::Str, &mut |_, res| match res {
+                Ok(c) => content.push(c),
+                Err(err) => is_valid &= !err.is_fatal(),
+            });
+            is_valid.then_some(content)
+        },
+        LiteralKind::RawStr {
+            n_hashes: Some(n_hashes),
+        } => {
+            let n_hashes = usize::from(n_hashes);
+            Some(text[2 + n_hashes..text.len() - 1 - n_hashes].to_string())
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::macro_str_arg;
+
+    #[test]
+    fn test_macro_str_arg() {
+        assert_eq!(macro_str_arg(r#"panic!("TODO")"#, 0).as_deref(), Some("TODO"));
+        assert_eq!(macro_str_arg(r#"todo![ "a\n{}" , x]"#, 0).as_deref(), Some("a\n{}"));
+        assert_eq!(macro_str_arg(r#"todo!{r"raw \n"}"#, 0).as_deref(), Some(r"raw \n"));
+        assert_eq!(
+            macro_str_arg(r#"assert_eq!(f(a, b), [1, 2], "msg {}", 1)"#, 2).as_deref(),
+            Some("msg {}")
+        );
+        assert_eq!(macro_str_arg("assert!(x, msg)", 1), None);
+        assert_eq!(macro_str_arg("panic!()", 0), None);
+        assert_eq!(macro_str_arg(r#"panic!("a" "b")"#, 0), None);
+    }
+}
@@ -0,0 +1,82 @@
+use rustc_hir as hir;
+use rustc_hir::intravisit::{self, Visitor};
+use rustc_middle::{hir::nested_filter, ty::TyCtxt};
+
+/// Checks if the generic parameter with the given [`DefId`](hir::def_id::DefId)
+/// is used in the item. Usages as the bounded type of a where predicate, like
+/// `T` in `where T: Clone` are ignored, since they only restrict the parameter.
+/// Any other usage, like `PhantomData<T>` counts.
+pub(super) fn is_param_used(tcx: TyCtxt<'_>, param: hir::def_id::DefId, item: hir::def_id::LocalDefId) -> bool {
+    let mut visitor = ParamUsageVisitor {
+        tcx,
+        param,
+        used: false,
+    };
+    match tcx.hir().get_by_def_id(item) {
+        hir::Node::Item(item) => visitor.visit_item(item),
+        hir::Node::TraitItem(item) => visitor.visit_trait_item(item),
+        hir::Node::ImplItem(item) => visitor.visit_impl_item(item),
+        hir::Node::ForeignItem(item) => visitor.visit_foreign_item(item),
+        _ => {},
+    }
+    visitor.used
+}
+
+struct ParamUsageVisitor<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    param: hir::def_id::DefId,
+    used: bool,
+}
+
+impl<'tcx> ParamUsageVisitor<'tcx> {
+    fn is_param_ty(&self, ty: &hir::Ty<'_>) -> bool {
+        matches!(
+            ty.kind,
+            hir::TyKind::Path(hir::QPath::Resolved(None, path)) if path.res.opt_def_id() == Some(self.param)
+        )
+    }
+
+    fn is_param_lifetime(&self, lifetime: &hir::Lifetime) -> bool {
+        matches!(lifetime.res, hir::LifetimeName::Param(id) if id.to_def_id() == self.param)
+    }
+}
+
+impl<'tcx> Visitor<'tcx> for ParamUsageVisitor<'tcx> {
+    type NestedFilter = nested_filter::All;
+
+    fn nested_visit_map(&mut self) -> Self::Map {
+        self.tcx.hir()
+    }
+
+    fn visit_where_predicate(&mut self, predicate: &'tcx hir::WherePredicate<'tcx>) {
+        match predicate {
+            hir::WherePredicate::BoundPredicate(bound) if self.is_param_ty(bound.bounded_ty) => {
+                for param in bound.bound_generic_params {
+                    self.visit_generic_param(param);
+                }
+                for bound in bound.bounds {
+                    self.visit_param_bound(bound);
+                }
+            },
+            hir::WherePredicate::RegionPredicate(region) if self.is_param_lifetime(region.lifetime) => {
+                for bound in region.bounds {
+                    self.visit_param_bound(bound);
+                }
+            },
+            _ => intravisit::walk_where_predicate(self, predicate),
+        }
+    }
+
+    fn visit_path(&mut self, path: &hir::Path<'tcx>, _id: hir::HirId) {
+        if path.res.opt_def_id() == Some(self.param) {
+            self.used = true;
+        }
+        intravisit::walk_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'tcx hir::Lifetime) {
+        if self.is_param_lifetime(lifetime) {
+            self.used = true;
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use rustc_hir as hir;
+use rustc_middle::ty::TyCtxt;
+use rustc_span::sym;
+
+/// Checks if the node with the given [`hir::HirId`] is test code. This is the
+/// case for nodes inside items marked with `#[cfg(test)]` and inside `#[test]`
+/// functions, including the items themselves.
+pub(super) fn is_in_test_code(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    let map = tcx.hir();
+    std::iter::once((hir_id, map.get(hir_id)))
+        .chain(map.parent_iter(hir_id))
+        .any(|(id, node)| is_cfg_test(tcx, id) || is_test_fn(tcx, node))
+}
+
+/// Checks for a `#[cfg(test)]` attribute. Other configurations containing
+/// `test`, like `#[cfg(any(test, feature = "testing"))]`, are not considered.
+fn is_cfg_test(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    tcx.hir().attrs(hir_id).iter().any(|attr| {
+        attr.has_name(sym::cfg)
+            && attr
+                .meta_item_list()
+                .is_some_and(|items| items.iter().any(|item| item.has_name(sym::test)))
+    })
+}
+
+/// The `#[test]` macro keeps the function and adds a constant with the same name,
+/// which is marked with `#[rustc_test_marker]`. This checks for such a constant.
+fn is_test_fn(tcx: TyCtxt<'_>, node: hir::Node<'_>) -> bool {
+    let hir::Node::Item(item) = node else {
+        return false;
+    };
+    if !matches!(item.kind, hir::ItemKind::Fn(..)) {
+        return false;
+    }
+
+    let map = tcx.hir();
+    map.module_items(tcx.parent_module_from_def_id(item.owner_id.def_id))
+        .any(|id| {
+            let other = map.item(id);
+            matches!(other.kind, hir::ItemKind::Const(..))
+                && other.ident.name == item.ident.name
+                && tcx.has_attr(other.owner_id, sym::rustc_test_marker)
+        })
+}
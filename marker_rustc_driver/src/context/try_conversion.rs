@@ -0,0 +1,59 @@
+use rustc_hir as hir;
+use rustc_middle::ty::{self, Ty, TyCtxt};
+use rustc_span::sym;
+
+/// Returns the source and target error type and the `From` impl, if the `?`
+/// operator, with the given desugared `match` expression, converts the error.
+///
+/// The desugared `?` operator calls `FromResidual::from_residual` to propagate
+/// the residual. For [`Result`], this calls `From::from` with the error, if the
+/// error types differ. The `From` trait is returned instead of the impl, if the
+/// impl depends on generic parameters and can't be resolved.
+pub(super) fn try_residual_conversion(
+    tcx: TyCtxt<'_>,
+    hir_id: hir::HirId,
+) -> Option<(Ty<'_>, Ty<'_>, hir::def_id::DefId)> {
+    let hir::Node::Expr(expr) = tcx.hir().get(hir_id) else {
+        return None;
+    };
+    let hir::ExprKind::Match(_, [break_arm, _], hir::MatchSource::TryDesugar(_)) = expr.kind else {
+        return None;
+    };
+    // The residual is either returned, or used to break out of a `try` block
+    let (hir::ExprKind::Ret(Some(from_residual)) | hir::ExprKind::Break(_, Some(from_residual))) = break_arm.body.kind
+    else {
+        return None;
+    };
+    let hir::ExprKind::Call(callee, _) = from_residual.kind else {
+        return None;
+    };
+
+    // `<Target as FromResidual<Residual>>::from_residual`
+    let ty::FnDef(_, args) = *super::typeck_body_of(tcx, hir_id).expr_ty(callee).kind() else {
+        return None;
+    };
+    let (ty::Adt(target_adt, target_args), ty::Adt(residual_adt, residual_args)) =
+        (args.type_at(0).kind(), args.type_at(1).kind())
+    else {
+        return None;
+    };
+    let result = tcx.get_diagnostic_item(sym::Result)?;
+    if target_adt.did() != result || residual_adt.did() != result {
+        return None;
+    }
+    let source_err = residual_args.type_at(1);
+    let target_err = target_args.type_at(1);
+    if tcx.erase_regions(source_err) == tcx.erase_regions(target_err) {
+        return None;
+    }
+
+    let from_fn = tcx.get_diagnostic_item(sym::from_fn)?;
+    let param_env = tcx.param_env(hir_id.owner.to_def_id());
+    let from_args = tcx.erase_regions(tcx.mk_args(&[target_err.into(), source_err.into()]));
+    let from_impl = ty::Instance::resolve(tcx, param_env, from_fn, from_args)
+        .ok()
+        .flatten()
+        .and_then(|instance| tcx.impl_of_method(instance.def_id()))
+        .unwrap_or_else(|| tcx.parent(from_fn));
+    Some((source_err, target_err, from_impl))
+}
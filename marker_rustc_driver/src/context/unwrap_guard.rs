@@ -0,0 +1,140 @@
+use rustc_hir as hir;
+use rustc_hir::def::Res;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::{sym, Symbol};
+
+/// Checks if the given expression is an `unwrap()` or `expect()` call on a local
+/// `Option` or `Result`, which has been checked before. The following checks
+/// are detected:
+///
+/// ```ignore
+/// if x.is_some() { x.unwrap(); }
+/// if x.is_none() { ... } else { x.unwrap(); }
+/// if x.is_none() { return; }
+/// x.unwrap();
+/// ```
+///
+/// The same checks are detected for `Result` with `is_ok()` and `is_err()`.
+/// Mutable variables are ignored, as they could be modified after the check.
+pub(super) fn is_guarded_unwrap(tcx: TyCtxt<'_>, hir_id: hir::HirId) -> bool {
+    let hir::Node::Expr(expr) = tcx.hir().get(hir_id) else {
+        return false;
+    };
+    let hir::ExprKind::MethodCall(segment, recv, _, _) = expr.kind else {
+        return false;
+    };
+    if !matches!(segment.ident.name.as_str(), "unwrap" | "expect") {
+        return false;
+    }
+    let Some(var_id) = local_var(recv) else {
+        return false;
+    };
+    if let hir::Node::Pat(hir::Pat {
+        kind: hir::PatKind::Binding(hir::BindingAnnotation(_, hir::Mutability::Mut), ..),
+        ..
+    }) = tcx.hir().get(var_id)
+    {
+        return false;
+    }
+
+    let typeck = super::typeck_body_of(tcx, hir_id);
+    let ty::Adt(adt, _) = typeck.expr_ty(recv).kind() else {
+        return false;
+    };
+    let (positive, negative) = if tcx.is_diagnostic_item(sym::Option, adt.did()) {
+        ("is_some", "is_none")
+    } else if tcx.is_diagnostic_item(sym::Result, adt.did()) {
+        ("is_ok", "is_err")
+    } else {
+        return false;
+    };
+    let guard = Guard {
+        typeck,
+        var_id,
+        positive: Symbol::intern(positive),
+        negative: Symbol::intern(negative),
+    };
+
+    let mut child = hir_id;
+    for (parent_id, parent) in tcx.hir().parent_iter(hir_id) {
+        match parent {
+            hir::Node::Expr(hir::Expr {
+                kind: hir::ExprKind::If(cond, then, els),
+                ..
+            }) => {
+                let is_then = then.hir_id == child;
+                let is_else = els.is_some_and(|els| els.hir_id == child);
+                if (is_then && guard.is_checked_by(cond, true)) || (is_else && guard.is_checked_by(cond, false)) {
+                    return true;
+                }
+            },
+            hir::Node::Block(block) => {
+                let mut preceding = block.stmts.iter().take_while(|stmt| stmt.hir_id != child);
+                if preceding.any(|stmt| guard.is_early_exit(stmt)) {
+                    return true;
+                }
+            },
+            hir::Node::Item(_)
+            | hir::Node::TraitItem(_)
+            | hir::Node::ImplItem(_)
+            | hir::Node::ForeignItem(_)
+            | hir::Node::AnonConst(_)
+            | hir::Node::ConstBlock(_) => return false,
+            _ => {},
+        }
+        child = parent_id;
+    }
+    false
+}
+
+fn local_var(expr: &hir::Expr<'_>) -> Option<hir::HirId> {
+    match expr.kind {
+        hir::ExprKind::Path(hir::QPath::Resolved(None, path)) => match path.res {
+            Res::Local(id) => Some(id),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+struct Guard<'tcx> {
+    typeck: &'tcx ty::TypeckResults<'tcx>,
+    var_id: hir::HirId,
+    /// The method, which checks that unwrapping succeeds, like `is_some`
+    positive: Symbol,
+    /// The method, which checks that unwrapping fails, like `is_none`
+    negative: Symbol,
+}
+
+impl<'tcx> Guard<'tcx> {
+    /// Checks if the condition being `expected` ensures that the unwrap succeeds.
+    fn is_checked_by(&self, cond: &hir::Expr<'_>, expected: bool) -> bool {
+        match cond.kind {
+            hir::ExprKind::DropTemps(inner) => self.is_checked_by(inner, expected),
+            hir::ExprKind::Unary(hir::UnOp::Not, inner) => self.is_checked_by(inner, !expected),
+            // If `a && b` is true, both operands are true. If `a || b` is false,
+            // both operands are false.
+            hir::ExprKind::Binary(op, lhs, rhs)
+                if (op.node == hir::BinOpKind::And && expected) || (op.node == hir::BinOpKind::Or && !expected) =>
+            {
+                self.is_checked_by(lhs, expected) || self.is_checked_by(rhs, expected)
+            },
+            hir::ExprKind::MethodCall(segment, recv, [], _) if local_var(recv) == Some(self.var_id) => {
+                let method = if expected { self.positive } else { self.negative };
+                segment.ident.name == method
+            },
+            _ => false,
+        }
+    }
+
+    /// Checks for statements like `if x.is_none() { return; }`.
+    fn is_early_exit(&self, stmt: &hir::Stmt<'_>) -> bool {
+        let (hir::StmtKind::Expr(expr) | hir::StmtKind::Semi(expr)) = stmt.kind else {
+            return false;
+        };
+        let hir::ExprKind::If(cond, then, None) = expr.kind else {
+            return false;
+        };
+        self.typeck.expr_ty(then).is_never() && self.is_checked_by(cond, false)
+    }
+}
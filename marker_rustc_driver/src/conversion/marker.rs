@@ -12,8 +12,8 @@ use std::cell::{OnceCell, RefCell};
 
 use crate::context::storage::Storage;
 use marker_api::{
-    ast::{Body, CommonItemData, Crate, EnumVariant, ItemField, ModItem, Visibility as AstVisibility},
-    common::{Level, SymbolId},
+    ast::{Attribute, Body, CommonItemData, Crate, EnumVariant, ItemField, ModItem, Visibility as AstVisibility},
+    common::{Level, NodeId, SpanId, SymbolId},
     prelude::*,
     sem::{Visibility as SemVisibility, VisibilityKind},
     span::{ExpnInfo, FilePos, Span, SpanSource},
@@ -66,6 +66,28 @@ impl<'ast, 'tcx> MarkerConverter<'ast, 'tcx> {
         })
     }
 
+    /// Converts a semantic type, which originates from the body containing the
+    /// given node.
+    pub fn body_sem_ty(&self, id: hir::HirId, ty: rustc_middle::ty::Ty<'tcx>) -> marker_api::sem::TyKind<'ast> {
+        self.with_body(id, |inner| inner.to_sem_ty(ty))
+    }
+
+    /// Converts a semantic type outside of a body. Generic parameters in the type
+    /// are resolved using the generics of the given `owner`.
+    pub fn item_sem_ty(
+        &self,
+        owner: hir::def_id::DefId,
+        ty: rustc_middle::ty::Ty<'tcx>,
+    ) -> marker_api::sem::TyKind<'ast> {
+        let old_owner = self.inner.rustc_generics_owner.replace(Some(owner));
+        debug_assert_eq!(old_owner, None);
+
+        let res = self.inner.to_sem_ty(ty);
+        self.inner.rustc_generics_owner.replace(None);
+
+        res
+    }
+
     forward_to_inner!(pub fn to_lint_level(&self, level: rustc_lint::Level) -> Level);
 
     pub fn body(&self, id: hir::BodyId) -> &'ast Body<'ast> {
@@ -85,7 +107,15 @@ impl<'ast, 'tcx> MarkerConverter<'ast, 'tcx> {
             return Some(*item);
         }
 
-        self.inner.to_item_from_id(item_id)
+        match self.inner.rustc_cx.hir().owner(item_id.owner_id) {
+            hir::OwnerNode::Item(item) => self.inner.to_item(item),
+            hir::OwnerNode::Crate(_) => Some(ItemKind::Mod(self.inner.local_crate().root_mod())),
+            // Associated and foreign items are converted with their parent
+            hir::OwnerNode::TraitItem(_) | hir::OwnerNode::ImplItem(_) | hir::OwnerNode::ForeignItem(_) => {
+                self.convert_parent_item(item_id.owner_id.into());
+                self.inner.items.borrow().get(&api_id).copied()
+            },
+        }
     }
 
     pub fn stmt(&self, hir_id: hir::HirId) -> Option<StmtKind<'ast>> {
@@ -118,26 +148,99 @@ impl<'ast, 'tcx> MarkerConverter<'ast, 'tcx> {
         })
     }
 
-    pub fn variant(&self, id: VariantId) -> Option<&'ast EnumVariant<'ast>> {
-        // Lint crates only gain access to ids of fields and variants, that are
-        // in scope. Marker's conversion first transforms the entire crate. Any enums
-        // defined on this level will therefore be available in the cache.
-        //
-        // Enums and Structs defined inside bodies, will add their fields and variants
-        // to the cache, ensuring again, that they are accessible, if a user asks for them.
-        //
-        // This simply means, that it's enough to only check the cache. This is also
-        // the reason why this function takes an API id and not the rustc variant.
+    pub fn variant(&self, def_id: hir::def_id::LocalDefId) -> Option<&'ast EnumVariant<'ast>> {
+        // Variants are converted with their enum. Items are converted on demand,
+        // if they're not part of the processed module, this requires the enum to
+        // be converted first.
+        let id = self.inner.to_variant_id(def_id);
+        if let Some(variant) = self.inner.variants.borrow().get(&id) {
+            return Some(variant);
+        }
+
+        self.convert_parent_item(self.inner.rustc_cx.local_def_id_to_hir_id(def_id));
         self.inner.variants.borrow().get(&id).copied()
     }
 
-    pub fn field(&self, id: FieldId) -> Option<&'ast ItemField<'ast>> {
-        // See docs of the `variant` method for an explanation, why it's
-        // enough to only check the cache.
+    pub fn field(&self, hir_id: hir::HirId) -> Option<&'ast ItemField<'ast>> {
+        // See docs of the `variant` method for an explanation, why the parent
+        // item has to be converted.
+        let id = self.inner.to_field_id(hir_id);
+        if let Some(field) = self.inner.fields.borrow().get(&id) {
+            return Some(field);
+        }
+
+        self.convert_parent_item(hir_id);
         self.inner.fields.borrow().get(&id).copied()
     }
 
+    pub fn attrs(&self, hir_id: hir::HirId) -> &'ast [Attribute<'ast>] {
+        // Check the cache
+        if let Some(attrs) = self.inner.attrs.borrow().get(&hir_id) {
+            return attrs;
+        }
+
+        let attrs = self.inner.to_attrs(self.inner.rustc_cx.hir().attrs(hir_id));
+        self.inner.attrs.borrow_mut().insert(hir_id, attrs);
+        attrs
+    }
+
+    /// Returns the [`NodeId`] of the given HIR node, if the node is represented
+    /// in Marker's AST. Bodies are converted on demand, to check if they contain
+    /// the node.
+    pub fn node_id(&self, node: hir::Node<'tcx>) -> Option<NodeId> {
+        match node {
+            hir::Node::Item(item) => self.item(item.item_id()).map(|item| item.id().into()),
+            hir::Node::TraitItem(hir::TraitItem { owner_id, .. })
+            | hir::Node::ImplItem(hir::ImplItem { owner_id, .. })
+            | hir::Node::ForeignItem(hir::ForeignItem { owner_id, .. }) => self
+                .item(hir::ItemId { owner_id: *owner_id })
+                .map(|item| item.id().into()),
+            hir::Node::Expr(expr) => {
+                self.convert_enclosing_body(expr.hir_id);
+                let id = self.inner.to_expr_id(expr.hir_id);
+                self.inner.exprs.borrow().contains_key(&id).then_some(id.into())
+            },
+            hir::Node::Stmt(stmt) => {
+                self.convert_enclosing_body(stmt.hir_id);
+                let id = self.inner.to_stmt_id(stmt.hir_id);
+                self.inner.stmts.borrow().contains_key(&id).then_some(id.into())
+            },
+            hir::Node::Field(field) => self.field(field.hir_id).map(|field| field.id().into()),
+            hir::Node::Variant(variant) => self.variant(variant.def_id).map(|variant| variant.id().into()),
+            _ => None,
+        }
+    }
+
+    /// Converts the item, which owns the given node. Only the local crate and
+    /// the processed module are converted upfront, other items are converted
+    /// on demand. This limits the HIR, which rustc tracks as an input of the
+    /// processed module.
+    fn convert_parent_item(&self, hir_id: hir::HirId) {
+        let owner_id = self.inner.rustc_cx.hir().get_parent_item(hir_id);
+        self.item(hir::ItemId { owner_id });
+    }
+
+    fn convert_enclosing_body(&self, hir_id: hir::HirId) {
+        let map = self.inner.rustc_cx.hir();
+        self.body(map.body_owned_by(map.enclosing_body_owner(hir_id)));
+    }
+
     forward_to_inner!(pub fn to_ty_def_id(&self, id: hir::def_id::DefId) -> TyDefId);
+    forward_to_inner!(pub fn to_item_id(&self, id: hir::def_id::DefId) -> ItemId);
+    forward_to_inner!(pub fn to_expr_id(&self, id: hir::HirId) -> ExprId);
+    forward_to_inner!(pub fn to_field_id(&self, id: hir::HirId) -> FieldId);
+    forward_to_inner!(pub fn to_symbol_id(&self, sym: rustc_span::Symbol) -> SymbolId);
+    forward_to_inner!(pub fn to_span_id(&self, rustc_span: rustc_span::Span) -> SpanId);
+    forward_to_inner!(pub fn to_sem_ty(&self, rustc_ty: rustc_middle::ty::Ty<'tcx>) -> marker_api::sem::TyKind<'ast>);
+    forward_to_inner!(pub fn to_const_value(
+        &self,
+        valtree: rustc_middle::ty::ValTree<'tcx>,
+        ty: rustc_middle::ty::Ty<'tcx>,
+    ) -> Option<marker_api::sem::ConstValue<'ast>>);
+    forward_to_inner!(pub fn to_object_safety_violations(
+        &self,
+        violations: &[rustc_middle::traits::ObjectSafetyViolation],
+    ) -> &'ast [marker_api::sem::ObjectSafetyViolation<'ast>]);
     forward_to_inner!(pub fn to_span(&self, rustc_span: rustc_span::Span) -> Span<'ast>);
     forward_to_inner!(pub fn to_span_source(&self, rust_span: rustc_span::Span) -> SpanSource<'ast>);
     forward_to_inner!(pub fn try_to_expn_info(&self, expn_id: rustc_span::ExpnId) -> Option<&'ast ExpnInfo<'ast>>);
@@ -172,6 +275,7 @@ struct MarkerConverterInner<'ast, 'tcx> {
     stmts: RefCell<FxHashMap<StmtId, StmtKind<'ast>>>,
     fields: RefCell<FxHashMap<FieldId, &'ast ItemField<'ast>>>,
     variants: RefCell<FxHashMap<VariantId, &'ast EnumVariant<'ast>>>,
+    attrs: RefCell<FxHashMap<hir::HirId, &'ast [Attribute<'ast>]>>,
 
     // Cached/Dummy values
     builtin_span_source: &'ast marker_api::span::BuiltinInfo<'ast>,
@@ -212,6 +316,10 @@ struct MarkerConverterInner<'ast, 'tcx> {
     /// Requested on demand from rustc using a [`hir::BodyId`] see
     /// [`MarkerConverterInner::rustc_body`] for more information
     rustc_ty_check: RefCell<Option<&'tcx rustc_middle::ty::TypeckResults<'tcx>>>,
+    /// The item, which defines the generic parameters of semantic types, which
+    /// are converted outside of bodies. Inside bodies, the generics are taken
+    /// from the owner of [`MarkerConverterInner::rustc_body`].
+    rustc_generics_owner: RefCell<Option<hir::def_id::DefId>>,
 }
 
 // General util functions
@@ -227,11 +335,13 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             stmts: RefCell::default(),
             fields: RefCell::default(),
             variants: RefCell::default(),
+            attrs: RefCell::default(),
             builtin_span_source: storage.alloc(marker_api::span::BuiltinInfo::default()),
             num_symbols: RefCell::default(),
             lang_item_map: RefCell::default(),
             rustc_body: RefCell::default(),
             rustc_ty_check: RefCell::default(),
+            rustc_generics_owner: RefCell::default(),
         };
 
         s.fill_create_lang_item_map();
@@ -1,3 +1,4 @@
+mod attr;
 mod expr;
 mod generic;
 mod item;
@@ -0,0 +1,147 @@
+use marker_api::{
+    ast::{parse_meta_items, AttrStyle, Attribute, MetaItem, MetaItemStorage, Token, TokenKind},
+    common::SpanId,
+    ffi::FfiStr,
+};
+use rustc_ast as ast;
+use rustc_ast::token;
+use rustc_ast::tokenstream::{TokenStream, TokenTree};
+use rustc_ast_pretty::pprust;
+
+use crate::conversion::marker::MarkerConverterInner;
+
+impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
+    #[must_use]
+    pub fn to_attrs(&self, attrs: &[ast::Attribute]) -> &'ast [Attribute<'ast>] {
+        self.alloc_slice(attrs.iter().map(|attr| self.to_attr(attr)))
+    }
+
+    fn to_attr(&self, attr: &ast::Attribute) -> Attribute<'ast> {
+        let style = match attr.style {
+            ast::AttrStyle::Outer => AttrStyle::Outer,
+            ast::AttrStyle::Inner => AttrStyle::Inner,
+        };
+        let builder = Attribute::builder().span(self.to_span_id(attr.span)).style(style);
+
+        match &attr.kind {
+            ast::AttrKind::Normal(normal) => {
+                let item = &normal.item;
+                let mut tokens = vec![];
+                let mut meta_items = None;
+                match &item.args {
+                    ast::AttrArgs::Empty => {},
+                    ast::AttrArgs::Delimited(args) => {
+                        let mut raw_tokens = vec![];
+                        collect_delimited_tokens(&mut raw_tokens, args.dspan, args.delim, &args.tokens);
+                        meta_items = self.to_meta_items(&raw_tokens);
+                        tokens.extend(raw_tokens.iter().map(|(kind, span)| self.to_token(kind, *span)));
+                    },
+                    ast::AttrArgs::Eq(eq_span, value) => {
+                        tokens.push(Token::new(
+                            TokenKind::Punct,
+                            self.to_symbol_id(rustc_span::Symbol::intern("=")),
+                            self.to_span_id(*eq_span),
+                        ));
+                        let ast::AttrArgsEq::Hir(lit) = value else {
+                            unreachable!("attribute values are lowered to literals in the HIR")
+                        };
+                        tokens.push(self.to_token(&token::TokenKind::Literal(lit.as_token_lit()), lit.span));
+                    },
+                }
+
+                builder
+                    .path(self.alloc_slice(item.path.segments.iter().map(|seg| self.to_ident(seg.ident))))
+                    .tokens(self.alloc_slice(tokens))
+                    .meta_items(meta_items.map(Into::into))
+                    .build()
+            },
+            ast::AttrKind::DocComment(_, comment) => {
+                let doc = rustc_span::symbol::Ident::new(rustc_span::sym::doc, attr.span);
+                builder
+                    .path(self.alloc_slice([self.to_ident(doc)]))
+                    .tokens(&[][..])
+                    .doc_comment(Some(self.to_symbol_id(*comment)))
+                    .build()
+            },
+        }
+    }
+
+    fn to_meta_items(&self, tokens: &[(token::TokenKind, rustc_span::Span)]) -> Option<&'ast [MetaItem<'ast>]> {
+        let texts: Vec<_> = tokens
+            .iter()
+            .map(|(kind, _)| {
+                let text = self.storage.alloc_str(&pprust::token_kind_to_string(kind));
+                (to_token_kind(kind), text)
+            })
+            .collect();
+        let spans: Vec<_> = tokens.iter().map(|(_, span)| *span).collect();
+        parse_meta_items(
+            &texts,
+            &MetaItemConverter {
+                cx: self,
+                spans: &spans,
+            },
+        )
+    }
+
+    fn to_token(&self, kind: &token::TokenKind, span: rustc_span::Span) -> Token<'ast> {
+        let text = rustc_span::Symbol::intern(&pprust::token_kind_to_string(kind));
+        Token::new(to_token_kind(kind), self.to_symbol_id(text), self.to_span_id(span))
+    }
+}
+
+fn collect_delimited_tokens(
+    tokens: &mut Vec<(token::TokenKind, rustc_span::Span)>,
+    dspan: ast::tokenstream::DelimSpan,
+    delim: token::Delimiter,
+    stream: &TokenStream,
+) {
+    // Invisible delimiters have no source representation
+    let visible = !matches!(delim, token::Delimiter::Invisible);
+    if visible {
+        tokens.push((token::TokenKind::OpenDelim(delim), dspan.open));
+    }
+    for tree in stream.trees() {
+        match tree {
+            TokenTree::Token(token, _spacing) => tokens.push((token.kind.clone(), token.span)),
+            TokenTree::Delimited(dspan, delim, stream) => {
+                collect_delimited_tokens(tokens, *dspan, *delim, stream);
+            },
+        }
+    }
+    if visible {
+        tokens.push((token::TokenKind::CloseDelim(delim), dspan.close));
+    }
+}
+
+fn to_token_kind(kind: &token::TokenKind) -> TokenKind {
+    match kind {
+        token::TokenKind::Ident(..) => TokenKind::Ident,
+        token::TokenKind::Lifetime(_) => TokenKind::Lifetime,
+        token::TokenKind::Literal(_) => TokenKind::Lit,
+        token::TokenKind::OpenDelim(_) => TokenKind::OpenDelim,
+        token::TokenKind::CloseDelim(_) => TokenKind::CloseDelim,
+        _ => TokenKind::Punct,
+    }
+}
+
+/// Stores the [`MetaItem`]s parsed from the tokens of an attribute input.
+struct MetaItemConverter<'a, 'ast, 'tcx> {
+    cx: &'a MarkerConverterInner<'ast, 'tcx>,
+    /// The spans of the input tokens
+    spans: &'a [rustc_span::Span],
+}
+
+impl<'a, 'ast, 'tcx> MetaItemStorage<'ast> for MetaItemConverter<'a, 'ast, 'tcx> {
+    fn span(&self, start: usize, end: usize) -> SpanId {
+        self.cx.to_span_id(self.spans[start].to(self.spans[end - 1]))
+    }
+
+    fn alloc_path(&self, path: &[&'ast str]) -> &'ast [FfiStr<'ast>] {
+        self.cx.alloc_slice(path.iter().map(|segment| FfiStr::from(*segment)))
+    }
+
+    fn alloc_items(&self, items: Vec<MetaItem<'ast>>) -> &'ast [MetaItem<'ast>] {
+        self.cx.alloc_slice(items)
+    }
+}
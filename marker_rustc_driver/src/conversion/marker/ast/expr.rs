@@ -7,11 +7,12 @@ use marker_api::{
         RefExpr, ReturnExpr, StrLitData, StrLitExpr, TryExpr, TupleExpr, UnaryOpExpr, UnaryOpKind, UnstableExpr,
         WhileExpr,
     },
-    common::{Safety, Syncness},
+    common::{Constness, ExprId, Safety, Syncness},
     span::Ident,
 };
 use rustc_hash::FxHashMap;
 use rustc_hir as hir;
+use rustc_middle as mid;
 use std::str::FromStr;
 
 use crate::conversion::marker::MarkerConverterInner;
@@ -25,8 +26,14 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         }
 
         let data = CommonExprData::new(id, self.to_span_id(block.span));
-        let expr =
-            ExprKind::Block(self.alloc(self.to_block_expr(data, block, None, Syncness::Sync, CaptureKind::Default)));
+        let expr = ExprKind::Block(self.alloc(self.to_block_expr(
+            data,
+            block,
+            None,
+            Syncness::Sync,
+            Constness::NotConst,
+            CaptureKind::Default,
+        )));
 
         self.exprs.borrow_mut().insert(id, expr);
         expr
@@ -79,6 +86,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         block,
                         *label,
                         Syncness::Sync,
+                        Constness::NotConst,
                         CaptureKind::Default,
                     )))
                 }
@@ -128,7 +136,13 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 MethodExpr::new(
                     data,
                     self.to_expr(receiver),
-                    self.to_path_segment(method),
+                    self.to_path_segment_with_res(
+                        method,
+                        &self
+                            .rustc_ty_check()
+                            .type_dependent_def(expr.hir_id)
+                            .map_or(hir::def::Res::Err, |(kind, id)| hir::def::Res::Def(kind, id)),
+                    ),
                     self.to_exprs(args),
                 )
             })),
@@ -199,7 +213,26 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 ExprKind::Index(self.alloc(IndexExpr::new(data, self.to_expr(operand), self.to_expr(index))))
             },
             hir::ExprKind::Field(operand, field) => {
-                ExprKind::Field(self.alloc(FieldExpr::new(data, self.to_expr(operand), self.to_ident(*field))))
+                let ty_check = self.rustc_ty_check();
+                let autoderefs = ty_check
+                    .expr_adjustments(operand)
+                    .iter()
+                    .filter(|adjustment| matches!(adjustment.kind, mid::ty::adjustment::Adjust::Deref(_)))
+                    .count();
+                let field_id = ty_check
+                    .opt_field_index(expr.hir_id)
+                    .and_then(|index| match ty_check.expr_ty_adjusted(operand).kind() {
+                        mid::ty::Adt(adt, _) if !adt.is_enum() => adt.non_enum_variant().fields[index].did.as_local(),
+                        _ => None,
+                    })
+                    .map(|def_id| self.to_field_id(self.rustc_cx.local_def_id_to_hir_id(def_id)));
+                ExprKind::Field(self.alloc(FieldExpr::new(
+                    data,
+                    self.to_expr(operand),
+                    self.to_ident(*field),
+                    field_id,
+                    autoderefs,
+                )))
             },
             hir::ExprKind::If(scrutinee, then, els) => ExprKind::If(self.alloc(IfExpr::new(
                 data,
@@ -235,13 +268,13 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             hir::ExprKind::Break(dest, expr) => ExprKind::Break(self.alloc(BreakExpr::new(
                 data,
                 dest.label.map(|label| self.to_ident(label.ident)),
-                self.to_expr_id(dest.target_id.expect("rustc would have errored")),
+                self.to_break_target_id(dest),
                 expr.map(|expr| self.to_expr(expr)),
             ))),
             hir::ExprKind::Continue(dest) => ExprKind::Continue(self.alloc(ContinueExpr::new(
                 data,
                 dest.label.map(|label| self.to_ident(label.ident)),
-                self.to_expr_id(dest.target_id.expect("rustc would have errored")),
+                self.to_break_target_id(dest),
             ))),
             hir::ExprKind::Ret(expr) => {
                 ExprKind::Return(self.alloc(ReturnExpr::new(data, expr.map(|expr| self.to_expr(expr)))))
@@ -256,6 +289,23 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 hir::LoopSource::ForLoop => unreachable!("is desugared at a higher node level"),
             },
             hir::ExprKind::Closure(closure) => self.to_expr_from_closure(data, expr, closure),
+            hir::ExprKind::ConstBlock(anon) => {
+                let body = self.rustc_cx.hir().body(anon.body);
+                let hir::ExprKind::Block(block, None) = body.value.kind else {
+                    unreachable!("the body of a const block is always a block")
+                };
+                let block_expr = self.with_body(anon.body, || {
+                    self.to_block_expr(
+                        data,
+                        block,
+                        None,
+                        Syncness::Sync,
+                        Constness::Const,
+                        CaptureKind::Default,
+                    )
+                });
+                ExprKind::Block(self.alloc(block_expr))
+            },
             hir::ExprKind::Cast(expr, ty) => {
                 ExprKind::As(self.alloc(AsExpr::new(data, self.to_expr(expr), self.to_syn_ty(ty))))
             },
@@ -287,6 +337,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         block: &hir::Block<'tcx>,
         label: Option<rustc_ast::Label>,
         syncness: Syncness,
+        constness: Constness,
         capture_kind: CaptureKind,
     ) -> BlockExpr<'ast> {
         let stmts: Vec<_> = block.stmts.iter().filter_map(|stmt| self.to_stmt(stmt)).collect();
@@ -302,6 +353,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             label.map(|label| self.to_ident(label.ident)),
             safety,
             syncness,
+            constness,
             capture_kind,
         )
     }
@@ -444,6 +496,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                             block,
                             None,
                             Syncness::Async,
+                            Constness::NotConst,
                             self.to_capture_kind(closure.capture_clause),
                         )
                     });
@@ -572,6 +625,18 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         unreachable!("try desugar always has the same structure")
     }
 
+    /// Labeled blocks are targeted by the [`hir::HirId`] of the block and not
+    /// of the block expression. Marker only has expressions, this function
+    /// therefore maps these blocks to the id of the block expression.
+    #[must_use]
+    fn to_break_target_id(&self, dest: &hir::Destination) -> ExprId {
+        let target = dest.target_id.expect("rustc would have errored");
+        match self.rustc_cx.hir().find(target) {
+            Some(hir::Node::Block(_)) => self.to_expr_id(self.rustc_cx.hir().parent_id(target)),
+            _ => self.to_expr_id(target),
+        }
+    }
+
     /// The "Show HIR" option on the [Playground] is a great resource to
     /// understand how this desugaring works. Here is a simple example to
     /// illustrate the current desugar:
@@ -75,6 +75,13 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             .iter()
             .filter_map(|predicate| {
                 match predicate {
+                    // These are the bounds of `impl Trait` parameters, which are
+                    // represented as `ImplTraitTy`s instead
+                    hir::WherePredicate::BoundPredicate(ty_bound)
+                        if matches!(ty_bound.origin, hir::PredicateOrigin::ImplTrait) =>
+                    {
+                        None
+                    },
                     hir::WherePredicate::BoundPredicate(ty_bound) => {
                         // FIXME Add span to API clause:
                         // let span = to_api_span_id(ty_bound.span);
@@ -96,12 +103,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                                         _ => unreachable!("lifetimes can only be bound by lifetimes"),
                                     })
                                     .collect();
-                                let bounds = if bounds.is_empty() {
-                                    self.alloc_slice(bounds)
-                                } else {
-                                    &[]
-                                };
-                                LifetimeClause::new(lifetime, bounds)
+                                LifetimeClause::new(lifetime, self.alloc_slice(bounds))
                             }))
                         })
                     },
@@ -185,6 +187,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                                     .expect("the lang item is used and should therefore be loaded"),
                             ),
                             self.to_syn_generic_args(Some(rustc_args)),
+                            self.to_span_id(*span),
                         ),
                         self.to_span_id(*span),
                     )),
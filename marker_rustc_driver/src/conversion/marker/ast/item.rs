@@ -1,9 +1,9 @@
 use marker_api::{
     ast::{
         self, AdtKind, AssocItemKind, Body, CommonItemData, CommonPatData, ConstItem, EnumItem, EnumVariant,
-        ExternBlockItem, ExternCrateItem, ExternItemKind, FnItem, FnParam, IdentPat, ImplItem, ItemField, ItemKind,
-        ModItem, PatKind, StaticItem, StructItem, TraitItem, TyAliasItem, UnionItem, UnstableItem, UseItem, UseKind,
-        Visibility,
+        ExternBlockItem, ExternCrateItem, ExternItemKind, ExternTypeItem, FnItem, FnParam, IdentPat, ImplItem,
+        ItemField, ItemKind, ModItem, PatKind, SelfKind, StaticItem, StructItem, TraitItem, TyAliasItem, UnionItem,
+        UnstableItem, UseItem, UseKind, Visibility,
     },
     common::{Abi, Constness, Mutability, Safety, Syncness},
     prelude::*,
@@ -34,10 +34,9 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         let id = self.to_item_id(rustc_item.owner_id);
         // During normal conversion, this'll never be hit. However, if the user
         // requests an item from an ID it might be, that the child has already
-        // been converted. This is not the case for items in the main crate,
-        // since all of them have been converted, but external crates could
-        // run into this issue. If performance becomes a problem, we can try
-        // benchmarking, a flag to disable this during initial translation.
+        // been converted. Items outside the processed module are converted on
+        // demand and can run into this issue. If performance becomes a problem,
+        // we can try benchmarking, a flag to disable this during initial translation.
         if let Some(item) = self.items.borrow().get(&id) {
             return Some(*item);
         }
@@ -161,7 +160,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                     ImplItem::new(
                         data,
                         matches!(imp.unsafety, hir::Unsafety::Unsafe),
-                        matches!(imp.polarity, rustc_ast::ImplPolarity::Positive),
+                        matches!(imp.polarity, rustc_ast::ImplPolarity::Negative(_)),
                         imp.of_trait.as_ref().map(|trait_ref| self.to_trait_ref(trait_ref)),
                         self.to_syn_generic_params(imp.generics),
                         self.to_syn_ty(imp.self_ty),
@@ -228,7 +227,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             self.to_syncness(header.asyncness),
             self.to_safety(header.unsafety),
             is_extern,
-            fn_sig.decl.implicit_self.has_implicit_self(),
+            self.to_self_kind(fn_sig.decl, body_info),
             self.to_abi(header.abi),
             params,
             return_ty,
@@ -236,6 +235,70 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         )
     }
 
+    fn to_self_kind(&self, decl: &hir::FnDecl<'tcx>, body_info: hir::TraitFn<'_>) -> Option<SelfKind> {
+        match decl.implicit_self {
+            hir::ImplicitSelfKind::Imm | hir::ImplicitSelfKind::Mut => return Some(SelfKind::Value),
+            hir::ImplicitSelfKind::ImmRef => return Some(SelfKind::Ref),
+            hir::ImplicitSelfKind::MutRef => return Some(SelfKind::MutRef),
+            hir::ImplicitSelfKind::None => {},
+        }
+
+        // Rustc only tracks the implicit forms of `self`. Explicitly typed `self`
+        // parameters, like `self: Box<Self>` have to be detected by their name.
+        let first_ident = match body_info {
+            hir::TraitFn::Required(idents) => idents.first().copied(),
+            hir::TraitFn::Provided(body_id) => self
+                .rustc_cx
+                .hir()
+                .body(body_id)
+                .params
+                .first()
+                .and_then(|param| param.pat.simple_ident()),
+        };
+        if first_ident?.name != rustc_span::symbol::kw::SelfLower {
+            return None;
+        }
+        let self_ty = decl.inputs.first()?;
+
+        let is_self_ty = |ty: &hir::Ty<'_>| {
+            matches!(
+                ty.kind,
+                hir::TyKind::Path(hir::QPath::Resolved(
+                    None,
+                    hir::Path {
+                        res: hir::def::Res::SelfTyAlias { .. } | hir::def::Res::SelfTyParam { .. },
+                        ..
+                    }
+                ))
+            )
+        };
+        let kind = match self_ty.kind {
+            _ if is_self_ty(self_ty) => SelfKind::Value,
+            hir::TyKind::Ref(_, mut_ty) if is_self_ty(mut_ty.ty) => match mut_ty.mutbl {
+                hir::Mutability::Not => SelfKind::Ref,
+                hir::Mutability::Mut => SelfKind::MutRef,
+            },
+            hir::TyKind::Path(hir::QPath::Resolved(
+                None,
+                hir::Path {
+                    res: hir::def::Res::Def(_, def_id),
+                    ..
+                },
+            )) => {
+                let lang_items = self.rustc_cx.lang_items();
+                if lang_items.owned_box() == Some(*def_id) {
+                    SelfKind::Box
+                } else if lang_items.pin_type() == Some(*def_id) {
+                    SelfKind::Pin
+                } else {
+                    SelfKind::Other
+                }
+            },
+            _ => SelfKind::Other,
+        };
+        Some(kind)
+    }
+
     fn to_fn_params(&self, decl: &hir::FnDecl<'tcx>, body_info: hir::TraitFn<'_>) -> &'ast [FnParam<'ast>] {
         match body_info {
             hir::TraitFn::Required(idents) => {
@@ -302,7 +365,8 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             return match item {
                 ItemKind::Static(data) => ExternItemKind::Static(data, CtorBlocker::new()),
                 ItemKind::Fn(data) => ExternItemKind::Fn(data, CtorBlocker::new()),
-                _ => unreachable!("only static and `Static` and `Fn` items can be found a foreign item id"),
+                ItemKind::ExternType(data) => ExternItemKind::Type(data, CtorBlocker::new()),
+                _ => unreachable!("only `Static`, `Fn` and `ExternType` items can be found a foreign item id"),
             };
         }
 
@@ -328,7 +392,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         Syncness::Sync,
                         Safety::Safe,
                         true,
-                        decl.implicit_self.has_implicit_self(),
+                        self.to_self_kind(decl, hir::TraitFn::Required(idents)),
                         abi,
                         self.to_fn_params(decl, hir::TraitFn::Required(idents)),
                         return_ty,
@@ -347,7 +411,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 CtorBlocker::new(),
             ),
             hir::ForeignItemKind::Type => {
-                todo!("foreign type are currently sadly not supported. See rust-marker/marker#182")
+                ExternItemKind::Type(self.alloc(ExternTypeItem::new(data)), CtorBlocker::new())
             },
         };
 
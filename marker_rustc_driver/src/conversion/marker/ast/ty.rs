@@ -1,7 +1,7 @@
 use marker_api::{
     ast::{
-        ArrayTy, BoolTy, CommonSynTyData, FnPtrTy, FnTyParameter, ImplTraitTy, InferredTy, NeverTy, NumTy, PathTy,
-        RawPtrTy, RefTy, SliceTy, TextTy, TraitObjTy, TupleTy, TyKind,
+        ArrayTy, BoolTy, CommonSynTyData, FnPtrTy, FnTyParameter, ImplTraitPosition, ImplTraitTy, InferredTy, NeverTy,
+        NumTy, PathTy, RawPtrTy, RefTy, SliceTy, TextTy, TraitObjTy, TupleTy, TyKind,
     },
     common::{NumKind, TextKind},
 };
@@ -57,8 +57,12 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                     unreachable!("the item of a `OpaqueDef` should be `OpaqueTy` {item:#?}");
                 };
                 let rust_bound = self.to_syn_ty_param_bound(opty.bounds);
+                let position = match opty.origin {
+                    hir::OpaqueTyOrigin::FnReturn(_) | hir::OpaqueTyOrigin::AsyncFn(_) => ImplTraitPosition::Return,
+                    hir::OpaqueTyOrigin::TyAlias { .. } => ImplTraitPosition::TyAlias,
+                };
                 // FIXME: Generics are a bit weird with opaque types
-                TyKind::ImplTrait(self.alloc(ImplTraitTy::new(data, rust_bound)))
+                TyKind::ImplTrait(self.alloc(ImplTraitTy::new(data, rust_bound, position)))
             },
             hir::TyKind::TraitObject(rust_bounds, rust_lt, _syntax) => TyKind::TraitObj(self.alloc(TraitObjTy::new(
                 data,
@@ -110,6 +114,27 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         qpath: &hir::QPath<'tcx>,
         rustc_ty: &hir::Ty<'_>,
     ) -> TyKind<'ast> {
+        // `impl Trait` in argument position is desugared to an anonymous generic
+        // parameter. The bounds are stored as a where clause on the parent item.
+        if let hir::QPath::Resolved(None, path) = qpath
+            && let hir::def::Res::Def(hir::def::DefKind::TyParam, def_id) = path.res
+            && let Some(local_id) = def_id.as_local()
+            && let Some(hir::Node::GenericParam(param)) = self.rustc_cx.hir().find_by_def_id(local_id)
+            && let hir::GenericParamKind::Type { synthetic: true, .. } = param.kind
+        {
+            let bounds = self
+                .rustc_cx
+                .hir()
+                .get_generics(self.rustc_cx.local_parent(local_id))
+                .and_then(|generics| {
+                    generics
+                        .bounds_for_param(local_id)
+                        .find(|pred| matches!(pred.origin, hir::PredicateOrigin::ImplTrait))
+                })
+                .map_or(&[][..], |pred| self.to_syn_ty_param_bound(pred.bounds));
+            return TyKind::ImplTrait(self.alloc(ImplTraitTy::new(data, bounds, ImplTraitPosition::Arg)));
+        }
+
         match qpath {
             hir::QPath::Resolved(_, path) => match path.res {
                 hir::def::Res::Def(
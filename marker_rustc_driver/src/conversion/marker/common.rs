@@ -255,8 +255,6 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 } else {
                     Vec::with_capacity(1)
                 };
-                segments.push(self.to_path_segment(segment));
-                let path = AstPath::new(self.alloc_slice(segments));
 
                 // Res resolution
                 let res = if segment.res == hir::def::Res::Err {
@@ -278,6 +276,12 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 } else {
                     self.to_path_target(&segment.res)
                 };
+                segments.push(AstPathSegment::new(
+                    self.to_ident(segment.ident),
+                    self.to_syn_generic_args(segment.args),
+                    res,
+                ));
+                let path = AstPath::new(self.alloc_slice(segments));
 
                 AstQPath::new(None, Some(marker_ty), path, res)
             },
@@ -299,6 +303,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                             self.to_span_id(*span),
                         ),
                         GenericArgs::new(&[]),
+                        AstPathTarget::Item(self.to_item_id(id)),
                     )])),
                     AstPathTarget::Item(self.to_item_id(id)),
                 )
@@ -416,7 +421,58 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
 
     #[must_use]
     pub fn to_path_segment(&self, segment: &hir::PathSegment<'tcx>) -> AstPathSegment<'ast> {
-        AstPathSegment::new(self.to_ident(segment.ident), self.to_syn_generic_args(segment.args))
+        self.to_path_segment_with_res(segment, &segment.res)
+    }
+
+    /// Converts the segment, with the given [`hir::def::Res`] as the target. This
+    /// is used for segments, which are resolved during type checking, like the
+    /// segments of method calls.
+    #[must_use]
+    pub fn to_path_segment_with_res(
+        &self,
+        segment: &hir::PathSegment<'tcx>,
+        res: &hir::def::Res,
+    ) -> AstPathSegment<'ast> {
+        AstPathSegment::new(
+            self.to_ident(segment.ident),
+            self.to_syn_generic_args(segment.args),
+            self.to_segment_target(res),
+        )
+    }
+
+    /// Segments can resolve to targets, which can't be the target of a complete
+    /// path, like modules or primitive types. These are mapped to items if
+    /// possible and to [`AstPathTarget::Unresolved`] otherwise.
+    fn to_segment_target(&self, res: &hir::def::Res) -> AstPathTarget {
+        match res {
+            hir::def::Res::Def(hir::def::DefKind::Mod, id) => AstPathTarget::Item(self.to_item_id(*id)),
+            hir::def::Res::Def(
+                hir::def::DefKind::LifetimeParam
+                | hir::def::DefKind::TyParam
+                | hir::def::DefKind::ConstParam
+                | hir::def::DefKind::TyAlias { .. }
+                | hir::def::DefKind::Fn
+                | hir::def::DefKind::Enum
+                | hir::def::DefKind::Struct
+                | hir::def::DefKind::Union
+                | hir::def::DefKind::Trait
+                | hir::def::DefKind::ForeignTy
+                | hir::def::DefKind::AssocTy
+                | hir::def::DefKind::TraitAlias
+                | hir::def::DefKind::AssocFn
+                | hir::def::DefKind::AssocConst
+                | hir::def::DefKind::Const
+                | hir::def::DefKind::Static(_)
+                | hir::def::DefKind::Ctor(..)
+                | hir::def::DefKind::Variant,
+                _,
+            )
+            | hir::def::Res::SelfTyParam { .. }
+            | hir::def::Res::SelfTyAlias { .. }
+            | hir::def::Res::SelfCtor(_)
+            | hir::def::Res::Local(_) => self.to_path_target(res),
+            _ => AstPathTarget::Unresolved,
+        }
     }
 
     pub fn to_trait_ref(&self, trait_ref: &rustc_hir::TraitRef<'tcx>) -> TraitRef<'ast> {
@@ -426,6 +482,10 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             },
             _ => unreachable!("reached `PolyTraitRef` which can't be translated {trait_ref:#?}"),
         };
-        TraitRef::new(trait_id, self.to_syn_generic_args_from_path(trait_ref.path))
+        TraitRef::new(
+            trait_id,
+            self.to_syn_generic_args_from_path(trait_ref.path),
+            self.to_span_id(trait_ref.path.span),
+        )
     }
 }
@@ -1,3 +1,4 @@
+mod constant;
 mod generic;
 mod item;
 mod ty;
@@ -0,0 +1,43 @@
+use marker_api::sem::{ConstBytes, ConstInt, ConstStr, ConstValue};
+use rustc_middle as mid;
+
+use crate::conversion::marker::MarkerConverterInner;
+
+impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
+    #[must_use]
+    pub fn to_sem_const_value(&self, ct: mid::ty::Const<'tcx>) -> ConstValue<'ast> {
+        match ct.kind() {
+            mid::ty::ConstKind::Value(valtree) => self.to_const_value(valtree, ct.ty()).unwrap_or(ConstValue::Unknown),
+            _ => ConstValue::Unknown,
+        }
+    }
+
+    #[must_use]
+    pub fn to_const_value(&self, valtree: mid::ty::ValTree<'tcx>, ty: mid::ty::Ty<'tcx>) -> Option<ConstValue<'ast>> {
+        let value = match ty.kind() {
+            mid::ty::TyKind::Bool => ConstValue::Bool(valtree.try_to_scalar_int()?.try_to_bool().ok()?),
+            mid::ty::TyKind::Char => ConstValue::Char(char::from_u32(valtree.try_to_scalar_int()?.try_to_u32().ok()?)?),
+            mid::ty::TyKind::Int(_) | mid::ty::TyKind::Uint(_) => {
+                let int = valtree.try_to_scalar_int()?;
+                let size = int.size();
+                #[allow(clippy::cast_sign_loss)]
+                let bits = if ty.is_signed() {
+                    int.try_to_int(size).ok()? as u128
+                } else {
+                    int.try_to_uint(size).ok()?
+                };
+                ConstValue::Int(ConstInt::new(bits, ty.is_signed(), self.to_sem_ty(ty)))
+            },
+            mid::ty::TyKind::Ref(_, inner, _) if inner.is_str() => {
+                let bytes = valtree.try_to_raw_bytes(self.rustc_cx, ty)?;
+                let value = std::str::from_utf8(bytes).ok()?;
+                ConstValue::Str(ConstStr::new(self.storage.alloc_str(value)))
+            },
+            _ => {
+                let bytes = valtree.try_to_raw_bytes(self.rustc_cx, ty)?;
+                ConstValue::Bytes(ConstBytes::new(self.alloc_slice(bytes.iter().copied())))
+            },
+        };
+        Some(value)
+    }
+}
@@ -1,4 +1,4 @@
-use marker_api::sem::{BindingArg, ConstArg, ConstValue, GenericArgKind, GenericArgs, TraitBound};
+use marker_api::sem::{BindingArg, ConstArg, GenericArgKind, GenericArgs, Lifetime, LifetimeKind, TraitBound};
 use rustc_middle as mid;
 
 use crate::conversion::marker::MarkerConverterInner;
@@ -6,25 +6,45 @@ use crate::conversion::marker::MarkerConverterInner;
 impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
     #[must_use]
     pub fn to_sem_generic_args(&self, args: &[mid::ty::GenericArg<'tcx>]) -> GenericArgs<'ast> {
-        let args: Vec<_> = args
-            .iter()
-            .filter_map(|arg| self.to_sem_generic_arg_kind(*arg))
-            .collect();
+        let args: Vec<_> = args.iter().map(|arg| self.to_sem_generic_arg_kind(*arg)).collect();
 
         GenericArgs::new(self.alloc_slice(args))
     }
 
     #[must_use]
-    fn to_sem_generic_arg_kind(&self, arg: mid::ty::GenericArg<'tcx>) -> Option<GenericArgKind<'ast>> {
+    fn to_sem_generic_arg_kind(&self, arg: mid::ty::GenericArg<'tcx>) -> GenericArgKind<'ast> {
         match &arg.unpack() {
-            mid::ty::GenericArgKind::Lifetime(_) => None,
-            mid::ty::GenericArgKind::Type(ty) => Some(GenericArgKind::Ty(self.to_sem_ty(*ty))),
-            mid::ty::GenericArgKind::Const(_) => {
-                Some(GenericArgKind::Const(self.alloc(ConstArg::new(ConstValue::new()))))
+            mid::ty::GenericArgKind::Lifetime(region) => {
+                GenericArgKind::Lifetime(self.alloc(self.to_sem_lifetime(*region)))
+            },
+            mid::ty::GenericArgKind::Type(ty) => GenericArgKind::Ty(self.to_sem_ty(*ty)),
+            mid::ty::GenericArgKind::Const(ct) => {
+                GenericArgKind::Const(self.alloc(ConstArg::new(self.to_sem_const_value(*ct))))
             },
         }
     }
 
+    #[must_use]
+    pub fn to_sem_lifetime(&self, region: mid::ty::Region<'tcx>) -> Lifetime<'ast> {
+        let kind = match region.kind() {
+            mid::ty::ReStatic => LifetimeKind::Static,
+            mid::ty::ReEarlyParam(_) | mid::ty::ReBound(..) | mid::ty::ReLateParam(_) | mid::ty::RePlaceholder(_) => {
+                if region.has_name() {
+                    LifetimeKind::Named
+                } else {
+                    LifetimeKind::Anonymous
+                }
+            },
+            mid::ty::ReVar(_) | mid::ty::ReErased | mid::ty::ReError(_) => LifetimeKind::Erased,
+        };
+        let name = if kind == LifetimeKind::Named {
+            region.get_name().map(|name| self.to_symbol_id(name))
+        } else {
+            None
+        };
+        Lifetime::new(kind, name)
+    }
+
     pub fn to_sem_trait_bounds(
         &self,
         bounds: &mid::ty::List<mid::ty::PolyExistentialPredicate<'tcx>>,
@@ -46,11 +66,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
         if let Some(main) = bounds.principal() {
             let main = main.skip_binder();
 
-            let mut generics: Vec<_> = main
-                .args
-                .iter()
-                .filter_map(|arg| self.to_sem_generic_arg_kind(arg))
-                .collect();
+            let mut generics: Vec<_> = main.args.iter().map(|arg| self.to_sem_generic_arg_kind(arg)).collect();
 
             bounds
                 .projection_bounds()
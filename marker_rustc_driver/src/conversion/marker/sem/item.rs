@@ -1,6 +1,7 @@
 use marker_api::prelude::*;
 use rustc_hir as hir;
 use rustc_middle as mid;
+use rustc_middle::traits::{MethodViolationCode, ObjectSafetyViolation};
 
 use crate::conversion::marker::MarkerConverterInner;
 
@@ -27,4 +28,54 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
 
         sem::Visibility::builder().kind(kind).build()
     }
+
+    pub fn to_object_safety_violations(
+        &self,
+        violations: &[ObjectSafetyViolation],
+    ) -> &'ast [sem::ObjectSafetyViolation<'ast>] {
+        self.alloc_slice(violations.iter().map(|violation| {
+            let (kind, name, span) = match violation {
+                ObjectSafetyViolation::SizedSelf(spans) => {
+                    (sem::ObjectSafetyViolationKind::SizedSelf, None, spans.first())
+                },
+                ObjectSafetyViolation::SupertraitSelf(spans) => {
+                    (sem::ObjectSafetyViolationKind::SupertraitSelf, None, spans.first())
+                },
+                ObjectSafetyViolation::SupertraitNonLifetimeBinder(spans) => {
+                    (sem::ObjectSafetyViolationKind::Other, None, spans.first())
+                },
+                ObjectSafetyViolation::Method(name, code, span) => {
+                    let kind = match code {
+                        MethodViolationCode::StaticMethod(_) => sem::ObjectSafetyViolationKind::StaticMethod,
+                        MethodViolationCode::ReferencesSelfInput(_) => sem::ObjectSafetyViolationKind::SelfInParams,
+                        MethodViolationCode::ReferencesSelfOutput => sem::ObjectSafetyViolationKind::SelfInReturnTy,
+                        MethodViolationCode::ReferencesImplTraitInTrait(_) => {
+                            sem::ObjectSafetyViolationKind::ImplTraitReturnTy
+                        },
+                        MethodViolationCode::AsyncFn => sem::ObjectSafetyViolationKind::AsyncFn,
+                        MethodViolationCode::WhereClauseReferencesSelf => {
+                            sem::ObjectSafetyViolationKind::WhereClauseReferencesSelf
+                        },
+                        MethodViolationCode::Generic => sem::ObjectSafetyViolationKind::GenericMethod,
+                        MethodViolationCode::UndispatchableReceiver(_) => {
+                            sem::ObjectSafetyViolationKind::UndispatchableReceiver
+                        },
+                    };
+                    (kind, Some(*name), Some(span))
+                },
+                ObjectSafetyViolation::AssocConst(name, span) => {
+                    (sem::ObjectSafetyViolationKind::AssocConst, Some(*name), Some(span))
+                },
+                ObjectSafetyViolation::GAT(name, span) => {
+                    (sem::ObjectSafetyViolationKind::GenericAssocTy, Some(*name), Some(span))
+                },
+            };
+
+            sem::ObjectSafetyViolation::builder()
+                .kind(kind)
+                .item_name(name.map(|name| self.to_symbol_id(name)))
+                .span(span.filter(|span| !span.is_dummy()).map(|span| self.to_span_id(*span)))
+                .build()
+        }))
+    }
 }
@@ -1,8 +1,8 @@
 use marker_api::{
-    common::{NumKind, TextKind},
+    common::{Abi, NumKind, TextKind},
     sem::{
-        self, AdtTy, AliasTy, ArrayTy, BoolTy, ClosureTy, ConstValue, FnPtrTy, FnTy, GenericTy, NeverTy, NumTy,
-        RawPtrTy, RefTy, SliceTy, TextTy, TraitObjTy, TupleTy, TyKind, UnstableTy,
+        self, AdtTy, AliasTy, ArrayTy, BoolTy, ClosureKind, ClosureTy, FnPtrTy, FnSig, FnTy, ForeignTy, GenericTy,
+        NeverTy, NumTy, RawPtrTy, RefTy, SliceTy, TextTy, TraitObjTy, TupleTy, TyKind, UnstableTy,
     },
 };
 use rustc_middle as mid;
@@ -64,15 +64,21 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         .build(),
                 ),
             ),
-            mid::ty::TyKind::Foreign(_) => {
-                todo!("foreign type are currently sadly not supported. See rust-marker/marker#182")
-            },
-            mid::ty::TyKind::Array(inner, _len) => TyKind::Array(
+            mid::ty::TyKind::Foreign(def_id) => TyKind::Foreign(
+                self.alloc(
+                    ForeignTy::builder()
+                        .data(data)
+                        .def_id(self.to_ty_def_id(*def_id))
+                        .build(),
+                ),
+            ),
+            mid::ty::TyKind::Array(inner, len) => TyKind::Array(
                 self.alloc(
                     ArrayTy::builder()
                         .data(data)
                         .inner_ty(self.to_sem_ty(*inner))
-                        .len(ConstValue::new())
+                        .len(self.to_sem_const_value(*len))
+                        .known_len(len.try_to_target_usize(self.rustc_cx).map(u128::from))
                         .build(),
                 ),
             ),
@@ -96,10 +102,11 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         .build(),
                 ),
             ),
-            mid::ty::TyKind::Ref(_lifetime, inner, muta) => TyKind::Ref(
+            mid::ty::TyKind::Ref(lifetime, inner, muta) => TyKind::Ref(
                 self.alloc(
                     RefTy::builder()
                         .data(data)
+                        .lifetime(self.to_sem_lifetime(*lifetime))
                         .mutability(self.to_mutability(*muta))
                         .inner_ty(self.to_sem_ty(*inner))
                         .build(),
@@ -111,6 +118,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                         .data(data)
                         .fn_id(self.to_item_id(*fn_id))
                         .generics(self.to_sem_generic_args(generic_args))
+                        .sig(self.to_sem_item_fn_sig(*fn_id, generic_args))
                         .build(),
                 ),
             ),
@@ -118,18 +126,7 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 self.alloc(
                     FnPtrTy::builder()
                         .data(data)
-                        .safety(self.to_safety(fn_info.unsafety()))
-                        .abi(self.to_abi(fn_info.abi()))
-                        .params(
-                            self.alloc_slice(
-                                fn_info
-                                    .inputs()
-                                    .skip_binder()
-                                    .iter()
-                                    .map(|input| self.to_sem_ty(*input)),
-                            ),
-                        )
-                        .return_ty(self.to_sem_ty(fn_info.output().skip_binder()))
+                        .sig(self.to_sem_fn_sig(fn_info.skip_binder()))
                         .build(),
                 ),
             ),
@@ -146,15 +143,36 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                     ),
                 )
             },
-            mid::ty::TyKind::Closure(id, generics) => TyKind::Closure(
-                self.alloc(
-                    ClosureTy::builder()
-                        .data(data)
-                        .def_id(self.to_ty_def_id(*id))
-                        .generics(self.to_sem_generic_args(generics))
-                        .build(),
-                ),
-            ),
+            mid::ty::TyKind::Closure(id, generics) => {
+                let closure = generics.as_closure();
+                let sig = closure.sig().skip_binder();
+                // Rustc passes the closure parameters as a single tuple
+                let [params] = sig.inputs() else {
+                    unreachable!("the parameters of closures are always tupled")
+                };
+                TyKind::Closure(
+                    self.alloc(
+                        ClosureTy::builder()
+                            .data(data)
+                            .def_id(self.to_ty_def_id(*id))
+                            .generics(self.to_sem_generic_args(generics))
+                            .kind(self.to_closure_kind(closure.kind()))
+                            .sig(
+                                FnSig::builder()
+                                    .safety(self.to_safety(sig.unsafety))
+                                    .abi(Abi::Default)
+                                    .params(
+                                        self.alloc_slice(
+                                            params.tuple_fields().iter().map(|param| self.to_sem_ty(param)),
+                                        ),
+                                    )
+                                    .return_ty(self.to_sem_ty(sig.output()))
+                                    .build(),
+                            )
+                            .build(),
+                    ),
+                )
+            },
             mid::ty::TyKind::Coroutine(_, _, _) | mid::ty::TyKind::CoroutineWitness(_, _) => {
                 TyKind::Unstable(self.alloc(UnstableTy::builder().data(data).build()))
             },
@@ -168,18 +186,17 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
                 ),
             ),
             mid::ty::TyKind::Param(param) => {
-                let body_id = self
-                    .rustc_body
-                    .borrow()
-                    .expect("semantic `TyKind::Param` is only valid inside bodies");
-                // This is a local id, this makes sense, since rustc only accesses
-                // expressions and therefore semantic types of the current crate.
-                // This should be fine...
-                let owner = self.rustc_cx.hir().body_owner_def_id(body_id);
-                let generic_info = self
-                    .rustc_cx
-                    .generics_of(owner.to_def_id())
-                    .type_param(param, self.rustc_cx);
+                let owner = self.rustc_generics_owner.borrow().unwrap_or_else(|| {
+                    let body_id = self
+                        .rustc_body
+                        .borrow()
+                        .expect("semantic `TyKind::Param` is only valid inside bodies or items");
+                    // This is a local id, this makes sense, since rustc only accesses
+                    // expressions and therefore semantic types of the current crate.
+                    // This should be fine...
+                    self.rustc_cx.hir().body_owner_def_id(body_id).to_def_id()
+                });
+                let generic_info = self.rustc_cx.generics_of(owner).type_param(param, self.rustc_cx);
                 TyKind::Generic(
                     self.alloc(
                         GenericTy::builder()
@@ -198,4 +215,51 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
             mid::ty::TyKind::Error(_) => unreachable!("would have triggered a rustc error"),
         }
     }
+
+    /// Converts the signature of the function item with the given id. The
+    /// lifetimes of the given generic args are usually erased. They are
+    /// replaced by the lifetime parameters of [`instantiate_identity`], to
+    /// retain the lifetimes of the item signature. Types and constants are
+    /// still instantiated with the given args.
+    ///
+    /// [`instantiate_identity`]: mid::ty::EarlyBinder::instantiate_identity
+    fn to_sem_item_fn_sig(
+        &self,
+        fn_id: rustc_hir::def_id::DefId,
+        generic_args: mid::ty::GenericArgsRef<'tcx>,
+    ) -> FnSig<'ast> {
+        let identity = mid::ty::GenericArgs::identity_for_item(self.rustc_cx, fn_id);
+        let args = self
+            .rustc_cx
+            .mk_args_from_iter(generic_args.iter().zip(identity).map(|(arg, identity_arg)| {
+                if matches!(arg.unpack(), mid::ty::GenericArgKind::Lifetime(_)) {
+                    identity_arg
+                } else {
+                    arg
+                }
+            }));
+        self.to_sem_fn_sig(
+            self.rustc_cx
+                .fn_sig(fn_id)
+                .instantiate(self.rustc_cx, args)
+                .skip_binder(),
+        )
+    }
+
+    fn to_sem_fn_sig(&self, sig: mid::ty::FnSig<'tcx>) -> FnSig<'ast> {
+        FnSig::builder()
+            .safety(self.to_safety(sig.unsafety))
+            .abi(self.to_abi(sig.abi))
+            .params(self.alloc_slice(sig.inputs().iter().map(|input| self.to_sem_ty(*input))))
+            .return_ty(self.to_sem_ty(sig.output()))
+            .build()
+    }
+
+    fn to_closure_kind(&self, kind: mid::ty::ClosureKind) -> ClosureKind {
+        match kind {
+            mid::ty::ClosureKind::Fn => ClosureKind::Fn,
+            mid::ty::ClosureKind::FnMut => ClosureKind::FnMut,
+            mid::ty::ClosureKind::FnOnce => ClosureKind::FnOnce,
+        }
+    }
 }
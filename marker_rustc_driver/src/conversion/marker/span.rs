@@ -1,6 +1,6 @@
 use marker_api::{
     prelude::Span,
-    span::{ExpnInfo, FileInfo, FilePos, SpanPos, SpanSource},
+    span::{ExpnInfo, FileInfo, FilePos, MacroKind, SpanPos, SpanSource},
 };
 
 use crate::conversion::marker::MarkerConverterInner;
@@ -55,14 +55,21 @@ impl<'ast, 'tcx> MarkerConverterInner<'ast, 'tcx> {
     }
 
     pub fn to_expn_info(&self, data: &rustc_span::ExpnData) -> ExpnInfo<'ast> {
-        debug_assert!(
-            matches!(&data.kind, rustc_span::ExpnKind::Macro(_, _)),
-            "this expansion data doesn't belong to a macro: {data:#?}"
-        );
+        let rustc_span::ExpnKind::Macro(kind, name) = data.kind else {
+            unreachable!("this expansion data doesn't belong to a macro: {data:#?}")
+        };
+        let kind = match kind {
+            rustc_span::MacroKind::Bang => MacroKind::Bang,
+            rustc_span::MacroKind::Attr => MacroKind::Attr,
+            rustc_span::MacroKind::Derive => MacroKind::Derive,
+        };
         ExpnInfo::new(
             self.to_expn_id(data.parent),
             self.to_span_id(data.call_site),
+            self.to_span_id(data.def_site),
             self.to_macro_id(data.macro_def_id.expect("filled, because this belongs to a macro")),
+            self.to_symbol_id(name),
+            kind,
         )
     }
 
@@ -17,30 +17,40 @@
 #![allow(rustdoc::private_intra_doc_links)]
 
 extern crate rustc_ast;
+extern crate rustc_ast_pretty;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
 extern crate rustc_errors;
 extern crate rustc_hash;
 extern crate rustc_hir;
 extern crate rustc_hir_analysis;
+extern crate rustc_infer;
 extern crate rustc_interface;
+extern crate rustc_lexer;
 extern crate rustc_lint;
 extern crate rustc_lint_defs;
 extern crate rustc_middle;
+extern crate rustc_parse;
 extern crate rustc_session;
 extern crate rustc_span;
 extern crate rustc_target;
+extern crate rustc_trait_selection;
 
 pub mod context;
 pub mod conversion;
 pub mod lint_pass;
 
 use std::env;
+use std::hash::Hash;
 use std::ops::Deref;
 
 use camino::Utf8Path;
-use marker_adapter::{LintCrateInfo, LINT_CRATES_ENV};
+use marker_adapter::{
+    LintCrateInfo, ManifestInfo, IGNORE_PATHS_ENV, INCLUDE_PATHS_ENV, LINT_CONFIGS_ENV, LINT_CRATES_ENV,
+    LINT_FILTERS_ENV, MANIFESTS_ENV, MAX_PER_LINT_ENV, SHOW_LINT_SOURCE_ENV,
+};
 use marker_error::Context;
+use rustc_data_structures::stable_hasher::StableHasher;
 
 use crate::conversion::rustc::RustcConverter;
 
@@ -49,6 +59,9 @@ const RUSTC_TOOLCHAIN_VERSION: &str = "nightly-2023-11-16";
 // endregion replace rust toolchain dev
 
 pub const MARKER_SYSROOT_ENV: &str = "MARKER_SYSROOT";
+/// Lint level flags, like `-Dmarker::lint_crate::lint_name`, separated by spaces.
+/// These are set by `cargo-marker` to apply the lint policy of the workspace.
+pub const MARKER_LINT_LEVELS_ENV: &str = "MARKER_LINT_LEVELS";
 
 struct DefaultCallbacks {
     env_vars: Vec<&'static str>,
@@ -65,22 +78,29 @@ impl rustc_driver::Callbacks for DefaultCallbacks {
 struct MarkerCallback {
     env_vars: Vec<&'static str>,
     lint_crates: Vec<LintCrateInfo>,
+    manifest: Option<ManifestInfo>,
 }
 
 impl rustc_driver::Callbacks for MarkerCallback {
     fn config(&mut self, config: &mut rustc_interface::Config) {
         let env_vars = std::mem::take(&mut self.env_vars);
         let lint_crates = self.lint_crates.clone();
+        let hashed_env_vars = env_vars.clone();
+        let hashed_lint_crates = self.lint_crates.clone();
         config.parse_sess_created = Some(Box::new(move |sess| {
             register_tracked_env(sess, &env_vars);
             register_tracked_files(sess, &lint_crates);
         }));
+        config.hash_untracked_state = Some(Box::new(move |_sess, hasher| {
+            hash_untracked_state(hasher, &hashed_env_vars, &hashed_lint_crates);
+        }));
 
         // Clippy explicitly calls any previous `register_lints` functions. This
         // will not be done here to keep it simple and to ensure that only known
         // code is executed.
         assert!(config.register_lints.is_none());
         let lint_crates = std::mem::take(&mut self.lint_crates);
+        let manifest = self.manifest.take();
 
         config.register_lints = Some(Box::new(move |_sess, lint_store| {
             // It looks like it can happen, that the `config` function is called
@@ -101,7 +121,19 @@ impl rustc_driver::Callbacks for MarkerCallback {
 
             lint_store.register_lints(&lints);
 
-            lint_store.register_late_pass(|_| Box::new(lint_pass::RustcLintPass));
+            // `format_args!` expressions are only available in the AST
+            lint_store.register_early_pass(|| Box::new(context::format_args::FormatArgsCollector));
+            let manifest = manifest.clone();
+            // Modules are linted separately, to let rustc replay the diagnostics
+            // of unchanged modules. The diagnostic limit of `--max-per-lint` applies
+            // to the entire crate, which requires the crate to be linted at once.
+            if env::var_os(MAX_PER_LINT_ENV).is_some() {
+                lint_store
+                    .register_late_pass(move |_| Box::new(lint_pass::RustcLintPass::new(manifest.clone(), false)));
+            } else {
+                lint_store
+                    .register_late_mod_pass(move |_| Box::new(lint_pass::RustcLintPass::new(manifest.clone(), true)));
+            }
         }));
     }
 }
@@ -110,10 +142,13 @@ fn register_tracked_env(sess: &mut rustc_session::parse::ParseSess, vars: &[&'st
     use rustc_span::Symbol;
     let env = sess.env_depinfo.get_mut();
 
+    // Unset variables have to be recorded as `None`. Cargo compares the recorded
+    // values with its environment and would otherwise consider the crate dirty,
+    // which results in a full recompilation and re-linting on every run.
     for key in vars {
         env.insert((
             Symbol::intern(key),
-            Some(Symbol::intern(&std::env::var(key).unwrap_or_default())),
+            std::env::var(key).ok().map(|value| Symbol::intern(&value)),
         ));
     }
 }
@@ -149,6 +184,30 @@ fn register_tracked_files(sess: &mut rustc_session::parse::ParseSess, lint_crate
     }
 }
 
+/// Hashes the inputs of the lint crates, which aren't tracked by rustc. A changed
+/// hash clears the incremental cache. Otherwise, rustc would replay the cached
+/// diagnostics of unchanged modules, even if the lint crates or their
+/// configuration changed.
+fn hash_untracked_state(hasher: &mut StableHasher, vars: &[&'static str], lint_crates: &[LintCrateInfo]) {
+    for key in vars {
+        (key, env::var_os(key)).hash(hasher);
+    }
+
+    // Lint crates are identified by their modification time, like Cargo does
+    let modified = |path: &Utf8Path| std::fs::metadata(path).and_then(|meta| meta.modified()).ok();
+    for lint_crate in lint_crates {
+        (lint_crate.path.as_str(), modified(&lint_crate.path)).hash(hasher);
+    }
+
+    #[cfg(debug_assertions)]
+    if let Ok(current_exe) = env::current_exe() {
+        std::fs::metadata(current_exe)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .hash(hasher);
+    }
+}
+
 /// If a command-line option matches `find_arg`, then apply the predicate `pred` on its value. If
 /// `true`, then return it. The parameter is assumed to be either `--arg=value` or `--arg value`.
 fn arg_value<'a, T: Deref<Target = str>>(
@@ -171,6 +230,23 @@ fn arg_value<'a, T: Deref<Target = str>>(
     None
 }
 
+/// Checks if the lint of a level flag, like `-Dmarker::lint_crate::lint_name`,
+/// is enabled by the lint filter of its lint crate. Flags of other lints are
+/// always kept.
+fn is_flag_of_enabled_lint(flag: &str, lint_crates: &[LintCrateInfo]) -> bool {
+    let Some((lint_crate, lint_name)) = flag
+        .get(2..)
+        .and_then(|name| name.strip_prefix("marker::"))
+        .and_then(|rest| rest.split_once("::"))
+    else {
+        return true;
+    };
+    lint_crates
+        .iter()
+        .filter(|krate| krate.name.replace('-', "_") == lint_crate)
+        .all(|krate| krate.is_lint_enabled(lint_name))
+}
+
 fn display_help() {
     println!(
         "\
@@ -272,7 +348,18 @@ pub fn try_main(args: impl Iterator<Item = String>) -> Result<(), MainError> {
     let in_primary_package = env::var("CARGO_PRIMARY_PACKAGE").is_ok();
 
     let enable_marker = !cap_lints_allow && (!no_deps || in_primary_package);
-    let env_vars = vec![LINT_CRATES_ENV, MARKER_SYSROOT_ENV];
+    let env_vars = vec![
+        LINT_CRATES_ENV,
+        LINT_FILTERS_ENV,
+        LINT_CONFIGS_ENV,
+        MANIFESTS_ENV,
+        MARKER_SYSROOT_ENV,
+        MARKER_LINT_LEVELS_ENV,
+        SHOW_LINT_SOURCE_ENV,
+        IGNORE_PATHS_ENV,
+        INCLUDE_PATHS_ENV,
+        MAX_PER_LINT_ENV,
+    ];
     if !enable_marker {
         rustc_driver::RunCompiler::new(&orig_args, &mut DefaultCallbacks { env_vars }).run()?;
         return Ok(());
@@ -281,6 +368,7 @@ pub fn try_main(args: impl Iterator<Item = String>) -> Result<(), MainError> {
     let lint_crates = LintCrateInfo::list_from_env()
         .context(|| "Error while determining the lint crates to load")?
         .unwrap_or_default();
+    let manifest = ManifestInfo::from_env().context(|| "Error while loading the package manifest")?;
 
     let additional_args = [
         // Make it possible to use `#[allow(marker::{lint_name})]` without
@@ -302,7 +390,23 @@ pub fn try_main(args: impl Iterator<Item = String>) -> Result<(), MainError> {
 
     orig_args.extend(additional_args);
 
-    let mut callback = MarkerCallback { env_vars, lint_crates };
+    // The lint levels are added last, to override the levels of previous flags.
+    // Lints disabled by the lint filter are not registered, their levels are
+    // therefore skipped.
+    if let Ok(lint_levels) = env::var(MARKER_LINT_LEVELS_ENV) {
+        orig_args.extend(
+            lint_levels
+                .split_whitespace()
+                .filter(|flag| is_flag_of_enabled_lint(flag, &lint_crates))
+                .map(str::to_string),
+        );
+    }
+
+    let mut callback = MarkerCallback {
+        env_vars,
+        lint_crates,
+        manifest,
+    };
     rustc_driver::RunCompiler::new(&orig_args, &mut callback).run()?;
 
     Ok(())
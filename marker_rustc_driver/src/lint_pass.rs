@@ -1,9 +1,12 @@
 use std::cell::OnceCell;
 
-use marker_adapter::{Adapter, LintCrateInfo};
-use marker_api::Lint;
+use marker_adapter::{
+    Adapter, LintCrateInfo, ManifestInfo, IGNORE_PATHS_ENV, INCLUDE_PATHS_ENV, MAX_PER_LINT_ENV, SHOW_LINT_SOURCE_ENV,
+};
+use marker_api::{ast::ItemKind, Lint};
+use rustc_hir as hir;
 
-use crate::context::{storage::Storage, RustcContext};
+use crate::context::{diag::PathFilter, storage::Storage, RustcContext};
 
 thread_local! {
     /// The [`Adapter`] loads the lint crates and is the general interface used
@@ -21,9 +24,23 @@ thread_local! {
     static ADAPTER: OnceCell<Adapter> = OnceCell::new();
 }
 
-pub struct RustcLintPass;
+pub struct RustcLintPass {
+    manifest: Option<ManifestInfo>,
+    /// If `true`, every module is processed separately by [`check_mod`]. This
+    /// requires the pass to be registered as a module pass, which rustc runs in
+    /// the `lint_mod` query. The diagnostics of unchanged modules are then
+    /// replayed from the incremental cache, instead of linting the module again.
+    ///
+    /// [`check_mod`]: rustc_lint::LateLintPass::check_mod
+    by_module: bool,
+}
 
 impl RustcLintPass {
+    #[must_use]
+    pub fn new(manifest: Option<ManifestInfo>, by_module: bool) -> Self {
+        Self { manifest, by_module }
+    }
+
     #[allow(clippy::missing_errors_doc)]
     pub fn init_adapter(lint_crates: &[LintCrateInfo]) -> Result<(), marker_adapter::Error> {
         ADAPTER.with(move |cell| {
@@ -42,15 +59,38 @@ rustc_lint_defs::impl_lint_pass!(RustcLintPass => []);
 
 impl<'tcx> rustc_lint::LateLintPass<'tcx> for RustcLintPass {
     fn check_crate(&mut self, rustc_cx: &rustc_lint::LateContext<'tcx>) {
-        ADAPTER.with(|adapter| {
-            process_crate(rustc_cx, adapter.get().unwrap());
-        });
+        if !self.by_module {
+            ADAPTER.with(|adapter| {
+                process_crate(rustc_cx, adapter.get().unwrap(), self.manifest.as_ref(), None);
+            });
+        }
+    }
+
+    fn check_mod(&mut self, rustc_cx: &rustc_lint::LateContext<'tcx>, _: &'tcx hir::Mod<'tcx>, hir_id: hir::HirId) {
+        if self.by_module {
+            ADAPTER.with(|adapter| {
+                process_crate(
+                    rustc_cx,
+                    adapter.get().unwrap(),
+                    self.manifest.as_ref(),
+                    Some(hir_id.expect_owner()),
+                );
+            });
+        }
     }
 }
 
-pub fn process_crate(rustc_cx: &rustc_lint::LateContext<'_>, adapter: &Adapter) {
+/// Processes the given module or the entire crate, if no module is given. A
+/// new [`Storage`] is used for every call, this ensures that rustc tracks all
+/// information used by a module.
+pub fn process_crate(
+    rustc_cx: &rustc_lint::LateContext<'_>,
+    adapter: &Adapter,
+    manifest: Option<&ManifestInfo>,
+    module: Option<hir::OwnerId>,
+) {
     let storage = Storage::default();
-    process_crate_lifetime(rustc_cx, &storage, adapter);
+    process_crate_lifetime(rustc_cx, &storage, adapter, manifest, module);
 }
 
 /// This function marks the start of the `'ast` lifetime. The lifetime is defined
@@ -58,16 +98,61 @@ pub fn process_crate(rustc_cx: &rustc_lint::LateContext<'_>, adapter: &Adapter)
 fn process_crate_lifetime<'ast, 'tcx: 'ast>(
     rustc_cx: &rustc_lint::LateContext<'tcx>,
     storage: &'ast Storage<'ast>,
-    adapter: &Adapter,
+    adapter: &'ast Adapter,
+    manifest: Option<&'ast ManifestInfo>,
+    module: Option<hir::OwnerId>,
 ) {
-    let driver_cx = RustcContext::new(rustc_cx.tcx, rustc_cx.lint_store, storage);
+    let driver_cx = RustcContext::new(
+        rustc_cx.tcx,
+        rustc_cx.lint_store,
+        storage,
+        manifest,
+        adapter.disabled_lints(),
+        PathFilter {
+            include: patterns_from_env(INCLUDE_PATHS_ENV),
+            ignore: patterns_from_env(IGNORE_PATHS_ENV),
+        },
+        std::env::var_os(SHOW_LINT_SOURCE_ENV).is_some().then_some(adapter),
+    );
 
     // To support debug printing of AST nodes, as these might sometimes require the
     // context. Note that this only sets the cx for the rustc side. Each lint crate
     // has their own storage for cx.
     marker_api::context::set_ast_cx(driver_cx.ast_cx());
 
-    let krate = driver_cx.marker_converter.local_crate();
+    match module {
+        None => adapter.process_krate(driver_cx.ast_cx(), driver_cx.marker_converter.local_crate()),
+        Some(hir::CRATE_OWNER_ID) => {
+            adapter.process_root_module(driver_cx.ast_cx(), driver_cx.marker_converter.local_crate());
+        },
+        // Other modules are converted on their own. This limits the HIR, which
+        // rustc tracks as an input of the module, to the module itself and the
+        // items requested by the lint crates.
+        Some(owner_id) => {
+            if let Some(ItemKind::Mod(module)) = driver_cx.marker_converter.item(hir::ItemId { owner_id }) {
+                adapter.process_module(driver_cx.ast_cx(), module);
+            }
+        },
+    }
+
+    driver_cx.emit_buffered_diags(adapter.lint_supersessions(), max_per_lint_from_env());
+}
+
+/// Parses the glob patterns of [`INCLUDE_PATHS_ENV`] or [`IGNORE_PATHS_ENV`].
+/// Invalid patterns are skipped, as they're already validated by `cargo-marker`.
+fn patterns_from_env(env: &str) -> Vec<glob::Pattern> {
+    let Ok(patterns) = std::env::var(env) else {
+        return vec![];
+    };
+    patterns
+        .split(';')
+        .filter(|pattern| !pattern.is_empty())
+        .filter_map(|pattern| glob::Pattern::new(pattern).ok())
+        .collect()
+}
 
-    adapter.process_krate(driver_cx.ast_cx(), krate);
+/// Parses the diagnostic limit of [`MAX_PER_LINT_ENV`]. An invalid value is
+/// ignored, as it's already validated by `cargo-marker`.
+fn max_per_lint_from_env() -> Option<usize> {
+    std::env::var(MAX_PER_LINT_ENV).ok()?.parse().ok()
 }
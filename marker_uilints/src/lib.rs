@@ -4,17 +4,38 @@
 mod utils;
 
 use marker_api::{
-    ast::{AstPathTarget, EnumVariant, ItemField, LetStmt, StaticItem},
+    ast::{
+        AstPathTarget, Crate, EnumVariant, ExprPosition, ExprPrecedence, FnItem, FnParam, FormatPiece, ItemField,
+        LetStmt, MetaItem, MetaItemKind, OutlivesSubject, StaticItem, TyParamBound,
+    },
+    common::{CaseStyle, ItemId, Level, NodeId},
     diagnostic::Applicability,
+    manifest::Manifest,
     prelude::*,
     sem::TyKind,
     LintPass, LintPassInfo, LintPassInfoBuilder,
 };
 
-#[derive(Default)]
-struct TestLintPass {}
+/// The configuration of this lint crate. It's set via the `MARKER_LINT_CONFIGS`
+/// environment value in the tests.
+#[derive(Default, serde::Deserialize)]
+struct Config {
+    crate_note: Option<String>,
+}
+
+struct TestLintPass {
+    config: Config,
+    /// The last checked function, used as the owner for type queries
+    fn_owner: Option<ItemId>,
+}
+
+impl TestLintPass {
+    fn new(config: Config) -> Self {
+        Self { config, fn_owner: None }
+    }
+}
 
-marker_api::export_lint_pass!(TestLintPass);
+marker_api::export_lint_pass!(TestLintPass, config = Config);
 
 marker_api::declare_lint! {
     /// # What it does
@@ -54,6 +75,17 @@ marker_api::declare_lint! {
     Allow,
 }
 
+marker_api::declare_lint! {
+    /// # What it does
+    /// A lint used for markers uitests.
+    ///
+    /// It prints information about panicking macros, if this lint is set to
+    /// warn at the expression node.
+    TEST_PANIC_MACRO,
+    Allow,
+    marker_api::common::MacroReport::All,
+}
+
 marker_api::declare_lint! {
     /// # What it does
     /// A lint to test [`marker_api::AstMap`].
@@ -68,6 +100,13 @@ marker_api::declare_lint! {
     Warn,
 }
 
+marker_api::declare_lint! {
+    /// # What it does
+    /// A lint to test lint supersessions. It's superseded by [`TEST_LINT`].
+    TEST_SUPERSEDED_LINT,
+    Warn,
+}
+
 fn emit_item_with_test_name_lint<'ast>(
     cx: &'ast MarkerContext<'ast>,
     node: impl EmissionNode<'ast>,
@@ -83,22 +122,76 @@ impl LintPass for TestLintPass {
             TEST_LINT,
             ITEM_WITH_TEST_NAME,
             PRINT_EVERY_EXPR,
+            TEST_PANIC_MACRO,
             utils::TEST_CONTAINS_RETURN,
+            utils::TEST_FIND_DUPLICATE_BLOCKS,
+            utils::TEST_FOR_EACH_EXPR_WITH_PARENT,
+            utils::TEST_COMPLEXITY,
+            utils::TEST_FIND_LARGE_STACK_VALUES,
+            utils::TEST_VISIT_PAT_AND_TY,
+            TEST_SUPERSEDED_LINT,
         ]))
+        .supersedes(TEST_LINT, TEST_SUPERSEDED_LINT)
         .build()
     }
 
+    fn check_crate<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, krate: &'ast Crate<'ast>) {
+        if let Some(note) = &self.config.crate_note {
+            cx.emit_lint(TEST_LINT, krate.root_mod(), "lint crate configuration")
+                .decorate(|diag| {
+                    diag.note(note);
+                });
+        }
+
+        if let Some(manifest) = cx.manifest() {
+            check_manifest(cx, manifest);
+        }
+
+        let attrs = cx.crate_attrs();
+        if !attrs.iter().any(|attr| {
+            attr.doc_comment()
+                .map_or(false, |doc| doc.contains("check_crate_attrs"))
+        }) {
+            return;
+        }
+
+        let forbids_unsafe = attrs
+            .iter()
+            .any(|attr| attr.has_path("forbid") && attr.tokens().iter().any(|token| token.text() == "unsafe_code"));
+        if !forbids_unsafe {
+            cx.emit_lint(
+                TEST_LINT,
+                krate.root_mod(),
+                "crate is missing `#![forbid(unsafe_code)]`",
+            );
+        }
+    }
+
     fn check_item<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
         utils::check_item(cx, item);
+        check_object_safety(cx, item);
+        check_supertraits(cx, item);
+        check_trait_methods(cx, item);
+        check_derives(cx, item);
+        check_transparent(cx, item);
+        check_lifetime_bounds(cx, item);
+        check_trait_bounds(cx, item);
+        check_derivable(cx, item);
+        check_auto_trait_impl(cx, item);
+        check_name_case(cx, item);
+        check_ty_alias(cx, item);
+        check_param_usage(cx, item);
+        check_used_in_crate(cx, item);
+        check_doc_examples(cx, item);
+        check_bidi_span(cx, item);
+        check_source_lines(cx, item);
+        check_expansion_origin(cx, item);
+        check_macro_def_site(cx, item);
+        check_supersession(cx, item);
 
         if let ItemKind::Fn(item) = item {
-            if let Some(ident) = item.ident() {
-                if ident.name() == "test_ty_id_resolution_trigger" {
-                    test_ty_id_resolution(cx);
-                } else if ident.name() == "uilints_please_ice_on_this" {
-                    panic!("free ice cream for everyone!!!");
-                }
-            }
+            self.fn_owner = Some(item.id());
+            check_fn(cx, item);
         }
 
         if let ItemKind::Static(item) = item {
@@ -119,6 +212,7 @@ impl LintPass for TestLintPass {
                 ItemKind::Enum(_) => Some("enum"),
                 ItemKind::Union(_) => Some("union"),
                 ItemKind::Trait(_) => Some("trait"),
+                ItemKind::ExternType(_) => Some("extern type"),
                 _ => None,
             };
 
@@ -137,6 +231,9 @@ impl LintPass for TestLintPass {
             });
         }
 
+        print_item_attrs(cx, item);
+        print_meta_items(cx, item);
+
         if let ItemKind::Fn(func) = item {
             if item
                 .ident()
@@ -183,6 +280,8 @@ impl LintPass for TestLintPass {
     }
 
     fn check_stmt<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) {
+        check_stmt_order(cx, stmt);
+
         // I didn't realize that `let_chains` are still unstable. This makes the
         // code significantly less readable -.-
         if let StmtKind::Let(lets) = stmt {
@@ -224,17 +323,152 @@ impl LintPass for TestLintPass {
                     let ids = cx.resolve_ty_ids(path);
                     diag.note(format!("Is this a {:#?} -> {}", path, ids.contains(&adt.def_id())));
                 });
-            } else if ident.name().starts_with("_check_ast_map") {
-                check_ast_map(cx, lets);
+            } else if ident.name().starts_with("_level") {
+                let ExprKind::IntLit(lit) = expr else { return };
+                cx.emit_lint(TEST_LINT, stmt, "scaling the level by value")
+                    .decorate(|diag| {
+                        if lit.value() > 10 {
+                            diag.with_level(Level::Deny);
+                        }
+                    });
+            } else if ident.name().starts_with("_index") {
+                let ExprKind::Index(index) = expr else { return };
+                cx.emit_lint(TEST_LINT, stmt, "checking index").decorate(|diag| {
+                    diag.note(format!("const_index: {:?}", index.const_index()));
+                    diag.note(format!("operand_array_len: {:?}", index.operand_array_len()));
+                });
+            } else if ident.name().starts_with("_block") {
+                let ExprKind::Block(block) = expr else { return };
+                cx.emit_lint(TEST_LINT, stmt, "checking block").decorate(|diag| {
+                    diag.note(format!("block_kind: {:?}", block.block_kind()));
+                    diag.note(format!("label: {:?}", block.label().map(Ident::name)));
+                });
+            } else if ident.name().starts_with("_match") {
+                let ExprKind::Match(match_expr) = expr else { return };
+                cx.emit_lint(TEST_LINT, stmt, "checking match arms").decorate(|diag| {
+                    for arm in match_expr.arms() {
+                        diag.span_note(
+                            format!(
+                                "guard: {:?}, is_catch_all: {}",
+                                arm.guard().map(|guard| guard.span().snippet_or("..")),
+                                arm.is_catch_all()
+                            ),
+                            arm.pat().span(),
+                        );
+                    }
+                });
+            } else if ident.name().starts_with("_iterator") {
+                if let Some(owner) = self.fn_owner {
+                    check_iterator(cx, stmt, expr, owner);
+                }
+            } else if ident.name().starts_with("_layout") {
+                if let Some(owner) = self.fn_owner {
+                    check_ty_layout(cx, stmt, expr, owner);
+                }
+            } else if ident.name().starts_with("_stack_size") {
+                if let Some(owner) = self.fn_owner {
+                    check_stack_size(cx, stmt, expr, owner);
+                }
+            } else if ident.name().starts_with("_drop") {
+                if let Some(owner) = self.fn_owner {
+                    check_drop(cx, stmt, expr, owner);
+                }
+            } else {
+                check_let_stmt(cx, stmt, lets, ident.name(), expr);
             }
         }
     }
 
+    fn check_local<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, local: &'ast LetStmt<'ast>) {
+        if local.pat().span().snippet_or("").contains("_local") {
+            cx.emit_lint(TEST_LINT, local, "checking local").decorate(|diag| {
+                diag.note(format!("Pat: {}", local.pat().span().snippet_or("..")));
+                diag.note(format!(
+                    "Ty: {:?}",
+                    local.ty().map(|ty| ty.span().snippet_or("..").to_string())
+                ));
+                diag.note(format!(
+                    "Init: {:?}",
+                    local.init().map(|init| init.span().snippet_or("..").to_string())
+                ));
+                diag.note(format!(
+                    "Else: {:?}",
+                    local.els().map(|els| els.span().snippet_or("..").to_string())
+                ));
+            });
+        }
+    }
+
+    fn check_generic_bound<'ast>(
+        &mut self,
+        cx: &'ast MarkerContext<'ast>,
+        bound: &'ast TyParamBound<'ast>,
+        owner: ItemId,
+    ) {
+        let Some(item) = cx.ast().item(owner) else { return };
+        let Some(ident) = item.ident() else { return };
+        if !ident.name().starts_with("CheckGenericBound") && !ident.name().starts_with("check_generic_bound") {
+            return;
+        }
+
+        let (desc, span) = match bound {
+            TyParamBound::TraitBound(bound) => {
+                let relaxed = if bound.is_relaxed() { "relaxed " } else { "" };
+                (format!("{relaxed}trait bound"), Some(bound.span()))
+            },
+            TyParamBound::Lifetime(lifetime) => ("lifetime bound".to_string(), lifetime.span()),
+            _ => unreachable!(),
+        };
+        cx.emit_lint(TEST_LINT, item, format!("checking generic bound of `{}`", ident.name()))
+            .decorate(|diag| {
+                diag.note(desc);
+                if let Some(span) = span {
+                    diag.span(span);
+                }
+            });
+    }
+
     fn check_expr<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) {
         cx.emit_lint(PRINT_EVERY_EXPR, expr, "expr").decorate(|diag| {
             diag.note(&format!("SpanSource: {:#?}", expr.span().source()));
             diag.note(&format!("Snippet: {:#?}", expr.span().snippet_or("<..>")));
         });
+
+        if let Some(info) = cx.panic_macro(expr) {
+            cx.emit_lint(TEST_PANIC_MACRO, expr, "found a panicking macro")
+                .decorate(|diag| {
+                    diag.span(info.span());
+                    diag.note(format!("kind: {:?}", info.kind()));
+                    diag.note(format!("message: {:?}", info.message()));
+                });
+        }
+    }
+}
+
+fn check_manifest<'ast>(cx: &'ast MarkerContext<'ast>, manifest: &'ast Manifest<'ast>) {
+    if let Some(span) = manifest.package_span() {
+        cx.emit_manifest_lint(TEST_LINT, span, "manifest package")
+            .decorate(|diag| {
+                diag.note(format!("edition: {:?}", manifest.edition()));
+            });
+    }
+    for feature in manifest.features() {
+        if let Some(span) = feature.span() {
+            cx.emit_manifest_lint(TEST_LINT, span, format!("manifest feature `{}`", feature.name()))
+                .decorate(|diag| {
+                    diag.note(format!("enables: {:?}", feature.enables().collect::<Vec<_>>()));
+                    diag.note(format!("is enabled: {}", feature.is_enabled()));
+                });
+        }
+    }
+    for dep in manifest.dependencies() {
+        if let Some(span) = dep.span() {
+            cx.emit_manifest_lint(TEST_LINT, span, format!("manifest dependency `{}`", dep.local_name()))
+                .decorate(|diag| {
+                    diag.note(format!("kind: {:?}", dep.kind()));
+                    diag.note(format!("is optional: {}", dep.is_optional()));
+                });
+        }
     }
 }
 
@@ -268,6 +502,1278 @@ fn check_ast_map<'ast>(cx: &'ast MarkerContext<'ast>, lets: &'ast LetStmt<'ast>)
     }
 }
 
+fn check_overloaded_op<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::BinaryOp(op) = expr else { return };
+    cx.emit_lint(TEST_LINT, stmt, "checking operator overloading")
+        .decorate(|diag| {
+            diag.note(format!("is_overloaded: {}", op.overloaded_fn().is_some()));
+        });
+}
+
+fn check_fn<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast FnItem<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    check_self_kind(cx, item, ident);
+    check_impl_trait(cx, item, ident);
+    check_async_output(cx, item, ident);
+    check_const_fn(cx, item, ident);
+    check_multipart_suggestion(cx, item, ident);
+    if ident.name() == "test_ty_id_resolution_trigger" {
+        test_ty_id_resolution(cx);
+    } else if ident.name() == "uilints_please_ice_on_this" {
+        panic!("free ice cream for everyone!!!");
+    }
+}
+
+/// Runs the checks for `let` statements, which are selected by the name of
+/// the binding.
+fn check_let_stmt<'ast>(
+    cx: &'ast MarkerContext<'ast>,
+    stmt: StmtKind<'ast>,
+    lets: &'ast LetStmt<'ast>,
+    name: &str,
+    expr: ExprKind<'ast>,
+) {
+    if name.starts_with("_raw_ptr") {
+        check_raw_ptr(cx, stmt, expr);
+    } else if name.starts_with("_enclosing_loop") {
+        check_enclosing_loop(cx, stmt, expr);
+    } else if name.starts_with("_check_ast_map") {
+        check_ast_map(cx, lets);
+    } else if name.starts_with("_overloaded") {
+        check_overloaded_op(cx, stmt, expr);
+    } else if name.starts_with("_node_at") {
+        check_node_at(cx, stmt, expr);
+    } else if name.starts_with("_lit_chars") {
+        check_lit_chars(cx, stmt, expr);
+    } else if name.starts_with("_last_use") {
+        check_last_use(cx, stmt, expr);
+    } else if name.starts_with("_format_args") {
+        check_format_args(cx, stmt, expr);
+    } else if name.starts_with("_simplify_bool") {
+        check_simplify_bool(cx, stmt, expr);
+    } else if name.starts_with("_parens") {
+        check_parens(cx, stmt, expr);
+    } else if name.starts_with("_is_blocking") {
+        check_blocking_call(cx, stmt, expr);
+    } else if name.starts_with("_in_crate") {
+        check_locality(cx, stmt, expr);
+    } else if name.starts_with("_try_conversion") {
+        check_try_conversion(cx, stmt, expr);
+    } else if name.starts_with("_enum_cast") {
+        check_enum_cast(cx, stmt, expr);
+    } else if name.starts_with("_labels") {
+        let ExprKind::Tuple(tuple) = expr else { return };
+        cx.emit_lint(TEST_LINT, stmt, "checking span labels").decorate(|diag| {
+            for (index, element) in tuple.elements().iter().enumerate() {
+                diag.span_label(format!("element {index}"), element.span());
+            }
+        });
+    } else if name.starts_with("_annotation") {
+        cx.emit_lint(TEST_LINT, stmt, "checking type annotation")
+            .decorate(|diag| {
+                diag.note(format!("annotation_matches_init: {:?}", lets.annotation_matches_init()));
+            });
+    } else if name.starts_with("_var") {
+        check_var(cx, stmt, expr);
+    } else if name.starts_with("_closure_body") {
+        let ExprKind::Closure(closure) = expr else { return };
+        cx.emit_lint(TEST_LINT, stmt, "checking closure body").decorate(|diag| {
+            let body = closure.body();
+            diag.span_note("body expression", body.expr().span());
+            diag.note(format!(
+                "same as `AstMap::body`: {}",
+                std::ptr::eq(body, cx.ast().body(closure.body_id()))
+            ));
+        });
+    } else if name.starts_with("_if_let") {
+        check_if_let(cx, stmt, expr);
+    } else if name.starts_with("_smart_ptr") {
+        check_smart_ptr(cx, stmt, expr);
+    } else if name.starts_with("_needless_collect") {
+        check_needless_collect(cx, stmt, expr);
+    } else if name.starts_with("_static_ref") {
+        check_static_ref(cx, stmt, expr);
+    } else if name.starts_with("_rewrite_match") {
+        check_match_rewrite(cx, stmt, expr);
+    } else if name.starts_with("_target") {
+        check_target(cx, stmt);
+    } else if name.starts_with("_segments") {
+        check_path_segments(cx, stmt, expr);
+    } else if name.starts_with("_diverges") {
+        cx.emit_lint(TEST_LINT, stmt, "checking divergence").decorate(|diag| {
+            diag.note(format!("diverges: {}", cx.expr_diverges(expr)));
+        });
+    } else if name.starts_with("_unwrap") {
+        check_unwrap(cx, stmt, expr);
+    } else if name.starts_with("_doc_hidden") {
+        check_doc_hidden(cx, stmt, expr);
+    } else if name.starts_with("_const_eval") {
+        check_const_eval(cx, stmt, lets, expr);
+    } else if name.starts_with("_field_access") {
+        check_field_access(cx, stmt, expr);
+    } else if name.starts_with("_identity_conversion") {
+        check_identity_conversion(cx, stmt, expr);
+    } else if name.starts_with("_float_op") {
+        check_float_op(cx, stmt, expr);
+    } else if name.starts_with("_file_loc") {
+        check_file_loc(cx, stmt, expr);
+    } else if name.starts_with("_dispatch") {
+        check_dispatch(cx, stmt, expr);
+    } else if name.starts_with("_suggest") {
+        check_suggestion_builder(cx, stmt, name, expr);
+    }
+}
+
+fn check_drop<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>, owner: ItemId) {
+    cx.emit_lint(TEST_LINT, stmt, "checking drop").decorate(|diag| {
+        diag.note(format!("needs_drop: {}", cx.needs_drop(expr.ty(), owner)));
+        diag.note(format!("has_drop_impl: {}", cx.has_drop_impl(expr.ty())));
+    });
+}
+
+fn check_suggestion_builder<'ast>(
+    cx: &'ast MarkerContext<'ast>,
+    stmt: StmtKind<'ast>,
+    name: &str,
+    expr: ExprKind<'ast>,
+) {
+    cx.emit_lint(TEST_LINT, stmt, "checking suggestion builder")
+        .decorate(|diag| {
+            let snippet = expr.span().snippet_or("..");
+            let sugg = if name.starts_with("_suggest_insert") {
+                cx.suggest_insert_before(expr, "&").insert_after(expr, ".clone()")
+            } else if name.starts_with("_suggest_invalid") {
+                cx.suggest_replace(expr, format!("{snippet} +")).validate_expr()
+            } else {
+                cx.suggest_replace(expr, format!("({snippet}) * 1")).validate_expr()
+            };
+            let sugg = sugg.msg("use this");
+            diag.note(format!("applicability: {:?}", sugg.resolved_applicability()));
+            sugg.emit(diag);
+        });
+}
+
+fn check_raw_ptr<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking raw pointer operation")
+        .decorate(|diag| {
+            diag.note(format!("is_raw_ptr_deref: {}", cx.is_raw_ptr_deref(expr)));
+            if let Some(op) = cx.raw_ptr_op(expr) {
+                diag.note(format!("kind: {:?}, origin: {:?}", op.kind(), op.origin()));
+                diag.note(format!("ptr: {}", op.ptr().span().snippet_or("..")));
+                diag.note(format!("pointee: {:?}", op.pointee()));
+            }
+        });
+}
+
+fn check_dispatch<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::Method(call) = expr else { return };
+    cx.emit_lint(TEST_LINT, stmt, "checking method dispatch")
+        .decorate(|diag| {
+            diag.note(format!("is_trait_method: {}", call.is_trait_method()));
+            diag.note(format!("dispatch: {:?}", call.dispatch()));
+            if let Some(trait_id) = call.defining_trait() {
+                if let Some(item) = cx.ast().item(trait_id) {
+                    diag.span_note("defining trait", item.ident().unwrap().span());
+                }
+            }
+        });
+}
+
+fn check_enclosing_loop<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking enclosing loop")
+        .decorate(|diag| {
+            if let Some(info) = cx.enclosing_loop(expr) {
+                let label = info.label().map(Ident::name);
+                diag.span_note(format!("kind: {:?}, label: {label:?}", info.kind()), info.expr().span());
+            } else {
+                diag.note("no enclosing loop");
+            }
+        });
+}
+
+fn check_stmt_order<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) {
+    let snippet = stmt.span().snippet_or("");
+    let kind = match stmt {
+        StmtKind::Let(_) if snippet.starts_with("let _stmt_order") => "let",
+        StmtKind::Expr(_) if snippet.starts_with("stmt_order(") => "expression",
+        StmtKind::Item(_) if snippet.starts_with("fn stmt_order") => "item",
+        _ => return,
+    };
+    cx.emit_lint(TEST_LINT, stmt, format!("visiting {kind} statement"));
+}
+
+fn check_file_loc<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(PRINT_SPAN_LINT, stmt, "checking file locations")
+        .decorate(|diag| {
+            for (name, loc) in [("start", expr.span().start_loc()), ("end", expr.span().end_loc())] {
+                match loc {
+                    Some(loc) => diag.note(format!("{name}: {}:{}:{}", loc.file(), loc.line(), loc.column())),
+                    None => diag.note(format!("{name}: None")),
+                };
+            }
+        });
+}
+
+fn check_stack_size<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>, owner: ItemId) {
+    /// A small threshold, which would usually be taken from the lint config
+    const MAX_STACK_SIZE: u64 = 1024;
+
+    cx.emit_lint(TEST_LINT, stmt, "checking stack size").decorate(|diag| {
+        let size = cx.stack_size_of(expr.ty(), owner);
+        diag.note(format!("stack_size_of: {size:?}"));
+        if size.is_some_and(|size| size > MAX_STACK_SIZE) {
+            diag.help("consider allocating it on the heap with `Box`");
+        }
+    });
+}
+
+fn check_float_op<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::BinaryOp(op) = expr else { return };
+    cx.emit_lint(TEST_LINT, stmt, "checking float operation")
+        .decorate(|diag| {
+            diag.note(format!("is_float_op: {}", op.is_float_op()));
+            if let TyKind::Num(num) = op.left().ty().peel_refs() {
+                diag.note(format!("float_bits: {:?}", num.numeric_kind().float_bits()));
+            }
+        });
+}
+
+fn check_identity_conversion<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking identity conversion")
+        .decorate(|diag| {
+            diag.note(format!("is_identity_conversion: {}", cx.is_identity_conversion(expr)));
+        });
+}
+
+fn check_field_access<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::Field(field) = expr else {
+        return;
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking field access").decorate(|diag| {
+        let base_ty = match field.base_ty() {
+            TyKind::Adt(adt) if cx.resolve_ty_ids("std::boxed::Box").contains(&adt.def_id()) => "Box",
+            TyKind::Adt(_) => "Adt",
+            TyKind::Ref(_) => "Ref",
+            _ => "other",
+        };
+        diag.note(format!("base_ty: {base_ty}"));
+        diag.note(format!(
+            "resolved_field: {:?}",
+            field.resolved_field().map(ItemField::ident)
+        ));
+        diag.note(format!("autoderefs: {}", field.autoderefs()));
+    });
+}
+
+fn check_const_eval<'ast>(
+    cx: &'ast MarkerContext<'ast>,
+    stmt: StmtKind<'ast>,
+    lets: &'ast LetStmt<'ast>,
+    expr: ExprKind<'ast>,
+) {
+    let mut consts = vec![];
+    if let ExprKind::Array(array) = expr {
+        consts.extend(array.len());
+    }
+    match lets.ty() {
+        Some(marker_api::ast::TyKind::Array(array)) => consts.extend(array.len()),
+        Some(marker_api::ast::TyKind::Path(path)) => {
+            consts.extend(path.path().generics().args().iter().filter_map(|arg| match arg {
+                marker_api::ast::GenericArgKind::Const(arg) => Some(arg.expr()),
+                _ => None,
+            }));
+        },
+        _ => {},
+    }
+
+    cx.emit_lint(TEST_LINT, stmt, "checking const evaluation")
+        .decorate(|diag| {
+            for value in consts {
+                let msg = match cx.try_eval_const(value) {
+                    Some(marker_api::sem::ConstValue::Int(int)) => {
+                        format!("int: {:?} (signed: {})", int.to_i128(), int.is_signed())
+                    },
+                    Some(value) => format!("value: {value:?}"),
+                    None => "value: None".to_string(),
+                };
+                diag.span_note(msg, value.expr().span());
+            }
+        });
+}
+
+fn check_doc_hidden<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking doc hidden").decorate(|diag| {
+        if let ExprKind::Call(call) = expr {
+            if let ExprKind::Path(path) = call.func() {
+                if let AstPathTarget::Item(id) = path.path().resolve() {
+                    diag.note(format!("is_doc_hidden (fn): {}", cx.is_doc_hidden(id)));
+                }
+            }
+        }
+        if let TyKind::Adt(adt) = expr.ty() {
+            diag.note(format!("is_ty_doc_hidden: {}", cx.is_ty_doc_hidden(adt.def_id())));
+        }
+    });
+}
+
+fn check_unwrap<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::Method(call) = expr else {
+        return;
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking unwrap call").decorate(|diag| {
+        diag.note(format!("is_unwrap_or_expect: {}", call.is_unwrap_or_expect()));
+        diag.note(format!(
+            "is_option_or_result: {}",
+            cx.is_option_or_result(call.receiver().ty())
+        ));
+        diag.note(format!("is_in_test_code: {}", cx.is_in_test_code(stmt)));
+        diag.note(format!("is_guarded_unwrap: {}", cx.is_guarded_unwrap(expr)));
+    });
+}
+
+fn check_path_segments<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let segments = match expr {
+        ExprKind::Call(call) => match call.func() {
+            ExprKind::Path(path) => path.segments(),
+            _ => return,
+        },
+        ExprKind::Method(call) => std::slice::from_ref(call.method()),
+        ExprKind::Path(path) => path.segments(),
+        _ => return,
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking path segments")
+        .decorate(|diag| {
+            for segment in segments {
+                diag.span_note(
+                    format!(
+                        "`{}`: generic args: {}, target: {:?}",
+                        segment.ident().name(),
+                        segment.generics().args().len(),
+                        segment.resolve(),
+                    ),
+                    segment.ident().span(),
+                );
+            }
+        });
+}
+
+fn check_target<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) {
+    // The UI tests are compiled for the host, the values are compared with the
+    // host to keep the output stable across platforms.
+    let target = cx.target();
+    cx.emit_lint(TEST_LINT, stmt, "checking the target").decorate(|diag| {
+        diag.note(format!("triple is set: {}", !target.triple().is_empty()));
+        diag.note(format!(
+            "pointer width matches host: {}",
+            target.pointer_width() == usize::BITS
+        ));
+        diag.note(format!(
+            "endianness matches host: {}",
+            target.endianness().is_little() == cfg!(target_endian = "little")
+        ));
+        diag.note(format!("os matches host: {}", target.os() == std::env::consts::OS));
+        diag.note(format!(
+            "arch matches host: {}",
+            target.arch() == std::env::consts::ARCH
+        ));
+    });
+}
+
+fn check_match_rewrite<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::Match(match_expr) = expr else { return };
+    cx.emit_lint(TEST_LINT, stmt, "checking match rewrites")
+        .decorate(|diag| {
+            diag.note(format!("is_boolean_match: {}", match_expr.is_boolean_match()));
+            diag.note(format!("matches_suggestion: {:?}", match_expr.matches_suggestion()));
+            diag.note(format!("if_let_suggestion: {:?}", match_expr.if_let_suggestion()));
+        });
+}
+
+fn check_ty_layout<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>, owner: ItemId) {
+    cx.emit_lint(TEST_LINT, stmt, "checking type layout").decorate(|diag| {
+        diag.note(format!("ty_has_niche: {}", cx.ty_has_niche(expr.ty(), owner)));
+        match cx.ty_layout(expr.ty(), owner) {
+            Some(layout) => diag.note(format!(
+                "size: {}, align: {}, option_size: {:?}, is_option_free: {}",
+                layout.size(),
+                layout.align(),
+                layout.option_size(),
+                layout.is_option_free()
+            )),
+            None => diag.note("unknown layout"),
+        };
+    });
+}
+
+fn check_iterator<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>, owner: ItemId) {
+    cx.emit_lint(TEST_LINT, stmt, "checking iterator").decorate(|diag| {
+        diag.note(format!("is_iterator_ty: {}", cx.is_iterator_ty(expr.ty(), owner)));
+        diag.note(format!("iterator_item_ty: {:?}", cx.iterator_item_ty(expr.ty(), owner)));
+        let is_adapter = match expr {
+            ExprKind::Method(call) => Some(call.is_iterator_adapter()),
+            ExprKind::Call(call) => Some(call.is_iterator_adapter()),
+            _ => None,
+        };
+        if let Some(is_adapter) = is_adapter {
+            diag.note(format!("is_iterator_adapter: {is_adapter}"));
+        }
+    });
+}
+
+fn check_parens<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking parentheses").decorate(|diag| {
+        let render = |operand: ExprKind<'_>, parent, position| {
+            // The snippet of parenthesized operands includes the parentheses
+            let snippet = operand.span().snippet_or("<?>");
+            let snippet = snippet
+                .strip_prefix('(')
+                .and_then(|snippet| snippet.strip_suffix(')'))
+                .unwrap_or(snippet);
+            cx.render_with_parens(snippet, operand.precedence(), parent, position)
+        };
+        match expr {
+            ExprKind::BinaryOp(op) => {
+                diag.note(format!(
+                    "left: `{}`",
+                    render(op.left(), op.precedence(), ExprPosition::Left)
+                ));
+                diag.note(format!(
+                    "right: `{}`",
+                    render(op.right(), op.precedence(), ExprPosition::Right)
+                ));
+            },
+            ExprKind::UnaryOp(op) => {
+                diag.note(format!(
+                    "operand: `{}`",
+                    render(op.expr(), op.precedence(), ExprPosition::Prefix)
+                ));
+            },
+            ExprKind::As(cast) => {
+                diag.note(format!(
+                    "operand: `{}`",
+                    render(cast.expr(), cast.precedence(), ExprPosition::Left)
+                ));
+            },
+            _ => {},
+        }
+        diag.note(format!(
+            "as receiver: `{}.foo()`",
+            render(expr, ExprPrecedence::Method, ExprPosition::Postfix)
+        ));
+        diag.note(format!(
+            "in `<...> < b`: `{} < b`",
+            render(expr, ExprPrecedence::Comparison, ExprPosition::Left)
+        ));
+    });
+}
+
+fn check_blocking_call<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking blocking call")
+        .decorate(|diag| {
+            diag.note(format!("is_blocking_call: {}", cx.is_blocking_call(expr)));
+            diag.note(format!(
+                "is_blocking_call_with: {}",
+                cx.is_blocking_call_with(expr, &["crate::db::*", "crate::Client::send"])
+            ));
+            diag.note(format!("is_in_async_context: {}", cx.is_in_async_context(expr)));
+        });
+}
+
+fn check_locality<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking locality").decorate(|diag| {
+        let ty = expr.ty().peel_refs();
+        diag.note(format!("is_local_ty: {}", cx.is_local_ty(ty)));
+        if let TyKind::TraitObj(obj) = ty {
+            for bound in obj.bounds() {
+                diag.note(format!("is_local_trait: {}", cx.is_local_trait(bound.trait_id())));
+            }
+        }
+    });
+}
+
+fn check_try_conversion<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking try conversion")
+        .decorate(|diag| {
+            let Some((source, target, from_impl)) = cx.try_from_residual_conversion(expr) else {
+                diag.note("conversion: None");
+                return;
+            };
+            diag.note(format!("source: {source:?}"));
+            diag.note(format!("target: {target:?}"));
+            // External items can't be requested from the AST map
+            if cx.is_local_ty(target) {
+                if let Some(item) = cx.ast().item(from_impl) {
+                    diag.span_note("from impl", item.span());
+                }
+            }
+        });
+}
+
+fn check_enum_cast<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::As(cast) = expr else { return };
+    cx.emit_lint(TEST_LINT, stmt, "checking enum cast").decorate(|diag| {
+        diag.note(format!("is_enum_to_int: {}", cast.is_enum_to_int()));
+        diag.note(format!("is_int_to_enum: {}", cast.is_int_to_enum()));
+        if let TyKind::Adt(adt) = cast.expr().ty() {
+            let discriminants = cx.enum_discriminants(adt.def_id()).map(|discriminants| {
+                discriminants
+                    .iter()
+                    .map(|discr| match discr.to_u128() {
+                        Some(value) => value.to_string(),
+                        None => discr.to_i128().unwrap().to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            });
+            diag.note(format!("enum_discriminants: {discriminants:?}"));
+        }
+    });
+}
+
+fn check_if_let<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    let ExprKind::If(if_expr) = expr else { return };
+    let ExprKind::Let(let_expr) = if_expr.condition() else {
+        return;
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking if let").decorate(|diag| {
+        diag.span_note("pattern", let_expr.pat().span());
+        diag.span_note("scrutinee", let_expr.scrutinee().span());
+        diag.note(format!("is_irrefutable: {}", let_expr.is_irrefutable()));
+    });
+}
+
+fn check_var<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking variable").decorate(|diag| {
+        let ExprKind::Path(path) = expr else { return };
+        match path.path().resolve() {
+            AstPathTarget::Var(var) => {
+                diag.note(format!("var_name: {}", cx.var_name(var)));
+                diag.note(format!("var_ty: {:?}", cx.var_ty(var)));
+                diag.span_note("declared here", cx.var_decl_span(var));
+            },
+            _ => {
+                diag.note("not a variable");
+            },
+        }
+    });
+}
+
+fn check_static_ref<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking reference lifetimes")
+        .decorate(|diag| {
+            let ty = expr.ty();
+            diag.note(format!("is_static_ref: {}", ty.is_static_ref()));
+            let Some(sig) = ty.fn_sig() else { return };
+            for (index, param) in sig.params().iter().enumerate() {
+                if let TyKind::Ref(ref_ty) = param {
+                    let lifetime = ref_ty.lifetime();
+                    diag.note(format!(
+                        "param {index}: kind: {:?}, name: {:?}, is_static_ref: {}, outlives_fn: {}",
+                        lifetime.kind(),
+                        lifetime.name(),
+                        ref_ty.is_static_ref(),
+                        ref_ty.outlives_fn(),
+                    ));
+                }
+            }
+            if let TyKind::Adt(adt) = sig.return_ty() {
+                for arg in adt.generics().args() {
+                    if let marker_api::sem::GenericArgKind::Lifetime(lifetime) = arg {
+                        diag.note(format!("output: kind: {:?}", lifetime.kind()));
+                    }
+                }
+            }
+        });
+}
+
+fn check_smart_ptr<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking smart pointer")
+        .decorate(|diag| {
+            let ty = expr.ty();
+            diag.note(format!("as_box: {:?}", ty.as_box()));
+            diag.note(format!("as_rc: {:?}", ty.as_rc()));
+            diag.note(format!("as_arc: {:?}", ty.as_arc()));
+            diag.note(format!("as_pin: {:?}", ty.as_pin()));
+            diag.note(format!("as_cow: {:?}", ty.as_cow()));
+        });
+}
+
+fn check_needless_collect<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking needless collect")
+        .decorate(|diag| {
+            // Check every expression of the method chain
+            let mut current = match expr {
+                ExprKind::For(for_expr) => for_expr.iterable(),
+                _ => expr,
+            };
+            loop {
+                if let Some(span) = cx.is_needless_collect(current) {
+                    diag.span_note("needless collect", span);
+                }
+                let ExprKind::Method(method) = current else { break };
+                current = method.receiver();
+            }
+        });
+}
+
+fn check_simplify_bool<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking bool simplification")
+        .decorate(|diag| {
+            if let ExprKind::BinaryOp(op) = expr {
+                diag.note(format!(
+                    "is_bool_literal_comparison: {}",
+                    op.is_bool_literal_comparison()
+                ));
+            }
+            diag.note(format!("simplified: {:?}", cx.simplify_bool(expr)));
+        });
+}
+
+fn check_format_args<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking format args").decorate(|diag| {
+        let Some(args) = cx.format_args(expr) else {
+            diag.note("format_args: None");
+            return;
+        };
+        for piece in args.template() {
+            match piece {
+                FormatPiece::Literal(lit) => diag.note(format!("literal: {:?}", lit.value())),
+                FormatPiece::Placeholder(placeholder) => {
+                    let msg = format!(
+                        "placeholder: argument={} ({:?}), trait={:?}, width={:?}, precision={:?}, \
+                            alignment={:?}, fill={:?}, plus={}, alternate={}, zero_pad={}",
+                        placeholder.argument(),
+                        placeholder.argument_kind(),
+                        placeholder.format_trait(),
+                        placeholder.width(),
+                        placeholder.precision(),
+                        placeholder.alignment(),
+                        placeholder.fill(),
+                        placeholder.sign_plus(),
+                        placeholder.alternate(),
+                        placeholder.zero_pad(),
+                    );
+                    match placeholder.span() {
+                        Some(span) => diag.span_note(msg, span),
+                        None => diag.note(msg),
+                    }
+                },
+                _ => unreachable!(),
+            };
+        }
+        for arg in args.arguments() {
+            let msg = format!("argument: {:?} {:?}", arg.kind(), arg.name());
+            diag.span_note(msg, arg.span());
+            if let Some(arg_expr) = arg.expr() {
+                diag.span_note(
+                    format!("argument expression (path: {})", matches!(arg_expr, ExprKind::Path(_))),
+                    arg_expr.span(),
+                );
+            }
+        }
+    });
+}
+
+fn check_last_use<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    // For clones, the receiver is checked
+    let expr = match expr {
+        ExprKind::Method(call) if call.method().ident().name() == "clone" => call.receiver(),
+        _ => expr,
+    };
+    cx.emit_lint(TEST_LINT, stmt, "checking last use").decorate(|diag| {
+        diag.span_note(format!("is_last_use: {}", cx.is_last_use(expr)), expr.span());
+    });
+}
+
+fn check_lit_chars<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking literal characters")
+        .decorate(|diag| match expr {
+            ExprKind::CharLit(lit) => {
+                diag.note(format!("value: {:?}", lit.value()));
+                diag.note(format!("is_ascii: {}", lit.is_ascii()));
+                diag.note(format!("is_control: {}", lit.is_control()));
+                diag.note(format!("is_combining: {}", lit.is_combining()));
+                diag.note(format!("is_bidi_control: {}", lit.is_bidi_control()));
+                diag.note(format!("is_zero_width: {}", lit.is_zero_width()));
+            },
+            ExprKind::StrLit(lit) => {
+                diag.note(format!("has_bidi_control: {}", lit.has_bidi_control()));
+                diag.note(format!("has_zero_width: {}", lit.has_zero_width()));
+            },
+            _ => {},
+        });
+}
+
+fn check_node_at<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>, expr: ExprKind<'ast>) {
+    cx.emit_lint(TEST_LINT, stmt, "checking node at span").decorate(|diag| {
+        let span = expr.span();
+        let spans = [
+            ("expr", span.clone()),
+            ("start", span.with_end(span.start())),
+            ("end", span.with_start(span.end())),
+        ];
+        for (name, span) in spans {
+            let Some(node) = cx.node_at(&span) else {
+                diag.note(format!("{name}: no node"));
+                continue;
+            };
+            let (kind, node_span) = match node {
+                NodeId::Expr(id) => ("expr", cx.ast().expr(id).span().clone()),
+                NodeId::Stmt(id) => ("stmt", cx.ast().stmt(id).span().clone()),
+                NodeId::Item(id) => ("item", cx.ast().unwrap_item(id).span().clone()),
+                NodeId::Field(id) => ("field", cx.ast().unwrap_field(id).span().clone()),
+                NodeId::Variant(id) => ("variant", cx.ast().unwrap_variant(id).span().clone()),
+                _ => unreachable!(),
+            };
+            diag.span_note(format!("{name}: {kind}"), &node_span);
+        }
+    });
+}
+
+fn check_self_kind<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast FnItem<'ast>, ident: &'ast Ident<'ast>) {
+    if !ident.name().starts_with("check_self_kind") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking self kind").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!("self_kind: {:?}", item.self_kind()));
+    });
+}
+
+fn check_impl_trait<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast FnItem<'ast>, ident: &'ast Ident<'ast>) {
+    if !ident.name().starts_with("check_impl_trait") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking impl trait").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!("has_impl_trait_params: {}", item.has_impl_trait_params()));
+        diag.note(format!("returns_impl_trait: {}", item.returns_impl_trait()));
+        let tys = item.params().iter().map(FnParam::ty).chain(item.return_ty().copied());
+        for ty in tys {
+            if let marker_api::ast::TyKind::ImplTrait(impl_ty) = ty.peel_refs() {
+                diag.span_note(
+                    format!(
+                        "position: {:?}, bounds: {}",
+                        impl_ty.position(),
+                        impl_ty.trait_bounds().len()
+                    ),
+                    impl_ty.span(),
+                );
+            }
+        }
+    });
+}
+
+fn check_async_output<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast FnItem<'ast>, ident: &'ast Ident<'ast>) {
+    if !ident.name().starts_with("check_async_output") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking async output").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!(
+            "return_ty: {:?}",
+            item.return_ty().map(|ty| ty.span().snippet_or("..."))
+        ));
+        diag.note(format!("async_output_ty: {:#?}", item.async_output_ty()));
+    });
+}
+
+fn check_const_fn<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast FnItem<'ast>, ident: &'ast Ident<'ast>) {
+    if !ident.name().starts_with("check_const_fn") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking const fn").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!("could_be_const_fn: {}", cx.could_be_const_fn(item.id())));
+        if let Some(violation) = cx.const_fn_violation(item.id()) {
+            diag.span_note(format!("{:?}", violation.kind()), violation.span());
+        }
+    });
+}
+
+fn check_multipart_suggestion<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast FnItem<'ast>, ident: &'ast Ident<'ast>) {
+    if !ident.name().starts_with("multipart_suggestion") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking multipart suggestions")
+        .decorate(|diag| {
+            diag.span(ident.span());
+            let params = item
+                .params()
+                .iter()
+                .map(|param| (param.pat().span().clone(), "_".to_string()));
+            diag.multipart_suggestion(
+                "rename the function and ignore the parameters",
+                std::iter::once((ident.span().clone(), format!("{}_renamed", ident.name()))).chain(params),
+                Applicability::MachineApplicable,
+            );
+        });
+}
+
+fn check_bidi_span<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("check_bidi") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking bidi control").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!(
+            "contains_bidi_control: {}",
+            item.span().contains_bidi_control()
+        ));
+        diag.note(format!("contains_zero_width: {}", item.span().contains_zero_width()));
+    });
+}
+
+fn check_source_lines<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("check_source_lines") {
+        return;
+    }
+    let marker_api::span::SpanSource::File(file) = item.span().source() else {
+        return;
+    };
+
+    for (line, text, span) in cx.source_lines(file) {
+        let tab_indent = text.trim_start_matches(' ').starts_with('\t');
+        let trailing_whitespace = text.ends_with([' ', '\t']);
+        let too_long = text.len() > 60;
+        if tab_indent || trailing_whitespace || too_long {
+            cx.emit_lint(TEST_LINT, item, "checking source line").decorate(|diag| {
+                diag.span(&span);
+                diag.note(format!(
+                    "line: {line}, length: {}, tab_indent: {tab_indent}, trailing_whitespace: {trailing_whitespace}",
+                    text.len()
+                ));
+            });
+        }
+    }
+}
+
+fn check_expansion_origin<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(origin) = item.span().expansion_origin() else {
+        return;
+    };
+    // Only items generated by macros in the test file are checked
+    let mut root_call_site = origin.call_site();
+    while let Some(expn) = root_call_site.expansion_origin() {
+        root_call_site = expn.call_site();
+    }
+    let marker_api::span::SpanSource::File(file) = root_call_site.source() else {
+        return;
+    };
+    if !file.file().ends_with("expansion_origin.rs") {
+        return;
+    }
+
+    cx.emit_lint(PRINT_SPAN_LINT, item, "checking expansion origin")
+        .decorate(|diag| {
+            diag.span(root_call_site);
+            let mut expn = Some(origin);
+            while let Some(info) = expn {
+                diag.span_note(
+                    format!("expanded by `{}` ({:?})", info.name(), info.kind()),
+                    info.call_site(),
+                );
+                expn = info.parent();
+            }
+        });
+}
+
+fn check_macro_def_site<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(origin) = item.span().expansion_origin() else {
+        return;
+    };
+    let marker_api::span::SpanSource::File(file) = origin.call_site().source() else {
+        return;
+    };
+    if !file.file().ends_with("include_paths.rs") {
+        return;
+    }
+
+    cx.emit_lint(
+        PRINT_SPAN_LINT,
+        item,
+        format!("checking the definition of `{}`", origin.name()),
+    )
+    .decorate(|diag| {
+        diag.span(origin.def_site());
+    });
+}
+
+fn check_supersession<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    let name = ident.name();
+    if !name.starts_with("superseded") {
+        return;
+    }
+
+    // The superseded lint is emitted first, to check that the order doesn't matter
+    cx.emit_lint(TEST_SUPERSEDED_LINT, item, "emitting the superseded lint")
+        .span(ident.span());
+    if name.contains("_and_test_lint") {
+        cx.emit_lint(TEST_LINT, item, "emitting the superseding lint")
+            .span(ident.span());
+    }
+}
+
+fn check_derives<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("CheckDerives") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking derives").decorate(|diag| {
+        diag.span(ident.span());
+        for derive in item.derives() {
+            diag.span_note(
+                format!("derive: {}, is_std: {}", derive.path(), derive.is_std()),
+                derive.span(),
+            );
+        }
+    });
+}
+
+fn check_transparent<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("CheckTransparent") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking transparent field")
+        .decorate(|diag| {
+            diag.span(ident.span());
+            match cx.transparent_inner_field(item.id()) {
+                Some(field) => diag.span_note("transparent inner field", field.span()),
+                None => diag.note("no transparent inner field"),
+            };
+        });
+}
+
+fn check_lifetime_bounds<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("CheckLifetimeBounds") && !ident.name().starts_with("check_lifetime_bounds") {
+        return;
+    }
+    let generics = match item {
+        ItemKind::Fn(item) => item.generics(),
+        ItemKind::Struct(item) => item.generics(),
+        _ => return,
+    };
+
+    cx.emit_lint(TEST_LINT, item, "checking lifetime bounds")
+        .decorate(|diag| {
+            diag.span(ident.span());
+            for bound in generics.lifetime_bounds() {
+                let longer = match bound.longer() {
+                    OutlivesSubject::Lifetime(lifetime) => lifetime.label().unwrap_or("'static").to_string(),
+                    OutlivesSubject::Ty(ty) => ty.span().snippet_or("..").to_string(),
+                    _ => unreachable!(),
+                };
+                diag.note(format!("`{longer}: {}`", bound.shorter().label().unwrap_or("'static")));
+            }
+        });
+}
+
+fn check_trait_bounds<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Fn(fn_item) = item else { return };
+    let Some(ident) = fn_item.ident() else { return };
+    if !ident.name().starts_with("check_trait_bounds") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking trait bounds").decorate(|diag| {
+        diag.span(ident.span());
+        for clause in fn_item.generics().clauses() {
+            let marker_api::ast::WhereClauseKind::Ty(clause) = clause else {
+                continue;
+            };
+            for bound in clause.bounds() {
+                if let marker_api::ast::TyParamBound::TraitBound(bound) = bound {
+                    diag.note(format!(
+                        "`{}: {}` with {} generic args",
+                        clause.ty().span().snippet_or(".."),
+                        bound.span().snippet_or(".."),
+                        bound.trait_ref().generics().args().len()
+                    ));
+                }
+            }
+        }
+    });
+}
+
+fn check_derivable<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Impl(impl_item) = item else { return };
+    if !impl_item.is_trait_impl() || !impl_item.ty().span().snippet_or("").starts_with("CheckDerivable") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking derivable impl")
+        .decorate(|diag| {
+            diag.span(impl_item.ty().span());
+            diag.note(format!("impl_is_derivable: {:?}", cx.impl_is_derivable(item.id())));
+        });
+}
+
+fn check_auto_trait_impl<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Impl(impl_item) = item else { return };
+    let Some(trait_ref) = impl_item.trait_ref() else { return };
+    if !impl_item.ty().span().snippet_or("").starts_with("CheckAutoTrait") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking auto trait impl")
+        .decorate(|diag| {
+            diag.span(impl_item.ty().span());
+            diag.span_label("trait", trait_ref.span());
+            diag.note(format!("is_unsafe: {}", impl_item.is_unsafe()));
+            diag.note(format!("is_negated: {}", impl_item.is_negated()));
+            diag.note(format!("impl_is_auto_trait: {:?}", cx.impl_is_auto_trait(item.id())));
+        });
+}
+
+fn check_name_case<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident
+        .name()
+        .to_lowercase()
+        .replace('_', "")
+        .starts_with("checknamecase")
+    {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking name case").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!("name_case: {:?}", item.name_case()));
+        for style in [
+            CaseStyle::SnakeCase,
+            CaseStyle::ScreamingSnakeCase,
+            CaseStyle::PascalCase,
+        ] {
+            diag.note(format!(
+                "{style:?}: matches: {}, suggestion: {}",
+                style.matches(ident.name()),
+                cx.suggest_rename(ident.name(), style)
+            ));
+        }
+    });
+}
+
+fn check_param_usage<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("CheckParamUsage") && !ident.name().starts_with("check_param_usage") {
+        return;
+    }
+    let generics = match item {
+        ItemKind::Fn(item) => item.generics(),
+        ItemKind::Struct(item) => item.generics(),
+        ItemKind::Enum(item) => item.generics(),
+        ItemKind::TyAlias(item) => item.generics(),
+        _ => return,
+    };
+
+    cx.emit_lint(TEST_LINT, item, "checking param usage").decorate(|diag| {
+        diag.span(ident.span());
+        for param in generics.params() {
+            if let Some(span) = param.span() {
+                diag.span_note(format!("used: {}", cx.is_param_used(param, item.id())), span);
+            }
+        }
+    });
+}
+
+fn check_doc_examples<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("CheckDocExamples") && !ident.name().starts_with("check_doc_examples") {
+        return;
+    }
+
+    for example in item.doc_examples() {
+        cx.emit_lint(TEST_LINT, item, "checking doc example").decorate(|diag| {
+            diag.span(example.span());
+            diag.note(format!("lang: {:?}, is_rust: {}", example.lang(), example.is_rust()));
+            diag.note(format!("code: {:?}", example.code()));
+        });
+    }
+}
+
+fn check_used_in_crate<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("CheckUsed") && !ident.name().starts_with("check_used") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking item usage").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!("is_used_in_crate: {}", cx.is_used_in_crate(item.id())));
+    });
+}
+
+fn check_ty_alias<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::TyAlias(alias) = item else { return };
+    let Some(ident) = item.ident() else { return };
+    if !ident.name().starts_with("NormalizeAlias") {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "checking type alias").decorate(|diag| {
+        diag.span(ident.span());
+        let sem_ty = alias.aliased_sem_ty();
+        diag.note(format!("aliased_sem_ty: {sem_ty:#?}"));
+        diag.note(format!("normalized: {:#?}", sem_ty.map(|ty| cx.normalize_alias(ty))));
+    });
+}
+
+fn print_meta_items<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    fn print<'ast>(diag: &mut marker_api::diagnostic::DiagnosticBuilder<'ast>, items: &[MetaItem<'ast>], depth: usize) {
+        for meta in items {
+            let kind = match meta.kind() {
+                MetaItemKind::Word => "word",
+                MetaItemKind::NameValue => "name value",
+                MetaItemKind::List => "list",
+                MetaItemKind::Lit => "literal",
+                _ => unreachable!(),
+            };
+            diag.span_note(
+                format!(
+                    "{:indent$}`{}`: {kind} (value: {:?})",
+                    "",
+                    meta.path().collect::<Vec<_>>().join("::"),
+                    meta.value(),
+                    indent = depth * 4
+                ),
+                meta.span(),
+            );
+            print(diag, meta.list(), depth + 1);
+        }
+    }
+
+    if !matches!(item.ident().map(marker_api::span::Ident::name), Some(name) if name.starts_with("meta_items")) {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "printing meta items").decorate(|diag| {
+        diag.span(item.ident().unwrap().span());
+        for attr in item.attrs() {
+            let path: Vec<_> = attr.path().iter().map(marker_api::span::Ident::name).collect();
+            match attr.meta_items() {
+                Some(items) => {
+                    diag.note(format!("`{}`", path.join("::")));
+                    print(diag, items, 1);
+                },
+                None => {
+                    diag.note(format!("`{}`: no meta items", path.join("::")));
+                },
+            }
+        }
+    });
+}
+
+fn print_item_attrs<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    if !matches!(
+        item.ident().map(marker_api::span::Ident::name),
+        Some(name) if name.starts_with("PrintAttrs") || name.starts_with("print_attrs")
+    ) {
+        return;
+    }
+
+    cx.emit_lint(TEST_LINT, item, "printing item attributes")
+        .decorate(|diag| {
+            diag.span(item.ident().unwrap().span());
+            for attr in item.attrs() {
+                let path: Vec<_> = attr.path().iter().map(marker_api::span::Ident::name).collect();
+                diag.note(format!(
+                    "{:?} `{}` (doc comment: {:?})",
+                    attr.style(),
+                    path.join("::"),
+                    attr.doc_comment()
+                ));
+                for token in attr.tokens() {
+                    diag.note(format!(
+                        "    {:?} `{}` at `{}`",
+                        token.kind(),
+                        token.text(),
+                        token.span().snippet_or("")
+                    ));
+                }
+            }
+        });
+}
+
+fn check_object_safety<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Trait(trait_item) = item else { return };
+    let Some(ident) = trait_item.ident() else { return };
+    if !ident.name().starts_with("CheckObjectSafety") {
+        return;
+    }
+
+    let trait_id = cx.resolve_ty_ids(&format!("crate::{}", ident.name()))[0];
+    cx.emit_lint(TEST_LINT, item, "checking object safety")
+        .decorate(|diag| {
+            diag.span(ident.span());
+            diag.note(format!("is_object_safe: {}", cx.is_object_safe(trait_id)));
+            for violation in cx.object_safety_violations(trait_id).unwrap() {
+                let msg = format!("{:?} ({:?})", violation.kind(), violation.item_name());
+                match violation.span() {
+                    Some(span) => diag.span_note(msg, span),
+                    None => diag.note(msg),
+                };
+            }
+        });
+}
+
+fn check_trait_methods<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Trait(trait_item) = item else { return };
+    let Some(ident) = trait_item.ident() else { return };
+    if !ident.name().starts_with("CheckTraitMethods") {
+        return;
+    }
+
+    let names = |methods: &mut dyn Iterator<Item = &FnItem<'_>>| {
+        methods
+            .filter_map(|method| method.ident().map(|ident| ident.name().to_string()))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    cx.emit_lint(TEST_LINT, item, "checking trait methods")
+        .decorate(|diag| {
+            diag.span(ident.span());
+            diag.note(format!("required: [{}]", names(&mut trait_item.required_methods())));
+            diag.note(format!("provided: [{}]", names(&mut trait_item.provided_methods())));
+        });
+}
+
+fn check_supertraits<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Trait(trait_item) = item else { return };
+    let Some(ident) = trait_item.ident() else { return };
+    if !ident.name().starts_with("CheckSupertraits") {
+        return;
+    }
+
+    let supertraits = trait_item.all_supertraits();
+    cx.emit_lint(TEST_LINT, item, "checking supertraits").decorate(|diag| {
+        diag.span(ident.span());
+        diag.note(format!("supertraits: {}", trait_item.supertraits().len()));
+        diag.note(format!("all_supertraits: {}", supertraits.len()));
+        for path in [
+            "core::fmt::Display",
+            "core::fmt::Debug",
+            "core::error::Error",
+            "core::clone::Clone",
+        ] {
+            let requires = cx.resolve_ty_ids(path).iter().any(|id| supertraits.contains(id));
+            diag.note(format!("requires `{path}`: {requires}"));
+        }
+    });
+}
+
 fn check_static_item<'ast>(cx: &'ast MarkerContext<'ast>, item: &'ast StaticItem<'ast>) {
     if let Some(name) = item.ident() {
         let name = name.name();
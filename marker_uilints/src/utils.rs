@@ -1,5 +1,9 @@
 use marker_api::prelude::*;
-use marker_utils::visitor::BoolTraversable;
+use marker_utils::complexity::{cognitive_complexity, cyclomatic_complexity};
+use marker_utils::duplicates::find_duplicate_blocks;
+use marker_utils::stack::find_large_stack_values;
+use marker_utils::visitor::{traverse_item, BoolTraversable, ExprParent, Traversable, Visitor, VisitorScope};
+use std::ops::ControlFlow;
 
 marker_api::declare_lint! {
     /// # What it does
@@ -8,7 +12,53 @@ marker_api::declare_lint! {
     Warn,
 }
 
+marker_api::declare_lint! {
+    /// # What it does
+    /// Tests the [`marker_utils::duplicates::find_duplicate_blocks`] function.
+    TEST_FIND_DUPLICATE_BLOCKS,
+    Warn,
+}
+
+marker_api::declare_lint! {
+    /// # What it does
+    /// Tests the [`marker_utils::visitor::Traversable::for_each_expr_with_parent`] function.
+    TEST_FOR_EACH_EXPR_WITH_PARENT,
+    Warn,
+}
+
+marker_api::declare_lint! {
+    /// # What it does
+    /// Tests the functions of the [`marker_utils::complexity`] module.
+    TEST_COMPLEXITY,
+    Warn,
+}
+
+marker_api::declare_lint! {
+    /// # What it does
+    /// Tests the [`marker_utils::stack::find_large_stack_values`] function.
+    TEST_FIND_LARGE_STACK_VALUES,
+    Warn,
+}
+
+marker_api::declare_lint! {
+    /// # What it does
+    /// Tests the `visit_pat` and `visit_ty` methods of [`marker_utils::visitor::Visitor`].
+    TEST_VISIT_PAT_AND_TY,
+    Warn,
+}
+
 pub fn check_item<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    check_find_duplicate_blocks(cx, item);
+
+    if let ItemKind::Enum(enum_item) = item {
+        if enum_item
+            .ident()
+            .is_some_and(|ident| ident.name().starts_with("TestVisitPatAndTy"))
+        {
+            check_visit_pat_and_ty(cx, item);
+        }
+    }
+
     let ItemKind::Fn(fn_item) = item else { return };
     let Some(ident) = fn_item.ident() else { return };
 
@@ -25,4 +75,121 @@ pub fn check_item<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
             diag.span(ident.span());
         });
     }
+
+    if ident.name().starts_with("test_complexity") {
+        let body = cx.ast().body(fn_item.body_id().unwrap());
+        cx.emit_lint(
+            TEST_COMPLEXITY,
+            item,
+            format!(
+                "testing complexity -> cyclomatic: {}, cognitive: {}",
+                cyclomatic_complexity(cx, body),
+                cognitive_complexity(cx, body)
+            ),
+        )
+        .decorate(|diag| {
+            diag.span(ident.span());
+        });
+    }
+
+    if ident.name().starts_with("test_find_large_stack_values") {
+        let body = cx.ast().body(fn_item.body_id().unwrap());
+        for (expr, size) in find_large_stack_values(cx, body, 1024) {
+            cx.emit_lint(
+                TEST_FIND_LARGE_STACK_VALUES,
+                expr,
+                format!("testing `find_large_stack_values` -> {size} bytes"),
+            );
+        }
+    }
+
+    if ident.name().starts_with("test_visit_pat_and_ty") {
+        check_visit_pat_and_ty(cx, item);
+    }
+
+    if ident.name().starts_with("test_for_each_expr_with_parent") {
+        let body = cx.ast().body(fn_item.body_id().unwrap());
+        let mut found = vec![];
+        let _: Option<()> = body.for_each_expr_with_parent(cx, |expr, parent| {
+            if matches!(expr, ExprKind::BinaryOp(_) | ExprKind::Path(_)) {
+                let parent = match parent {
+                    Some(ExprParent::Expr(ExprKind::BinaryOp(op))) => format!("binary `{:?}` expression", op.kind()),
+                    Some(ExprParent::Expr(ExprKind::Block(_))) => "block expression".to_string(),
+                    Some(ExprParent::Expr(ExprKind::Call(_))) => "call expression".to_string(),
+                    Some(ExprParent::Expr(ExprKind::If(_))) => "if expression".to_string(),
+                    Some(ExprParent::Expr(_)) => "other expression".to_string(),
+                    Some(ExprParent::Stmt(StmtKind::Let(_))) => "let statement".to_string(),
+                    Some(_) => "other statement".to_string(),
+                    None => "none".to_string(),
+                };
+                found.push((expr.id(), parent));
+            }
+            ControlFlow::Continue(())
+        });
+
+        for (id, parent) in found {
+            cx.emit_lint(
+                TEST_FOR_EACH_EXPR_WITH_PARENT,
+                cx.ast().expr(id),
+                format!("testing `for_each_expr_with_parent` -> parent: {parent}"),
+            );
+        }
+    }
+}
+
+fn check_visit_pat_and_ty<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    /// Emits a lint for every visited pattern and type. The traversal stops at
+    /// the first `!` type, to test that the descent can be short-circuited.
+    struct PatTyVisitor {
+        item: ItemId,
+    }
+    impl Visitor<()> for PatTyVisitor {
+        fn scope(&self) -> VisitorScope {
+            VisitorScope::AllBodies
+        }
+
+        fn visit_pat<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, pat: PatKind<'ast>) -> ControlFlow<()> {
+            let item = cx.ast().item(self.item).unwrap();
+            cx.emit_lint(TEST_VISIT_PAT_AND_TY, item, "visiting pattern")
+                .decorate(|diag| {
+                    diag.span(pat.span());
+                });
+            ControlFlow::Continue(())
+        }
+
+        fn visit_ty<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, ty: ast::TyKind<'ast>) -> ControlFlow<()> {
+            let item = cx.ast().item(self.item).unwrap();
+            cx.emit_lint(TEST_VISIT_PAT_AND_TY, item, "visiting type")
+                .decorate(|diag| {
+                    diag.span(ty.span());
+                });
+            if matches!(ty, ast::TyKind::Never(_)) {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        }
+    }
+
+    let mut visitor = PatTyVisitor { item: item.id() };
+    let _ = traverse_item(cx, &mut visitor, item);
+}
+
+fn check_find_duplicate_blocks<'ast>(cx: &'ast MarkerContext<'ast>, item: ItemKind<'ast>) {
+    let ItemKind::Mod(mod_item) = item else { return };
+    let Some(ident) = mod_item.ident() else { return };
+
+    if ident.name().starts_with("test_find_duplicate_blocks") {
+        for (first, second) in find_duplicate_blocks(cx, item, 2) {
+            cx.emit_lint(
+                TEST_FIND_DUPLICATE_BLOCKS,
+                item,
+                "testing `find_duplicate_blocks` -> duplicate found",
+            )
+            .decorate(|diag| {
+                diag.span(&second);
+                diag.span_note("first occurrence here", &first);
+            });
+        }
+    }
 }
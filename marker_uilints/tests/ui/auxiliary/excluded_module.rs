@@ -0,0 +1,4 @@
+// This module is included by `include_paths.rs`. Diagnostics in this file are
+// dropped, as it doesn't match the `MARKER_INCLUDE_PATHS` pattern of the test.
+
+pub const FIND_ME_EXCLUDED: i32 = 0;
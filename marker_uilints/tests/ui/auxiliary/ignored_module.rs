@@ -0,0 +1,4 @@
+// This module is included by `ignore_paths.rs`. Diagnostics in this file are
+// suppressed by the `MARKER_IGNORE_PATHS` pattern of the test.
+
+pub const FIND_ME_IGNORED: i32 = 0;
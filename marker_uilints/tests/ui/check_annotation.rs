@@ -0,0 +1,20 @@
+#![allow(unused)]
+
+struct Wrapper<'a> {
+    value: &'a str,
+}
+
+fn main() {
+    let _annotation_1: Vec<i32> = Vec::<i32>::new();
+    let _annotation_2: Vec<i32> = Vec::new();
+    let _annotation_3: Vec<_> = vec![1u8];
+    let _annotation_4: &[i32] = &Vec::<i32>::new();
+    let _annotation_5: Box<dyn std::fmt::Debug> = Box::new(1);
+    let _annotation_6: u64 = 1;
+    let _annotation_7 = String::new();
+    let _annotation_8: Wrapper<'static> = Wrapper { value: "static" };
+    let _annotation_9: &str = &String::new();
+    let _annotation_10: u64 = 1u64;
+    let _annotation_11: String = "marker".into();
+    let _annotation_12: String = String::new().clone();
+}
@@ -0,0 +1,15 @@
+fn main() {
+    let _local_plain = 1;
+    let _local_typed: u32 = 2;
+    let _local_uninit: i64;
+    _local_uninit = 3;
+
+    let Some(_local_else) = Some(4) else {
+        return;
+    };
+
+    // Locals in nested blocks should also be checked
+    let _ = {
+        let _local_nested = 5;
+    };
+}
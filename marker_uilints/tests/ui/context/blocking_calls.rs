@@ -0,0 +1,46 @@
+#![allow(unused)]
+
+use std::sync::{Mutex, RwLock};
+use std::time::Duration;
+
+mod db {
+    pub fn query() -> u32 {
+        1
+    }
+}
+
+struct Client;
+
+impl Client {
+    fn send(&self) -> u32 {
+        2
+    }
+}
+
+fn sync_context(mutex: &Mutex<u32>) {
+    let _is_blocking_1 = std::thread::sleep(Duration::from_millis(1));
+    let _is_blocking_2 = mutex.lock();
+    let _is_blocking_3 = Mutex::lock(mutex);
+}
+
+async fn async_context(mutex: &Mutex<u32>, lock: &RwLock<u32>, client: &Client) {
+    let _is_blocking_4 = std::fs::read_to_string("file.txt");
+    let _is_blocking_5 = std::fs::File::open("file.txt");
+    let _is_blocking_6 = mutex.lock();
+    let _is_blocking_7 = lock.read();
+    let _is_blocking_8 = db::query();
+    let _is_blocking_9 = client.send();
+    let _is_blocking_10 = Duration::from_millis(1);
+
+    let closure = || {
+        let _is_blocking_11 = std::thread::sleep(Duration::from_millis(1));
+    };
+}
+
+fn async_block() {
+    let _ = async {
+        let _is_blocking_12 = std::thread::sleep(Duration::from_millis(1));
+    };
+}
+
+fn main() {}
@@ -0,0 +1,4 @@
+//! check_crate_attrs: This crate is missing `#![forbid(unsafe_code)]`
+#![allow(unused)]
+
+fn main() {}
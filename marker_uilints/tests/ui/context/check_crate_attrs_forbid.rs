@@ -0,0 +1,4 @@
+//! check_crate_attrs: This crate has `#![forbid(unsafe_code)]`
+#![forbid(unsafe_code)]
+
+fn main() {}
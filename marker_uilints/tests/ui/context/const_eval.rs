@@ -0,0 +1,26 @@
+#![feature(adt_const_params)]
+#![allow(incomplete_features)]
+
+struct Wrapper<const N: usize>;
+struct Flag<const B: bool>;
+struct Letter<const C: char>;
+struct Name<const S: &'static str>;
+struct Signed<const I: i8>;
+
+const LEN: usize = 3;
+
+fn generic<const N: usize>() {
+    let _const_eval_generic: [u8; N] = [0; N];
+}
+
+fn main() {
+    let _const_eval_sum = [0u8; 2 + 2];
+    let _const_eval_const_item: [u8; LEN * 2] = [0; LEN * 2];
+    let _const_eval_max: Wrapper<{ usize::MAX }> = Wrapper;
+    let _const_eval_bool: Flag<{ 1 < 2 }> = Flag;
+    let _const_eval_char: Letter<{ 'a' }> = Letter;
+    let _const_eval_str: Name<{ "marker" }> = Name;
+    let _const_eval_signed: Signed<{ -1 - 1 }> = Signed;
+
+    generic::<1>();
+}
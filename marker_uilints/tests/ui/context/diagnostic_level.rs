@@ -0,0 +1,14 @@
+fn main() {
+    let _level_low = 1;
+    let _level_high = 100;
+}
+
+#[allow(marker::marker_uilints::test_lint)]
+fn allowed() {
+    let _level_high = 100;
+}
+
+#[deny(marker::marker_uilints::test_lint)]
+fn denied() {
+    let _level_low = 1;
+}
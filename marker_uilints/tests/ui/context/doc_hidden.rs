@@ -0,0 +1,28 @@
+#![feature(print_internals)]
+
+#[doc(hidden)]
+pub struct HiddenStruct;
+
+pub struct VisibleStruct;
+
+#[doc(hidden)]
+pub fn hidden_fn() -> VisibleStruct {
+    VisibleStruct
+}
+
+pub fn visible_fn() -> HiddenStruct {
+    HiddenStruct
+}
+
+#[doc(hidden)]
+pub mod hidden_mod {
+    pub fn inner_fn() {}
+}
+
+fn main() {
+    let _doc_hidden_fn = hidden_fn();
+    let _doc_hidden_visible_fn = visible_fn();
+    let _doc_hidden_in_hidden_mod = hidden_mod::inner_fn();
+    let _doc_hidden_extern_fn = std::io::_print(format_args!(""));
+    let _doc_hidden_extern_ty = String::new();
+}
@@ -0,0 +1,42 @@
+#![allow(unused)]
+
+struct Guard;
+
+impl Drop for Guard {
+    fn drop(&mut self) {}
+}
+
+struct HoldsGuard {
+    guard: Guard,
+}
+
+#[derive(Clone, Copy)]
+struct Plain {
+    value: u32,
+}
+
+fn generic<T, U: Copy>(t: T, u: U) {
+    let _drop_generic = t;
+    let _drop_copy_generic = u;
+}
+
+struct Wrapper<T>(T);
+
+impl<T: Copy> Wrapper<T> {
+    // The bound is declared by the impl and not by the method
+    fn method(&self) {
+        let _drop_parent_param = self.0;
+    }
+}
+
+fn main() {
+    let _drop_guard = Guard;
+    let _drop_holds_guard = HoldsGuard { guard: Guard };
+    let _drop_plain = Plain { value: 1 };
+    let _drop_string = String::new();
+    let _drop_vec = vec![1, 2, 3];
+    let _drop_tuple = (1_u32, Box::new(2_u8));
+    let _drop_int = 17_u64;
+    let _drop_ref = &Guard;
+    Wrapper(1).method();
+}
@@ -0,0 +1,57 @@
+#![allow(unreachable_code, unused_variables, clippy::diverging_sub_expression)]
+
+fn early_return(x: Result<u8, u8>) -> Result<u8, u8> {
+    let _diverges_if = if x.is_err() {
+        return Err(0);
+    };
+    let _diverges_value = x.is_ok();
+    x
+}
+
+fn diverging_branches(x: Option<u8>) -> u8 {
+    let _diverges_match = match x {
+        Some(_) => return 1,
+        None => panic!("no value"),
+    };
+}
+
+fn partial_match(x: Option<u8>) -> u8 {
+    let _diverges_partial = match x {
+        Some(value) => value,
+        None => return 0,
+    };
+    _diverges_partial
+}
+
+fn loops() {
+    for i in 0..10 {
+        let _diverges_continue = if i % 2 == 0 { continue } else { break };
+    }
+    let _diverges_loop = loop {};
+}
+
+fn labeled_blocks() {
+    let _diverges_labeled = 'a: {
+        break 'a 1;
+    };
+    'outer: for i in 0..10 {
+        let _diverges_labeled_continue = 'b: {
+            if i % 2 == 0 {
+                break 'b 2;
+            }
+            continue 'outer;
+        };
+        let _diverges_labeled_never = 'c: {
+            continue 'outer;
+        };
+    }
+}
+
+fn panics() {
+    let _diverges_block = {
+        let x = 1;
+        unreachable!("{x}");
+    };
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+macro_rules! log {
+    ($($arg:tt)*) => {
+        println!($($arg)*)
+    };
+}
+
+fn main() {
+    let name = "marker";
+    let version = 7;
+    let width = 4;
+
+    let _format_args_plain = format!("hello");
+    let _format_args_captured = format!("{name} v{version}");
+    let _format_args_explicit = format!("{} {:?} {0}", name, version + 1);
+    let _format_args_named = format!("{value:>8.2} {value:#?}", value = 1.5);
+    let _format_args_options = format!("{:-^width$} {:+08}", name, version);
+    let _format_args_print = println!("{name}");
+    let _format_args_nested = format!("{}", format!("{version}"));
+    let _format_args_macro = log!("{name}: {}", version);
+    let _format_args_none = name.len();
+}
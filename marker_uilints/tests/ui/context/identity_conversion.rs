@@ -0,0 +1,48 @@
+use std::path::{Path, PathBuf};
+
+fn generic<T>(value: T) -> T {
+    let _identity_conversion_generic: T = value.into();
+    _identity_conversion_generic
+}
+
+fn generic_into<T: Into<u64>>(value: T) -> u64 {
+    let _identity_conversion_generic_into = value.into();
+    _identity_conversion_generic_into
+}
+
+fn main() {
+    let string = String::from("marker");
+    let path = Path::new("marker");
+
+    // Identity conversions
+    let _identity_conversion_into: String = string.clone().into();
+    let _identity_conversion_from = String::from(string.clone());
+    let _identity_conversion_as_ref: &Path = path.as_ref();
+    let _identity_conversion_qualified = <u32 as Into<u32>>::into(1);
+
+    // Conversions between different types
+    let _identity_conversion_str_into: String = "marker".into();
+    let _identity_conversion_str_to_string = "marker".to_string();
+    let _identity_conversion_str_to_owned = "marker".to_owned();
+    let _identity_conversion_path_from = PathBuf::from(path);
+    let _identity_conversion_int_from = u64::from(1u32);
+    let _identity_conversion_as_ref_str: &str = string.as_ref();
+    let _identity_conversion_ref_into: String = (&string).into();
+
+    // Other calls, including clones
+    let _identity_conversion_to_string = string.to_string();
+    let _identity_conversion_to_owned = string.to_owned();
+    let _identity_conversion_clone = string.clone();
+    let _identity_conversion_len = string.len();
+    let _identity_conversion_to_owned_last = string.to_owned();
+
+    let _array = [0u8; {
+        let _convert = || {
+            let _identity_conversion_anon_const: usize = 1usize.into();
+        };
+        1
+    }];
+
+    generic(1);
+    generic_into(1u8);
+}
@@ -0,0 +1,19 @@
+#![allow(unused)]
+
+struct Local;
+
+trait LocalTrait {}
+
+impl LocalTrait for Local {}
+
+fn main() {
+    let _in_crate_1 = Local;
+    let _in_crate_2 = String::new();
+    let _in_crate_3 = &&Local;
+    let _in_crate_4 = 1_u32;
+    let _in_crate_5 = || 1;
+    let _in_crate_6 = &Local as &dyn LocalTrait;
+    let _in_crate_7 = &Local as &(dyn LocalTrait + Send);
+    let _in_crate_8 = &1 as &dyn std::fmt::Debug;
+    let _in_crate_9 = Some(Local);
+}
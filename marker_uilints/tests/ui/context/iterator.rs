@@ -0,0 +1,50 @@
+struct Counter(u32);
+
+impl Iterator for Counter {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        self.0 += 1;
+        Some(self.0)
+    }
+}
+
+fn generic<I: Iterator<Item = u8>, T>(iter: I, value: T) {
+    let _iterator_param = iter;
+    let _iterator_not_iter = value;
+}
+
+fn generic_assoc<I: Iterator>(iter: I) {
+    let _iterator_assoc = iter.skip(1);
+}
+
+struct Wrapper<T>(T);
+
+impl<T> Wrapper<T> {
+    // The bound is declared by the method and not by the impl of `T`
+    fn method_bound(self)
+    where
+        T: Iterator,
+    {
+        let _iterator_method_bound = self.0;
+    }
+}
+
+fn main() {
+    let data = vec![1_u8, 2, 3];
+    let _iterator_vec = data.clone();
+    let _iterator_slice_iter = data.iter();
+    let _iterator_into_iter = data.clone().into_iter();
+    let _iterator_filter = data.iter().filter(|x| **x > 1);
+    let _iterator_map = data.iter().map(|x| u16::from(*x));
+    let _iterator_collect: Vec<u8> = data.iter().copied().collect();
+    let _iterator_next = data.iter().next();
+    let _iterator_custom = Counter(0);
+    let _iterator_custom_take = Counter(0).take(3);
+    let _iterator_range = 0..10_i32;
+    let _iterator_path_map = Iterator::map(data.iter(), |x| u16::from(*x));
+    let _iterator_path_count = Iterator::count(data.iter());
+
+    generic(data.into_iter(), 1);
+    generic_assoc(Counter(0));
+}
@@ -0,0 +1,50 @@
+fn consume<T>(_: T) {}
+
+struct Wrapper<'a>(&'a String);
+
+fn main() {
+    let a = String::from("a");
+    let _last_use_redundant = a.clone();
+
+    let b = String::from("b");
+    let _last_use_used_later = b.clone();
+    consume(b);
+
+    let c = String::from("c");
+    let c_ref = &c;
+    let _last_use_borrowed = c.clone();
+    consume(c_ref);
+
+    let d = String::from("d");
+    let d_wrapper = Wrapper(&d);
+    let _last_use_borrowed_in_struct = d.clone();
+    consume(d_wrapper);
+
+    let e = vec![1, 2, 3];
+    let _len = e.len();
+    let _last_use_after_borrow = e.clone();
+
+    let f = String::from("f");
+    for _ in 0..3 {
+        let _last_use_in_loop = f.clone();
+    }
+
+    let g = String::from("g");
+    let _last_use_move = g;
+
+    let h = String::from("h");
+    let _closure = || {
+        let _last_use_in_closure = h.clone();
+    };
+
+    let i = String::from("i");
+    if i.is_empty() {
+        let _last_use_branch = i.clone();
+    } else {
+        consume(i);
+    }
+}
+
+fn param(x: String) {
+    let _last_use_param = x.clone();
+}
@@ -0,0 +1,15 @@
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    let _node_at_binary = point.x + point.y;
+    let _node_at_block = {
+        let value = 3;
+        value * 2
+    };
+    let _node_at_for = for i in 0..3 {};
+    let _node_at_closure = |a: u32| a + 1;
+}
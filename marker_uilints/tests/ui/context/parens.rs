@@ -0,0 +1,17 @@
+fn main() {
+    let a: i32 = 1;
+    let b = 2;
+    let c = 3;
+    let x = 1.5f32;
+
+    let _parens_1 = a * b + c;
+    let _parens_2 = a * (b + c);
+    let _parens_3 = a - (b - c);
+    let _parens_4 = (a - b) - c;
+    let _parens_5 = -(a + b);
+    let _parens_6 = !a;
+    let _parens_7 = x as i32;
+    let _parens_8 = (a < b) == (b < c);
+    let _parens_9 = a.pow(2);
+    let _parens_10 = (a | b) & c;
+}
@@ -0,0 +1,40 @@
+fn side_effect() -> bool {
+    true
+}
+
+fn main() {
+    let x = true;
+    let a = false;
+    let b = true;
+    let (i, j) = (1, 2);
+    let (f, g) = (1.0, 2.0);
+
+    // Literal comparisons
+    let _simplify_bool_eq_true = x == true;
+    let _simplify_bool_eq_false = x == false;
+    let _simplify_bool_ne_false = false != x;
+    let _simplify_bool_lit_lit = true == false;
+
+    // De Morgan
+    let _simplify_bool_not_and = !(a && b);
+    let _simplify_bool_not_or = !(a || b && x);
+    let _simplify_bool_not_cmp = !(i < j || i == j);
+    let _simplify_bool_not_float = !(f < g);
+    let _simplify_bool_double_not = !!x;
+
+    // Precedence
+    let _simplify_bool_nested = (a || b) && x == true;
+    let _simplify_bool_not_nested = !((a || b) && x);
+    let _simplify_bool_cast = (i as u8 == 1) == false;
+    let _simplify_bool_bitwise = (i & 1 == 0) != true;
+
+    // Constants and side effects
+    let _simplify_bool_and_true = side_effect() && true;
+    let _simplify_bool_and_false = side_effect() && false;
+    let _simplify_bool_false_and = false && side_effect();
+    let _simplify_bool_or_false = false || x;
+
+    // Nothing to simplify
+    let _simplify_bool_simple = a && !b;
+    let _simplify_bool_int = !i == 3;
+}
@@ -0,0 +1,10 @@
+// This file uses CRLF line endings
+
+pub fn check_source_lines() {
+	let _tab = 1;
+    let _trailing = 2;    
+    let _fits = "Sixty characters, without the line ending";
+    let _long = "This line is longer than sixty characters, it's flagged";
+}
+
+fn main() {}
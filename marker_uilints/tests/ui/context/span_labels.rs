@@ -0,0 +1,9 @@
+fn main() {
+    let first = 1;
+    let second = 2;
+
+    let _labels = (
+        first,
+        second,
+    );
+}
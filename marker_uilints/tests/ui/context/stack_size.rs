@@ -0,0 +1,31 @@
+#![allow(unused)]
+
+struct Small {
+    a: u32,
+    b: [u8; 4],
+}
+
+struct Large {
+    buffer: [u64; 256],
+}
+
+/// A recursive type, its size is only known due to the indirection
+struct List {
+    value: u32,
+    next: Option<Box<List>>,
+}
+
+fn generic<T: Default>(value: T) {
+    let _stack_size_generic = [value];
+    let _stack_size_generic_ref = &T::default();
+}
+
+fn main() {
+    let _stack_size_small = Small { a: 1, b: [0; 4] };
+    let _stack_size_large = Large { buffer: [0; 256] };
+    let _stack_size_array = [0_u8; 4096];
+    let _stack_size_nested = [[0_u16; 32]; 32];
+    let _stack_size_boxed = Box::new([0_u8; 4096]);
+    let _stack_size_list = List { value: 1, next: None };
+    let _stack_size_str = "duck";
+}
@@ -0,0 +1,38 @@
+#![allow(unused)]
+use std::num::ParseIntError;
+
+#[derive(Debug)]
+struct AppError;
+
+impl From<ParseIntError> for AppError {
+    fn from(_: ParseIntError) -> Self {
+        AppError
+    }
+}
+
+fn converted(text: &str) -> Result<u32, AppError> {
+    let _try_conversion_1 = text.parse::<u32>()?;
+    Ok(1)
+}
+
+fn same_error(text: &str) -> Result<u32, ParseIntError> {
+    let _try_conversion_2 = text.parse::<u32>()?;
+    Ok(1)
+}
+
+fn option(value: Option<u32>) -> Option<u32> {
+    let _try_conversion_3 = value?;
+    Some(1)
+}
+
+fn boxed(text: &str) -> Result<u32, Box<dyn std::error::Error>> {
+    let _try_conversion_4 = text.parse::<u32>()?;
+    Ok(1)
+}
+
+fn generic<E: From<ParseIntError>>(text: &str) -> Result<u32, E> {
+    let _try_conversion_5 = text.parse::<u32>()?;
+    Ok(1)
+}
+
+fn main() {}
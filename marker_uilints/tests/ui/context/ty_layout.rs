@@ -0,0 +1,47 @@
+use std::num::NonZeroU32;
+use std::ptr::NonNull;
+
+enum Full {
+    A,
+    B,
+}
+
+#[allow(dead_code)]
+enum WithPayload {
+    A(u8),
+    B(u16),
+}
+
+fn generic<T: Copy>(value: T, reference: &T, values: Vec<T>) {
+    let _layout_param = value;
+    let _layout_ref_to_param = reference;
+    let _layout_vec_of_param = values;
+}
+
+struct Wrapper<T>(T);
+
+impl<T: Copy> Wrapper<T> {
+    // The parameter is declared by the impl and not by the method
+    fn method(&self) {
+        let _layout_parent_param = &self.0;
+        let _layout_no_param = 1u16;
+    }
+}
+
+fn main() {
+    let _layout_u32 = 1u32;
+    let _layout_non_zero = NonZeroU32::new(1).unwrap();
+    let _layout_ref = &1u64;
+    let _layout_raw_ptr = &1u64 as *const u64;
+    let _layout_non_null = NonNull::<u64>::dangling();
+    let _layout_bool = true;
+    let _layout_unit = ();
+    let _layout_enum = Full::A;
+    let _layout_enum_payload = WithPayload::A(1);
+    let _layout_str: &str = "";
+    let _layout_box = Box::new([0u8; 16]);
+
+    generic(1, &1, vec![]);
+    let _ = (Full::B, WithPayload::B(0));
+    Wrapper(1).method();
+}
@@ -0,0 +1,47 @@
+#![allow(clippy::unnecessary_literal_unwrap, clippy::question_mark)]
+
+fn guarded(x: Option<u8>, y: Result<u8, ()>) {
+    if x.is_some() {
+        let _unwrap_guarded_option = x.unwrap();
+    }
+    if y.is_ok() && x.is_some() {
+        let _unwrap_guarded_result = y.expect("checked");
+    }
+    if x.is_none() {
+        return;
+    }
+    let _unwrap_early_return = x.unwrap();
+}
+
+fn unguarded(x: Option<u8>, y: Result<u8, ()>) {
+    let _unwrap_option = x.unwrap();
+    let _unwrap_result = y.expect("unchecked");
+    if x.is_none() {
+        let _unwrap_inverted = x.unwrap();
+    }
+    if !x.is_some() {
+        let _unwrap_negated = x.unwrap();
+    } else {
+        let _unwrap_negated_else = x.unwrap();
+    }
+}
+
+struct Unwrapper;
+
+impl Unwrapper {
+    fn unwrap(&self) -> u8 {
+        0
+    }
+}
+
+fn other_types(x: Unwrapper, y: Option<u8>) {
+    let _unwrap_custom = x.unwrap();
+    let _unwrap_other_method = y.unwrap_or(0);
+}
+
+#[test]
+fn test_fn() {
+    let _unwrap_in_test = Some(1).unwrap();
+}
+
+fn main() {}
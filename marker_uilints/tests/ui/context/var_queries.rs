@@ -0,0 +1,27 @@
+fn consume<T>(_: T) {}
+
+fn with_param(mut param: u32) {
+    param += 1;
+    let _var_param = param;
+}
+
+fn main() {
+    let mut counter = 0u8;
+    counter += 1;
+    let _var_counter = counter;
+
+    let text = String::from("marker");
+    let ref text_ref = text;
+    let _var_ref = text_ref;
+
+    let (a, (b, _)) = (1u16, (true, 'c'));
+    let _var_tuple = b;
+    consume(a);
+
+    let closure = |value: i64| {
+        let _var_closure = value;
+    };
+    closure(1);
+
+    let _var_not_a_var = with_param;
+}
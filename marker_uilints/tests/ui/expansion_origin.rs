@@ -0,0 +1,23 @@
+use std::alloc::System;
+
+macro_rules! inner_item {
+    ($name:ident) => {
+        pub struct $name;
+    };
+}
+
+macro_rules! outer_item {
+    ($name:ident) => {
+        inner_item!($name);
+    };
+}
+
+outer_item!(ExpansionOriginNested);
+
+#[derive(Clone)]
+struct ExpansionOriginDerived;
+
+#[global_allocator]
+static ALLOCATOR: System = System;
+
+fn main() {}
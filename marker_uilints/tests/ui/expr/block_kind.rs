@@ -0,0 +1,17 @@
+#![feature(inline_const)]
+
+async fn foo() {
+    let _block_default = { 1 };
+    let _block_labeled = 'label: {
+        if true {
+            break 'label 2;
+        }
+        1
+    };
+    let _block_unsafe = unsafe { 1 };
+    let _block_async = async { 1 };
+    let _block_async_move = async move { 1 };
+    let _block_const = const { 1 + 2 };
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+fn outer() {
+    let mut counter = 0;
+    let _closure_body_block = |step: i32| {
+        counter += step;
+        counter
+    };
+    let _closure_body_expr = |value: i32| value * 2;
+}
+
+fn main() {
+    let _closure_body_nested = || {
+        let inner = || 1;
+        inner() + 1
+    };
+}
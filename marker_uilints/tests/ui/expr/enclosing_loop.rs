@@ -0,0 +1,34 @@
+#![allow(unused, unreachable_code, clippy::never_loop)]
+
+fn main() {
+    let _enclosing_loop_none = 1;
+
+    loop {
+        let _enclosing_loop_loop = 1;
+        break;
+    }
+
+    let mut i = 0;
+    'outer: while i < 10 {
+        i += 1;
+        for j in 0..i {
+            let _enclosing_loop_for = j;
+            let _enclosing_loop_continue = continue;
+            let _enclosing_loop_labeled = continue 'outer;
+        }
+        let _enclosing_loop_while = i;
+    }
+
+    let _ = 'block: {
+        loop {
+            let _enclosing_loop_block = break 'block 2;
+        }
+    };
+
+    loop {
+        let closure = || {
+            let _enclosing_loop_closure = 1;
+        };
+        break;
+    }
+}
@@ -0,0 +1,29 @@
+#![feature(repr128)]
+#![allow(incomplete_features, unused)]
+
+enum Level {
+    Low = 1,
+    Medium,
+    High = 10,
+}
+
+#[repr(i8)]
+enum Signed {
+    Neg = -2,
+    Zero = 0,
+}
+
+#[repr(u128)]
+enum Large {
+    Small = 1,
+    Max = u128::MAX,
+}
+
+fn main() {
+    let _enum_cast_1 = Level::Medium as u32;
+    let _enum_cast_2 = Signed::Neg as i64;
+    let _enum_cast_3 = std::cmp::Ordering::Less as i8;
+    let _enum_cast_4 = 1u8 as u32;
+    let _enum_cast_5 = true as u8;
+    let _enum_cast_6 = Large::Max as u128;
+}
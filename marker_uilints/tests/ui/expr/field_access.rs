@@ -0,0 +1,25 @@
+#[derive(Clone, Copy)]
+struct Point {
+    x: u32,
+    y: u32,
+}
+
+struct Wrapper {
+    point: Point,
+}
+
+fn main() {
+    let point = Point { x: 1, y: 2 };
+    let boxed = Box::new(Point { x: 3, y: 4 });
+    let nested = &Box::new(Wrapper {
+        point: Point { x: 5, y: 6 },
+    });
+    let tuple = (7, 8);
+
+    let _field_access_direct = point.x;
+    let _field_access_auto_deref = boxed.y;
+    let _field_access_explicit_deref = (*boxed).x;
+    let _field_access_through_ref = nested.point;
+    let _field_access_nested = nested.point.y;
+    let _field_access_tuple = tuple.0;
+}
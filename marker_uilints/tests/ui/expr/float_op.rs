@@ -0,0 +1,12 @@
+#![allow(invalid_nan_comparisons)]
+
+fn main() {
+    let a = 1.0_f32;
+    let b = 2.0_f64;
+    let _float_op_eq = a == 1.5;
+    let _float_op_add = b + 0.5;
+    let _float_op_ref = &b < &2.0;
+    let _float_op_nan = f64::NAN == f64::NAN;
+    let _float_op_int = 1 + 2;
+    let _float_op_shift = 1_u8 << 2;
+}
@@ -0,0 +1,33 @@
+#![allow(irrefutable_let_patterns)]
+
+struct Wrapper(i32);
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+enum Single {
+    Only(i32),
+}
+
+fn main() {
+    let value = Some(1);
+    let wrapper = Wrapper(2);
+    let point = Point { x: 1, y: 2 };
+    let single = Single::Only(3);
+    let array = [1, 2, 3];
+    let slice: &[i32] = &array;
+
+    let _if_let_binding = if let x = value { 1 } else { 0 };
+    let _if_let_some = if let Some(x) = value { x } else { 0 };
+    let _if_let_tuple_struct = if let Wrapper(inner) = wrapper { inner } else { 0 };
+    let _if_let_struct = if let Point { x, .. } = &point { *x } else { 0 };
+    let _if_let_tuple = if let (_, y) = (1, value) { 1 } else { 0 };
+    let _if_let_tuple_lit = if let (1, y) = (1, value) { 1 } else { 0 };
+    let _if_let_single_variant = if let Single::Only(x) = single { x } else { 0 };
+    let _if_let_array = if let [first, ..] = array { first } else { 0 };
+    let _if_let_slice = if let [first, ..] = slice { *first } else { 0 };
+    let _if_let_slice_rest = if let [..] = slice { 1 } else { 0 };
+    let _if_let_or = if let Some(_) | None = value { 1 } else { 0 };
+}
@@ -0,0 +1,17 @@
+fn const_generic<const N: usize>(array: [u8; N]) {
+    let _index_generic_len = array[1];
+}
+
+fn main() {
+    let array = [1, 2, 3, 4];
+    let slice: &[i32] = &array;
+    let idx = 2;
+
+    let _index_const = array[3];
+    let _index_out_of_bounds = (&array)[7];
+    let _index_var = array[idx];
+    let _index_slice = slice[1];
+    let _index_range = &array[1..];
+
+    const_generic([1; 3]);
+}
@@ -0,0 +1,14 @@
+fn main() {
+    let _lit_chars_ascii = 'a';
+    let _lit_chars_control = '\n';
+    let _lit_chars_unicode = 'ß';
+    let _lit_chars_combining = '\u{301}';
+    let _lit_chars_rlo = '\u{202E}';
+    let _lit_chars_isolate = '\u{2066}';
+    let _lit_chars_zwsp = '\u{200B}';
+
+    let _lit_chars_str = "hello";
+    let _lit_chars_str_bidi = "access level: \u{202E}user\u{2066}";
+    let _lit_chars_str_zwj = "admin\u{200D}";
+    let _lit_chars_raw_str = r"\u{202E}";
+}
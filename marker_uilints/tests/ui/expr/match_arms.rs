@@ -0,0 +1,20 @@
+fn main() {
+    let value = Some(1);
+    let _match_option = match value {
+        Some(x) if x > 10 => 1,
+        Some(1 | 2) => 2,
+        x if x.is_none() => 3,
+        None | _ => 4,
+    };
+    let _match_binding = match value {
+        None => 1,
+        other @ _ => 2,
+    };
+    let _match_wildcard = match (value, 1) {
+        (Some(_), _) => 1,
+        (_, _) => 2,
+    };
+    let _match_mut_binding = match value {
+        mut x => 1,
+    };
+}
@@ -0,0 +1,71 @@
+#![allow(clippy::all)]
+
+struct Point {
+    x: i32,
+}
+
+fn main() {
+    let value = Some(3);
+
+    // `matches!`
+    let _rewrite_match_bool = match value {
+        Some(1) => true,
+        Some(2) => true,
+        _ => false,
+    };
+    let _rewrite_match_bool_negated = match value {
+        Some(x) if x > 2 => false,
+        _ => true,
+    };
+    let _rewrite_match_bool_block = match value {
+        None => { true },
+        _ => { false },
+    };
+    let _rewrite_match_bool_same_bindings = match (value, 1) {
+        (Some(x), 1) => true,
+        (None, x) => true,
+        _ => false,
+    };
+    let _rewrite_match_bool_other_bindings = match (value, 1) {
+        (Some(x), _) => true,
+        (None, y) => true,
+        _ => false,
+    };
+    let _rewrite_match_bool_guards = match value {
+        Some(x) if x > 2 => true,
+        Some(x) if x < 0 => true,
+        _ => false,
+    };
+    let _rewrite_match_bool_no_catch_all = match value {
+        Some(_) => true,
+        None => false,
+    };
+    let _rewrite_match_bool_same = match value {
+        Some(_) => true,
+        _ => true,
+    };
+
+    // `if let`
+    let _rewrite_match_if_let = match value {
+        Some(x) => println!("{x}"),
+        _ => {},
+    };
+    let _rewrite_match_if_let_block = match (Point { x: 1 }) {
+        Point { x: 0 } => {
+            println!("zero");
+        },
+        _other => (),
+    };
+    let _rewrite_match_if_let_guard = match value {
+        Some(x) if x > 2 => println!("{x}"),
+        _ => {},
+    };
+    let _rewrite_match_if_let_non_trivial = match value {
+        Some(x) => println!("{x}"),
+        None => println!("none"),
+    };
+    let _rewrite_match_if_let_swapped = match value {
+        None => {},
+        other => println!("{other:?}"),
+    };
+}
@@ -0,0 +1,45 @@
+trait Shape {
+    fn area(&self) -> u32;
+}
+
+trait Named: Shape {
+    fn name(&self) -> &str;
+}
+
+struct Square(u32);
+
+impl Square {
+    fn area(&self) -> u32 {
+        self.0 * self.0
+    }
+}
+
+impl Shape for Square {
+    fn area(&self) -> u32 {
+        Square::area(self)
+    }
+}
+
+impl Named for Square {
+    fn name(&self) -> &str {
+        "square"
+    }
+}
+
+fn generic<T: Shape>(shape: &T) {
+    let _dispatch_generic = shape.area();
+}
+
+fn main() {
+    let square = Square(2);
+    let _dispatch_inherent = square.area();
+    let _dispatch_trait_method = square.name();
+
+    let named: &dyn Named = &square;
+    let _dispatch_dyn = named.name();
+    let _dispatch_dyn_super = named.area();
+
+    let _dispatch_iter = [1, 2].iter().map(|x| x + 1);
+    let _dispatch_option = Some(1).map(|x| x + 1);
+    generic(&square);
+}
@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+fn main() {
+    let items = [1, 2, 3];
+
+    // Needless
+    let _needless_collect_into_iter = items.iter().map(|x| x * 2).collect::<Vec<_>>().into_iter();
+    let _needless_collect_for = for _ in items.iter().map(|x| x * 2).collect::<Vec<_>>() {};
+    let _needless_collect_var: Vec<_> = items.iter().copied().collect();
+    let _ = _needless_collect_var.into_iter().count();
+    let _needless_collect_var_for: Vec<_> = items.iter().copied().collect();
+    for _ in _needless_collect_var_for {}
+
+    // Not needless
+    let _needless_collect_iter = items.iter().map(|x| x * 2).collect::<Vec<_>>().iter().count();
+    let _needless_collect_var_iter: Vec<_> = items.iter().copied().collect();
+    for _ in _needless_collect_var_iter.iter() {}
+    let _needless_collect_loop: Vec<_> = items.iter().copied().collect();
+    loop {
+        for _ in _needless_collect_loop {}
+        break;
+    }
+    let _needless_collect_closure: Vec<_> = items.iter().copied().collect();
+    let _count = move || _needless_collect_closure.into_iter().count();
+    let _needless_collect_len = items.iter().map(|x| x * 2).collect::<Vec<_>>().len();
+    let _needless_collect_set = items.iter().collect::<HashSet<_>>().into_iter();
+    let _needless_collect_twice: Vec<_> = items.iter().copied().collect();
+    for _ in _needless_collect_twice.iter() {}
+    let _ = _needless_collect_twice.len();
+    let _needless_collect_unused: Vec<_> = items.iter().copied().collect();
+}
@@ -0,0 +1,25 @@
+use std::ops::Add;
+
+#[derive(Clone, Copy, PartialEq)]
+struct Meters(u32);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+fn main() {
+    let a = Meters(1);
+    let b = Meters(2);
+    let _overloaded_add = a + b;
+    let _overloaded_eq = a == b;
+    let _overloaded_string = String::new() + "duck";
+
+    let _overloaded_int = 1 + 2;
+    let _overloaded_float = 1.0 * 2.0;
+    let _overloaded_bool = true && false;
+    let _overloaded_cmp = 1 < 2;
+}
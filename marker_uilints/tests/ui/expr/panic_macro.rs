@@ -0,0 +1,46 @@
+#![warn(marker::marker_uilints::test_panic_macro)]
+
+fn check(value: u32, message: &str) {
+    if value == 0 {
+        panic!("TODO: handle zero");
+    }
+    if value == 1 {
+        panic!("{}", message);
+    }
+    if value == 2 {
+        panic!(r#"raw "message""#);
+    }
+    if value == 3 {
+        todo!();
+    }
+    if value == 4 {
+        todo!("support {}", value);
+    }
+    if value == 5 {
+        unimplemented!("escaped \"quotes\"\n");
+    }
+    if value == 6 {
+        unreachable!();
+    }
+
+    assert!(value > 10);
+    assert!(value > 10, "value too small: {}", value);
+    assert_eq!(value, 11, "expected eleven");
+    assert_ne!(value, 12);
+    debug_assert!(value < 100, "value too large");
+}
+
+macro_rules! my_panic {
+    () => {
+        panic!("custom")
+    };
+}
+
+fn custom() {
+    my_panic!();
+}
+
+fn main() {
+    check(11, "msg");
+    custom();
+}
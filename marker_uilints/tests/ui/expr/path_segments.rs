@@ -0,0 +1,16 @@
+use std::collections::{BTreeMap, HashMap};
+
+mod collections {
+    pub fn make<T: Default>() -> T {
+        T::default()
+    }
+}
+
+fn main() {
+    let _segments_turbofish = HashMap::<u32, u8>::new();
+    let _segments_plain: BTreeMap<u32, u8> = BTreeMap::new();
+    let _segments_full = std::collections::HashSet::<u8>::with_capacity(4);
+    let _segments_mod = collections::make::<String>();
+    let _segments_prim = u32::from_le_bytes([0; 4]);
+    let _segments_method = [1, 2].into_iter().collect::<Vec<u8>>();
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let mut counter = 0;
+    let text = String::from("Hello");
+
+    let _ty_fn = |x: u32| x + 1;
+    let _ty_fn_mut = |x: u32| counter += x;
+    let _ty_fn_once = move || text;
+}
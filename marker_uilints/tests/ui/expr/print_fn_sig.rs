@@ -0,0 +1,11 @@
+fn generic<T: Copy>(value: T) -> (T, T) {
+    (value, value)
+}
+
+extern "C" fn c_callback(_: u32) {}
+
+fn main() {
+    let _ty_fn_def = generic::<u8>;
+    let _ty_fn_ptr = c_callback as extern "C" fn(u32);
+    let _ty_unsafe_fn_ptr = generic::<char> as unsafe fn(char) -> (char, char);
+}
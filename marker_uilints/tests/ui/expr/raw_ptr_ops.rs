@@ -0,0 +1,34 @@
+#![allow(unused)]
+
+fn main() {
+    let value = 12_u32;
+    let address = 0x1234_usize;
+    let ptr = &value as *const u32;
+    let int_ptr = address as *const u32;
+    let mut mut_ptr = &value as *const u32;
+    mut_ptr = int_ptr;
+    let reference = &value;
+
+    // Casts
+    let _raw_ptr_cast_ref = &value as *const u32;
+    let _raw_ptr_cast_int = address as *const u32;
+    let _raw_ptr_cast_ptr = &value as *const u32 as *const u8;
+    let _raw_ptr_cast_var = ptr as *const u8;
+    let _raw_ptr_cast_unknown = mut_ptr as *const u8;
+
+    unsafe {
+        // Dereferences
+        let _raw_ptr_deref = *ptr;
+        let _raw_ptr_deref_int = *(address as *const u32);
+        let _raw_ptr_deref_int_var = *int_ptr;
+        let _raw_ptr_deref_ref = *reference;
+
+        // Arithmetic
+        let _raw_ptr_add = ptr.add(1);
+        let _raw_ptr_offset = (&value as *const u32).offset(-1);
+        let _raw_ptr_wrapping = (address as *const u8).wrapping_sub(2);
+
+        // Not a pointer operation
+        let _raw_ptr_other = value.wrapping_add(2);
+    }
+}
@@ -0,0 +1,11 @@
+//@rustc-env:MARKER_IGNORE_PATHS=tests/ui/auxiliary/**;;[invalid
+
+// The empty and invalid patterns are skipped by the driver. Only the valid
+// pattern suppresses the diagnostic in the auxiliary module.
+
+#[path = "auxiliary/ignored_module.rs"]
+mod ignored_module;
+
+const FIND_ME_KEPT: i32 = 0;
+
+fn main() {}
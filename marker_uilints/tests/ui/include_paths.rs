@@ -0,0 +1,22 @@
+//@rustc-env:MARKER_INCLUDE_PATHS=tests/ui/include_paths.rs
+
+#[path = "auxiliary/excluded_module.rs"]
+mod excluded_module;
+
+const FIND_ME_INCLUDED: i32 = 0;
+
+macro_rules! local_item {
+    () => {
+        struct LocalItem;
+    };
+}
+
+// Diagnostics at the definition of a local macro are included
+local_item!();
+
+// The definition of `Clone` is in the standard library. Its path might be
+// remapped and not map to a local file. The diagnostic is dropped either way.
+#[derive(Clone)]
+struct DerivedItem;
+
+fn main() {}
@@ -0,0 +1,35 @@
+use std::future::Future;
+
+async fn check_async_output_free() -> u8 {
+    16
+}
+
+async fn check_async_output_unit() {}
+
+fn check_async_output_sync() -> impl Future<Output = u8> {
+    async { 16 }
+}
+
+struct Foo;
+
+impl Foo {
+    async fn check_async_output_impl<'a>(&self, x: &'a u32) -> &'a u32 {
+        x
+    }
+}
+
+trait Trait {
+    async fn check_async_output_required(&self) -> String;
+
+    async fn check_async_output_provided<T: Default>(&self) -> T {
+        T::default()
+    }
+}
+
+impl Trait for Foo {
+    async fn check_async_output_required(&self) -> String {
+        String::new()
+    }
+}
+
+fn main() {}
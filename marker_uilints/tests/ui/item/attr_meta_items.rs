@@ -0,0 +1,14 @@
+#![allow(unused)]
+
+#[allow(dead_code, clippy::needless_return)]
+#[repr(C, align(8))]
+struct meta_items_struct;
+
+#[must_use = "a message"]
+#[marker::custom(bar, baz = "x", flag = true, nested(a, "lit", ::b::c),)]
+#[marker::invalid(a + b)]
+fn meta_items_fn() -> u32 {
+    0
+}
+
+fn main() {}
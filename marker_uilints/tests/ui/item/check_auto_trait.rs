@@ -0,0 +1,22 @@
+#![feature(negative_impls)]
+
+struct CheckAutoTraitPtr(*mut u8);
+
+unsafe impl Send for CheckAutoTraitPtr {}
+unsafe impl Sync for CheckAutoTraitPtr {}
+
+impl Clone for CheckAutoTraitPtr {
+    fn clone(&self) -> Self {
+        Self(self.0)
+    }
+}
+
+struct CheckAutoTraitNegative;
+
+impl !Send for CheckAutoTraitNegative {}
+
+unsafe trait CheckAutoTraitMarker {}
+
+unsafe impl CheckAutoTraitMarker for CheckAutoTraitNegative {}
+
+fn main() {}
@@ -0,0 +1,116 @@
+#![allow(unused)]
+
+struct CheckDerivableNamed {
+    name: String,
+    retries: u32,
+    enabled: bool,
+    label: &'static str,
+}
+
+impl Default for CheckDerivableNamed {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            retries: 0,
+            enabled: false,
+            label: "",
+        }
+    }
+}
+
+impl Clone for CheckDerivableNamed {
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            retries: self.retries,
+            enabled: self.enabled.clone(),
+            label: self.label,
+        }
+    }
+}
+
+struct CheckDerivableTuple(Vec<u8>, u64);
+
+impl Default for CheckDerivableTuple {
+    fn default() -> Self {
+        CheckDerivableTuple(Vec::default(), u64::default())
+    }
+}
+
+impl Clone for CheckDerivableTuple {
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), self.1)
+    }
+}
+
+struct CheckDerivableUnit;
+
+impl Default for CheckDerivableUnit {
+    fn default() -> Self {
+        Self
+    }
+}
+
+#[derive(Copy)]
+struct CheckDerivableCopy(u8);
+
+impl Clone for CheckDerivableCopy {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+enum CheckDerivableEnum {
+    A,
+    B(u32),
+}
+
+impl Default for CheckDerivableEnum {
+    fn default() -> Self {
+        Self::A
+    }
+}
+
+struct CheckDerivableNonDefault {
+    retries: u32,
+    name: String,
+}
+
+impl Default for CheckDerivableNonDefault {
+    fn default() -> Self {
+        Self {
+            retries: 3,
+            name: String::new(),
+        }
+    }
+}
+
+impl Clone for CheckDerivableNonDefault {
+    fn clone(&self) -> Self {
+        Self {
+            retries: self.retries,
+            name: String::new(),
+        }
+    }
+}
+
+struct CheckDerivableSwapped(u32, u32);
+
+impl Clone for CheckDerivableSwapped {
+    fn clone(&self) -> Self {
+        Self(self.1, self.0)
+    }
+}
+
+struct CheckDerivableGeneric<T>(T);
+
+impl<T: Default> Default for CheckDerivableGeneric<T> {
+    fn default() -> Self {
+        Self(T::default())
+    }
+}
+
+#[derive(Default, Clone)]
+struct CheckDerivableDerived(u32);
+
+fn main() {}
@@ -0,0 +1,37 @@
+#![allow(unused)]
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+struct CheckDerivesAll;
+
+#[derive(Clone)]
+#[derive(std::fmt::Debug)]
+enum CheckDerivesEnum {
+    A,
+    B,
+}
+
+#[derive(Clone, Copy)]
+union CheckDerivesUnion {
+    a: u32,
+}
+
+struct CheckDerivesNone;
+
+#[derive(Clone)]
+struct CheckDerivesGeneric<T>(T);
+
+impl Clone for CheckDerivesNone {
+    fn clone(&self) -> Self {
+        Self
+    }
+}
+
+mod nested {
+    #[derive(Debug)]
+    pub struct CheckDerivesNested;
+}
+
+fn main() {
+    #[derive(PartialEq)]
+    struct CheckDerivesLocal;
+}
@@ -0,0 +1,25 @@
+#![allow(unused)]
+
+use std::fmt::Debug;
+
+struct CheckGenericBoundStruct<'a, T: Clone + 'a, U: ?Sized>
+where
+    U: Debug,
+{
+    value: &'a T,
+    other: Box<U>,
+}
+
+trait CheckGenericBoundTrait: Clone + Send {
+    type CheckGenericBoundAssoc: Iterator<Item = u32>;
+}
+
+fn check_generic_bound_fn<T>(_: T, _: impl Copy)
+where
+    for<'a> &'a T: IntoIterator,
+{
+}
+
+impl<T: Default> CheckGenericBoundStruct<'_, T, str> where T: Clone {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#![allow(unused)]
+
+use std::fmt::Debug;
+
+fn check_lifetime_bounds_fn<'a, 'b: 'a, T: 'a + Debug>(a: &'a T, b: &'b str)
+where
+    'b: 'static,
+    Vec<T>: 'b,
+{
+}
+
+struct CheckLifetimeBoundsStruct<'a, 'b: 'a + 'static, T: ?Sized + 'b> {
+    a: &'a &'b T,
+}
+
+fn check_lifetime_bounds_none<T: Debug>(_: T) {}
+
+fn main() {}
@@ -0,0 +1,17 @@
+#![allow(dead_code, non_camel_case_types, non_upper_case_globals, non_snake_case)]
+
+struct CheckNameCaseHTTPServer;
+
+struct check_name_case_v1_2;
+
+enum CheckNameCase_Mixed {}
+
+const CHECK_NAME_CASE_MAX: u32 = 1;
+
+static checkNameCaseCount: u32 = 0;
+
+fn check_name_case_2d_point() {}
+
+mod check_name_case_module {}
+
+fn main() {}
@@ -0,0 +1,28 @@
+trait CheckObjectSafetySafe {
+    fn by_ref(&self) -> u32;
+    fn by_mut(&mut self);
+    fn sized_only() -> Self
+    where
+        Self: Sized;
+}
+
+trait CheckObjectSafetySized: Sized {}
+
+trait CheckObjectSafetySupertrait: PartialEq<Self> {}
+
+trait CheckObjectSafetyMethods {
+    const CONST: u32;
+
+    fn static_fn();
+    fn self_param(&self, other: Self);
+    fn self_return(&self) -> Self;
+    fn generic<T>(&self, t: T);
+}
+
+trait CheckObjectSafetyAssocTy {
+    type Item<'a>
+    where
+        Self: 'a;
+}
+
+fn main() {}
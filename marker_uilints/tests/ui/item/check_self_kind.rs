@@ -0,0 +1,24 @@
+use std::pin::Pin;
+use std::rc::Rc;
+
+struct Item;
+
+impl Item {
+    fn check_self_kind_none() {}
+    fn check_self_kind_value(self) {}
+    fn check_self_kind_mut_value(mut self) {}
+    fn check_self_kind_explicit_value(self: Self) {}
+    fn check_self_kind_ref(&self) {}
+    fn check_self_kind_explicit_ref(self: &Self) {}
+    fn check_self_kind_mut_ref(&mut self) {}
+    fn check_self_kind_box(self: Box<Self>) {}
+    fn check_self_kind_pin(self: Pin<&mut Self>) {}
+    fn check_self_kind_rc(self: Rc<Self>) {}
+}
+
+trait Trait {
+    fn check_self_kind_trait_ref(&self);
+    fn check_self_kind_trait_box(self: Box<Self>);
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use std::fmt::{Debug, Display};
+
+trait CheckSupertraitsNone {}
+
+trait CheckSupertraitsDisplay: Display {}
+
+trait CheckSupertraitsError: std::error::Error {}
+
+trait CheckSupertraitsWhere
+where
+    Self: Display + Clone,
+{
+}
+
+trait CheckSupertraitsNested: CheckSupertraitsDisplay + Debug {}
+
+fn main() {}
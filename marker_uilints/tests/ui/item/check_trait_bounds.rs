@@ -0,0 +1,31 @@
+#![allow(unused)]
+
+use std::future::Future;
+
+fn check_trait_bounds_fn<F>(f: F) -> u32
+where
+    F: Fn(u32) -> u32,
+{
+    f(1)
+}
+
+fn check_trait_bounds_fn_mut<F, T>(mut f: F)
+where
+    F: FnMut(&str) -> T + Send,
+    T: Clone,
+{
+    f("marker");
+}
+
+async fn check_trait_bounds_async<F>(f: F) -> u32
+where
+    F: FnOnce() -> u32,
+{
+    f()
+}
+
+fn check_trait_bounds_future<F: Future<Output = u32>>(f: F) -> F {
+    f
+}
+
+fn main() {}
@@ -0,0 +1,29 @@
+#![allow(unused)]
+
+use std::marker::PhantomData;
+
+#[repr(transparent)]
+struct CheckTransparentNewtype(f64);
+
+struct CheckTransparentNamed {
+    value: u32,
+    marker: PhantomData<String>,
+}
+
+struct CheckTransparentGeneric<T>((), T, [u8; 0]);
+
+struct CheckTransparentAligned(u16, [u32; 0]);
+
+struct CheckTransparentPair(u32, u32);
+
+struct CheckTransparentUnit;
+
+struct CheckTransparentEmpty {
+    marker: PhantomData<u8>,
+}
+
+enum CheckTransparentEnum {
+    A(u32),
+}
+
+fn main() {}
@@ -0,0 +1,114 @@
+#![allow(dead_code)]
+
+fn check_const_fn_simple(a: u32, b: u32) -> u32 {
+    if a > b { a - b } else { b - a }
+}
+
+const fn check_const_fn_already(a: u32) -> u32 {
+    a
+}
+
+fn check_const_fn_loop(n: u32) -> u32 {
+    let mut sum = 0;
+    let mut i = 0;
+    while i < n {
+        sum += i;
+        i += 1;
+    }
+    sum
+}
+
+fn check_const_fn_panic(a: Option<u32>) -> u32 {
+    match a {
+        Some(a) => a,
+        None => panic!("no value"),
+    }
+}
+
+fn check_const_fn_vec_new() -> Vec<u32> {
+    Vec::new()
+}
+
+fn check_const_fn_non_const_call(a: &str) -> String {
+    let len = a.len();
+    a.to_string() + &len.to_string()
+}
+
+fn check_const_fn_heap() -> Vec<u32> {
+    vec![1, 2, 3]
+}
+
+fn check_const_fn_box(a: u32) -> Box<u32> {
+    Box::new(a)
+}
+
+fn check_const_fn_drop(a: String) -> usize {
+    a.len()
+}
+
+fn check_const_fn_mut_ref(a: &mut u32) {
+    *a += 1;
+}
+
+fn check_const_fn_float(a: f32) -> f32 {
+    a * 2.0
+}
+
+fn check_const_fn_ptr_cast(a: &u32) -> usize {
+    a as *const u32 as usize
+}
+
+static VALUE: u32 = 8;
+
+fn check_const_fn_static() -> u32 {
+    VALUE
+}
+
+fn check_const_fn_closure(a: u32) -> u32 {
+    let f = |x: u32| x + 1;
+    f(a)
+}
+
+fn check_const_fn_trait_call(a: u32, b: u32) -> bool {
+    a.clone() == b
+}
+
+fn check_const_fn_for_loop(a: &[u32]) -> u32 {
+    let mut sum = 0;
+    for x in a {
+        sum += x;
+    }
+    sum
+}
+
+async fn check_const_fn_async() -> u32 {
+    1
+}
+
+extern "C" fn check_const_fn_extern() -> u32 {
+    1
+}
+
+struct Foo(u32);
+
+impl Foo {
+    fn check_const_fn_method(&self) -> u32 {
+        self.0
+    }
+}
+
+trait Bar {
+    fn check_const_fn_trait_method(&self) -> u32;
+}
+
+impl Bar for Foo {
+    fn check_const_fn_trait_method(&self) -> u32 {
+        self.0
+    }
+}
+
+fn check_const_fn_ctor() -> Foo {
+    Foo(1)
+}
+
+fn main() {}
@@ -0,0 +1,53 @@
+/// Returns the answer.
+///
+/// ```
+/// let answer = check_doc_examples_fn();
+/// assert_eq!(answer, 42);
+/// ```
+///
+/// Some text in between.
+///
+/// ```rust,no_run
+/// # fn main() {
+/// check_doc_examples_fn().unwrap();
+/// # }
+/// ```
+///
+/// ```text
+/// Not Rust code
+/// ```
+///
+/// ~~~
+/// ~~~
+pub fn check_doc_examples_fn() -> u32 {
+    42
+}
+
+pub struct CheckDocExamplesStruct;
+
+impl CheckDocExamplesStruct {
+    /// * A list with an example:
+    ///
+    ///   ```edition2021
+    ///   let value = 1;
+    ///
+    ///   let other = value;
+    ///   ```
+    pub fn check_doc_examples_method() {}
+}
+
+/**
+ * ```ignore
+ * let block = "comment";
+ * ```
+ */
+pub fn check_doc_examples_block() {}
+
+/// ```
+/// let unclosed = true;
+pub fn check_doc_examples_unclosed() {}
+
+#[doc = "```\nlet ignored = true;\n```"]
+pub fn check_doc_examples_attr() {}
+
+fn main() {}
@@ -0,0 +1,18 @@
+#![feature(extern_types)]
+#![allow(unused)]
+
+extern "C" {
+    type PrintMeOpaque;
+
+    fn find_me_create() -> *mut FindMeOpaque;
+}
+
+extern "C" {
+    pub type FindMeOpaque;
+}
+
+fn create() {
+    let _ty = unsafe { find_me_create() };
+}
+
+fn main() {}
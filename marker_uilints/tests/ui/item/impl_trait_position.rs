@@ -0,0 +1,22 @@
+#![allow(unused)]
+use std::fmt::Debug;
+
+fn check_impl_trait_arg(a: impl Clone + Send, b: &impl Debug) {}
+
+fn check_impl_trait_return() -> impl Iterator<Item = u32> {
+    0..10
+}
+
+fn check_impl_trait_generic<T: Clone>(a: T) -> T {
+    a
+}
+
+async fn check_impl_trait_async(a: impl Debug) -> u32 {
+    1
+}
+
+trait Trait {
+    fn check_impl_trait_in_trait(&self, a: impl Debug) -> impl Debug;
+}
+
+fn main() {}
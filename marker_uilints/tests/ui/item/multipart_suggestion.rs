@@ -0,0 +1,5 @@
+fn multipart_suggestion(a: u8, (b, c): (u8, u8)) {}
+
+fn multipart_suggestion_no_params() {}
+
+fn main() {}
@@ -0,0 +1,16 @@
+type Chain = u8;
+type NormalizeAliasChain = Chain;
+
+type NormalizeAliasProjection = <Vec<u16> as IntoIterator>::Item;
+
+type NormalizeAliasGeneric<T> = Option<T>;
+
+trait Trait {
+    type NormalizeAliasAssoc;
+}
+
+impl Trait for () {
+    type NormalizeAliasAssoc = <Option<u32> as IntoIterator>::Item;
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use std::marker::PhantomData;
+
+struct CheckParamUsageStruct<'a, T, U, V, const N: usize>
+where
+    T: Copy,
+{
+    a: &'a T,
+    phantom: PhantomData<U>,
+    array: [u8; N],
+    _v: PhantomData<fn() -> Option<V>>,
+}
+
+fn check_param_usage_arg<'a, T, U: Clone>(_: T)
+where
+    U: Copy,
+{
+}
+
+fn check_param_usage_body<T: Default, U>() {
+    let _ = T::default();
+}
+
+fn check_param_usage_bound<T, U: Iterator<Item = T>>(_: U) {}
+
+fn check_param_usage_lifetimes<'a, 'b: 'a, 'c>(_: &'b u8) {}
+
+fn check_param_usage_const<const N: usize, const M: usize>() -> usize {
+    N
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![allow(unused)]
+
+/// A doc comment
+#[must_use = "a message"]
+#[allow(dead_code, clippy::needless_return)]
+#[cfg_attr(all(), doc = "Conditional doc")]
+pub fn print_attrs_fn() -> u32 {
+    0
+}
+
+#[repr(C, align(8))]
+#[derive(Debug)]
+struct PrintAttrsStruct {
+    field: [u8; 4],
+}
+
+mod print_attrs_mod {
+    #![allow(non_camel_case_types)]
+}
+
+fn main() {}
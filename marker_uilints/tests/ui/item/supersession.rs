@@ -0,0 +1,12 @@
+fn superseded_alone() {}
+
+fn superseded_and_test_lint() {}
+
+#[allow(marker::marker_uilints::test_lint)]
+fn superseded_and_test_lint_allowed() {}
+
+mod superseded_mod {
+    fn superseded_and_test_lint_nested() {}
+}
+
+fn main() {}
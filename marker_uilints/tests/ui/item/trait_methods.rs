@@ -0,0 +1,16 @@
+trait CheckTraitMethods {
+    const REQUIRED_CONST: u8;
+    type Output;
+
+    fn required(&self) -> u8;
+    fn required_static();
+
+    fn provided(&self) -> u8 {
+        self.required()
+    }
+    fn provided_static() {}
+}
+
+trait CheckTraitMethodsEmpty {}
+
+fn main() {}
@@ -0,0 +1,77 @@
+#![allow(dead_code)]
+
+pub fn check_used_called() {}
+
+pub fn check_used_never() {}
+
+pub(crate) fn check_used_recursive(n: u32) -> u32 {
+    if n == 0 { 0 } else { check_used_recursive(n - 1) }
+}
+
+#[no_mangle]
+pub extern "C" fn check_used_no_mangle() {}
+
+pub fn check_used_as_value() {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheckUsedDerived;
+
+pub struct CheckUsedCtor(u32);
+
+pub enum CheckUsedEnum {
+    A,
+}
+
+pub struct CheckUsedImplOnly;
+
+impl CheckUsedImplOnly {
+    pub fn check_used_method(&self) {}
+
+    pub fn check_used_assoc() -> Self {
+        Self
+    }
+}
+
+pub struct CheckUsedSelfOnly(u32);
+
+impl CheckUsedSelfOnly {
+    pub fn new() -> CheckUsedSelfOnly {
+        CheckUsedSelfOnly(0)
+    }
+
+    pub fn check_used_copy(&self) -> CheckUsedSelfOnly {
+        CheckUsedSelfOnly::new()
+    }
+}
+
+pub trait CheckUsedTrait {
+    fn check_used_trait_fn(&self);
+}
+
+impl CheckUsedTrait for CheckUsedImplOnly {
+    fn check_used_trait_fn(&self) {}
+}
+
+impl std::fmt::Display for CheckUsedCtor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+macro_rules! call_in_macro {
+    () => {
+        check_used_in_macro()
+    };
+}
+
+fn check_used_in_macro() {}
+
+fn main() {
+    check_used_called();
+    let _ = check_used_as_value;
+    let _ = CheckUsedCtor(1);
+    let _ = CheckUsedEnum::A;
+    let value = CheckUsedImplOnly::check_used_assoc();
+    value.check_used_method();
+    call_in_macro!();
+}
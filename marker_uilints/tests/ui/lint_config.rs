@@ -0,0 +1,3 @@
+//@rustc-env:MARKER_LINT_CONFIGS={"marker_uilints":{"crate_note":"configured-by-the-test"}}
+
+fn main() {}
@@ -0,0 +1,6 @@
+//@rustc-env:MARKER_LINT_LEVELS=-Dmarker::marker_uilints::unknown_lint
+
+// Lints of the policy, which are not defined by the lint crate, should be
+// reported as unknown lints.
+
+fn main() {}
@@ -0,0 +1,6 @@
+//@rustc-env:CARGO_MANIFEST_DIR=tests/ui/manifest_lint
+//@rustc-env:MARKER_MANIFESTS=[{"manifest_dir":"tests/ui/manifest_lint","edition":"2021","features":[{"name":"default","enables":["serde"]}],"dependencies":[{"name":"serde","rename":null,"kind":"normal","optional":true},{"name":"pretty_assertions","rename":"pretty","kind":"dev","optional":false}]}]
+
+// The lints are emitted at the spans of the `manifest_lint/Cargo.toml` file
+
+fn main() {}
@@ -0,0 +1,6 @@
+//@rustc-env:CARGO_MANIFEST_DIR=tests/ui/manifest_lint
+//@rustc-env:MARKER_MANIFESTS=[{"manifest_dir":"tests/ui/manifest_lint","edition":"2024","features":[{"name":"default","enables":["serde"]}],"dependencies":[{"name":"serde","rename":null,"kind":"normal","optional":true}]}]
+
+// An unknown edition shouldn't hide the rest of the manifest
+
+fn main() {}
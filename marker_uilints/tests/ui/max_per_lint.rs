@@ -0,0 +1,9 @@
+//@rustc-env:MARKER_MAX_PER_LINT=2
+
+fn main() {
+    let local = 1;
+    let _var_first = local;
+    let _var_second = local;
+    let _var_third = local;
+    let _var_fourth = local;
+}
@@ -8,6 +8,10 @@ fn print_me() -> PrintMeConstGenerics<3> {
 
 impl<const N: usize> PrintMeConstGenerics<N> {}
 
+struct PrintMeConstDefault<T, const N: usize = 3, const FLAG: bool = { 1 > 0 }> {
+    data: [T; N],
+}
+
 fn main() {
     let _ty: PrintMeConstGenerics<3> = PrintMeConstGenerics { data: [1.0, 1.5, 2.0] };
 }
@@ -0,0 +1,7 @@
+//@rustc-env:MARKER_SHOW_LINT_SOURCE=1
+
+// Each diagnostic names the lint crate, which emitted it
+
+const FIND_ME_CONST: i32 = 0;
+
+fn main() {}
@@ -0,0 +1,17 @@
+use std::borrow::Cow;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+type BoxAlias<T> = Box<T>;
+
+fn main() {
+    let _smart_ptr_box = Box::new(1_u8);
+    let _smart_ptr_rc = Rc::new(1_u16);
+    let _smart_ptr_arc = Arc::new(1_u32);
+    let _smart_ptr_pin = Box::pin(1_u64);
+    let _smart_ptr_cow: Cow<'_, str> = Cow::Borrowed("marker");
+    let _smart_ptr_alias: BoxAlias<i8> = Box::new(1);
+    let _smart_ptr_vec = vec![1_u8];
+    let _smart_ptr_pin_ref = Pin::new(&mut 1_i16);
+}
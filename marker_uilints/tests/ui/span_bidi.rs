@@ -0,0 +1,29 @@
+#![allow(text_direction_codepoint_in_comment, text_direction_codepoint_in_literal)]
+
+fn check_bidi_clean() -> bool {
+    // A normal comment
+    let access_level = "user";
+    access_level == "user"
+}
+
+fn check_bidi_comment() -> bool {
+    let access_level = "user";
+    /* ‮ } ⁦ if access_level != "user" { ⁩ ⁦ // Check if admin */
+    access_level == "user"
+}
+
+fn check_bidi_str() -> bool {
+    let access_level = "user";
+    access_level != "user‮ ⁦// Check if admin⁩ ⁦"
+}
+
+fn check_bidi_escaped() -> &'static str {
+    "\u{202E}"
+}
+
+fn check_bidi_zero_width() -> bool {
+    let access_level = "user";
+    access_level == "user​"
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+macro_rules! number {
+    () => {
+        4
+    };
+}
+
+fn main() {
+    let _file_loc_simple = 1 + 2;
+    let _file_loc_unicode = ("🦆🦆", "über");
+    let _file_loc_multi_line = [
+        1,
+        2,
+    ];
+    let _file_loc_macro = number!();
+}
@@ -0,0 +1,23 @@
+use std::borrow::Cow;
+
+fn lifetimes<'a>(_static: &'static str, _named: &'a u8, _elided: &u16) -> Cow<'static, str> {
+    Cow::Borrowed("marker")
+}
+
+fn leak<'a>(value: Box<u32>) -> Cow<'a, u32> {
+    Cow::Borrowed(Box::leak(value))
+}
+
+fn early_bound<'a, T: 'a>(value: &'a T) -> Cow<'a, T>
+where
+    T: Clone,
+{
+    Cow::Borrowed(value)
+}
+
+fn main() {
+    let _static_ref_literal = "erased in expression types";
+    let _static_ref_fn = lifetimes;
+    let _static_ref_leak = leak;
+    let _static_ref_early_bound = early_bound::<u8>;
+}
@@ -0,0 +1,37 @@
+fn stmt_order(_: u32) {}
+
+fn main() {
+    let _stmt_order_first = 1;
+    stmt_order(2);
+
+    if _stmt_order_first == 1 {
+        let _stmt_order_if = 3;
+    } else {
+        let _stmt_order_else = 4;
+    }
+
+    match _stmt_order_first {
+        1 => {
+            stmt_order(5);
+        },
+        _ => {},
+    }
+
+    for _ in 0..1 {
+        let _stmt_order_loop = 6;
+    }
+
+    let _closure = || {
+        stmt_order(7);
+    };
+
+    {
+        let _stmt_order_block = 8;
+    }
+
+    fn stmt_order_item() {
+        let _stmt_order_in_item = 9;
+    }
+
+    let _stmt_order_last = 10;
+}
@@ -0,0 +1,15 @@
+macro_rules! sum {
+    ($a:expr, $b:expr) => {
+        $a + $b
+    };
+}
+
+fn main() {
+    let a = 1;
+    let b = 2;
+    let _suggest_replace = a + b;
+    let _suggest_invalid = a + b;
+    let _suggest_macro = sum!(a, b);
+    let value = String::new();
+    let _suggest_insert = value;
+}
@@ -0,0 +1,68 @@
+#![allow(unused)]
+
+fn test_complexity_linear(a: u32) -> u32 {
+    let b = a + 1;
+    b * 2
+}
+
+fn test_complexity_try(x: Option<u32>) -> Option<u32> {
+    let value = x?;
+    if value > 2 && value < 10 {
+        return Some(value);
+    }
+    match value {
+        0 => None,
+        1 => Some(2),
+        _ => Some(3),
+    }
+}
+
+fn test_complexity_loop(items: &[u32], limit: u32) -> u32 {
+    let mut sum = 0;
+    for item in items {
+        if *item > limit || *item == 0 {
+            continue;
+        } else {
+            sum += item;
+        }
+    }
+    sum
+}
+
+fn test_complexity_else_if(a: u32, b: bool, c: bool) -> u32 {
+    if a == 0 {
+        1
+    } else if a == 1 && b && c {
+        2
+    } else if b || c && a > 2 {
+        3
+    } else {
+        4
+    }
+}
+
+fn test_complexity_nested(grid: &[Vec<u32>]) -> Option<(usize, usize)> {
+    'outer: for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if row[x] == 0 {
+                break 'outer;
+            }
+            let find = |value: u32| if value == 1 { Some((x, y)) } else { None };
+            if let Some(pos) = find(row[x]) {
+                return Some(pos);
+            }
+            x += 1;
+        }
+    }
+    None
+}
+
+fn test_complexity_nested_item(a: u32) -> u32 {
+    fn inner(a: u32) -> u32 {
+        if a > 2 { a } else { 0 }
+    }
+    inner(a)
+}
+
+fn main() {}
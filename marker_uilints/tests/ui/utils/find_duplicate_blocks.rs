@@ -0,0 +1,58 @@
+#![allow(unused)]
+
+mod test_find_duplicate_blocks {
+    fn foo(a: u32) -> u32 {
+        a + 1
+    }
+
+    fn first(x: u32) -> u32 {
+        let y = foo(x) * 2;
+        let z = foo(y) + 1;
+        z
+    }
+
+    fn second(x: u32) -> u32 {
+        // The first two statements are identical to the ones in `first`
+        let y = foo(x) * 2;
+        let z = foo(y) + 1;
+        z * 2
+    }
+
+    fn different(x: u32) -> u32 {
+        // Different literals
+        let y = foo(x) * 3;
+        let z = foo(y) + 1;
+        z
+    }
+
+    fn repetition(mut x: u32) -> u32 {
+        // Overlapping repetitions in the same block are not reported
+        x += 1;
+        x += 1;
+        x += 1;
+        x
+    }
+
+    fn macros(x: u32) {
+        // Statements from macros are ignored
+        println!("{x}");
+        println!("{x}");
+    }
+}
+
+mod other {
+    fn first(x: u32) -> u32 {
+        let y = x * 2;
+        let z = y + 1;
+        z
+    }
+
+    fn second(x: u32) -> u32 {
+        // Not checked, the module is not named `test_find_duplicate_blocks`
+        let y = x * 2;
+        let z = y + 1;
+        z
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+struct Large {
+    buffer: [u8; 2048],
+}
+
+fn test_find_large_stack_values<T: Default>() {
+    let _small = [0_u8; 16];
+    let _large = Large { buffer: [0; 2048] };
+    let _array = [0_u64; 256];
+    let _nested = [[0_u8; 512]; 4];
+    let _boxed = Box::new([0_u8; 4096]);
+    let _generic = [T::default()];
+    let _closure = || {
+        let _in_closure = [0_u8; 2048];
+    };
+
+    fn nested_item() {
+        let _in_nested_item = [0_u8; 2048];
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+#![allow(unused)]
+
+fn foo(a: i32) -> i32 {
+    a
+}
+
+fn test_for_each_expr_with_parent(a: i32, b: i32) -> i32 {
+    let x = a * b + 1;
+    if x > 2 {
+        return foo(a - b);
+    }
+
+    // The discriminant of a nested item has no parent
+    enum Nested {
+        A = 1 + 2,
+    }
+
+    x
+}
+
+fn main() {}
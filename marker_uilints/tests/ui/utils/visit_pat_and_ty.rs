@@ -0,0 +1,33 @@
+#![allow(unused)]
+
+struct Wrapper<T> {
+    inner: Vec<T>,
+}
+
+enum TestVisitPatAndTyEnum {
+    Unit,
+    Tuple(u8, Option<i16>),
+    Named { field: [u32; 2] },
+}
+
+fn test_visit_pat_and_ty_sig((a, b): (u8, &str), c: [u16; 2]) -> Option<u32> {
+    let (x, _): (u32, Wrapper<fn(i8) -> bool>) = todo!();
+    let v @ 1..=3 = 2_u8 else { return None };
+    match Some(x) {
+        Some(0 | 1) | None => None,
+        Some(y) => Some(y as u32),
+    }
+}
+
+fn test_visit_pat_and_ty_closure() {
+    let mut z = 0;
+    let add = |n: i32, m| -> i32 { n + m };
+    (z, _) = (add(1, 2), 3);
+    for [first, .., last] in [[1, 2, 3]] {}
+}
+
+fn test_visit_pat_and_ty_break(first: u8) -> ! {
+    // The traversal is stopped by the `!` type, this isn't visited
+    let not_visited: u8 = first;
+    panic!()
+}
@@ -0,0 +1,224 @@
+//! This module contains metrics to measure the complexity of function bodies.
+//!
+//! The metrics can be used to warn about functions, which are hard to understand
+//! or test. The threshold should usually be configurable by users, via the
+//! [configuration](marker_api::export_lint_pass) of the lint crate:
+//!
+//! ```ignore
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     cognitive_complexity_threshold: u32,
+//! }
+//!
+//! // In the lint pass:
+//! let score = cognitive_complexity(cx, body);
+//! if score > self.config.cognitive_complexity_threshold {
+//!     cx.emit_lint(COGNITIVE_COMPLEXITY, item, format!("this function has a cognitive complexity of {score}"));
+//! }
+//! ```
+//!
+//! Closures are part of the body, which they're defined in. Bodies of nested
+//! items are not included, they should be measured separately.
+
+use std::collections::HashSet;
+use std::ops::ControlFlow;
+
+use marker_api::{
+    ast::{BinaryOpKind, Body},
+    prelude::*,
+};
+
+use crate::visitor::{traverse_body, Visitor, VisitorScope};
+
+/// Returns the cyclomatic complexity of the given body. This is the number of
+/// linearly independent paths through the body. It starts at `1` and increases
+/// by one for every:
+///
+/// * `if` expression, including `else if` and `if let`
+/// * `while` and `for` loop
+/// * match arm after the first one
+/// * `&&` and `||` operator
+/// * `?` operator
+///
+/// ```
+/// # fn check(x: Option<u32>) -> Option<u32> {
+/// // Complexity: 1
+/// let value = x?;  // +1 for `?`
+/// if value > 2 && value < 10 { // +1 for `if`, +1 for `&&`
+///     return Some(value);
+/// }
+/// match value {
+///     0 => None,
+///     1 => Some(2),    // +1
+///     _ => Some(3),    // +1
+/// }
+/// // Total: 6
+/// # }
+/// ```
+#[must_use]
+pub fn cyclomatic_complexity<'ast>(cx: &'ast MarkerContext<'ast>, body: &'ast Body<'ast>) -> u32 {
+    let mut visitor = CyclomaticVisitor {
+        score: 1,
+        item_depth: 0,
+    };
+    let _ = traverse_body(cx, &mut visitor, body);
+    visitor.score
+}
+
+/// Returns the cognitive complexity of the given body. This metric estimates how
+/// hard the code is to understand. It's based on the "Cognitive Complexity" white
+/// paper by Sonar. Structures which break the linear flow increase the
+/// score. Nested structures are weighted higher, since they're harder to follow:
+///
+/// * `if`, `match`, `while`, `for` and `loop`: `+1`, plus the current nesting level
+/// * `else if` and `else`: `+1`
+/// * A sequence of the same logical operator, like `a && b && c`: `+1`
+/// * `break` and `continue` with a label: `+1`
+///
+/// The nesting level is increased inside the structures of the first bullet point
+/// and inside closures. Match arms and the `?` operator don't increase the score,
+/// since they don't make the code harder to read.
+///
+/// ```
+/// # fn check(items: &[u32], limit: u32) -> u32 {
+/// let mut sum = 0;
+/// for item in items {                  // +1
+///     if *item > limit || *item == 0 { // +2 (nesting = 1), +1 for `||`
+///         continue;
+///     } else {                         // +1
+///         sum += item;
+///     }
+/// }
+/// sum
+/// // Total: 5
+/// # }
+/// ```
+#[must_use]
+pub fn cognitive_complexity<'ast>(cx: &'ast MarkerContext<'ast>, body: &'ast Body<'ast>) -> u32 {
+    let mut visitor = CognitiveVisitor::default();
+    let _ = traverse_body(cx, &mut visitor, body);
+    visitor.score
+}
+
+struct CyclomaticVisitor {
+    score: u32,
+    /// Nested items are traversed by [`VisitorScope::AllBodies`], but not counted.
+    item_depth: usize,
+}
+
+impl Visitor<()> for CyclomaticVisitor {
+    fn scope(&self) -> VisitorScope {
+        VisitorScope::AllBodies
+    }
+
+    fn visit_item<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<()> {
+        self.item_depth += 1;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_item_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<()> {
+        self.item_depth -= 1;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expr<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
+        if self.item_depth > 0 {
+            return ControlFlow::Continue(());
+        }
+
+        self.score += match expr {
+            ExprKind::If(_) | ExprKind::While(_) | ExprKind::For(_) | ExprKind::Try(_) => 1,
+            ExprKind::Match(match_expr) => u32::try_from(match_expr.arms().len().saturating_sub(1)).unwrap_or(u32::MAX),
+            ExprKind::BinaryOp(op) if matches!(op.kind(), BinaryOpKind::And | BinaryOpKind::Or) => 1,
+            _ => 0,
+        };
+        ControlFlow::Continue(())
+    }
+}
+
+#[derive(Default)]
+struct CognitiveVisitor {
+    score: u32,
+    nesting: u32,
+    /// Nested items are traversed by [`VisitorScope::AllBodies`], but not counted.
+    item_depth: usize,
+    /// The `if` expressions, which are the `else` branch of another `if`.
+    else_ifs: HashSet<ExprId>,
+}
+
+impl CognitiveVisitor {
+    /// Returns `true`, if the expression increases the nesting level for its children.
+    fn is_nesting(&self, expr: ExprKind<'_>) -> bool {
+        match expr {
+            ExprKind::If(_) => !self.else_ifs.contains(&expr.id()),
+            ExprKind::Match(_) | ExprKind::While(_) | ExprKind::For(_) | ExprKind::Loop(_) | ExprKind::Closure(_) => {
+                true
+            },
+            _ => false,
+        }
+    }
+}
+
+impl Visitor<()> for CognitiveVisitor {
+    fn scope(&self) -> VisitorScope {
+        VisitorScope::AllBodies
+    }
+
+    fn visit_item<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<()> {
+        self.item_depth += 1;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_item_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<()> {
+        self.item_depth -= 1;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expr<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
+        if self.item_depth > 0 {
+            return ControlFlow::Continue(());
+        }
+
+        self.score += match expr {
+            ExprKind::If(if_expr) => {
+                let increment = if self.else_ifs.contains(&expr.id()) {
+                    1
+                } else {
+                    1 + self.nesting
+                };
+                match if_expr.els() {
+                    Some(ExprKind::If(else_if)) => {
+                        self.else_ifs.insert(else_if.id());
+                        increment
+                    },
+                    Some(_) => increment + 1,
+                    None => increment,
+                }
+            },
+            ExprKind::Match(_) | ExprKind::While(_) | ExprKind::For(_) | ExprKind::Loop(_) => 1 + self.nesting,
+            ExprKind::BinaryOp(op) if matches!(op.kind(), BinaryOpKind::And | BinaryOpKind::Or) => {
+                // Only the first operator of a sequence is counted
+                match (op.left(), op.kind()) {
+                    (ExprKind::BinaryOp(left), BinaryOpKind::And) if matches!(left.kind(), BinaryOpKind::And) => 0,
+                    (ExprKind::BinaryOp(left), BinaryOpKind::Or) if matches!(left.kind(), BinaryOpKind::Or) => 0,
+                    _ => 1,
+                }
+            },
+            ExprKind::Break(brk) if brk.label().is_some() => 1,
+            ExprKind::Continue(cont) if cont.label().is_some() => 1,
+            _ => 0,
+        };
+
+        if self.is_nesting(expr) {
+            self.nesting += 1;
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expr_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
+        if self.item_depth == 0 && self.is_nesting(expr) {
+            self.nesting -= 1;
+        }
+        ControlFlow::Continue(())
+    }
+}
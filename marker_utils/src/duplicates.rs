@@ -0,0 +1,301 @@
+//! This module contains utilities to find structurally identical code, like
+//! copy-pasted statements.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::ops::ControlFlow;
+
+use marker_api::prelude::*;
+
+use crate::visitor::{traverse_stmt, Traversable, Visitor, VisitorScope};
+
+/// Finds sequences of at least `min_stmts` statements, which are structurally
+/// identical to another sequence inside the given node. Every returned pair
+/// contains the [`Span`]s of both statement sequences, starting at the first
+/// and ending at the last identical statement. A sequence, which occurs more
+/// than twice, is reported for every duplicate, together with its first
+/// occurrence.
+///
+/// Two statements are structurally identical, if they consist of the same
+/// expressions and use the same identifiers, literals and types. Formatting and
+/// comments are ignored. Statements from macro expansions are never reported.
+///
+/// ```
+/// # fn foo(a: u32) -> u32 { a }
+/// # fn bar(a: u32) -> u32 { a }
+/// fn first(x: u32) -> u32 {
+///     let y = foo(x) * 2;
+///     let z = bar(y) + 1;
+///     z
+/// }
+///
+/// fn second(x: u32) -> u32 {
+///     // These two statements are identical to the ones in `first`
+///     let y = foo(x) * 2;
+///     let z = bar(y) + 1;
+///     z * 2
+/// }
+/// ```
+///
+/// The entire crate can be checked, by passing the root module, like
+/// `find_duplicate_blocks(cx, ItemKind::Mod(krate.root_mod()), 3)`, in
+/// [`LintPass::check_crate`](marker_api::LintPass::check_crate). Bodies of
+/// nested items and closures are included in the search.
+///
+/// The statement sequences are grouped by their structure in a hash map. Every
+/// duplicate is only compared with the first sequence of its group, this avoids
+/// comparing every sequence with each other.
+pub fn find_duplicate_blocks<'ast, T: Traversable<'ast, ()>>(
+    cx: &'ast MarkerContext<'ast>,
+    node: T,
+    min_stmts: usize,
+) -> Vec<(Span<'ast>, Span<'ast>)> {
+    let min_stmts = min_stmts.max(1);
+
+    // Collect the statements and their structure of every block
+    let mut collector = BlockCollector::default();
+    let _ = node.traverse(cx, &mut collector);
+    let blocks: Vec<(&[StmtKind<'ast>], Vec<StmtTokens>)> = collector
+        .blocks
+        .into_iter()
+        .filter_map(|id| match cx.ast().expr(id) {
+            ExprKind::Block(block) if block.stmts().len() >= min_stmts => Some(block.stmts()),
+            _ => None,
+        })
+        .map(|stmts| (stmts, stmts.iter().map(|stmt| stmt_tokens(cx, *stmt)).collect()))
+        .collect();
+
+    // Group the windows of `min_stmts` statements by their structure. The
+    // windows are visited in order, the first window of a group is therefore
+    // its first occurrence.
+    let mut groups: HashMap<&[StmtTokens], Vec<(usize, usize)>> = HashMap::new();
+    for (block_index, (_, stmts)) in blocks.iter().enumerate() {
+        for start in 0..=(stmts.len() - min_stmts) {
+            let window = &stmts[start..start + min_stmts];
+            if window.iter().any(Option::is_none) {
+                continue;
+            }
+            groups.entry(window).or_default().push((block_index, start));
+        }
+    }
+
+    // Extend the duplicates to the longest identical sequence
+    let mut matches = vec![];
+    for group in groups.values() {
+        let Some((&(a_block, a_start), duplicates)) = group.split_first() else {
+            continue;
+        };
+        let a_stmts = &blocks[a_block].1;
+        for &(b_block, b_start) in duplicates {
+            let b_stmts = &blocks[b_block].1;
+            let is_same = |a: usize, b: usize| a_stmts[a].is_some() && a_stmts[a] == b_stmts[b];
+
+            // Only the longest sequence should be reported, which is the one
+            // starting with the first identical statement
+            if a_start > 0 && b_start > 0 && is_same(a_start - 1, b_start - 1) {
+                continue;
+            }
+
+            let mut len = min_stmts;
+            while a_start + len < a_stmts.len()
+                && b_start + len < b_stmts.len()
+                && is_same(a_start + len, b_start + len)
+            {
+                len += 1;
+            }
+
+            // Overlapping sequences in the same block are repetitions and not
+            // duplicated blocks
+            if a_block == b_block && a_start + len > b_start {
+                continue;
+            }
+
+            matches.push(((a_block, a_start), (b_block, b_start), len));
+        }
+    }
+
+    // The groups are unordered, sorting the matches makes the result deterministic
+    matches.sort_unstable();
+    let seq_span = |(block, start): (usize, usize), len: usize| {
+        let stmts = blocks[block].0;
+        stmts[start].span().with_end(stmts[start + len - 1].span().end())
+    };
+    matches
+        .into_iter()
+        .map(|(a, b, len)| (seq_span(a, len), seq_span(b, len)))
+        .collect()
+}
+
+#[derive(Default)]
+struct BlockCollector {
+    blocks: Vec<ExprId>,
+}
+
+impl Visitor<()> for BlockCollector {
+    fn scope(&self) -> VisitorScope {
+        VisitorScope::AllBodies
+    }
+
+    fn visit_expr<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
+        if let ExprKind::Block(block) = expr {
+            if !block.span().is_from_expansion() {
+                self.blocks.push(block.id());
+            }
+        }
+        ControlFlow::Continue(())
+    }
+}
+
+/// A token of the structure of a statement. The tokens of a statement are
+/// collected in pre-order, each node adds the number of its variable children.
+/// This makes the token sequence unambiguous.
+#[derive(Debug, PartialEq, Eq, Hash)]
+enum Token {
+    /// The hash of a node kind, like the discriminant of [`ExprKind`]
+    Kind(u64),
+    Count(usize),
+    /// The source code of leaf nodes, like literals, paths and patterns
+    Text(String),
+}
+
+/// The tokens of a statement or `None`, if the statement comes from a macro
+/// expansion and should not be compared.
+type StmtTokens = Option<Vec<Token>>;
+
+fn hash_of(value: impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn stmt_tokens<'ast>(cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) -> StmtTokens {
+    if stmt.span().is_from_expansion() {
+        return None;
+    }
+
+    let mut collector = TokenCollector::default();
+    let _ = traverse_stmt(cx, &mut collector, stmt);
+    Some(collector.tokens)
+}
+
+#[derive(Default)]
+struct TokenCollector {
+    tokens: Vec<Token>,
+}
+
+impl TokenCollector {
+    fn text(&mut self, span: &Span<'_>) {
+        self.tokens.push(Token::Text(span.snippet_or("").to_string()));
+    }
+
+    fn count(&mut self, count: usize) {
+        self.tokens.push(Token::Count(count));
+    }
+
+    fn label(&mut self, label: Option<&Ident<'_>>) {
+        self.tokens.push(Token::Text(label.map_or("", Ident::name).to_string()));
+    }
+}
+
+impl Visitor<()> for TokenCollector {
+    fn visit_stmt<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) -> ControlFlow<()> {
+        self.tokens.push(Token::Kind(hash_of(mem::discriminant(&stmt))));
+        match stmt {
+            StmtKind::Let(lets) => {
+                self.text(lets.pat().span());
+                match lets.ty() {
+                    Some(ty) => self.text(ty.span()),
+                    None => self.count(0),
+                }
+                self.count(usize::from(lets.init().is_some()));
+                self.count(usize::from(lets.els().is_some()));
+            },
+            // Nested items are not traversed with the default scope
+            StmtKind::Item(item) => self.text(item.item().span()),
+            _ => {},
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expr<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
+        self.tokens.push(Token::Kind(hash_of(mem::discriminant(&expr))));
+        match expr {
+            ExprKind::Block(e) => {
+                self.tokens.push(Token::Kind(hash_of(e.block_kind())));
+                self.label(e.label());
+                self.count(e.stmts().len());
+                self.count(usize::from(e.expr().is_some()));
+            },
+            ExprKind::UnaryOp(e) => self.tokens.push(Token::Kind(hash_of(mem::discriminant(&e.kind())))),
+            ExprKind::BinaryOp(e) => self.tokens.push(Token::Kind(hash_of(mem::discriminant(&e.kind())))),
+            ExprKind::Ref(e) => self.tokens.push(Token::Kind(hash_of(e.mutability()))),
+            ExprKind::Assign(e) => {
+                self.text(e.assignee().span());
+                self.tokens
+                    .push(Token::Kind(hash_of(e.op().map(|op| mem::discriminant(&op)))));
+            },
+            ExprKind::As(e) => self.text(e.cast_ty().span()),
+            ExprKind::Call(e) => self.count(e.args().len()),
+            ExprKind::Method(e) => {
+                self.text(e.method().ident().span());
+                self.count(e.args().len());
+            },
+            ExprKind::Array(e) => {
+                self.count(e.elements().len());
+                self.count(usize::from(e.len().is_some()));
+            },
+            ExprKind::Tuple(e) => self.count(e.elements().len()),
+            ExprKind::Ctor(e) => {
+                self.count(e.path().segments().len());
+                for seg in e.path().segments() {
+                    self.text(seg.ident().span());
+                }
+                self.count(e.fields().len());
+                for field in e.fields() {
+                    self.text(field.ident().span());
+                }
+                self.count(usize::from(e.base().is_some()));
+            },
+            ExprKind::Range(e) => {
+                self.count(usize::from(e.start().is_some()));
+                self.count(usize::from(e.end().is_some()));
+                self.count(usize::from(e.is_inclusive()));
+            },
+            ExprKind::Field(e) => self.text(e.field().span()),
+            ExprKind::If(e) => self.count(usize::from(e.els().is_some())),
+            ExprKind::Let(e) => self.text(e.pat().span()),
+            ExprKind::Match(e) => {
+                self.count(e.arms().len());
+                for arm in e.arms() {
+                    self.text(arm.pat().span());
+                    self.count(usize::from(arm.guard().is_some()));
+                }
+            },
+            ExprKind::Break(e) => {
+                self.label(e.label());
+                self.count(usize::from(e.expr().is_some()));
+            },
+            ExprKind::Return(e) => self.count(usize::from(e.expr().is_some())),
+            ExprKind::For(e) => {
+                self.label(e.label());
+                self.text(e.pat().span());
+            },
+            ExprKind::Loop(e) => self.label(e.label()),
+            ExprKind::While(e) => self.label(e.label()),
+            // The bodies of closures are not traversed with the default scope
+            ExprKind::Closure(_)
+            | ExprKind::IntLit(_)
+            | ExprKind::FloatLit(_)
+            | ExprKind::StrLit(_)
+            | ExprKind::CharLit(_)
+            | ExprKind::BoolLit(_)
+            | ExprKind::Path(_)
+            | ExprKind::Continue(_)
+            | ExprKind::Unstable(_) => self.text(expr.span()),
+            _ => {},
+        }
+        ControlFlow::Continue(())
+    }
+}
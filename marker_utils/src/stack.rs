@@ -0,0 +1,110 @@
+//! This module contains utilities to find large values on the stack, which
+//! should be moved to the heap with a [`Box`] or [`Vec`]. The size limit should
+//! usually be configurable by users, via the
+//! [configuration](marker_api::export_lint_pass) of the lint crate:
+//!
+//! ```ignore
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     max_stack_size: u64,
+//! }
+//!
+//! // In `LintPass::check_body`:
+//! for (expr, size) in find_large_stack_values(cx, body, self.config.max_stack_size) {
+//!     cx.emit_lint(LARGE_STACK_VALUE, expr, format!("this value occupies {size} bytes on the stack"))
+//!         .decorate(|diag| diag.help("consider allocating it on the heap with `Box`"));
+//! }
+//! ```
+
+use std::ops::ControlFlow;
+
+use marker_api::{ast::Body, prelude::*};
+
+use crate::visitor::{traverse_body, Visitor, VisitorScope};
+
+/// Returns the values inside the given body, which occupy more than `max_size`
+/// bytes on the stack, together with their size. The size is determined by
+/// [`MarkerContext::stack_size_of`].
+///
+/// Values are the initializers of `let` statements and array expressions, like
+/// `[0; 4096]`. An array, which initializes a `let` statement, is only reported
+/// once, while arrays nested inside a large initializer are reported as well.
+/// Arrays are also reported, if they're moved to the heap afterwards, like in
+/// `Box::new([0; 4096])`, since the array is created on the stack first.
+///
+/// Values with an unknown size, like values of generic types, are never
+/// reported. Closures are part of the body, bodies of nested items are not
+/// included.
+#[must_use]
+pub fn find_large_stack_values<'ast>(
+    cx: &'ast MarkerContext<'ast>,
+    body: &'ast Body<'ast>,
+    max_size: u64,
+) -> Vec<(ExprKind<'ast>, u64)> {
+    let mut visitor = StackValueVisitor {
+        owner: body.owner(),
+        max_size,
+        values: vec![],
+        item_depth: 0,
+    };
+    let _ = traverse_body(cx, &mut visitor, body);
+    visitor
+        .values
+        .into_iter()
+        .map(|(id, size)| (cx.ast().expr(id), size))
+        .collect()
+}
+
+struct StackValueVisitor {
+    owner: ItemId,
+    max_size: u64,
+    values: Vec<(ExprId, u64)>,
+    /// Nested items are traversed by [`VisitorScope::AllBodies`], but not checked.
+    item_depth: usize,
+}
+
+impl StackValueVisitor {
+    fn check_value<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) {
+        // Arrays, which initialize a `let` statement, have already been checked
+        if self.item_depth > 0 || self.values.iter().any(|(id, _)| *id == expr.id()) {
+            return;
+        }
+        if let Some(size) = cx.stack_size_of(expr.ty(), self.owner) {
+            if size > self.max_size {
+                self.values.push((expr.id(), size));
+            }
+        }
+    }
+}
+
+impl Visitor<()> for StackValueVisitor {
+    fn scope(&self) -> VisitorScope {
+        VisitorScope::AllBodies
+    }
+
+    fn visit_item<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<()> {
+        self.item_depth += 1;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_item_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<()> {
+        self.item_depth -= 1;
+        ControlFlow::Continue(())
+    }
+
+    fn visit_stmt<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, stmt: StmtKind<'ast>) -> ControlFlow<()> {
+        if let StmtKind::Let(local) = stmt {
+            if let Some(init) = local.init() {
+                self.check_value(cx, init);
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    fn visit_expr<'ast>(&mut self, cx: &'ast MarkerContext<'ast>, expr: ExprKind<'ast>) -> ControlFlow<()> {
+        if let ExprKind::Array(_) = expr {
+            self.check_value(cx, expr);
+        }
+        ControlFlow::Continue(())
+    }
+}
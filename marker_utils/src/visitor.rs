@@ -1,7 +1,7 @@
 use std::ops::ControlFlow;
 
 use marker_api::{
-    ast::{EnumVariant, ItemField},
+    ast::{EnumVariant, GenericArgKind, GenericArgs, ItemField, TyKind},
     prelude::*,
 };
 
@@ -89,6 +89,43 @@ pub trait Visitor<B> {
     fn visit_expr<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _expr: ExprKind<'ast>) -> ControlFlow<B> {
         ControlFlow::Continue(())
     }
+
+    /// This is called for patterns, like the ones of `let` statements, function
+    /// parameters and match arms. Nested patterns are visited as well.
+    fn visit_pat<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _pat: PatKind<'ast>) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+
+    /// This is called for syntactic types, like the type annotations of `let`
+    /// statements, the types of fields and function signatures and the generic
+    /// arguments of paths in types. Nested types are visited as well.
+    fn visit_ty<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _ty: TyKind<'ast>) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+
+    /// This is called after the item and all its children have been visited.
+    fn visit_item_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _item: ItemKind<'ast>) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+
+    /// This is called after the body and all its children have been visited.
+    fn visit_body_post<'ast>(
+        &mut self,
+        _cx: &'ast MarkerContext<'ast>,
+        _body: &'ast ast::Body<'ast>,
+    ) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+
+    /// This is called after the statement and all its children have been visited.
+    fn visit_stmt_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _stmt: StmtKind<'ast>) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
+
+    /// This is called after the expression and all its children have been visited.
+    fn visit_expr_post<'ast>(&mut self, _cx: &'ast MarkerContext<'ast>, _expr: ExprKind<'ast>) -> ControlFlow<B> {
+        ControlFlow::Continue(())
+    }
 }
 
 pub fn traverse_item<'ast, B>(
@@ -126,27 +163,46 @@ pub fn traverse_item<'ast, B>(
             }
         },
         ItemKind::Static(item) => {
+            traverse_ty(cx, visitor, item.ty())?;
             traverse_body_id(cx, visitor, item.body_id())?;
         },
         ItemKind::Const(item) => {
+            traverse_ty(cx, visitor, item.ty())?;
             traverse_body_id(cx, visitor, item.body_id())?;
         },
         ItemKind::Fn(item) => {
+            for param in item.params() {
+                traverse_pat(cx, visitor, param.pat())?;
+                traverse_ty(cx, visitor, param.ty())?;
+            }
+            if let Some(return_ty) = item.return_ty() {
+                traverse_ty(cx, visitor, *return_ty)?;
+            }
             traverse_body_id(cx, visitor, item.body_id())?;
         },
         ItemKind::Struct(item) => {
             for field in item.fields() {
                 visitor.visit_field(cx, field)?;
+                traverse_ty(cx, visitor, field.ty())?;
             }
         },
         ItemKind::Union(item) => {
             for field in item.fields() {
                 visitor.visit_field(cx, field)?;
+                traverse_ty(cx, visitor, field.ty())?;
+            }
+        },
+        ItemKind::TyAlias(item) => {
+            if let Some(aliased_ty) = item.aliased_ty() {
+                traverse_ty(cx, visitor, aliased_ty)?;
             }
         },
         ItemKind::Enum(item) => {
             for variant in item.variants() {
                 visitor.visit_variant(cx, variant)?;
+                for field in variant.fields() {
+                    traverse_ty(cx, visitor, field.ty())?;
+                }
                 if let Some(const_expr) = variant.discriminant() {
                     traverse_expr(cx, visitor, const_expr.expr())?;
                 }
@@ -158,6 +214,7 @@ pub fn traverse_item<'ast, B>(
             }
         },
         ItemKind::Impl(item) => {
+            traverse_ty(cx, visitor, item.ty())?;
             for assoc_item in item.items() {
                 traverse_item(cx, visitor, assoc_item.as_item())?;
             }
@@ -167,11 +224,14 @@ pub fn traverse_item<'ast, B>(
                 traverse_item(cx, visitor, ext_item.as_item())?;
             }
         },
-        ItemKind::ExternCrate(_) | ItemKind::Use(_) | ItemKind::Unstable(_) | ItemKind::TyAlias(_) => {
+        ItemKind::ExternCrate(_) | ItemKind::Use(_) | ItemKind::ExternType(_) | ItemKind::Unstable(_) => {
             // These items have no sub nodes, which are visited by this visitor
         },
         _ => unreachable!("all items are covered"),
     }
+
+    visitor.visit_item_post(cx, kind)?;
+
     ControlFlow::Continue(())
 }
 
@@ -184,6 +244,8 @@ pub fn traverse_body<'ast, B>(
 
     traverse_expr(cx, visitor, body.expr())?;
 
+    visitor.visit_body_post(cx, body)?;
+
     ControlFlow::Continue(())
 }
 
@@ -199,6 +261,10 @@ pub fn traverse_stmt<'ast, B>(
             traverse_item(cx, visitor, item.item())?;
         },
         StmtKind::Let(lt) => {
+            traverse_pat(cx, visitor, lt.pat())?;
+            if let Some(ty) = lt.ty() {
+                traverse_ty(cx, visitor, ty)?;
+            }
             if let Some(init) = lt.init() {
                 traverse_expr(cx, visitor, init)?;
             }
@@ -212,6 +278,8 @@ pub fn traverse_stmt<'ast, B>(
         _ => unreachable!("all statements are covered"),
     }
 
+    visitor.visit_stmt_post(cx, stmt)?;
+
     ControlFlow::Continue(())
 }
 
@@ -233,6 +301,15 @@ pub fn traverse_expr<'ast, B>(
             }
         },
         ExprKind::Closure(e) => {
+            for param in e.params() {
+                traverse_pat(cx, visitor, param.pat())?;
+                if let Some(ty) = param.ty() {
+                    traverse_ty(cx, visitor, ty)?;
+                }
+            }
+            if let Some(return_ty) = e.return_ty() {
+                traverse_ty(cx, visitor, return_ty)?;
+            }
             if let VisitorScope::AllBodies = visitor.scope() {
                 let body = cx.ast().body(e.body_id());
                 traverse_body(cx, visitor, body)?;
@@ -252,10 +329,12 @@ pub fn traverse_expr<'ast, B>(
             traverse_expr(cx, visitor, e.expr())?;
         },
         ExprKind::Assign(e) => {
+            traverse_pat(cx, visitor, e.assignee())?;
             traverse_expr(cx, visitor, e.value())?;
         },
         ExprKind::As(e) => {
             traverse_expr(cx, visitor, e.expr())?;
+            traverse_ty(cx, visitor, e.cast_ty())?;
         },
         ExprKind::Call(e) => {
             traverse_expr(cx, visitor, e.func())?;
@@ -317,11 +396,13 @@ pub fn traverse_expr<'ast, B>(
             }
         },
         ExprKind::Let(e) => {
+            traverse_pat(cx, visitor, e.pat())?;
             traverse_expr(cx, visitor, e.scrutinee())?;
         },
         ExprKind::Match(e) => {
             traverse_expr(cx, visitor, e.scrutinee())?;
             for arm in e.arms() {
+                traverse_pat(cx, visitor, arm.pat())?;
                 if let Some(guard) = arm.guard() {
                     traverse_expr(cx, visitor, guard)?;
                 }
@@ -339,6 +420,7 @@ pub fn traverse_expr<'ast, B>(
             }
         },
         ExprKind::For(e) => {
+            traverse_pat(cx, visitor, e.pat())?;
             traverse_expr(cx, visitor, e.iterable())?;
             traverse_expr(cx, visitor, e.block())?;
         },
@@ -365,6 +447,143 @@ pub fn traverse_expr<'ast, B>(
         _ => unreachable!("all expressions are covered"),
     }
 
+    visitor.visit_expr_post(cx, expr)?;
+
+    ControlFlow::Continue(())
+}
+
+pub fn traverse_pat<'ast, B>(
+    cx: &'ast MarkerContext<'ast>,
+    visitor: &mut dyn Visitor<B>,
+    pat: PatKind<'ast>,
+) -> ControlFlow<B> {
+    visitor.visit_pat(cx, pat)?;
+
+    match pat {
+        PatKind::Ident(p) => {
+            if let Some(binding_pat) = p.binding_pat() {
+                traverse_pat(cx, visitor, binding_pat)?;
+            }
+        },
+        PatKind::Ref(p) => {
+            traverse_pat(cx, visitor, p.pat())?;
+        },
+        PatKind::Struct(p) => {
+            for field in p.fields() {
+                traverse_pat(cx, visitor, field.pat())?;
+            }
+        },
+        PatKind::Tuple(p) => {
+            for el in p.elements() {
+                traverse_pat(cx, visitor, *el)?;
+            }
+        },
+        PatKind::Slice(p) => {
+            for el in p.elements() {
+                traverse_pat(cx, visitor, *el)?;
+            }
+        },
+        PatKind::Or(p) => {
+            for alt in p.pats() {
+                traverse_pat(cx, visitor, *alt)?;
+            }
+        },
+        PatKind::Place(p) => {
+            traverse_expr(cx, visitor, p.place())?;
+        },
+        PatKind::Range(p) => {
+            if let Some(start) = p.start() {
+                traverse_expr(cx, visitor, start)?;
+            }
+            if let Some(end) = p.end() {
+                traverse_expr(cx, visitor, end)?;
+            }
+        },
+        PatKind::Wildcard(_) | PatKind::Rest(_) | PatKind::Lit(_) | PatKind::Path(_) | PatKind::Unstable(_) => {
+            // These patterns have no sub nodes, which are visited by this visitor
+        },
+        _ => unreachable!("all patterns are covered"),
+    }
+
+    ControlFlow::Continue(())
+}
+
+pub fn traverse_ty<'ast, B>(
+    cx: &'ast MarkerContext<'ast>,
+    visitor: &mut dyn Visitor<B>,
+    ty: TyKind<'ast>,
+) -> ControlFlow<B> {
+    fn traverse_generic_args<'ast, B>(
+        cx: &'ast MarkerContext<'ast>,
+        visitor: &mut dyn Visitor<B>,
+        generics: &GenericArgs<'ast>,
+    ) -> ControlFlow<B> {
+        for arg in generics.args() {
+            match arg {
+                GenericArgKind::Ty(arg) => traverse_ty(cx, visitor, arg.ty())?,
+                GenericArgKind::Binding(arg) => traverse_ty(cx, visitor, arg.ty())?,
+                _ => {},
+            }
+        }
+        ControlFlow::Continue(())
+    }
+
+    visitor.visit_ty(cx, ty)?;
+
+    match ty {
+        TyKind::Tuple(t) => {
+            for el in t.types() {
+                traverse_ty(cx, visitor, *el)?;
+            }
+        },
+        TyKind::Array(t) => {
+            traverse_ty(cx, visitor, t.inner_ty())?;
+            if let Some(len) = t.len() {
+                traverse_expr(cx, visitor, len.expr())?;
+            }
+        },
+        TyKind::Slice(t) => {
+            traverse_ty(cx, visitor, t.inner_ty())?;
+        },
+        TyKind::Ref(t) => {
+            traverse_ty(cx, visitor, t.inner_ty())?;
+        },
+        TyKind::RawPtr(t) => {
+            traverse_ty(cx, visitor, t.inner_ty())?;
+        },
+        TyKind::FnPtr(t) => {
+            for param in t.params() {
+                traverse_ty(cx, visitor, param.ty())?;
+            }
+            if let Some(return_ty) = t.return_ty() {
+                traverse_ty(cx, visitor, *return_ty)?;
+            }
+        },
+        TyKind::Path(t) => {
+            let path = t.path();
+            if let Some(self_ty) = path.self_ty() {
+                traverse_ty(cx, visitor, self_ty)?;
+            }
+            if let Some(path_ty) = path.path_ty() {
+                traverse_ty(cx, visitor, path_ty)?;
+            }
+            for segment in path.segments() {
+                traverse_generic_args(cx, visitor, segment.generics())?;
+            }
+        },
+        TyKind::Bool(_)
+        | TyKind::Num(_)
+        | TyKind::Text(_)
+        | TyKind::Never(_)
+        | TyKind::TraitObj(_)
+        | TyKind::ImplTrait(_)
+        | TyKind::Inferred(_) => {
+            // These types have no sub nodes, which are visited by this visitor.
+            // The bounds of trait types are currently not traversed.
+        },
+        _ => unreachable!("all types are covered"),
+    }
+
     ControlFlow::Continue(())
 }
 
@@ -425,6 +644,186 @@ where
             ControlFlow::Break(b) => Some(b),
         }
     }
+
+    /// This function calls the given closure for every expression in the node,
+    /// together with the [`ExprParent`] of the expression. Like
+    /// [`for_each_expr`](Traversable::for_each_expr), this traversal will not
+    /// enter any nested bodies.
+    ///
+    /// The parent is carried along during the traversal, which makes this cheaper
+    /// than looking up the parent of every expression. The parent is `None`, if the
+    /// expression is the root of a body, the traversal or an item, like the
+    /// discriminant of an enum variant.
+    ///
+    /// For example, the following code finds all binary expressions, which are
+    /// directly nested in another binary expression:
+    ///
+    /// ```
+    /// # use marker_api::prelude::*;
+    /// # use std::ops::ControlFlow;
+    /// # use marker_utils::visitor::{ExprParent, Traversable};
+    /// fn nested_binary_ops<'ast>(cx: &'ast MarkerContext<'ast>, body: &'ast ast::Body<'ast>) -> Vec<ExprId> {
+    ///     let mut nested = vec![];
+    ///     let _: Option<()> = body.for_each_expr_with_parent(
+    ///         cx,
+    ///         |expr, parent| {
+    ///             if let (ExprKind::BinaryOp(_), Some(ExprParent::Expr(ExprKind::BinaryOp(_)))) = (expr, parent) {
+    ///                 nested.push(expr.id());
+    ///             }
+    ///             ControlFlow::Continue(())
+    ///         }
+    ///     );
+    ///     nested
+    /// }
+    /// ```
+    fn for_each_expr_with_parent<F: for<'a> FnMut(ExprKind<'a>, Option<ExprParent<'a>>) -> ControlFlow<B>>(
+        self,
+        cx: &'ast MarkerContext<'ast>,
+        f: F,
+    ) -> Option<B> {
+        /// The IDs of the enclosing nodes. `None` marks bodies and items, which
+        /// separate expressions from the parents of the enclosing node.
+        #[derive(Debug, Copy, Clone)]
+        enum ParentId {
+            Expr(ExprId),
+            Stmt(StmtId),
+            None,
+        }
+
+        struct ParentVisitor<F> {
+            f: F,
+            parents: Vec<ParentId>,
+        }
+        impl<B, F> Visitor<B> for ParentVisitor<F>
+        where
+            F: for<'a> FnMut(ExprKind<'a>, Option<ExprParent<'a>>) -> ControlFlow<B>,
+        {
+            fn visit_item<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                _item: ItemKind<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.push(ParentId::None);
+                ControlFlow::Continue(())
+            }
+
+            fn visit_body<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                _body: &'v_ast ast::Body<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.push(ParentId::None);
+                ControlFlow::Continue(())
+            }
+
+            fn visit_stmt<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                stmt: StmtKind<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.push(ParentId::Stmt(stmt.id()));
+                ControlFlow::Continue(())
+            }
+
+            fn visit_expr<'v_ast>(
+                &mut self,
+                cx: &'v_ast MarkerContext<'v_ast>,
+                expr: ExprKind<'v_ast>,
+            ) -> ControlFlow<B> {
+                let parent = match self.parents.last() {
+                    Some(ParentId::Expr(id)) => Some(ExprParent::Expr(cx.ast().expr(*id))),
+                    Some(ParentId::Stmt(id)) => Some(ExprParent::Stmt(cx.ast().stmt(*id))),
+                    Some(ParentId::None) | None => None,
+                };
+                (self.f)(expr, parent)?;
+                self.parents.push(ParentId::Expr(expr.id()));
+                ControlFlow::Continue(())
+            }
+
+            fn visit_item_post<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                _item: ItemKind<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.pop();
+                ControlFlow::Continue(())
+            }
+
+            fn visit_body_post<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                _body: &'v_ast ast::Body<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.pop();
+                ControlFlow::Continue(())
+            }
+
+            fn visit_stmt_post<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                _stmt: StmtKind<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.pop();
+                ControlFlow::Continue(())
+            }
+
+            fn visit_expr_post<'v_ast>(
+                &mut self,
+                _cx: &'v_ast MarkerContext<'v_ast>,
+                _expr: ExprKind<'v_ast>,
+            ) -> ControlFlow<B> {
+                self.parents.pop();
+                ControlFlow::Continue(())
+            }
+        }
+        let mut visitor = ParentVisitor { f, parents: vec![] };
+
+        match self.traverse(cx, &mut visitor) {
+            ControlFlow::Continue(()) => None,
+            ControlFlow::Break(b) => Some(b),
+        }
+    }
+}
+
+/// The enclosing node of an expression, as provided by
+/// [`Traversable::for_each_expr_with_parent`].
+#[non_exhaustive]
+#[derive(Debug, Copy, Clone)]
+pub enum ExprParent<'ast> {
+    /// The expression is a direct child of this expression.
+    Expr(ExprKind<'ast>),
+    /// The expression is a direct child of this statement, like the initializer
+    /// of a `let` statement.
+    Stmt(StmtKind<'ast>),
+}
+
+impl<'ast> ExprParent<'ast> {
+    /// Returns the parent expression, if the parent is an expression.
+    #[must_use]
+    pub fn expr(self) -> Option<ExprKind<'ast>> {
+        match self {
+            ExprParent::Expr(expr) => Some(expr),
+            ExprParent::Stmt(_) => None,
+        }
+    }
+
+    /// Returns the parent statement, if the parent is a statement.
+    #[must_use]
+    pub fn stmt(self) -> Option<StmtKind<'ast>> {
+        match self {
+            ExprParent::Stmt(stmt) => Some(stmt),
+            ExprParent::Expr(_) => None,
+        }
+    }
+
+    /// Returns the [`Span`] of the parent node.
+    #[must_use]
+    pub fn span(&self) -> &Span<'ast> {
+        match self {
+            ExprParent::Expr(expr) => expr.span(),
+            ExprParent::Stmt(stmt) => stmt.span(),
+        }
+    }
 }
 
 /// This macro implements the [`Traversable`] trait for a given node that implements `Copy`
@@ -442,6 +841,8 @@ impl_traversable_for!(ExprKind<'ast>, traverse_expr);
 impl_traversable_for!(StmtKind<'ast>, traverse_stmt);
 impl_traversable_for!(ItemKind<'ast>, traverse_item);
 impl_traversable_for!(&'ast ast::Body<'ast>, traverse_body);
+impl_traversable_for!(PatKind<'ast>, traverse_pat);
+impl_traversable_for!(TyKind<'ast>, traverse_ty);
 
 /// This trait extends the [`Traversable`] trait with more functions, specific to
 /// the `bool` return type.